@@ -1,4 +1,21 @@
 fn main() {
+  // Build info for the about dialog and diagnostics (`about_info`).
+  let git_hash = std::process::Command::new("git")
+    .args(["rev-parse", "--short", "HEAD"])
+    .output()
+    .ok()
+    .filter(|out| out.status.success())
+    .and_then(|out| String::from_utf8(out.stdout).ok())
+    .map(|hash| hash.trim().to_string())
+    .unwrap_or_default();
+  println!("cargo:rustc-env=HELLTIME_GIT_HASH={git_hash}");
+  let built_at = std::env::var("SOURCE_DATE_EPOCH").ok().and_then(|s| s.parse::<u64>().ok()).unwrap_or_else(|| {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+  });
+  println!("cargo:rustc-env=HELLTIME_BUILD_TIME={built_at}");
+  println!("cargo:rerun-if-changed=../.git/HEAD");
+  println!("cargo:rerun-if-changed=../.git/refs/heads");
+  println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
   tauri_build::build()
 }
-
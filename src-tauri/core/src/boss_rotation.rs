@@ -0,0 +1,77 @@
+use crate::schedule::{parse_event_time, ScheduleResponse};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Rotation order observed in-game; used until enough history has been recorded.
+const DEFAULT_ROTATION: [&str; 3] = ["Wandering Death", "Avarice", "Ashava"];
+
+fn normalize(name: &str) -> String {
+  name.trim().to_lowercase()
+}
+
+/// Predict the boss following `sequence` (oldest first).
+///
+/// Uses the most frequently observed successor of the last known boss; falls back
+/// to the default rotation when the history has never seen that boss followed by anything.
+pub fn predict_next(sequence: &[String]) -> Option<String> {
+  let last = sequence.last()?;
+  let last_key = normalize(last);
+
+  let mut successors: HashMap<String, (usize, &String)> = HashMap::new();
+  for pair in sequence.windows(2) {
+    if normalize(&pair[0]) == last_key {
+      let entry = successors.entry(normalize(&pair[1])).or_insert((0, &pair[1]));
+      entry.0 += 1;
+    }
+  }
+
+  if let Some((_, (_, name))) = successors.iter().max_by(|a, b| a.1 .0.cmp(&b.1 .0).then_with(|| b.0.cmp(a.0))) {
+    return Some((*name).clone());
+  }
+
+  let idx = DEFAULT_ROTATION.iter().position(|b| normalize(b) == last_key)?;
+  Some(DEFAULT_ROTATION[(idx + 1) % DEFAULT_ROTATION.len()].to_string())
+}
+
+/// Fill in `predictedBoss` on world boss entries the API sent without a `boss` name.
+///
+/// `history` holds past (start, boss) sightings; only those before the first scheduled
+/// spawn are used so occurrences present in both don't count twice. Entries are walked
+/// in start order so consecutive unknown spawns chain their predictions.
+pub fn annotate(schedule: &mut ScheduleResponse, history: &[(DateTime<Utc>, String)]) {
+  let mut order: Vec<(DateTime<Utc>, usize)> = schedule
+    .world_boss
+    .iter()
+    .enumerate()
+    .filter_map(|(i, raw)| parse_event_time(raw).map(|start| (start, i)))
+    .collect();
+  order.sort();
+
+  let Some(&(first_start, _)) = order.first() else { return };
+  let mut sequence: Vec<String> = history
+    .iter()
+    .filter(|(start, _)| *start < first_start)
+    .map(|(_, boss)| boss.clone())
+    .collect();
+
+  for (_, i) in order {
+    let raw = &mut schedule.world_boss[i];
+    let known = raw
+      .get("boss")
+      .and_then(|v| v.as_str())
+      .map(str::trim)
+      .filter(|s| !s.is_empty())
+      .map(str::to_string);
+
+    if let Some(boss) = known {
+      sequence.push(boss);
+      continue;
+    }
+
+    let Some(predicted) = predict_next(&sequence) else { continue };
+    if let Some(obj) = raw.as_object_mut() {
+      obj.insert("predictedBoss".into(), serde_json::Value::String(predicted.clone()));
+    }
+    sequence.push(predicted);
+  }
+}
@@ -0,0 +1,118 @@
+use crate::schedule::{EventKind, ScheduleResponse, ScheduledEvent};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Last successfully fetched schedule plus its age.
+///
+/// A stale value is kept after the TTL expires (or after `invalidate`) so callers
+/// can fall back to it when the next fetch fails.
+#[derive(Debug)]
+pub struct ScheduleCache {
+  ttl: Duration,
+  last_fetch: Option<Instant>,
+  /// Wall-clock time of the fetch that produced `value`; survives `invalidate`.
+  fetched_at: Option<DateTime<Utc>>,
+  value: Option<ScheduleResponse>,
+}
+
+impl ScheduleCache {
+  pub fn new(ttl: Duration) -> Self {
+    Self { ttl, last_fetch: None, fetched_at: None, value: None }
+  }
+
+  /// Applies from the next freshness check on; the cached value is kept.
+  pub fn set_ttl(&mut self, ttl: Duration) {
+    self.ttl = ttl;
+  }
+
+  /// The cached schedule if it is younger than the TTL at `now`.
+  pub fn fresh_at(&self, now: Instant) -> Option<&ScheduleResponse> {
+    let at = self.last_fetch?;
+    if now.saturating_duration_since(at) < self.ttl {
+      self.value.as_ref()
+    } else {
+      None
+    }
+  }
+
+  pub fn fresh(&self) -> Option<&ScheduleResponse> {
+    self.fresh_at(Instant::now())
+  }
+
+  /// The cached schedule regardless of age.
+  pub fn value(&self) -> Option<&ScheduleResponse> {
+    self.value.as_ref()
+  }
+
+  /// Time since the last successful fetch, `None` if never fetched or invalidated.
+  pub fn age(&self) -> Option<Duration> {
+    self.last_fetch.map(|at| at.elapsed())
+  }
+
+  /// When the cached schedule was fetched.
+  pub fn fetched_at(&self) -> Option<DateTime<Utc>> {
+    self.fetched_at
+  }
+
+  pub fn store_at(&mut self, value: ScheduleResponse, now: Instant) {
+    self.last_fetch = Some(now);
+    self.fetched_at = Some(Utc::now());
+    self.value = Some(value);
+  }
+
+  pub fn store(&mut self, value: ScheduleResponse) {
+    self.store_at(value, Instant::now());
+  }
+
+  /// Force the next fetch to hit the network (keeps the stale value as a fallback).
+  pub fn invalidate(&mut self) {
+    self.last_fetch = None;
+  }
+}
+
+/// An upcoming event whose start time changed between two fetches.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShiftedEvent {
+  pub kind: EventKind,
+  pub id: i64,
+  pub from: DateTime<Utc>,
+  pub to: DateTime<Utc>,
+}
+
+/// What a refresh changed about events that have not started yet. Events the API
+/// adds at the end of its window and predicted (extrapolated) ones are not changes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleDiff {
+  pub shifted: Vec<ShiftedEvent>,
+  /// Upcoming events that are no longer in the schedule.
+  pub cancelled: Vec<ScheduledEvent>,
+}
+
+impl ScheduleDiff {
+  pub fn is_empty(&self) -> bool {
+    self.shifted.is_empty() && self.cancelled.is_empty()
+  }
+}
+
+/// Compare `new` against `old`, matching events by category and id.
+pub fn diff(old: &ScheduleResponse, new: &ScheduleResponse, now: DateTime<Utc>) -> ScheduleDiff {
+  let mut changes = ScheduleDiff::default();
+  for kind in EventKind::ALL {
+    let current: HashMap<i64, DateTime<Utc>> =
+      new.events(kind).into_iter().filter(|e| !e.predicted).map(|e| (e.id, e.start)).collect();
+    for event in old.events(kind).into_iter().filter(|e| !e.predicted && e.start > now) {
+      match current.get(&event.id) {
+        Some(&start) if start != event.start => {
+          changes.shifted.push(ShiftedEvent { kind, id: event.id, from: event.start, to: start })
+        }
+        Some(_) => {}
+        None => changes.cancelled.push(event),
+      }
+    }
+  }
+  changes
+}
@@ -0,0 +1,137 @@
+//! Season-dependent event timing. The Helltide rhythm has changed between seasons
+//! (hourly, every 2h15m, ...), so it lives in `cadence.json`: a copy is bundled and a
+//! newer one can be fetched from the repository without shipping a new binary.
+
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The cadence shipped with this build.
+pub const BUNDLED: &str = include_str!("../cadence.json");
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Cadence {
+  /// Bumped with every published change; a file only replaces a lower version.
+  pub version: u32,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub season: Option<String>,
+  pub helltide: HelltideCadence,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HelltideCadence {
+  /// Time from one Helltide start to the next.
+  pub interval_minutes: i64,
+  /// How long a Helltide stays open after its start.
+  pub duration_minutes: i64,
+  /// First mystery chest reset, counted from the start...
+  pub chest_offset_minutes: i64,
+  /// ...then every this many minutes while the Helltide is open.
+  pub chest_interval_minutes: i64,
+}
+
+impl HelltideCadence {
+  pub fn interval(&self) -> Duration {
+    Duration::minutes(self.interval_minutes)
+  }
+
+  pub fn duration(&self) -> Duration {
+    Duration::minutes(self.duration_minutes)
+  }
+
+  pub fn chest_offset(&self) -> Duration {
+    Duration::minutes(self.chest_offset_minutes)
+  }
+
+  pub fn chest_interval(&self) -> Duration {
+    Duration::minutes(self.chest_interval_minutes)
+  }
+}
+
+impl Default for Cadence {
+  fn default() -> Self {
+    Cadence::bundled()
+  }
+}
+
+impl Cadence {
+  pub fn bundled() -> Cadence {
+    Cadence::parse(BUNDLED.as_bytes()).expect("bundled cadence.json is valid")
+  }
+
+  /// Parse and sanity-check a `cadence.json`.
+  pub fn parse(bytes: &[u8]) -> Result<Cadence, String> {
+    let cadence: Cadence = serde_json::from_slice(bytes).map_err(|e| format!("invalid cadence: {e}"))?;
+    cadence.validate()?;
+    Ok(cadence)
+  }
+
+  /// Reject values that would make the predictor loop or schedule nonsense.
+  pub fn validate(&self) -> Result<(), String> {
+    let h = &self.helltide;
+    if h.interval_minutes <= 0 || h.duration_minutes <= 0 || h.chest_interval_minutes <= 0 || h.chest_offset_minutes < 0 {
+      return Err("cadence durations must be positive".into());
+    }
+    if h.duration_minutes > h.interval_minutes {
+      return Err("Helltides would overlap: duration exceeds interval".into());
+    }
+    if h.chest_offset_minutes >= h.duration_minutes {
+      return Err("first chest reset is after the Helltide ends".into());
+    }
+    Ok(())
+  }
+
+  /// Whichever of `self` and `other` has the higher version.
+  pub fn newest(self, other: Cadence) -> Cadence {
+    if other.version > self.version {
+      other
+    } else {
+      self
+    }
+  }
+}
+
+/// The cadence in use, plus where a downloaded copy is kept.
+#[derive(Debug)]
+pub struct CadenceStore {
+  path: PathBuf,
+  cadence: Cadence,
+}
+
+impl CadenceStore {
+  /// The saved download if it is valid and newer than the bundled file.
+  pub fn load(path: PathBuf) -> Self {
+    let saved = std::fs::read(&path).ok().and_then(|bytes| match Cadence::parse(&bytes) {
+      Ok(cadence) => Some(cadence),
+      Err(e) => {
+        corelog!("✗ Saved cadence at {:?} ignored: {}", path, e);
+        None
+      }
+    });
+    let cadence = saved.into_iter().fold(Cadence::bundled(), Cadence::newest);
+    Self { path, cadence }
+  }
+
+  pub fn get(&self) -> &Cadence {
+    &self.cadence
+  }
+
+  /// Adopt `cadence` if it is newer, saving it for the next start. Returns whether
+  /// it was adopted.
+  pub fn offer(&mut self, cadence: Cadence) -> Result<bool, String> {
+    if cadence.version <= self.cadence.version {
+      return Ok(false);
+    }
+    if let Some(dir) = self.path.parent() {
+      std::fs::create_dir_all(dir).map_err(|e| format!("failed to create {:?}: {e}", dir))?;
+    }
+    let bytes = serde_json::to_vec_pretty(&cadence).map_err(|e| format!("failed to serialize cadence: {e}"))?;
+    let tmp = self.path.with_extension("json.tmp");
+    std::fs::write(&tmp, bytes).map_err(|e| format!("failed to write {:?}: {e}", tmp))?;
+    std::fs::rename(&tmp, &self.path).map_err(|e| format!("failed to replace {:?}: {e}", self.path))?;
+    self.cadence = cadence;
+    Ok(true)
+  }
+}
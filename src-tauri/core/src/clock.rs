@@ -0,0 +1,133 @@
+use chrono::{DateTime, Duration as ChronoDuration, FixedOffset, Local, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Keep a handful of samples so a single slow response can't swing the estimate.
+const MAX_SAMPLES: usize = 5;
+
+/// The `Date` header only has one-second resolution; smaller offsets are noise.
+const MIN_SIGNIFICANT_SKEW_MS: i64 = 1_500;
+
+/// Estimates how far the local clock is off compared to the API server.
+///
+/// Each fetch contributes `server Date - midpoint(request sent, response received)`.
+/// The estimate is the median of the recent samples, so countdowns stay stable
+/// even when individual round-trips are slow.
+#[derive(Debug, Default)]
+pub struct ClockSkew {
+  samples: VecDeque<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClockStatus {
+  /// Milliseconds to add to the local clock to get server time.
+  pub offset_ms: i64,
+  pub samples: usize,
+  pub server_now: String,
+}
+
+impl ClockSkew {
+  pub fn record(&mut self, server_date: DateTime<Utc>, sent_at: DateTime<Utc>, received_at: DateTime<Utc>) {
+    let rtt = received_at - sent_at;
+    let midpoint = sent_at + rtt / 2;
+    let sample = (server_date - midpoint).num_milliseconds();
+    if self.samples.len() == MAX_SAMPLES {
+      self.samples.pop_front();
+    }
+    self.samples.push_back(sample);
+  }
+
+  /// Drop all samples, e.g. after the local clock was changed.
+  pub fn reset(&mut self) {
+    self.samples.clear();
+  }
+
+  /// Median offset in milliseconds, or 0 if the clocks agree within header resolution.
+  pub fn offset_ms(&self) -> i64 {
+    if self.samples.is_empty() {
+      return 0;
+    }
+    let mut sorted: Vec<i64> = self.samples.iter().copied().collect();
+    sorted.sort_unstable();
+    let median = sorted[sorted.len() / 2];
+    if median.abs() < MIN_SIGNIFICANT_SKEW_MS {
+      0
+    } else {
+      median
+    }
+  }
+
+  /// Local clock corrected by the estimated skew.
+  pub fn server_now(&self) -> DateTime<Utc> {
+    Utc::now() + ChronoDuration::milliseconds(self.offset_ms())
+  }
+
+  pub fn status(&self) -> ClockStatus {
+    ClockStatus {
+      offset_ms: self.offset_ms(),
+      samples: self.samples.len(),
+      server_now: self.server_now().to_rfc3339(),
+    }
+  }
+}
+
+/// Parse an HTTP `Date` header (IMF-fixdate, e.g. `Tue, 15 Nov 1994 08:12:31 GMT`).
+pub fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+  DateTime::parse_from_rfc2822(value.trim()).ok().map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Format a UTC instant as local wall-clock time (`HH:MM`).
+pub fn format_local_time(at: DateTime<Utc>) -> String {
+  format_time_in(at, &Local)
+}
+
+/// [`format_local_time`] in a given time zone. Display strings are always derived
+/// from the UTC instant, so after a DST or time zone switch they only need to be
+/// formatted again, never recomputed.
+pub fn format_time_in<Tz: TimeZone>(at: DateTime<Utc>, tz: &Tz) -> String
+where
+  Tz::Offset: std::fmt::Display,
+{
+  at.with_timezone(tz).format("%H:%M").to_string()
+}
+
+/// Real-world offsets run from UTC−12:00 to UTC+14:00.
+const MAX_OFFSET_MINUTES: i32 = 14 * 60;
+
+/// Longest label shown next to times in the secondary zone.
+pub const MAX_ZONE_LABEL_CHARS: usize = 12;
+
+/// A second time zone shown next to local times, for groups that coordinate in a
+/// shared one. It is a fixed UTC offset: there is no time zone database to follow
+/// DST with, so the offset has to be changed when the shared zone switches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecondaryZone {
+  /// Shown after the time, e.g. `CET` or `Gilde`.
+  pub label: String,
+  /// Minutes east of UTC.
+  pub offset_minutes: i32,
+}
+
+impl SecondaryZone {
+  pub fn validate(&self) -> Result<(), String> {
+    let label = self.label.trim();
+    if label.is_empty() || label.chars().count() > MAX_ZONE_LABEL_CHARS || label.chars().any(char::is_control) {
+      return Err(format!("time zone label must be 1 to {MAX_ZONE_LABEL_CHARS} characters: {:?}", self.label));
+    }
+    if self.offset_minutes.abs() > MAX_OFFSET_MINUTES {
+      return Err(format!("UTC offset out of range: {} minutes", self.offset_minutes));
+    }
+    Ok(())
+  }
+
+  /// `None` for offsets chrono cannot represent (±24h), which `validate` rejects.
+  pub fn offset(&self) -> Option<FixedOffset> {
+    FixedOffset::east_opt(self.offset_minutes.checked_mul(60)?)
+  }
+
+  /// `HH:MM label`, e.g. `20:30 CET`.
+  pub fn format(&self, at: DateTime<Utc>) -> Option<String> {
+    Some(format!("{} {}", format_time_in(at, &self.offset()?), self.label.trim()))
+  }
+}
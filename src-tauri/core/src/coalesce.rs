@@ -0,0 +1,65 @@
+//! Coalesces desktop notifications: reminders arriving within [`WINDOW`] of each
+//! other go out as one summary ("3 events starting soon"), and no more than
+//! [`MAX_PER_MINUTE`] notifications are shown per minute. Mostly matters right after
+//! startup, when several reminders can be due at once.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How long the first notification of a batch waits for others to join it.
+pub const WINDOW: Duration = Duration::from_secs(2);
+pub const MAX_PER_MINUTE: usize = 4;
+
+/// What to show once a batch is complete.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Batch<T> {
+  One(T),
+  /// Several notifications, in arrival order, to be shown as one summary.
+  Summary(Vec<T>),
+}
+
+#[derive(Debug)]
+pub struct NotificationCoalescer<T> {
+  pending: Vec<T>,
+  /// When each of the notifications of the last minute went out.
+  shown: VecDeque<Instant>,
+  /// Batches dropped by the per-minute limit since startup.
+  suppressed: u64,
+}
+
+impl<T> Default for NotificationCoalescer<T> {
+  fn default() -> Self {
+    Self { pending: Vec::new(), shown: VecDeque::new(), suppressed: 0 }
+  }
+}
+
+impl<T> NotificationCoalescer<T> {
+  /// Queue a notification. Returns `true` if it started a new batch, in which case
+  /// the caller calls [`flush`](Self::flush) after [`WINDOW`].
+  pub fn push(&mut self, item: T) -> bool {
+    self.pending.push(item);
+    self.pending.len() == 1
+  }
+
+  /// Take the pending batch. `None` if nothing is pending or the per-minute limit
+  /// is reached, in which case the batch is dropped.
+  pub fn flush(&mut self, now: Instant) -> Option<Batch<T>> {
+    let mut items = std::mem::take(&mut self.pending);
+    if items.is_empty() {
+      return None;
+    }
+    while self.shown.front().is_some_and(|at| now.saturating_duration_since(*at) >= Duration::from_secs(60)) {
+      self.shown.pop_front();
+    }
+    if self.shown.len() >= MAX_PER_MINUTE {
+      self.suppressed += 1;
+      return None;
+    }
+    self.shown.push_back(now);
+    Some(if items.len() == 1 { Batch::One(items.remove(0)) } else { Batch::Summary(items) })
+  }
+
+  pub fn suppressed(&self) -> u64 {
+    self.suppressed
+  }
+}
@@ -0,0 +1,74 @@
+//! Connection state derived from schedule fetch outcomes, so the UI and tray can
+//! flag stale data instead of silently showing old times.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+/// After this long without a successful fetch the cached schedule is no longer
+/// trusted and the state drops from `Degraded` to `Offline`.
+pub const OFFLINE_AFTER: Duration = Duration::minutes(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+  /// The last fetch succeeded (or none has been attempted yet).
+  Online,
+  /// Fetches fail, but the cached schedule is recent enough to keep using.
+  Degraded,
+  /// Fetches fail and there is no recent data.
+  Offline,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionStatus {
+  pub state: ConnectionState,
+  pub last_success: Option<DateTime<Utc>>,
+  pub last_error: Option<String>,
+  pub consecutive_failures: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionTracker {
+  last_success: Option<DateTime<Utc>>,
+  last_error: Option<String>,
+  failures: u32,
+}
+
+impl ConnectionTracker {
+  pub fn state(&self, now: DateTime<Utc>) -> ConnectionState {
+    if self.failures == 0 {
+      return ConnectionState::Online;
+    }
+    match self.last_success {
+      Some(at) if now - at < OFFLINE_AFTER => ConnectionState::Degraded,
+      _ => ConnectionState::Offline,
+    }
+  }
+
+  pub fn status(&self, now: DateTime<Utc>) -> ConnectionStatus {
+    ConnectionStatus {
+      state: self.state(now),
+      last_success: self.last_success,
+      last_error: self.last_error.clone(),
+      consecutive_failures: self.failures,
+    }
+  }
+
+  /// Returns whether the state changed.
+  pub fn record_success(&mut self, now: DateTime<Utc>) -> bool {
+    let before = self.state(now);
+    self.last_success = Some(now);
+    self.last_error = None;
+    self.failures = 0;
+    before != ConnectionState::Online
+  }
+
+  /// Returns whether the state changed.
+  pub fn record_failure(&mut self, error: impl Into<String>, now: DateTime<Utc>) -> bool {
+    let before = self.state(now);
+    self.last_error = Some(error.into());
+    self.failures += 1;
+    before != self.state(now)
+  }
+}
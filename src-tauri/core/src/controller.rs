@@ -0,0 +1,119 @@
+//! One owner for the main window's visibility, and the one queue the overlay and
+//! reminder switches are flipped through. The switches themselves live only in the
+//! preferences; the controller never keeps a copy that could drift from them.
+//!
+//! Tray clicks, commands and window events are turned into [`Message`]s and handled
+//! one at a time by [`Controller::handle`], which only updates its state and says
+//! what to do ([`Effect`]). The app runs the effects afterwards, so window events
+//! fired by `hide()`/`show()` simply queue up behind the transition that caused them.
+
+use crate::window_state::{MinimizeAction, Plan, Visibility, WindowMachine};
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Message {
+  /// Left click on the tray icon: toggle the window.
+  TrayClick(Instant),
+  /// Bring the window up (tray menu, deep link, notification click).
+  ShowWindow,
+  /// Show or hide the window from the keyboard (hotkey, tray menu); unlike
+  /// [`TrayClick`](Message::TrayClick) not debounced.
+  ToggleWindow,
+  /// Close button with "hide to tray".
+  CloseToTray,
+  /// The window was resized; `minimized` comes from the OS.
+  Resized { minimized: bool, to_tray: bool },
+  /// The OS focused the window, so it is on screen whatever the state says.
+  Focused,
+  ToggleOverlay,
+  ToggleReminders,
+  /// The window has been hidden for the configured time; drop its webview.
+  UnloadIdle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+  /// Show, unminimize, put in the taskbar and focus.
+  ShowWindow,
+  /// Already visible; only focus it.
+  FocusWindow,
+  /// Hide and remove from the taskbar.
+  HideWindow,
+  /// Put back in the taskbar after the OS showed it on its own.
+  ShowInTaskbar,
+  /// Destroy the hidden window; `ShowWindow` rebuilds it.
+  UnloadWindow,
+  /// Flip the preference, whatever it is when the effect runs.
+  ToggleOverlay,
+  ToggleReminders,
+}
+
+#[derive(Debug, Clone)]
+pub struct Controller {
+  window: WindowMachine,
+}
+
+impl Controller {
+  pub fn new(visibility: Visibility) -> Self {
+    let mut window = WindowMachine::default();
+    window.set(visibility);
+    Self { window }
+  }
+
+  pub fn visibility(&self) -> Visibility {
+    self.window.visibility()
+  }
+
+  pub fn handle(&mut self, message: Message) -> Vec<Effect> {
+    match message {
+      Message::TrayClick(at) => {
+        if !self.window.accept_tray_click(at) {
+          return Vec::new();
+        }
+        let target = self.window.toggled();
+        self.move_window(target, false)
+      }
+      Message::ShowWindow => self.move_window(Visibility::Visible, false),
+      Message::ToggleWindow => {
+        let target = self.window.toggled();
+        self.move_window(target, false)
+      }
+      // Forced: close/minimize mean the window is on screen whatever the state says.
+      Message::CloseToTray => self.move_window(Visibility::Hidden, true),
+      Message::Resized { minimized, to_tray } => match self.window.observe_minimized(minimized, to_tray) {
+        MinimizeAction::HideToTray => self.move_window(Visibility::Hidden, true),
+        MinimizeAction::Minimized | MinimizeAction::None => Vec::new(),
+      },
+      Message::Focused => {
+        if self.window.set(Visibility::Visible) {
+          vec![Effect::ShowInTaskbar]
+        } else {
+          Vec::new()
+        }
+      }
+      Message::ToggleOverlay => vec![Effect::ToggleOverlay],
+      Message::ToggleReminders => vec![Effect::ToggleReminders],
+      // Only a window that is still hidden; it may have been shown since the timer started.
+      Message::UnloadIdle if self.window.visibility() == Visibility::Hidden => {
+        self.move_window(Visibility::Unloaded, false)
+      }
+      Message::UnloadIdle => Vec::new(),
+    }
+  }
+
+  fn move_window(&mut self, target: Visibility, force: bool) -> Vec<Effect> {
+    match self.window.begin(target, force) {
+      Plan::Start => {
+        self.window.finish();
+        vec![match target {
+          Visibility::Visible => Effect::ShowWindow,
+          Visibility::Hidden => Effect::HideWindow,
+          Visibility::Unloaded => Effect::UnloadWindow,
+        }]
+      }
+      // Restoring a visible window still brings it to the front.
+      Plan::AlreadyThere if target == Visibility::Visible => vec![Effect::FocusWindow],
+      Plan::AlreadyThere | Plan::Busy => Vec::new(),
+    }
+  }
+}
@@ -0,0 +1,186 @@
+use crate::cadence::HelltideCadence;
+use crate::clock::{format_local_time, format_time_in, SecondaryZone};
+use crate::i18n::{self, Locale, Msg};
+use crate::schedule::{EventKind, ScheduleResponse, Titled};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde::Serialize;
+
+/// World bosses stay up this long after spawning (typical fight plus despawn).
+pub const WORLD_BOSS_DURATION: Duration = Duration::minutes(15);
+/// Legion events are short waves.
+pub const LEGION_DURATION: Duration = Duration::minutes(5);
+
+/// Next occurrence of one category as reported to the UI, tray and CLI.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NextEvent {
+  pub kind: EventKind,
+  pub id: i64,
+  pub start: String,
+  pub start_local: String,
+  /// Start in the secondary zone from the preferences (`20:30 CET`), if one is set.
+  pub start_secondary: Option<String>,
+  pub seconds_until: i64,
+  pub boss: Option<String>,
+  pub boss_predicted: bool,
+  pub zone: Option<String>,
+}
+
+impl Titled for NextEvent {
+  fn title_parts(&self) -> (EventKind, Option<&str>, bool, Option<&str>) {
+    (self.kind, self.boss.as_deref(), self.boss_predicted, self.zone.as_deref())
+  }
+}
+
+/// An event that has started and is still running.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActiveEvent {
+  pub kind: EventKind,
+  pub id: i64,
+  pub start: String,
+  pub end: String,
+  pub seconds_remaining: i64,
+  /// Share of the event's duration that has passed, 0.0 to 1.0.
+  pub progress: f64,
+  pub boss: Option<String>,
+  pub boss_predicted: bool,
+  pub zone: Option<String>,
+}
+
+impl Titled for ActiveEvent {
+  fn title_parts(&self) -> (EventKind, Option<&str>, bool, Option<&str>) {
+    (self.kind, self.boss.as_deref(), self.boss_predicted, self.zone.as_deref())
+  }
+}
+
+/// How long an event of `kind` runs after its start.
+pub fn event_duration(kind: EventKind, cadence: &HelltideCadence) -> Duration {
+  match kind {
+    EventKind::Helltide => cadence.duration(),
+    EventKind::Legion => LEGION_DURATION,
+    EventKind::WorldBoss => WORLD_BOSS_DURATION,
+  }
+}
+
+/// Events of `kinds` running at `now`, the one ending first first.
+pub fn active_events(
+  schedule: &ScheduleResponse,
+  kinds: &[EventKind],
+  cadence: &HelltideCadence,
+  now: DateTime<Utc>,
+) -> Vec<ActiveEvent> {
+  let mut out: Vec<ActiveEvent> = kinds
+    .iter()
+    .filter_map(|kind| {
+      let duration = event_duration(*kind, cadence);
+      let e = schedule.events(*kind).into_iter().rev().find(|e| e.start <= now)?;
+      let end = e.end_or(duration);
+      (now < end).then(|| ActiveEvent {
+        kind: e.kind,
+        id: e.id,
+        start: e.start.to_rfc3339(),
+        end: end.to_rfc3339(),
+        seconds_remaining: (end - now).num_seconds(),
+        progress: (now - e.start).num_seconds() as f64 / (end - e.start).num_seconds().max(1) as f64,
+        boss: e.boss,
+        boss_predicted: e.boss_predicted,
+        zone: e.zone,
+      })
+    })
+    .collect();
+  out.sort_by_key(|e| e.seconds_remaining);
+  out
+}
+
+/// Next upcoming event for each of `kinds`, soonest first. `now` should already be
+/// skew-corrected (see `ClockSkew::server_now`).
+pub fn next_events(
+  schedule: &ScheduleResponse,
+  kinds: &[EventKind],
+  now: DateTime<Utc>,
+  secondary: Option<&SecondaryZone>,
+) -> Vec<NextEvent> {
+  let mut out: Vec<NextEvent> = kinds
+    .iter()
+    .filter_map(|kind| schedule.next_event(*kind, now))
+    .map(|e| NextEvent {
+      kind: e.kind,
+      id: e.id,
+      start: e.start.to_rfc3339(),
+      start_local: format_local_time(e.start),
+      start_secondary: secondary.and_then(|zone| zone.format(e.start)),
+      seconds_until: (e.start - now).num_seconds(),
+      boss: e.boss,
+      boss_predicted: e.boss_predicted,
+      zone: e.zone,
+    })
+    .collect();
+  out.sort_by_key(|e| e.seconds_until);
+  out
+}
+
+/// Plain text of the running and next events of `kinds` for pasting into a group
+/// chat, with start times in `tz`, whose UTC offset the header names:
+///
+/// ```text
+/// Next events (UTC+02:00):
+/// Helltide: active, 42m left
+/// Legion: 10:30 (in 12m)
+/// ```
+pub fn share_text<Tz: TimeZone>(
+  locale: Locale,
+  schedule: &ScheduleResponse,
+  kinds: &[EventKind],
+  cadence: &HelltideCadence,
+  now: DateTime<Utc>,
+  tz: &Tz,
+) -> String
+where
+  Tz::Offset: std::fmt::Display,
+{
+  let zone = format!("UTC{}", now.with_timezone(tz).format("%:z"));
+  let mut lines = vec![i18n::format(locale, Msg::ShareHeader, &[("zone", &zone)])];
+  for event in active_events(schedule, kinds, cadence, now) {
+    let remaining = format_countdown(event.seconds_remaining);
+    lines.push(i18n::format(locale, Msg::TooltipActive, &[("event", &event.title_in(locale)), ("remaining", &remaining)]));
+  }
+  let mut next: Vec<_> = kinds.iter().filter_map(|kind| schedule.next_event(*kind, now)).collect();
+  next.sort_by_key(|e| e.start);
+  for event in &next {
+    let args = [
+      ("event", event.title_in(locale)),
+      ("time", format_time_in(event.start, tz)),
+      ("remaining", format_countdown((event.start - now).num_seconds())),
+    ];
+    let args: Vec<(&str, &str)> = args.iter().map(|(name, value)| (*name, value.as_str())).collect();
+    lines.push(i18n::format(locale, Msg::ShareEvent, &args));
+  }
+  if lines.len() == 1 {
+    lines.push(i18n::text(locale, Msg::ShareNothing).to_string());
+  }
+  lines.join("\n")
+}
+
+/// Compact countdown for menus and tooltips: `1h 05m`, `12m`, `<1m`.
+pub fn format_countdown(secs: i64) -> String {
+  if secs < 60 {
+    return "<1m".to_string();
+  }
+  let hours = secs / 3600;
+  let minutes = (secs % 3600) / 60;
+  if hours > 0 {
+    format!("{}h {:02}m", hours, minutes)
+  } else {
+    format!("{}m", minutes)
+  }
+}
+
+/// Clock-style countdown matching the frontend's `formatCountdown`: `hh:mm:ss` or `mm:ss`.
+pub fn format_clock(secs: i64) -> String {
+  let secs = secs.max(0);
+  let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+  if h > 0 {
+    format!("{:02}:{:02}:{:02}", h, m, s)
+  } else {
+    format!("{:02}:{:02}", m, s)
+  }
+}
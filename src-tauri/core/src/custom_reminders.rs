@@ -0,0 +1,138 @@
+//! One-off reminders attached to a single event occurrence ("25 minutes before this
+//! particular world boss"), separate from the per-category lead times. They follow
+//! their event when a refresh moves it and go away when it is cancelled.
+
+use crate::cache::ScheduleDiff;
+use crate::json_store::JsonStore;
+use crate::reminders::FIRE_WINDOW;
+use crate::schedule::{EventKind, ScheduledEvent};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Longest lead time accepted for a one-off reminder.
+pub const MAX_MINUTES_BEFORE: i64 = 24 * 60;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomReminder {
+  pub id: u64,
+  pub kind: EventKind,
+  pub event_id: i64,
+  pub event_start: DateTime<Utc>,
+  pub minutes_before: i64,
+  pub fire_at: DateTime<Utc>,
+}
+
+impl CustomReminder {
+  /// Stable key for de-duplication, alongside the event reminder keys.
+  pub fn key(&self) -> String {
+    format!("custom:{}", self.id)
+  }
+
+  pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+    now >= self.fire_at && now <= self.fire_at + FIRE_WINDOW
+  }
+
+  fn expired(&self, now: DateTime<Utc>) -> bool {
+    now > self.fire_at + FIRE_WINDOW
+  }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Saved {
+  next_id: u64,
+  reminders: Vec<CustomReminder>,
+}
+
+/// The one-off reminders, persisted as JSON.
+#[derive(Debug, Default)]
+pub struct CustomReminderStore {
+  saved: JsonStore<Saved>,
+}
+
+impl CustomReminderStore {
+  pub fn load(path: PathBuf) -> Self {
+    Self { saved: JsonStore::load(path, "custom reminders") }
+  }
+
+  /// Reminders whose fire window has not passed, soonest first.
+  pub fn list(&mut self, now: DateTime<Utc>) -> Vec<CustomReminder> {
+    let before = self.saved.reminders.len();
+    self.saved.reminders.retain(|r| !r.expired(now));
+    if self.saved.reminders.len() != before {
+      self.saved.save();
+    }
+    let mut out = self.saved.reminders.clone();
+    out.sort_by_key(|r| r.fire_at);
+    out
+  }
+
+  /// Remind `minutes_before` the start of `event`. Adding the same reminder twice
+  /// returns the existing one.
+  pub fn add(&mut self, event: &ScheduledEvent, minutes_before: i64, now: DateTime<Utc>) -> Result<CustomReminder, String> {
+    if !(1..=MAX_MINUTES_BEFORE).contains(&minutes_before) {
+      return Err(format!("minutes before must be between 1 and {MAX_MINUTES_BEFORE}"));
+    }
+    let fire_at = event.start - ChronoDuration::minutes(minutes_before);
+    if fire_at <= now {
+      return Err(format!("event starts in less than {minutes_before} minutes"));
+    }
+    if let Some(existing) = self
+      .saved
+      .reminders
+      .iter()
+      .find(|r| r.kind == event.kind && r.event_id == event.id && r.minutes_before == minutes_before)
+    {
+      return Ok(existing.clone());
+    }
+
+    self.saved.next_id += 1;
+    let reminder = CustomReminder {
+      id: self.saved.next_id,
+      kind: event.kind,
+      event_id: event.id,
+      event_start: event.start,
+      minutes_before,
+      fire_at,
+    };
+    self.saved.reminders.push(reminder.clone());
+    self.saved.save();
+    Ok(reminder)
+  }
+
+  /// Returns whether a reminder with `id` existed.
+  pub fn remove(&mut self, id: u64) -> bool {
+    let before = self.saved.reminders.len();
+    self.saved.reminders.retain(|r| r.id != id);
+    let removed = self.saved.reminders.len() != before;
+    if removed {
+      self.saved.save();
+    }
+    removed
+  }
+
+  /// Move reminders along with shifted events and drop those of cancelled ones.
+  /// Returns whether anything changed.
+  pub fn reschedule(&mut self, changes: &ScheduleDiff) -> bool {
+    let mut changed = false;
+    for reminder in &mut self.saved.reminders {
+      if let Some(shift) = changes.shifted.iter().find(|s| s.kind == reminder.kind && s.id == reminder.event_id) {
+        reminder.event_start = shift.to;
+        reminder.fire_at = shift.to - ChronoDuration::minutes(reminder.minutes_before);
+        changed = true;
+      }
+    }
+    let before = self.saved.reminders.len();
+    self
+      .saved
+      .reminders
+      .retain(|r| !changes.cancelled.iter().any(|e| e.kind == r.kind && e.id == r.event_id));
+    changed |= self.saved.reminders.len() != before;
+    if changed {
+      self.saved.save();
+    }
+    changed
+  }
+}
@@ -0,0 +1,52 @@
+use crate::schedule::EventKind;
+use serde::Serialize;
+
+pub const SCHEME: &str = "helltime";
+
+/// Where a `helltime://` link should take the UI.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "route", rename_all = "snake_case")]
+pub enum DeepLink {
+  /// `helltime://` or `helltime://show`: just bring the window up.
+  Show,
+  /// `helltime://show-next-helltide`, `-legion`, `-world-boss`.
+  ShowNext { kind: EventKind },
+  /// `helltime://settings` or `helltime://settings/<section>`.
+  Settings { section: Option<String> },
+}
+
+fn kind_from_slug(slug: &str) -> Option<EventKind> {
+  match slug {
+    "helltide" => Some(EventKind::Helltide),
+    "legion" => Some(EventKind::Legion),
+    "world-boss" | "world_boss" | "worldboss" => Some(EventKind::WorldBoss),
+    _ => None,
+  }
+}
+
+/// Parse a `helltime://` URL. Unknown routes return `None` so a malformed or
+/// hostile link never does more than nothing.
+pub fn parse(url: &str) -> Option<DeepLink> {
+  let (scheme, rest) = url.trim().split_once("://")?;
+  if !scheme.eq_ignore_ascii_case(SCHEME) {
+    return None;
+  }
+
+  let path = rest.split(['?', '#']).next().unwrap_or_default().trim_matches('/').to_ascii_lowercase();
+  let mut segments = path.split('/').filter(|s| !s.is_empty());
+
+  match segments.next() {
+    None | Some("show") => Some(DeepLink::Show),
+    Some("settings") => {
+      let section = segments.next().filter(|s| s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'));
+      Some(DeepLink::Settings { section: section.map(str::to_string) })
+    }
+    Some(route) => route.strip_prefix("show-next-").and_then(kind_from_slug).map(|kind| DeepLink::ShowNext { kind }),
+  }
+}
+
+/// First `helltime://` argument on a command line, as passed by the OS when a link is opened.
+pub fn find_in_args<I: IntoIterator<Item = String>>(args: I) -> Option<String> {
+  let prefix = format!("{}://", SCHEME);
+  args.into_iter().find(|a| a.to_ascii_lowercase().starts_with(&prefix))
+}
@@ -0,0 +1,77 @@
+//! Discord IPC framing and activity payloads for Rich Presence.
+//!
+//! Discord's local RPC is a stream of frames `[opcode: u32 LE][len: u32 LE][json]`
+//! over a named pipe (Windows) or unix socket. Transport lives in the app.
+
+use crate::countdown::{format_countdown, NextEvent};
+use crate::i18n::{self, Locale, Msg};
+use crate::schedule::Titled;
+use chrono::DateTime;
+use serde_json::{json, Value};
+
+pub const OP_HANDSHAKE: u32 = 0;
+pub const OP_FRAME: u32 = 1;
+pub const OP_CLOSE: u32 = 2;
+
+/// Discord rejects activity updates faster than 5 per 20 seconds.
+pub const MIN_UPDATE_INTERVAL_SECS: u64 = 15;
+
+pub fn encode_frame(opcode: u32, payload: &Value) -> Vec<u8> {
+  let body = serde_json::to_vec(payload).unwrap_or_default();
+  let mut out = Vec::with_capacity(8 + body.len());
+  out.extend_from_slice(&opcode.to_le_bytes());
+  out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+  out.extend_from_slice(&body);
+  out
+}
+
+/// `(opcode, payload length)` from a frame header.
+pub fn decode_header(header: [u8; 8]) -> (u32, u32) {
+  let opcode = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+  let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+  (opcode, len)
+}
+
+pub fn handshake(client_id: &str) -> Value {
+  json!({ "v": 1, "client_id": client_id })
+}
+
+/// `SET_ACTIVITY` command; `activity: None` clears the presence.
+pub fn set_activity(pid: u32, activity: Option<Value>, nonce: u64) -> Value {
+  json!({
+    "cmd": "SET_ACTIVITY",
+    "args": { "pid": pid, "activity": activity },
+    "nonce": nonce.to_string(),
+  })
+}
+
+/// Presence for the soonest event: "Next Helltide in 12m" with a live countdown
+/// to its start and the other categories as the state line. The large image key
+/// is the event kind (`helltide`, `legion`, `world_boss`), uploaded as app assets.
+pub fn activity_for(locale: Locale, next: &[NextEvent]) -> Option<Value> {
+  let first = next.first()?;
+  let details = i18n::format(
+    locale,
+    Msg::PresenceNext,
+    &[("event", &first.title_in(locale)), ("countdown", &format_countdown(first.seconds_until))],
+  );
+  let mut activity = json!({
+    "details": details,
+    "assets": {
+      "large_image": first.kind.key(),
+      "large_text": i18n::event_label(locale, first.kind),
+    },
+  });
+
+  let others: Vec<String> = next[1..]
+    .iter()
+    .map(|e| format!("{} {}", e.title_in(locale), format_countdown(e.seconds_until)))
+    .collect();
+  if !others.is_empty() {
+    activity["state"] = json!(others.join(" · "));
+  }
+  if let Ok(start) = DateTime::parse_from_rfc3339(&first.start) {
+    activity["timestamps"] = json!({ "end": start.timestamp() });
+  }
+  Some(activity)
+}
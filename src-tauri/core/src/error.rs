@@ -0,0 +1,107 @@
+//! Errors returned by commands, with a category the UI can act on (retry later,
+//! point at the settings, fall back to the main window) besides the message. They
+//! serialize as `{ "kind": "network", "message": "…" }`, plus `retryAfterSecs` for
+//! [`AppError::RateLimited`].
+
+use crate::fetch::FetchError;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+  /// The API could not be reached, timed out or answered with an error status.
+  Network(String),
+  /// A response arrived but is not a usable schedule.
+  Parse(String),
+  /// Held back by the local rate limiter.
+  RateLimited { retry_after_secs: u64 },
+  /// The overlay window could not be created or changed.
+  OverlayUnavailable(String),
+  /// New settings were rejected, e.g. an invalid proxy URL or a missing folder.
+  InvalidSettings(String),
+  /// Stored settings (the sync file) could not be read back.
+  SettingsCorrupt(String),
+  /// Reading or writing a file failed.
+  Storage(String),
+  ShuttingDown,
+  Other(String),
+}
+
+impl AppError {
+  /// Stable identifier of the category, the `kind` field.
+  pub fn kind(&self) -> &'static str {
+    match self {
+      AppError::Network(_) => "network",
+      AppError::Parse(_) => "parse",
+      AppError::RateLimited { .. } => "rateLimited",
+      AppError::OverlayUnavailable(_) => "overlayUnavailable",
+      AppError::InvalidSettings(_) => "invalidSettings",
+      AppError::SettingsCorrupt(_) => "settingsCorrupt",
+      AppError::Storage(_) => "storage",
+      AppError::ShuttingDown => "shuttingDown",
+      AppError::Other(_) => "other",
+    }
+  }
+}
+
+impl fmt::Display for AppError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      AppError::Network(e)
+      | AppError::Parse(e)
+      | AppError::OverlayUnavailable(e)
+      | AppError::InvalidSettings(e)
+      | AppError::SettingsCorrupt(e)
+      | AppError::Storage(e)
+      | AppError::Other(e) => f.write_str(e),
+      AppError::RateLimited { retry_after_secs } => write!(f, "rate limited, next request in {retry_after_secs}s"),
+      AppError::ShuttingDown => f.write_str("shutting down"),
+    }
+  }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let retry_after = match self {
+      AppError::RateLimited { retry_after_secs } => Some(*retry_after_secs),
+      _ => None,
+    };
+    let mut out = serializer.serialize_struct("AppError", if retry_after.is_some() { 3 } else { 2 })?;
+    out.serialize_field("kind", self.kind())?;
+    out.serialize_field("message", &self.to_string())?;
+    if let Some(secs) = retry_after {
+      out.serialize_field("retryAfterSecs", &secs)?;
+    }
+    out.end()
+  }
+}
+
+impl From<FetchError> for AppError {
+  fn from(e: FetchError) -> Self {
+    match e {
+      FetchError::RateLimited(wait) => AppError::RateLimited { retry_after_secs: wait.as_secs().max(1) },
+      FetchError::TooLarge(_) | FetchError::UnexpectedContentType(_) | FetchError::InvalidJson(_) => {
+        AppError::Parse(e.to_string())
+      }
+      FetchError::Timeout | FetchError::BodyTimeout | FetchError::Network(_) | FetchError::Status(_) => {
+        AppError::Network(e.to_string())
+      }
+    }
+  }
+}
+
+/// Errors from code that still reports plain strings.
+impl From<String> for AppError {
+  fn from(e: String) -> Self {
+    AppError::Other(e)
+  }
+}
+
+/// For callers that still report plain strings.
+impl From<AppError> for String {
+  fn from(e: AppError) -> Self {
+    e.to_string()
+  }
+}
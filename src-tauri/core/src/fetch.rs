@@ -0,0 +1,232 @@
+use crate::clock;
+use crate::schedule::ScheduleResponse;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Bodies larger than this are refused; a schedule is a few kilobytes.
+pub const MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+
+/// The parts of an HTTP response the schedule fetch cares about.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+  pub status: u16,
+  /// Raw `Date` header, used for clock-skew estimation.
+  pub date: Option<String>,
+  /// All response headers, kept for the raw API inspector.
+  pub headers: Vec<(String, String)>,
+  pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+  /// First header named `name`, compared case-insensitively.
+  pub fn header(&self, name: &str) -> Option<&str> {
+    self.headers.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+  }
+}
+
+/// Exactly what the API sent for one request, for inspecting parse failures and
+/// attaching to bug reports.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawResponse {
+  pub url: String,
+  pub received_at: DateTime<Utc>,
+  pub status: u16,
+  pub headers: Vec<(String, String)>,
+  /// Body as text; invalid UTF-8 is replaced rather than dropped.
+  pub body: String,
+  /// Why the body did not parse as a schedule, if it did not.
+  pub parse_error: Option<String>,
+}
+
+impl RawResponse {
+  pub fn new(url: &str, resp: &HttpResponse, received_at: DateTime<Utc>) -> Self {
+    let parse_error = serde_json::from_slice::<ScheduleResponse>(&resp.body).err().map(|e| e.to_string());
+    Self {
+      url: url.to_string(),
+      received_at,
+      status: resp.status,
+      headers: resp.headers.clone(),
+      body: String::from_utf8_lossy(&resp.body).into_owned(),
+      parse_error,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FetchError {
+  /// No response headers in time (including connecting).
+  Timeout,
+  /// Headers arrived but the body did not finish in time.
+  BodyTimeout,
+  Network(String),
+  Status(u16),
+  /// The body exceeds the limit, which is carried.
+  TooLarge(usize),
+  /// A success response that is not JSON, e.g. a captive portal's login page.
+  UnexpectedContentType(String),
+  InvalidJson(String),
+  /// Refused locally by the rate limiter; carries the wait until the next slot.
+  RateLimited(Duration),
+}
+
+impl FetchError {
+  /// Timeouts, connection errors and server errors are worth another attempt;
+  /// client errors and unparsable bodies will not get better by retrying.
+  pub fn is_retryable(&self) -> bool {
+    match self {
+      FetchError::Timeout | FetchError::BodyTimeout | FetchError::Network(_) => true,
+      FetchError::Status(code) => *code >= 500 || *code == 429,
+      FetchError::TooLarge(_)
+      | FetchError::UnexpectedContentType(_)
+      | FetchError::InvalidJson(_)
+      | FetchError::RateLimited(_) => false,
+    }
+  }
+}
+
+impl fmt::Display for FetchError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      FetchError::Timeout => write!(f, "request timed out"),
+      FetchError::BodyTimeout => write!(f, "response body timed out"),
+      FetchError::Network(e) => write!(f, "request failed: {e}"),
+      FetchError::Status(code) => write!(f, "bad status: {code}"),
+      FetchError::TooLarge(limit) => write!(f, "response larger than {} KiB", limit / 1024),
+      FetchError::UnexpectedContentType(kind) => write!(f, "unexpected content type: {kind}"),
+      FetchError::InvalidJson(e) => write!(f, "invalid json: {e}"),
+      FetchError::RateLimited(wait) => write!(f, "rate limited, next request in {}s", wait.as_secs().max(1)),
+    }
+  }
+}
+
+impl std::error::Error for FetchError {}
+
+/// Minimal GET abstraction so the fetch/retry/parse path can be driven by
+/// recorded responses in tests instead of the network.
+pub trait HttpFetcher {
+  fn get(&self, url: &str) -> impl Future<Output = Result<HttpResponse, FetchError>> + Send;
+
+  /// Wait between retries. Replay fetchers keep the default and never sleep.
+  fn sleep(&self, _duration: Duration) -> impl Future<Output = ()> + Send {
+    async {}
+  }
+}
+
+/// Wraps a fetcher and keeps the last response it saw, whether or not it parsed.
+pub struct RecordingFetcher<'a, F> {
+  inner: &'a F,
+  last: Mutex<Option<RawResponse>>,
+}
+
+impl<'a, F: HttpFetcher + Sync> RecordingFetcher<'a, F> {
+  pub fn new(inner: &'a F) -> Self {
+    Self { inner, last: Mutex::new(None) }
+  }
+
+  pub fn into_last(self) -> Option<RawResponse> {
+    self.last.into_inner().unwrap_or_else(|e| e.into_inner())
+  }
+}
+
+impl<F: HttpFetcher + Sync> HttpFetcher for RecordingFetcher<'_, F> {
+  fn get(&self, url: &str) -> impl Future<Output = Result<HttpResponse, FetchError>> + Send {
+    let url = url.to_string();
+    async move {
+      let resp = self.inner.get(&url).await?;
+      *self.last.lock().unwrap_or_else(|e| e.into_inner()) = Some(RawResponse::new(&url, &resp, Utc::now()));
+      Ok(resp)
+    }
+  }
+
+  fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+    self.inner.sleep(duration)
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+  /// Total attempts including the first one.
+  pub attempts: u32,
+  /// Delay before the second attempt, doubled for each further one.
+  pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+  fn default() -> Self {
+    Self { attempts: 3, backoff: Duration::from_millis(500) }
+  }
+}
+
+/// A parsed schedule plus the timing data needed to estimate clock skew.
+#[derive(Debug, Clone)]
+pub struct FetchedSchedule {
+  pub schedule: ScheduleResponse,
+  pub sent_at: DateTime<Utc>,
+  pub received_at: DateTime<Utc>,
+  pub server_date: Option<DateTime<Utc>>,
+  /// How many attempts were needed (1 = first try succeeded).
+  pub attempts: u32,
+}
+
+/// `application/json`, `text/json` or a `+json` type, ignoring parameters. Mirrors
+/// serving static files sometimes send `text/plain`, which is let through as well.
+pub fn is_json(content_type: &str) -> bool {
+  let essence = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+  matches!(essence.as_str(), "application/json" | "text/json" | "text/plain") || essence.ends_with("+json")
+}
+
+async fn fetch_once<F: HttpFetcher>(fetcher: &F, url: &str) -> Result<FetchedSchedule, FetchError> {
+  let sent_at = Utc::now();
+  let resp = fetcher.get(url).await?;
+  let received_at = Utc::now();
+
+  if !(200..300).contains(&resp.status) {
+    return Err(FetchError::Status(resp.status));
+  }
+  if resp.body.len() > MAX_BODY_BYTES {
+    return Err(FetchError::TooLarge(MAX_BODY_BYTES));
+  }
+  if let Some(kind) = resp.header("content-type").filter(|kind| !is_json(kind)) {
+    return Err(FetchError::UnexpectedContentType(kind.to_string()));
+  }
+
+  let schedule = serde_json::from_slice(&resp.body).map_err(|e| FetchError::InvalidJson(e.to_string()))?;
+  Ok(FetchedSchedule {
+    schedule,
+    sent_at,
+    received_at,
+    server_date: resp.date.as_deref().and_then(clock::parse_http_date),
+    attempts: 1,
+  })
+}
+
+/// Fetch and parse the schedule, retrying transient failures per `policy`.
+pub async fn fetch_schedule<F: HttpFetcher>(
+  fetcher: &F,
+  url: &str,
+  policy: RetryPolicy,
+) -> Result<FetchedSchedule, FetchError> {
+  let attempts = policy.attempts.max(1);
+  let mut delay = policy.backoff;
+  let mut attempt = 1;
+  loop {
+    match fetch_once(fetcher, url).await {
+      Ok(mut fetched) => {
+        fetched.attempts = attempt;
+        return Ok(fetched);
+      }
+      Err(e) if attempt < attempts && e.is_retryable() => {
+        corelog!("⚠ Schedule fetch attempt {} failed ({}), retrying in {:?}", attempt, e, delay);
+        fetcher.sleep(delay).await;
+        delay *= 2;
+        attempt += 1;
+      }
+      Err(e) => return Err(e),
+    }
+  }
+}
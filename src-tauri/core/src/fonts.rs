@@ -0,0 +1,21 @@
+//! Installed font families for the overlay font picker. Enumeration is platform
+//! code in the app; cleaning up the names is shared.
+
+/// Trim, drop vertical (`@`-prefixed) and empty names, de-duplicate and sort
+/// case-insensitively.
+pub fn normalize(names: impl IntoIterator<Item = String>) -> Vec<String> {
+  let mut out: Vec<String> = names
+    .into_iter()
+    .map(|name| name.trim().to_string())
+    .filter(|name| !name.is_empty() && !name.starts_with('@'))
+    .collect();
+  out.sort_by_key(|name| name.to_lowercase());
+  out.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+  out
+}
+
+/// Family names from `fc-list : family`. A line lists a family's localized names
+/// separated by commas; the first one is used.
+pub fn parse_fc_list(output: &str) -> Vec<String> {
+  normalize(output.lines().filter_map(|line| line.split(',').next()).map(|name| name.replace("\\-", "-")))
+}
@@ -0,0 +1,36 @@
+//! Game mode: while Diablo IV runs, helltime does less in the background so it
+//! never costs the game frames. Detection parsing and throttling live here; the
+//! process lookup itself is platform code in the app.
+
+use std::time::Duration;
+
+/// Executable of the game (also under Wine/Proton, where `/proc/*/comm` shows it).
+pub const GAME_PROCESS: &str = "Diablo IV.exe";
+
+/// How often the process list is checked.
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Non-essential periodic work runs this many times less often while the game runs.
+pub const THROTTLE_FACTOR: u32 = 4;
+
+pub fn is_game_process(name: &str) -> bool {
+  name.trim().eq_ignore_ascii_case(GAME_PROCESS)
+}
+
+/// Whether `tasklist /FO CSV /NH` output lists the game. Each line starts with the
+/// quoted image name: `"Diablo IV.exe","1234","Console","1","2.345.678 K"`.
+pub fn tasklist_has_game(output: &str) -> bool {
+  output
+    .lines()
+    .filter_map(|line| line.trim().strip_prefix('"')?.split('"').next())
+    .any(is_game_process)
+}
+
+/// Interval for non-essential work (tooltip refresh and the like).
+pub fn throttled(interval: Duration, game_running: bool) -> Duration {
+  if game_running {
+    interval * THROTTLE_FACTOR
+  } else {
+    interval
+  }
+}
@@ -0,0 +1,78 @@
+//! Helltide timing: which one is open, the mystery chest resets inside it, and
+//! predicted starts past the end of the API's schedule. All of it follows the
+//! season's [`HelltideCadence`].
+
+use crate::cadence::HelltideCadence;
+use crate::schedule::{EventKind, ScheduleResponse, ScheduledEvent};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// The predictor keeps at least this many future Helltides in the schedule.
+pub const PREDICT_AHEAD: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChestReset {
+  /// Id of the Helltide the reset belongs to.
+  pub helltide_id: i64,
+  pub at: DateTime<Utc>,
+}
+
+impl ChestReset {
+  /// Stable key for de-duplication, alongside the event reminder keys.
+  pub fn key(&self) -> String {
+    format!("chest:{}:{}", self.helltide_id, self.at.timestamp())
+  }
+}
+
+pub fn is_active(helltide: &ScheduledEvent, cadence: &HelltideCadence, now: DateTime<Utc>) -> bool {
+  helltide.start <= now && now < helltide.end_or(cadence.duration())
+}
+
+/// The Helltide running at `now`, if any.
+pub fn active_helltide(schedule: &ScheduleResponse, cadence: &HelltideCadence, now: DateTime<Utc>) -> Option<ScheduledEvent> {
+  schedule.events(EventKind::Helltide).into_iter().rev().find(|h| is_active(h, cadence, now))
+}
+
+/// Chest resets of one Helltide, in order.
+pub fn resets_of(helltide: &ScheduledEvent, cadence: &HelltideCadence) -> Vec<ChestReset> {
+  let end = helltide.end_or(cadence.duration());
+  std::iter::successors(Some(helltide.start + cadence.chest_offset()), |at| Some(*at + cadence.chest_interval()))
+    .take_while(|at| *at < end)
+    .map(|at| ChestReset { helltide_id: helltide.id, at })
+    .collect()
+}
+
+/// All chest resets after `now` across the scheduled Helltides, soonest first.
+pub fn upcoming_resets(schedule: &ScheduleResponse, cadence: &HelltideCadence, now: DateTime<Utc>) -> Vec<ChestReset> {
+  let mut out: Vec<ChestReset> = schedule
+    .events(EventKind::Helltide)
+    .iter()
+    .filter(|h| h.end_or(cadence.duration()) > now)
+    .flat_map(|h| resets_of(h, cadence))
+    .filter(|r| r.at > now)
+    .collect();
+  out.sort_by_key(|r| r.at);
+  out
+}
+
+/// Append predicted Helltides (`"predicted": true`) after the last scheduled one
+/// until [`PREDICT_AHEAD`] start after `now`. Does nothing without any known start.
+pub fn extrapolate(schedule: &mut ScheduleResponse, cadence: &HelltideCadence, now: DateTime<Utc>) {
+  let known = schedule.events(EventKind::Helltide);
+  let Some(mut last) = known.last().map(|h| h.start) else { return };
+  let mut upcoming = known.iter().filter(|h| h.start > now).count();
+  while upcoming < PREDICT_AHEAD {
+    last += cadence.interval();
+    if last <= now {
+      continue;
+    }
+    schedule.helltide.push(serde_json::json!({
+      "id": last.timestamp(),
+      "timestamp": last.timestamp(),
+      "startTime": last.to_rfc3339(),
+      "predicted": true,
+    }));
+    upcoming += 1;
+  }
+}
@@ -0,0 +1,190 @@
+use crate::json_store::JsonStore;
+use crate::schedule::{EventKind, ScheduledEvent};
+use chrono::{DateTime, Duration as ChronoDuration, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Occurrences older than this are pruned on every write.
+const RETENTION_DAYS: i64 = 90;
+
+/// How many boss spawns the rotation history in `event_stats` includes.
+const BOSS_ROTATION_LEN: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+  pub kind: EventKind,
+  pub id: i64,
+  pub start: DateTime<Utc>,
+  #[serde(default)]
+  pub boss: Option<String>,
+  #[serde(default)]
+  pub zone: Option<String>,
+  pub observed_at: DateTime<Utc>,
+  /// The user said they were there (`mark_attending`).
+  #[serde(default)]
+  pub attended: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HistoryRange {
+  #[serde(default)]
+  pub from: Option<DateTime<Utc>>,
+  #[serde(default)]
+  pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BossSighting {
+  pub start: DateTime<Utc>,
+  pub boss: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EventStats {
+  pub total: HashMap<EventKind, usize>,
+  /// Occurrences that started today (local calendar day).
+  pub today: HashMap<EventKind, usize>,
+  /// Mean gap between consecutive starts, per category.
+  pub average_interval_secs: HashMap<EventKind, i64>,
+  /// Occurrences the user marked as attended.
+  pub attended: HashMap<EventKind, usize>,
+  /// Most recent world boss spawns, oldest first.
+  pub boss_rotation: Vec<BossSighting>,
+}
+
+/// Log of every event occurrence seen in the schedule, persisted as JSON.
+#[derive(Debug, Default)]
+pub struct EventHistory {
+  entries: JsonStore<Vec<HistoryEntry>>,
+}
+
+impl EventHistory {
+  pub fn load(path: PathBuf) -> Self {
+    Self { entries: JsonStore::load(path, "event history") }
+  }
+
+  /// Record newly observed occurrences. Known (kind, id) pairs take over a moved
+  /// start and a boss or zone the API reveals later; a name once seen is kept.
+  pub fn record(&mut self, events: &[ScheduledEvent], now: DateTime<Utc>) {
+    let mut changed = 0;
+    for event in events {
+      // Predicted names are guesses; never feed them back into the rotation history.
+      let boss = if event.boss_predicted { None } else { event.boss.clone() };
+      if let Some(entry) = self.entries.iter_mut().find(|e| e.kind == event.kind && e.id == event.id) {
+        let updated = HistoryEntry {
+          start: event.start,
+          boss: boss.or_else(|| entry.boss.clone()),
+          zone: event.zone.clone().or_else(|| entry.zone.clone()),
+          ..entry.clone()
+        };
+        if *entry != updated {
+          *entry = updated;
+          changed += 1;
+        }
+        continue;
+      }
+      self.entries.push(HistoryEntry {
+        kind: event.kind,
+        id: event.id,
+        start: event.start,
+        boss,
+        zone: event.zone.clone(),
+        observed_at: now,
+        attended: false,
+      });
+      changed += 1;
+    }
+
+    let cutoff = now - ChronoDuration::days(RETENTION_DAYS);
+    let before = self.entries.len();
+    self.entries.retain(|e| e.start >= cutoff);
+
+    if changed > 0 || self.entries.len() != before {
+      self.entries.sort_by_key(|e| e.start);
+      self.entries.save();
+    }
+  }
+
+  /// Mark the occurrence `id` as attended. Without `kind` the id has to be unique
+  /// across the categories.
+  pub fn mark_attended(&mut self, kind: Option<EventKind>, id: i64) -> Result<HistoryEntry, String> {
+    let mut matches = self.entries.iter_mut().filter(|e| e.id == id && kind.is_none_or(|kind| e.kind == kind));
+    let entry = matches.next().ok_or_else(|| format!("no event with id {id} in the history"))?;
+    if matches.next().is_some() {
+      return Err(format!("event id {id} is ambiguous, pass its kind"));
+    }
+    let changed = !entry.attended;
+    entry.attended = true;
+    let entry = entry.clone();
+    if changed {
+      self.entries.save();
+    }
+    Ok(entry)
+  }
+
+  pub fn attended(&self, kind: EventKind, id: i64) -> bool {
+    self.entries.iter().any(|e| e.kind == kind && e.id == id && e.attended)
+  }
+
+  /// Entries whose start lies within the range (both bounds inclusive and optional).
+  pub fn query(&self, range: &HistoryRange) -> Vec<HistoryEntry> {
+    self
+      .entries
+      .iter()
+      .filter(|e| range.from.is_none_or(|from| e.start >= from))
+      .filter(|e| range.to.is_none_or(|to| e.start <= to))
+      .cloned()
+      .collect()
+  }
+
+  /// All recorded world boss sightings with a known name, oldest first.
+  pub fn boss_sequence(&self) -> Vec<(DateTime<Utc>, String)> {
+    self
+      .entries
+      .iter()
+      .filter(|e| e.kind == EventKind::WorldBoss)
+      .filter_map(|e| e.boss.clone().map(|boss| (e.start, boss)))
+      .collect()
+  }
+
+  /// Aggregate statistics over occurrences that have already started.
+  pub fn stats(&self, now: DateTime<Utc>) -> EventStats {
+    let today = now.with_timezone(&Local).date_naive();
+    let past: Vec<&HistoryEntry> = self.entries.iter().filter(|e| e.start <= now).collect();
+
+    let mut total = HashMap::new();
+    let mut today_counts = HashMap::new();
+    let mut average_interval_secs = HashMap::new();
+    let mut attended = HashMap::new();
+
+    for kind in EventKind::ALL {
+      attended.insert(kind, self.entries.iter().filter(|e| e.kind == kind && e.attended).count());
+      let starts: Vec<DateTime<Utc>> = past.iter().filter(|e| e.kind == kind).map(|e| e.start).collect();
+      total.insert(kind, starts.len());
+      today_counts.insert(
+        kind,
+        starts.iter().filter(|s| s.with_timezone(&Local).date_naive() == today).count(),
+      );
+      if starts.len() >= 2 {
+        let span = (starts[starts.len() - 1] - starts[0]).num_seconds();
+        average_interval_secs.insert(kind, span / (starts.len() as i64 - 1));
+      }
+    }
+
+    let bosses: Vec<BossSighting> = past
+      .iter()
+      .filter(|e| e.kind == EventKind::WorldBoss)
+      .filter_map(|e| e.boss.clone().map(|boss| BossSighting { start: e.start, boss }))
+      .collect();
+    let skip = bosses.len().saturating_sub(BOSS_ROTATION_LEN);
+
+    EventStats {
+      total,
+      today: today_counts,
+      average_interval_secs,
+      attended,
+      boss_rotation: bosses.into_iter().skip(skip).collect(),
+    }
+  }
+}
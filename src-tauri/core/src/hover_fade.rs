@@ -0,0 +1,50 @@
+//! Fade the overlay while the cursor rests on it, so it never hides what is
+//! underneath. The overlay is click-through and gets no mouse events, so the app
+//! samples the cursor position and feeds it to [`HoverFade`].
+
+use std::time::{Duration, Instant};
+
+/// How long the cursor has to stay on the overlay before it fades.
+pub const DELAY: Duration = Duration::from_millis(300);
+
+/// How often the cursor position is sampled while the option is on.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Opacity of the faded overlay.
+pub const FADED_OPACITY: f64 = 0.2;
+
+#[derive(Debug, Default)]
+pub struct HoverFade {
+  /// When the cursor entered the overlay, while it stays inside.
+  entered: Option<Instant>,
+  faded: bool,
+}
+
+impl HoverFade {
+  /// Feed one cursor sample. Returns the new faded state when it changes: faded
+  /// once the cursor stayed inside for [`DELAY`], back to normal as soon as it left.
+  pub fn observe(&mut self, inside: bool, now: Instant) -> Option<bool> {
+    let faded = if inside {
+      let entered = *self.entered.get_or_insert(now);
+      now.duration_since(entered) >= DELAY
+    } else {
+      self.entered = None;
+      false
+    };
+    (faded != self.faded).then(|| {
+      self.faded = faded;
+      faded
+    })
+  }
+
+  /// Forget the cursor, e.g. when the overlay was hidden. Returns `Some(false)` if
+  /// it was faded.
+  pub fn reset(&mut self) -> Option<bool> {
+    self.entered = None;
+    std::mem::take(&mut self.faded).then_some(false)
+  }
+
+  pub fn faded(&self) -> bool {
+    self.faded
+  }
+}
@@ -0,0 +1,173 @@
+//! Diablo IV HUD layouts, so the overlay can be placed where it covers neither the
+//! globes, the skill bar nor the minimap. Each layout has its HUD elements and a few
+//! named safe zones; areas are fractions of the monitor (measured at 16:9), so the
+//! presets work at any resolution. Positions are physical pixels, like the overlay's.
+
+use serde::{Deserialize, Serialize};
+
+/// The HUD arrangement picked in the game's options.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HudLayout {
+  /// Skill bar centered between the health and resource globes.
+  #[default]
+  Default,
+  /// Skill bar and globes moved to the bottom left.
+  LeftSkillBar,
+}
+
+/// Part of the screen as fractions of the monitor's width and height.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Area {
+  pub left: f64,
+  pub top: f64,
+  pub right: f64,
+  pub bottom: f64,
+}
+
+const fn area(left: f64, top: f64, right: f64, bottom: f64) -> Area {
+  Area { left, top, right, bottom }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HudElement {
+  pub id: &'static str,
+  pub area: Area,
+}
+
+/// Where the overlay fits without covering the HUD. It is placed inside `area` at
+/// `align` (0 = left/top, 0.5 = centered, 1 = right/bottom).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SafeZone {
+  pub id: &'static str,
+  pub area: Area,
+  pub align: (f64, f64),
+}
+
+/// A monitor or window in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+  pub x: i32,
+  pub y: i32,
+  pub width: u32,
+  pub height: u32,
+}
+
+impl Rect {
+  /// Whether the point (e.g. the cursor) lies inside.
+  pub fn contains(&self, x: f64, y: f64) -> bool {
+    let (left, top) = (self.x as f64, self.y as f64);
+    (left..left + self.width as f64).contains(&x) && (top..top + self.height as f64).contains(&y)
+  }
+
+  fn scale(&self, area: &Area) -> (f64, f64, f64, f64) {
+    let (w, h) = (self.width as f64, self.height as f64);
+    (
+      self.x as f64 + area.left * w,
+      self.y as f64 + area.top * h,
+      self.x as f64 + area.right * w,
+      self.y as f64 + area.bottom * h,
+    )
+  }
+
+  fn overlaps(&self, (left, top, right, bottom): (f64, f64, f64, f64)) -> bool {
+    let (x, y) = (self.x as f64, self.y as f64);
+    x < right && left < x + self.width as f64 && y < bottom && top < y + self.height as f64
+  }
+}
+
+const MINIMAP: HudElement = HudElement { id: "minimap", area: area(0.83, 0.0, 1.0, 0.30) };
+const OBJECTIVES: HudElement = HudElement { id: "objectives", area: area(0.80, 0.30, 1.0, 0.55) };
+const PARTY: HudElement = HudElement { id: "party", area: area(0.0, 0.25, 0.12, 0.50) };
+
+const DEFAULT_ELEMENTS: [HudElement; 7] = [
+  MINIMAP,
+  OBJECTIVES,
+  PARTY,
+  HudElement { id: "chat", area: area(0.0, 0.68, 0.24, 0.92) },
+  HudElement { id: "health_globe", area: area(0.28, 0.80, 0.37, 1.0) },
+  HudElement { id: "skill_bar", area: area(0.37, 0.80, 0.63, 1.0) },
+  HudElement { id: "resource_globe", area: area(0.63, 0.80, 0.72, 1.0) },
+];
+
+const LEFT_SKILL_BAR_ELEMENTS: [HudElement; 7] = [
+  MINIMAP,
+  OBJECTIVES,
+  PARTY,
+  HudElement { id: "chat", area: area(0.0, 0.55, 0.24, 0.79) },
+  HudElement { id: "health_globe", area: area(0.01, 0.80, 0.10, 1.0) },
+  HudElement { id: "skill_bar", area: area(0.10, 0.80, 0.36, 1.0) },
+  HudElement { id: "resource_globe", area: area(0.36, 0.80, 0.45, 1.0) },
+];
+
+const TOP_LEFT: SafeZone = SafeZone { id: "top_left", area: area(0.01, 0.02, 0.30, 0.24), align: (0.0, 0.0) };
+const TOP_CENTER: SafeZone = SafeZone { id: "top_center", area: area(0.35, 0.02, 0.65, 0.16), align: (0.5, 0.0) };
+const RIGHT_MIDDLE: SafeZone = SafeZone { id: "right_middle", area: area(0.80, 0.57, 0.99, 0.78), align: (1.0, 0.0) };
+
+const DEFAULT_ZONES: [SafeZone; 4] = [
+  TOP_LEFT,
+  TOP_CENTER,
+  RIGHT_MIDDLE,
+  SafeZone { id: "bottom_right", area: area(0.73, 0.80, 0.99, 0.98), align: (1.0, 1.0) },
+];
+
+const LEFT_SKILL_BAR_ZONES: [SafeZone; 5] = [
+  TOP_LEFT,
+  TOP_CENTER,
+  RIGHT_MIDDLE,
+  SafeZone { id: "bottom_center", area: area(0.46, 0.80, 0.79, 0.98), align: (0.5, 1.0) },
+  SafeZone { id: "bottom_right", area: area(0.80, 0.80, 0.99, 0.98), align: (1.0, 1.0) },
+];
+
+impl HudLayout {
+  pub fn elements(self) -> &'static [HudElement] {
+    match self {
+      HudLayout::Default => &DEFAULT_ELEMENTS,
+      HudLayout::LeftSkillBar => &LEFT_SKILL_BAR_ELEMENTS,
+    }
+  }
+
+  pub fn safe_zones(self) -> &'static [SafeZone] {
+    match self {
+      HudLayout::Default => &DEFAULT_ZONES,
+      HudLayout::LeftSkillBar => &LEFT_SKILL_BAR_ZONES,
+    }
+  }
+}
+
+/// A safe zone the overlay fits in, with the top-left corner to move it to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Suggestion {
+  pub zone: &'static str,
+  pub x: i32,
+  pub y: i32,
+}
+
+/// Where an overlay of `size` goes in each safe zone of `layout` it fits in on
+/// `monitor`, in the layout's order.
+pub fn suggest(layout: HudLayout, monitor: Rect, size: (u32, u32)) -> Vec<Suggestion> {
+  layout
+    .safe_zones()
+    .iter()
+    .filter_map(|zone| {
+      let (left, top, right, bottom) = monitor.scale(&zone.area);
+      let (free_x, free_y) = (right - left - size.0 as f64, bottom - top - size.1 as f64);
+      if free_x < 0.0 || free_y < 0.0 {
+        return None;
+      }
+      let x = (left + free_x * zone.align.0).round() as i32;
+      let y = (top + free_y * zone.align.1).round() as i32;
+      Some(Suggestion { zone: zone.id, x, y })
+    })
+    .collect()
+}
+
+/// Ids of the HUD elements of `layout` that `overlay` covers on `monitor`.
+pub fn covered(layout: HudLayout, monitor: Rect, overlay: Rect) -> Vec<&'static str> {
+  layout
+    .elements()
+    .iter()
+    .filter(|element| overlay.overlaps(monitor.scale(&element.area)))
+    .map(|element| element.id)
+    .collect()
+}
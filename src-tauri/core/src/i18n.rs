@@ -0,0 +1,253 @@
+//! Text the backend shows by itself: tray menu, tray tooltip, window titles, the
+//! overlay's positioning labels, the Discord presence and event names. The web UI
+//! translates its own strings, including notifications, which are sent from there.
+//!
+//! The strings live in one JSON bundle per locale under `assets/locales`, compiled
+//! into the binary.
+
+use crate::schedule::EventKind;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Windows tray tooltips hold 128 UTF-16 units including the terminator.
+pub const TRAY_TOOLTIP_MAX_UTF16: usize = 127;
+
+/// FIRST STRONG ISOLATE and POP DIRECTIONAL ISOLATE.
+const FSI: char = '\u{2068}';
+const PDI: char = '\u{2069}';
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Locale {
+  #[default]
+  En,
+  De,
+}
+
+impl Locale {
+  pub const ALL: [Locale; 2] = [Locale::En, Locale::De];
+
+  /// BCP 47 language tag.
+  pub fn tag(self) -> &'static str {
+    match self {
+      Locale::En => "en",
+      Locale::De => "de",
+    }
+  }
+
+  /// Match a BCP 47 or POSIX locale name ("de-AT", "de_DE.UTF-8", "en") by its
+  /// language. `C`/`POSIX` and unknown languages give `None`.
+  pub fn from_tag(tag: &str) -> Option<Locale> {
+    let language = tag.split(['-', '_', '.', '@']).next()?.to_ascii_lowercase();
+    match language.as_str() {
+      "en" => Some(Locale::En),
+      "de" => Some(Locale::De),
+      _ => None,
+    }
+  }
+
+  /// First supported locale among the system's preferred ones, else English.
+  pub fn detect<'a>(candidates: impl IntoIterator<Item = &'a str>) -> Locale {
+    candidates.into_iter().find_map(Locale::from_tag).unwrap_or_default()
+  }
+}
+
+/// Declares [`Msg`] together with the key each message has in the bundles.
+macro_rules! messages {
+  ($($(#[$doc:meta])* $name:ident = $key:literal,)*) => {
+    /// Backend strings. Placeholders in braces are filled by [`format`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Msg {
+      $($(#[$doc])* $name,)*
+    }
+
+    impl Msg {
+      pub const ALL: &'static [Msg] = &[$(Msg::$name),*];
+
+      /// Key in the locale bundles (`assets/locales/*.json`).
+      pub fn key(self) -> &'static str {
+        match self {
+          $(Msg::$name => $key,)*
+        }
+      }
+    }
+  };
+}
+
+messages! {
+  MenuRestore = "menu_restore",
+  MenuOverlay = "menu_overlay",
+  MenuReminder = "menu_reminder",
+  MenuPauseReminders = "menu_pause_reminders",
+  MenuPause30m = "menu_pause_30m",
+  MenuPause1h = "menu_pause_1h",
+  MenuPauseTomorrow = "menu_pause_tomorrow",
+  MenuResume = "menu_resume",
+  MenuProfiles = "menu_profiles",
+  MenuNoProfiles = "menu_no_profiles",
+  MenuCopyDiagnostics = "menu_copy_diagnostics",
+  MenuCopyNextEvents = "menu_copy_next_events",
+  MenuPeekNext = "menu_peek_next",
+  MenuRevealOverlay = "menu_reveal_overlay",
+  MenuExportCalendar = "menu_export_calendar",
+  MenuCheckUpdates = "menu_check_updates",
+  MenuUpdateAvailable = "menu_update_available",
+  MenuExit = "menu_exit",
+  /// `{time}`: local time of the last successful fetch.
+  TooltipDataFrom = "tooltip_data_from",
+  TooltipConnectionProblems = "tooltip_connection_problems",
+  TooltipOffline = "tooltip_offline",
+  /// `{remaining}`: countdown until reminders resume.
+  TooltipRemindersPaused = "tooltip_reminders_paused",
+  /// `{event}` title, `{remaining}` time until a running event ends.
+  TooltipActive = "tooltip_active",
+  OverlayTitle = "overlay_title",
+  /// Accessible name of the overlay's drag handle while it is being positioned.
+  OverlayMove = "overlay_move",
+  OverlayDragHint = "overlay_drag_hint",
+  /// Short form of [`Msg::OverlayDragHint`] for the toast-sized placeholder.
+  OverlayDrag = "overlay_drag",
+  OverlayError = "overlay_error",
+  /// Discord presence details; `{event}` title, `{countdown}` time until start.
+  PresenceNext = "presence_next",
+  /// Title of a coalesced notification; `{count}` reminders in it.
+  NotificationSummary = "notification_summary",
+  /// Notification button that marks the event as attended and mutes its reminders.
+  NotificationAttend = "notification_attend",
+  /// Label of a notification's click action where the daemon shows one (Linux).
+  NotificationOpen = "notification_open",
+  /// Body of the phone push settings' test message.
+  PushTest = "push_test",
+  /// Reminder shown by the backend without the UI; `{minutes}` until the start.
+  ReminderStartsIn = "reminder_starts_in",
+  /// Backend reminder before a Helltide closes; `{minutes}` until the end.
+  ReminderEndsIn = "reminder_ends_in",
+  /// Backend reminder before a mystery chest reset; `{minutes}` until then.
+  ReminderChestIn = "reminder_chest_in",
+  /// First line of the copied event times; `{zone}` like `UTC+02:00`.
+  ShareHeader = "share_header",
+  /// `{event}` title, local start `{time}`, `{remaining}` until then.
+  ShareEvent = "share_event",
+  ShareNothing = "share_nothing",
+  WebviewMissingTitle = "webview_missing_title",
+  /// `{url}` to download the runtime, `{error}` as reported.
+  WebviewMissingBody = "webview_missing_body",
+}
+
+/// One locale's strings, keyed like [`Msg::key`] plus `event_<kind>` for the
+/// category names.
+pub type Bundle = HashMap<String, String>;
+
+/// The bundle of `locale`, parsed on first use.
+pub fn bundle(locale: Locale) -> &'static Bundle {
+  static EN: OnceLock<Bundle> = OnceLock::new();
+  static DE: OnceLock<Bundle> = OnceLock::new();
+  let (cell, json) = match locale {
+    Locale::En => (&EN, include_str!("../assets/locales/en.json")),
+    Locale::De => (&DE, include_str!("../assets/locales/de.json")),
+  };
+  // The bundles are compiled in and covered by the tests, so a parse error is a build defect.
+  cell.get_or_init(|| serde_json::from_str(json).expect("locale bundle is valid JSON"))
+}
+
+/// `key` in `locale`, falling back to English and then to the key itself.
+fn lookup(locale: Locale, key: &str) -> &str {
+  bundle(locale)
+    .get(key)
+    .or_else(|| bundle(Locale::En).get(key))
+    .map(String::as_str)
+    .unwrap_or(key)
+}
+
+pub fn text(locale: Locale, msg: Msg) -> &'static str {
+  lookup(locale, msg.key())
+}
+
+/// [`text`] with `{name}` placeholders replaced.
+pub fn format(locale: Locale, msg: Msg, args: &[(&str, &str)]) -> String {
+  args.iter().fold(text(locale, msg).to_string(), |s, (name, value)| s.replace(&format!("{{{name}}}"), value))
+}
+
+/// Event category name, used when the API sends no better title.
+pub fn event_label(locale: Locale, kind: EventKind) -> &'static str {
+  match bundle(locale).get(&format!("event_{}", kind.key())) {
+    Some(label) => label,
+    None => kind.label(),
+  }
+}
+
+/// The overlay window's own text, fetched once per locale change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayTexts {
+  pub move_label: &'static str,
+  pub drag_hint: &'static str,
+  pub drag: &'static str,
+  pub error: &'static str,
+  /// Category titles for events the API sends no better title for.
+  pub labels: HashMap<EventKind, &'static str>,
+}
+
+impl OverlayTexts {
+  pub fn new(locale: Locale) -> Self {
+    Self {
+      move_label: text(locale, Msg::OverlayMove),
+      drag_hint: text(locale, Msg::OverlayDragHint),
+      drag: text(locale, Msg::OverlayDrag),
+      error: text(locale, Msg::OverlayError),
+      labels: EventKind::ALL.into_iter().map(|kind| (kind, event_label(locale, kind))).collect(),
+    }
+  }
+}
+
+/// Cut `text` to at most `max_units` UTF-16 code units without splitting a
+/// surrogate pair or an [`isolate`], marking the cut with an ellipsis.
+pub fn truncate_utf16(text: &str, max_units: usize) -> String {
+  if text.encode_utf16().count() <= max_units {
+    return text.to_string();
+  }
+  let budget = max_units.saturating_sub(1);
+  let mut used = 0;
+  let mut open = 0;
+  let mut out = String::new();
+  for c in text.chars() {
+    // Room for the ellipsis and the PDIs still owed.
+    if used + c.len_utf16() + open + usize::from(c == FSI) > budget {
+      break;
+    }
+    match c {
+      FSI => open += 1,
+      PDI => open = open.saturating_sub(1),
+      _ => {}
+    }
+    used += c.len_utf16();
+    out.push(c);
+  }
+  if max_units > 0 {
+    out.push('…');
+  }
+  out.extend(std::iter::repeat_n(PDI, open));
+  out
+}
+
+/// Whether `c` is a strong right-to-left character (Hebrew, Arabic, Syriac, Thaana,
+/// N'Ko and their presentation forms).
+fn is_rtl(c: char) -> bool {
+  matches!(
+    c,
+    '\u{0590}'..='\u{08FF}' | '\u{FB1D}'..='\u{FDFF}' | '\u{FE70}'..='\u{FEFF}' | '\u{10800}'..='\u{10FFF}' | '\u{1E800}'..='\u{1EFFF}'
+  )
+}
+
+/// `text` wrapped in a bidi isolate when it contains right-to-left characters, so
+/// an Arabic or Hebrew zone name keeps its own direction inside a left-to-right line
+/// and does not reorder the words around it.
+pub fn isolate(text: &str) -> Cow<'_, str> {
+  if text.chars().any(is_rtl) {
+    Cow::Owned(format!("{FSI}{text}{PDI}"))
+  } else {
+    Cow::Borrowed(text)
+  }
+}
@@ -0,0 +1,189 @@
+//! iCalendar (RFC 5545) export of upcoming events, for Outlook, Google Calendar
+//! and other calendar apps.
+
+use crate::cadence::HelltideCadence;
+use crate::clock::SecondaryZone;
+use crate::countdown::event_duration;
+use crate::i18n::{self, Locale};
+use crate::schedule::{EventKind, ScheduleResponse, ScheduledEvent, Titled};
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+
+/// Export window when the caller does not ask for one.
+pub const DEFAULT_HOURS_AHEAD: u32 = 48;
+/// The API only knows a few days ahead; longer windows are capped.
+pub const MAX_HOURS_AHEAD: u32 = 24 * 14;
+/// Lead time of the calendar alarm attached to every event.
+pub const ALARM_MINUTES: i64 = 10;
+
+/// How often subscribed calendar apps are asked to re-fetch the feed.
+pub const FEED_REFRESH_MINUTES: u32 = 30;
+
+/// Lines longer than this many octets are folded (RFC 5545 §3.1).
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Events of `kinds` starting within `hours_ahead` hours after `now`, sorted by start.
+pub fn upcoming(schedule: &ScheduleResponse, kinds: &[EventKind], now: DateTime<Utc>, hours_ahead: u32) -> Vec<ScheduledEvent> {
+  let until = now + Duration::hours(hours_ahead.min(MAX_HOURS_AHEAD) as i64);
+  let mut events: Vec<ScheduledEvent> = kinds
+    .iter()
+    .flat_map(|kind| schedule.events(*kind))
+    .filter(|e| e.start > now && e.start <= until)
+    .collect();
+  events.sort_by_key(|e| (e.start, e.kind.key()));
+  events
+}
+
+/// A complete VCALENDAR with one VEVENT (and a display VALARM) per event. UIDs are
+/// derived from category and id, so re-importing updates instead of duplicating.
+/// With a secondary zone, times are written in it (with its VTIMEZONE) instead of UTC.
+pub fn calendar(
+  events: &[ScheduledEvent],
+  locale: Locale,
+  cadence: &HelltideCadence,
+  stamp: DateTime<Utc>,
+  zone: Option<&SecondaryZone>,
+) -> String {
+  render(events, locale, cadence, stamp, zone, None)
+}
+
+/// [`calendar`] for subscriptions (`webcal://`), with a refresh hint so calendar
+/// apps pick up new and changed events.
+pub fn feed(
+  events: &[ScheduledEvent],
+  locale: Locale,
+  cadence: &HelltideCadence,
+  stamp: DateTime<Utc>,
+  zone: Option<&SecondaryZone>,
+) -> String {
+  render(events, locale, cadence, stamp, zone, Some(FEED_REFRESH_MINUTES))
+}
+
+fn render(
+  events: &[ScheduledEvent],
+  locale: Locale,
+  cadence: &HelltideCadence,
+  stamp: DateTime<Utc>,
+  zone: Option<&SecondaryZone>,
+  refresh_minutes: Option<u32>,
+) -> String {
+  let mut out = String::new();
+  for line in ["BEGIN:VCALENDAR", "VERSION:2.0", "PRODID:-//helltime//helltime//EN", "CALSCALE:GREGORIAN", "METHOD:PUBLISH"] {
+    push_line(&mut out, line);
+  }
+  push_line(&mut out, "X-WR-CALNAME:helltime");
+  if let Some(minutes) = refresh_minutes {
+    // RFC 7986 property plus the older one Outlook and Google understand.
+    push_line(&mut out, &format!("REFRESH-INTERVAL;VALUE=DURATION:PT{minutes}M"));
+    push_line(&mut out, &format!("X-PUBLISHED-TTL:PT{minutes}M"));
+  }
+  let zone = zone.and_then(|zone| Some((zone, zone.offset()?)));
+  if let Some((zone, offset)) = zone {
+    push_timezone(&mut out, zone, offset);
+  }
+
+  for event in events {
+    let summary = escape(&event.title_in(locale));
+    let end = event.start + event_duration(event.kind, cadence);
+    push_line(&mut out, "BEGIN:VEVENT");
+    push_line(&mut out, &format!("UID:{}-{}@helltime", event.kind.key(), event.id));
+    push_line(&mut out, &format!("DTSTAMP:{}", timestamp(stamp)));
+    match zone {
+      Some((_, offset)) => {
+        let tzid = tzid(offset);
+        push_line(&mut out, &format!("DTSTART;TZID={tzid}:{}", local_timestamp(event.start, offset)));
+        push_line(&mut out, &format!("DTEND;TZID={tzid}:{}", local_timestamp(end, offset)));
+      }
+      None => {
+        push_line(&mut out, &format!("DTSTART:{}", timestamp(event.start)));
+        push_line(&mut out, &format!("DTEND:{}", timestamp(end)));
+      }
+    }
+    push_line(&mut out, &format!("SUMMARY:{summary}"));
+    push_line(&mut out, &format!("CATEGORIES:{}", escape(i18n::event_label(locale, event.kind))));
+    if event.predicted {
+      // Extrapolated from the cadence, not reported by the API.
+      push_line(&mut out, "STATUS:TENTATIVE");
+    }
+    push_line(&mut out, "TRANSP:TRANSPARENT");
+    push_line(&mut out, "BEGIN:VALARM");
+    push_line(&mut out, "ACTION:DISPLAY");
+    push_line(&mut out, &format!("DESCRIPTION:{summary}"));
+    push_line(&mut out, &format!("TRIGGER:-PT{ALARM_MINUTES}M"));
+    push_line(&mut out, "END:VALARM");
+    push_line(&mut out, "END:VEVENT");
+  }
+
+  push_line(&mut out, "END:VCALENDAR");
+  out
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+  at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn local_timestamp(at: DateTime<Utc>, offset: FixedOffset) -> String {
+  at.with_timezone(&offset).format("%Y%m%dT%H%M%S").to_string()
+}
+
+/// `UTC+0200`: derived from the offset, so it needs no quoting and two exports with
+/// the same offset share the zone.
+fn tzid(offset: FixedOffset) -> String {
+  format!("UTC{}", utc_offset(offset))
+}
+
+/// `+0200`, as in TZOFFSETFROM/TZOFFSETTO.
+fn utc_offset(offset: FixedOffset) -> String {
+  let secs = offset.local_minus_utc();
+  let sign = if secs < 0 { '-' } else { '+' };
+  let minutes = secs.abs() / 60;
+  format!("{sign}{:02}{:02}", minutes / 60, minutes % 60)
+}
+
+/// A VTIMEZONE for the fixed offset: one STANDARD observance without DST rules.
+fn push_timezone(out: &mut String, zone: &SecondaryZone, offset: FixedOffset) {
+  let offset_text = utc_offset(offset);
+  push_line(out, "BEGIN:VTIMEZONE");
+  push_line(out, &format!("TZID:{}", tzid(offset)));
+  push_line(out, "BEGIN:STANDARD");
+  push_line(out, "DTSTART:19700101T000000");
+  push_line(out, &format!("TZOFFSETFROM:{offset_text}"));
+  push_line(out, &format!("TZOFFSETTO:{offset_text}"));
+  push_line(out, &format!("TZNAME:{}", escape(zone.label.trim())));
+  push_line(out, "END:STANDARD");
+  push_line(out, "END:VTIMEZONE");
+}
+
+/// Escape a TEXT value.
+fn escape(text: &str) -> String {
+  let mut out = String::with_capacity(text.len());
+  for c in text.chars() {
+    match c {
+      '\\' | ';' | ',' => {
+        out.push('\\');
+        out.push(c);
+      }
+      '\n' => out.push_str("\\n"),
+      '\r' => {}
+      c => out.push(c),
+    }
+  }
+  out
+}
+
+/// Append a content line with CRLF, folding it at octet boundaries that do not
+/// split a UTF-8 sequence.
+fn push_line(out: &mut String, line: &str) {
+  let mut used = 0;
+  let mut limit = MAX_LINE_OCTETS;
+  for c in line.chars() {
+    if used + c.len_utf8() > limit {
+      out.push_str("\r\n ");
+      used = 0;
+      // The leading space of a continuation line counts towards its length.
+      limit = MAX_LINE_OCTETS - 1;
+    }
+    out.push(c);
+    used += c.len_utf8();
+  }
+  out.push_str("\r\n");
+}
@@ -0,0 +1,65 @@
+//! A value persisted as one JSON file next to the preferences, for the stores that
+//! only need "load at start, rewrite on change" (history, queues, profiles …).
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+
+/// `T` plus the file it is saved to. Derefs to `T`; changes are written by
+/// [`JsonStore::save`]. The default has no file and never writes, for tests.
+#[derive(Debug, Default)]
+pub struct JsonStore<T> {
+  path: Option<PathBuf>,
+  /// What is stored, for log messages ("event history").
+  label: &'static str,
+  value: T,
+}
+
+impl<T: Serialize + DeserializeOwned + Default> JsonStore<T> {
+  /// The saved value, or the default when the file is missing or corrupt.
+  pub fn load(path: PathBuf, label: &'static str) -> Self {
+    let value = match std::fs::read(&path) {
+      Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+        corelog!("✗ Saved {} at {:?} is corrupt, starting fresh: {}", label, path, e);
+        T::default()
+      }),
+      Err(_) => T::default(),
+    };
+    Self { path: Some(path), label, value }
+  }
+
+  /// Write the current value; failures are logged, the in-memory value stays.
+  pub fn save(&self) {
+    let Some(path) = &self.path else { return };
+    if let Some(dir) = path.parent() {
+      let _ = std::fs::create_dir_all(dir);
+    }
+    let bytes = match serde_json::to_vec(&self.value) {
+      Ok(bytes) => bytes,
+      Err(e) => {
+        corelog!("✗ Failed to serialize {}: {}", self.label, e);
+        return;
+      }
+    };
+    // Write-then-rename so a crash mid-write never leaves a truncated file.
+    let tmp = path.with_extension("json.tmp");
+    if let Err(e) = std::fs::write(&tmp, bytes).and_then(|_| std::fs::rename(&tmp, path)) {
+      corelog!("✗ Failed to write {} {:?}: {}", self.label, path, e);
+    }
+  }
+}
+
+impl<T> Deref for JsonStore<T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.value
+  }
+}
+
+impl<T> DerefMut for JsonStore<T> {
+  fn deref_mut(&mut self) -> &mut T {
+    &mut self.value
+  }
+}
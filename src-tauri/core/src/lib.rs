@@ -0,0 +1,55 @@
+//! Platform-independent core of helltime: everything that can be unit tested
+//! without a Tauri runtime, a window or the network.
+
+#[macro_use]
+pub mod log;
+
+pub mod boss_rotation;
+pub mod cache;
+pub mod cadence;
+pub mod clock;
+pub mod coalesce;
+pub mod connection;
+pub mod controller;
+pub mod countdown;
+pub mod custom_reminders;
+pub mod deep_link;
+pub mod discord;
+pub mod error;
+pub mod fetch;
+pub mod fonts;
+pub mod game_mode;
+pub mod helltide;
+pub mod history;
+pub mod hover_fade;
+pub mod hud_layout;
+pub mod ics;
+pub mod i18n;
+pub mod json_store;
+pub mod local_api;
+pub mod metrics;
+pub mod motion;
+pub mod mqtt;
+pub mod network_change;
+pub mod onboarding;
+pub mod overlay_compat;
+pub mod overlay_style;
+pub mod polling;
+pub mod power_saving;
+pub mod prefs;
+pub mod profiles;
+pub mod push;
+pub mod rate_limit;
+pub mod reminders;
+pub mod schedule;
+pub mod shortcuts;
+pub mod sync;
+pub mod taskbar;
+pub mod telemetry;
+pub mod toast_template;
+pub mod tray_icon;
+pub mod update;
+pub mod validation;
+pub mod visibility;
+pub mod webhook;
+pub mod window_state;
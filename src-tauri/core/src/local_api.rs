@@ -0,0 +1,118 @@
+//! Request routing and response framing for the localhost integration API.
+//! The socket handling lives in the app; this part is pure so it can be tested.
+
+use serde::Serialize;
+
+pub const DEFAULT_PORT: u16 = 47620;
+
+/// Upper bound for a request head; anything larger is rejected.
+pub const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+  Schedule,
+  Next,
+  Status,
+  /// Browser-source page for OBS.
+  ObsPage,
+  /// Server-sent events stream of `/next` payloads, consumed by the OBS page.
+  Events,
+  /// iCalendar feed of upcoming events for calendar subscriptions.
+  Calendar,
+  NotFound,
+  MethodNotAllowed,
+  BadRequest,
+}
+
+/// Route a raw HTTP/1.x request head. Only `GET` is served; query strings are ignored.
+pub fn route(request_head: &str) -> Route {
+  let mut parts = request_head.lines().next().unwrap_or_default().split_whitespace();
+  let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+    return Route::BadRequest;
+  };
+  if method != "GET" {
+    return Route::MethodNotAllowed;
+  }
+
+  let path = target.split(['?', '#']).next().unwrap_or_default();
+  match path.trim_end_matches('/') {
+    "/schedule" => Route::Schedule,
+    "/next" => Route::Next,
+    "/status" => Route::Status,
+    "/obs" => Route::ObsPage,
+    "/events" => Route::Events,
+    "/calendar.ics" => Route::Calendar,
+    _ => Route::NotFound,
+  }
+}
+
+/// Transparent, self-updating countdown page sized for an OBS browser source.
+pub const OBS_PAGE: &str = include_str!("../assets/obs.html");
+
+/// How often the `/events` stream pushes a fresh payload.
+pub const SSE_INTERVAL_SECS: u64 = 5;
+
+/// Response head that opens a server-sent events stream.
+pub fn sse_head() -> &'static [u8] {
+  b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-store\r\nAccess-Control-Allow-Origin: *\r\nConnection: keep-alive\r\n\r\n"
+}
+
+/// One SSE message carrying `value` as JSON.
+pub fn sse_event<T: Serialize>(value: &T) -> Vec<u8> {
+  let data = serde_json::to_string(value).unwrap_or_else(|_| "null".to_string());
+  format!("data: {}\n\n", data).into_bytes()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reply {
+  pub status: u16,
+  pub content_type: &'static str,
+  pub body: Vec<u8>,
+}
+
+fn reason(status: u16) -> &'static str {
+  match status {
+    200 => "OK",
+    400 => "Bad Request",
+    404 => "Not Found",
+    405 => "Method Not Allowed",
+    503 => "Service Unavailable",
+    _ => "Internal Server Error",
+  }
+}
+
+impl Reply {
+  pub fn json<T: Serialize>(status: u16, value: &T) -> Self {
+    match serde_json::to_vec(value) {
+      Ok(body) => Self { status, content_type: "application/json", body },
+      Err(e) => Self::error(500, &format!("serialization failed: {e}")),
+    }
+  }
+
+  pub fn html(body: &str) -> Self {
+    Self { status: 200, content_type: "text/html; charset=utf-8", body: body.as_bytes().to_vec() }
+  }
+
+  pub fn calendar(body: String) -> Self {
+    Self { status: 200, content_type: "text/calendar; charset=utf-8", body: body.into_bytes() }
+  }
+
+  pub fn error(status: u16, message: &str) -> Self {
+    Self::json(status, &serde_json::json!({ "error": message }))
+  }
+
+  /// Full HTTP/1.1 response. Browser sources (OBS) load from other origins, so
+  /// CORS is open; the server only ever binds to loopback.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut out = format!(
+      "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+      self.status,
+      reason(self.status),
+      self.content_type,
+      self.body.len()
+    )
+    .into_bytes();
+    out.extend_from_slice(&self.body);
+    out
+  }
+}
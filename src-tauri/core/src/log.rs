@@ -0,0 +1,27 @@
+//! Where core's log lines go. The app routes them into its own log (`applog!`), so
+//! they end up in crash reports too; without a hook (tests, the CLI) they go to
+//! stderr.
+
+use std::sync::OnceLock;
+
+static HOOK: OnceLock<fn(String)> = OnceLock::new();
+
+/// Send core's log lines to `hook` from now on; only the first call takes effect.
+pub fn set_hook(hook: fn(String)) {
+  let _ = HOOK.set(hook);
+}
+
+pub fn write(line: String) {
+  match HOOK.get() {
+    Some(hook) => hook(line),
+    None => eprintln!("{line}"),
+  }
+}
+
+/// `format!`-style log line through [`write`].
+#[macro_export]
+macro_rules! corelog {
+  ($($arg:tt)*) => {
+    $crate::log::write(format!($($arg)*))
+  };
+}
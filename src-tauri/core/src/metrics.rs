@@ -0,0 +1,85 @@
+//! Lightweight in-memory counters since startup, for the debug panel and bug
+//! reports. Atomics, so they can be bumped from any thread without a lock; nothing
+//! is persisted.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+  fetch_attempts: AtomicU64,
+  fetch_successes: AtomicU64,
+  fetch_failures: AtomicU64,
+  /// Total time of finished fetches (retries included), for the average.
+  fetch_latency_ms: AtomicU64,
+  cache_hits: AtomicU64,
+  reminders_fired: AtomicU64,
+  overlay_shows: AtomicU64,
+}
+
+/// `get_metrics` result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsSnapshot {
+  pub fetch_attempts: u64,
+  pub fetch_successes: u64,
+  pub fetch_failures: u64,
+  /// Over finished fetches; `None` before the first one.
+  pub average_fetch_latency_ms: Option<u64>,
+  pub cache_hits: u64,
+  pub reminders_fired: u64,
+  pub overlay_shows: u64,
+}
+
+impl Metrics {
+  /// A fetch from the schedule API was started. Attempts that never finish (still
+  /// running, or held back by the rate limiter) only show up here.
+  pub fn fetch_started(&self) {
+    self.fetch_attempts.fetch_add(1, Ordering::Relaxed);
+  }
+
+  /// A started fetch finished after `latency`.
+  pub fn fetch_finished(&self, latency: Duration, ok: bool) {
+    let counter = if ok { &self.fetch_successes } else { &self.fetch_failures };
+    counter.fetch_add(1, Ordering::Relaxed);
+    self.fetch_latency_ms.fetch_add(latency.as_millis() as u64, Ordering::Relaxed);
+  }
+
+  pub fn cache_hit(&self) {
+    self.cache_hits.fetch_add(1, Ordering::Relaxed);
+  }
+
+  pub fn reminder_fired(&self) {
+    self.reminders_fired.fetch_add(1, Ordering::Relaxed);
+  }
+
+  pub fn overlay_shown(&self) {
+    self.overlay_shows.fetch_add(1, Ordering::Relaxed);
+  }
+
+  pub fn snapshot(&self) -> MetricsSnapshot {
+    let successes = self.fetch_successes.load(Ordering::Relaxed);
+    let failures = self.fetch_failures.load(Ordering::Relaxed);
+    let finished = successes + failures;
+    MetricsSnapshot {
+      fetch_attempts: self.fetch_attempts.load(Ordering::Relaxed),
+      fetch_successes: successes,
+      fetch_failures: failures,
+      average_fetch_latency_ms: (finished > 0).then(|| self.fetch_latency_ms.load(Ordering::Relaxed) / finished),
+      cache_hits: self.cache_hits.load(Ordering::Relaxed),
+      reminders_fired: self.reminders_fired.load(Ordering::Relaxed),
+      overlay_shows: self.overlay_shows.load(Ordering::Relaxed),
+    }
+  }
+
+  /// One line for logs and diagnostics.
+  pub fn summary(&self) -> String {
+    let s = self.snapshot();
+    let latency = s.average_fetch_latency_ms.map(|ms| format!("{ms}ms")).unwrap_or_else(|| "—".into());
+    format!(
+      "fetches {}/{} ok ({} failed, avg {}), cache hits {}, reminders {}, overlay shows {}",
+      s.fetch_successes, s.fetch_attempts, s.fetch_failures, latency, s.cache_hits, s.reminders_fired, s.overlay_shows
+    )
+  }
+}
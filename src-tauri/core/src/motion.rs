@@ -0,0 +1,25 @@
+//! Reduced motion: no overlay animations, no tray pulse and no flashing cues. By
+//! default this follows the system's "Show animations" setting; the lookup itself
+//! is platform code in the app.
+
+use crate::prefs::ReducedMotion;
+
+/// Whether motion is reduced for `setting` while the system has animations
+/// `system_animations` (on when it cannot be determined).
+pub fn reduced(setting: ReducedMotion, system_animations: bool) -> bool {
+  match setting {
+    ReducedMotion::Auto => !system_animations,
+    ReducedMotion::On => true,
+    ReducedMotion::Off => false,
+  }
+}
+
+/// `gsettings get org.gnome.desktop.interface enable-animations` output; `None`
+/// when it is not a boolean (no GNOME schema, no gsettings).
+pub fn parse_gsettings_bool(output: &str) -> Option<bool> {
+  match output.trim() {
+    "true" => Some(true),
+    "false" => Some(false),
+    _ => None,
+  }
+}
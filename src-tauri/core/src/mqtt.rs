@@ -0,0 +1,254 @@
+//! MQTT 3.1.1 publishing of countdowns and "event active" states for smart-home
+//! dashboards, with optional Home Assistant discovery. Only what a publisher needs
+//! is encoded here (CONNECT with a last will, QoS 0 PUBLISH, DISCONNECT); the TCP
+//! transport lives in the app.
+//!
+//! Topics under the prefix (default `helltime`):
+//!
+//! ```text
+//! helltime/status                     online | offline (last will)
+//! helltime/<event>/next_start         RFC 3339 start of the next occurrence
+//! helltime/<event>/seconds_until      seconds until that start
+//! helltime/<event>/active             ON | OFF
+//! ```
+
+use crate::countdown::{ActiveEvent, NextEvent};
+use crate::schedule::EventKind;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+
+pub const DEFAULT_PORT: u16 = 1883;
+
+/// States are published this often (and on connecting).
+pub const PUBLISH_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Sent in CONNECT; the broker drops the connection after 1.5× this without a packet,
+/// which [`PUBLISH_INTERVAL`] stays well inside.
+pub const KEEP_ALIVE_SECS: u16 = 60;
+
+/// Pause before the first reconnect, doubled per failure up to [`MAX_RECONNECT_DELAY`].
+pub const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+pub const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(5 * 60);
+
+pub const ONLINE: &str = "online";
+pub const OFFLINE: &str = "offline";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MqttSettings {
+  pub enabled: bool,
+  /// Broker host name or address; plain TCP, no TLS.
+  pub host: String,
+  pub port: u16,
+  pub username: Option<String>,
+  pub password: Option<String>,
+  /// First level of every state topic.
+  pub topic_prefix: String,
+  /// Publish Home Assistant discovery configs so the entities appear on their own.
+  pub discovery: bool,
+  pub discovery_prefix: String,
+}
+
+impl Default for MqttSettings {
+  fn default() -> Self {
+    Self {
+      enabled: false,
+      host: String::new(),
+      port: DEFAULT_PORT,
+      username: None,
+      password: None,
+      topic_prefix: "helltime".into(),
+      discovery: true,
+      discovery_prefix: "homeassistant".into(),
+    }
+  }
+}
+
+impl MqttSettings {
+  pub fn validate(&self) -> Result<(), String> {
+    if !self.enabled {
+      return Ok(());
+    }
+    let host = self.host.trim();
+    if host.is_empty() || host.contains("://") || host.contains(char::is_whitespace) {
+      return Err(format!("MQTT broker must be a host name or address: {host:?}"));
+    }
+    if self.port == 0 {
+      return Err("MQTT port must not be 0".to_string());
+    }
+    for (name, prefix) in [("topic prefix", &self.topic_prefix), ("discovery prefix", &self.discovery_prefix)] {
+      let prefix = prefix.trim();
+      if prefix.is_empty() || prefix.starts_with('/') || prefix.ends_with('/') || prefix.contains(['+', '#', '\0']) {
+        return Err(format!("MQTT {name} must be a topic without wildcards or outer slashes: {prefix:?}"));
+      }
+    }
+    if self.password.is_some() && self.username.is_none() {
+      return Err("MQTT password needs a user name".to_string());
+    }
+    Ok(())
+  }
+
+  pub fn status_topic(&self) -> String {
+    format!("{}/status", self.topic_prefix.trim())
+  }
+
+  fn topic(&self, kind: EventKind, name: &str) -> String {
+    format!("{}/{}/{}", self.topic_prefix.trim(), kind.key(), name)
+  }
+}
+
+/// A retained QoS 0 message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+  pub topic: String,
+  pub payload: String,
+}
+
+/// State messages for `kinds`; categories without a known next event only get
+/// their `active` state.
+pub fn states(settings: &MqttSettings, kinds: &[EventKind], next: &[NextEvent], active: &[ActiveEvent]) -> Vec<Message> {
+  let mut out = Vec::new();
+  for kind in kinds {
+    if let Some(event) = next.iter().find(|e| e.kind == *kind) {
+      out.push(Message { topic: settings.topic(*kind, "next_start"), payload: event.start.clone() });
+      out.push(Message { topic: settings.topic(*kind, "seconds_until"), payload: event.seconds_until.to_string() });
+    }
+    let running = active.iter().any(|e| e.kind == *kind);
+    out.push(Message { topic: settings.topic(*kind, "active"), payload: if running { "ON" } else { "OFF" }.into() });
+  }
+  out
+}
+
+/// Home Assistant discovery configs: a timestamp sensor, a duration sensor and a
+/// binary sensor per shown category. Hidden categories get an empty config, which
+/// removes their entities.
+pub fn discovery(settings: &MqttSettings, shown: &[EventKind]) -> Vec<Message> {
+  let prefix = settings.discovery_prefix.trim();
+  let device = json!({ "identifiers": ["helltime"], "name": "helltime", "manufacturer": "helltime" });
+  let mut out = Vec::new();
+  for kind in EventKind::ALL {
+    let key = kind.key();
+    let entities = [
+      ("sensor", "next", "next_start", json!({ "name": format!("{} next start", kind.label()), "device_class": "timestamp" })),
+      (
+        "sensor",
+        "countdown",
+        "seconds_until",
+        json!({ "name": format!("{} countdown", kind.label()), "device_class": "duration", "unit_of_measurement": "s" }),
+      ),
+      (
+        "binary_sensor",
+        "active",
+        "active",
+        json!({ "name": format!("{} active", kind.label()), "payload_on": "ON", "payload_off": "OFF" }),
+      ),
+    ];
+    for (component, object, state, mut config) in entities {
+      let topic = format!("{prefix}/{component}/helltime_{key}_{object}/config");
+      if !shown.contains(&kind) {
+        out.push(Message { topic, payload: String::new() });
+        continue;
+      }
+      config["unique_id"] = json!(format!("helltime_{key}_{object}"));
+      config["state_topic"] = json!(settings.topic(kind, state));
+      config["availability_topic"] = json!(settings.status_topic());
+      config["device"] = device.clone();
+      out.push(Message { topic, payload: config.to_string() });
+    }
+  }
+  out
+}
+
+/// Reconnect pause after `failures` failed attempts in a row.
+pub fn reconnect_delay(failures: u32) -> Duration {
+  RECONNECT_DELAY.saturating_mul(1 << failures.min(16)).min(MAX_RECONNECT_DELAY)
+}
+
+const CONNECT: u8 = 0x10;
+const CONNACK: u8 = 0x20;
+const PUBLISH: u8 = 0x30;
+const DISCONNECT: u8 = 0xe0;
+const RETAIN: u8 = 0x01;
+
+/// Variable-length "remaining length" (§2.2.3).
+fn push_length(out: &mut Vec<u8>, mut len: usize) {
+  loop {
+    let mut byte = (len % 128) as u8;
+    len /= 128;
+    if len > 0 {
+      byte |= 0x80;
+    }
+    out.push(byte);
+    if len == 0 {
+      break;
+    }
+  }
+}
+
+fn push_str(out: &mut Vec<u8>, value: &[u8]) {
+  out.extend_from_slice(&(value.len().min(u16::MAX as usize) as u16).to_be_bytes());
+  out.extend_from_slice(&value[..value.len().min(u16::MAX as usize)]);
+}
+
+fn packet(header: u8, body: &[u8]) -> Vec<u8> {
+  let mut out = vec![header];
+  push_length(&mut out, body.len());
+  out.extend_from_slice(body);
+  out
+}
+
+/// CONNECT with a clean session and a retained [`OFFLINE`] last will on the status
+/// topic, so dashboards notice when helltime goes away without saying so.
+pub fn connect(settings: &MqttSettings, client_id: &str) -> Vec<u8> {
+  let mut flags = 0x02 | 0x04 | 0x20; // clean session, will, will retain
+  if settings.username.is_some() {
+    flags |= 0x80;
+  }
+  if settings.password.is_some() {
+    flags |= 0x40;
+  }
+  let mut body = Vec::new();
+  push_str(&mut body, b"MQTT");
+  body.push(4); // protocol level 3.1.1
+  body.push(flags);
+  body.extend_from_slice(&KEEP_ALIVE_SECS.to_be_bytes());
+  push_str(&mut body, client_id.as_bytes());
+  push_str(&mut body, settings.status_topic().as_bytes());
+  push_str(&mut body, OFFLINE.as_bytes());
+  if let Some(username) = &settings.username {
+    push_str(&mut body, username.as_bytes());
+  }
+  if let Some(password) = &settings.password {
+    push_str(&mut body, password.as_bytes());
+  }
+  packet(CONNECT, &body)
+}
+
+/// A retained QoS 0 PUBLISH.
+pub fn publish(message: &Message) -> Vec<u8> {
+  let mut body = Vec::new();
+  push_str(&mut body, message.topic.as_bytes());
+  body.extend_from_slice(message.payload.as_bytes());
+  packet(PUBLISH | RETAIN, &body)
+}
+
+pub fn disconnect() -> Vec<u8> {
+  packet(DISCONNECT, &[])
+}
+
+/// Check the broker's CONNACK (4 bytes).
+pub fn check_connack(packet: [u8; 4]) -> Result<(), String> {
+  if packet[0] != CONNACK || packet[1] != 2 {
+    return Err(format!("unexpected reply to CONNECT: {packet:02x?}"));
+  }
+  match packet[3] {
+    0 => Ok(()),
+    1 => Err("broker does not support MQTT 3.1.1".to_string()),
+    2 => Err("client id rejected".to_string()),
+    3 => Err("broker unavailable".to_string()),
+    4 => Err("bad user name or password".to_string()),
+    5 => Err("not authorized".to_string()),
+    code => Err(format!("connection refused ({code})")),
+  }
+}
@@ -0,0 +1,46 @@
+//! Refresh right after the network comes back (laptop resumed on another Wi-Fi,
+//! cable plugged in) instead of showing stale times until the next poll. Waiting
+//! for changes is platform code in the app; Linux samples the routing table.
+
+use std::time::Duration;
+
+/// Wait this long after a change before fetching, for DHCP and DNS to settle.
+pub const SETTLE: Duration = Duration::from_secs(3);
+
+/// How often the routing table is sampled where there is no change notification.
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default routes in `/proc/net/route` as `interface gateway` pairs; empty while
+/// offline. Columns: Iface, Destination, Gateway, Flags, ... with hex values.
+pub fn default_routes(route_table: &str) -> Vec<String> {
+  const RTF_UP: u32 = 0x1;
+  route_table
+    .lines()
+    .skip(1)
+    .filter_map(|line| {
+      let cols: Vec<&str> = line.split_whitespace().collect();
+      let (iface, destination, gateway, flags) = (cols.first()?, cols.get(1)?, cols.get(2)?, cols.get(3)?);
+      let up = u32::from_str_radix(flags, 16).is_ok_and(|f| f & RTF_UP != 0);
+      (*destination == "00000000" && up).then(|| format!("{iface} {gateway}"))
+    })
+    .collect()
+}
+
+/// Remembers the last sampled network to tell when to refresh.
+#[derive(Debug, Default)]
+pub struct NetworkWatch {
+  last: Option<Vec<String>>,
+}
+
+impl NetworkWatch {
+  /// Record the current default `routes`; true when the machine is online on a
+  /// network other than at the last sample (back online, or switched networks).
+  /// The first sample only sets the baseline.
+  pub fn observe(&mut self, mut routes: Vec<String>) -> bool {
+    routes.sort();
+    let changed = self.last.as_ref().is_some_and(|last| *last != routes);
+    let online = !routes.is_empty();
+    self.last = Some(routes);
+    changed && online
+  }
+}
@@ -0,0 +1,52 @@
+//! First-run flow: what still needs the user's attention before helltime can remind
+//! reliably. The backend gathers the facts, the UI walks through [`Step`]s and calls
+//! `complete_onboarding` at the end; completion is kept in the preferences.
+
+use serde::Serialize;
+
+/// Desktop notification permission as reported by the OS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+  Granted,
+  Denied,
+  /// Not decided yet; asking will show the OS prompt.
+  Prompt,
+  /// The query failed.
+  Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+  Notifications,
+  Autostart,
+  OverlayPosition,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingStatus {
+  pub completed: bool,
+  pub notification_permission: Permission,
+  /// Whether helltime starts with the system; `None` where autostart is not supported.
+  pub autostart: Option<bool>,
+  /// Steps worth showing, in order.
+  pub steps: Vec<Step>,
+}
+
+impl OnboardingStatus {
+  pub fn new(completed: bool, notification_permission: Permission, autostart: Option<bool>, overlay_enabled: bool) -> Self {
+    let mut steps = Vec::new();
+    if notification_permission != Permission::Granted {
+      steps.push(Step::Notifications);
+    }
+    if autostart == Some(false) {
+      steps.push(Step::Autostart);
+    }
+    if overlay_enabled {
+      steps.push(Step::OverlayPosition);
+    }
+    Self { completed, notification_permission, autostart, steps }
+  }
+}
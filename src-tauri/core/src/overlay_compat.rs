@@ -0,0 +1,62 @@
+//! Wine/Proton compatibility for the overlay window. Wine implements the layered
+//! and transparent extended window styles only partly: per-pixel transparency turns
+//! black without a compositor and `WS_EX_TRANSPARENT` toggled after creation is
+//! ignored or makes the window vanish. Under Wine the overlay uses a simplified
+//! style set instead and reports what it gives up in `OverlayStatus`. Detection
+//! (`wine_get_version` in ntdll) is platform code in the app.
+
+use serde::Serialize;
+
+/// Set to `1` or `0` to force the compatibility mode on or off.
+pub const ENV_OVERRIDE: &str = "HELLTIME_WINE_COMPAT";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayCompat {
+  #[default]
+  Standard,
+  /// Simplified window styles for Wine/Proton.
+  Wine,
+}
+
+/// What the overlay cannot do in a compatibility mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DegradedFeature {
+  /// Opaque background instead of the transparent window; the background opacity
+  /// setting has no effect.
+  Transparency,
+  /// Click-through only through the webview's cursor-event flag, without
+  /// `WS_EX_TRANSPARENT`; fullscreen games may still lose clicks over the overlay.
+  ClickThroughHitTest,
+}
+
+/// The mode for a process where `wine_get_version` returned `wine_version`, with
+/// [`ENV_OVERRIDE`] set to `env_override`.
+pub fn detect(wine_version: Option<&str>, env_override: Option<&str>) -> OverlayCompat {
+  match env_override.map(str::trim) {
+    Some("1") => OverlayCompat::Wine,
+    Some("0") => OverlayCompat::Standard,
+    _ if wine_version.is_some() => OverlayCompat::Wine,
+    _ => OverlayCompat::Standard,
+  }
+}
+
+impl OverlayCompat {
+  pub fn degraded(self) -> Vec<DegradedFeature> {
+    match self {
+      OverlayCompat::Standard => Vec::new(),
+      OverlayCompat::Wine => vec![DegradedFeature::Transparency, DegradedFeature::ClickThroughHitTest],
+    }
+  }
+
+  /// Whether the overlay window is created transparent.
+  pub fn transparent(self) -> bool {
+    self == OverlayCompat::Standard
+  }
+
+  /// Whether click-through also sets `WS_EX_TRANSPARENT | WS_EX_LAYERED`.
+  pub fn layered_click_through(self) -> bool {
+    self == OverlayCompat::Standard
+  }
+}
@@ -0,0 +1,124 @@
+//! How the overlay looks, independent of the engine drawing it. The settings UI
+//! sends it through the overlay facade, so the webview and a native engine get the
+//! same values (`OverlayWindowSettings` in `lib/overlay_window.ts`).
+
+use crate::schedule::EventKind;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What the overlay shows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayMode {
+  /// A countdown line per category.
+  #[default]
+  Overview,
+  /// Only a toast shortly before events.
+  Toast,
+  /// One compact row of countdowns.
+  Minibar,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextShadow {
+  #[default]
+  #[serde(rename = "soft")]
+  Soft,
+  #[serde(rename = "outline8")]
+  Outline8,
+  #[serde(rename = "blur")]
+  Blur,
+  #[serde(rename = "none")]
+  None,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OverlayTheme {
+  #[default]
+  Standard,
+  HighContrast,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizePreset {
+  Small,
+  #[default]
+  Medium,
+  Large,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OverlayStyle {
+  pub enabled: bool,
+  pub mode: OverlayMode,
+  /// Categories shown on the overlay; missing ones are shown.
+  pub categories: HashMap<EventKind, bool>,
+  /// `#rrggbb`.
+  pub bg_hex: String,
+  /// Background alpha only; text stays opaque.
+  pub bg_opacity: f64,
+  pub scale_x: f64,
+  pub scale_y: f64,
+  pub text_shadow: TextShadow,
+  pub theme: OverlayTheme,
+  /// Empty for the built-in font stack.
+  pub font_family: String,
+  /// 0 for the built-in weights, else 100–900.
+  pub font_weight: u16,
+  pub size_preset: SizePreset,
+  pub wrap_text: bool,
+  /// Dragged size in logical pixels; 0 for preset × scale.
+  pub width: u32,
+  pub height: u32,
+}
+
+impl Default for OverlayStyle {
+  fn default() -> Self {
+    Self {
+      enabled: true,
+      mode: OverlayMode::default(),
+      categories: HashMap::new(),
+      bg_hex: "#0b1220".into(),
+      bg_opacity: 0.2,
+      scale_x: 1.0,
+      scale_y: 1.0,
+      text_shadow: TextShadow::default(),
+      theme: OverlayTheme::default(),
+      font_family: String::new(),
+      font_weight: 0,
+      size_preset: SizePreset::default(),
+      wrap_text: false,
+      width: 0,
+      height: 0,
+    }
+  }
+}
+
+impl OverlayStyle {
+  pub fn shows(&self, kind: EventKind) -> bool {
+    self.categories.get(&kind).copied().unwrap_or(true)
+  }
+
+  /// Reject values an engine could not draw; the UI clamps the same ranges.
+  pub fn validate(&self) -> Result<(), String> {
+    let hex = self.bg_hex.strip_prefix('#').unwrap_or_default();
+    if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+      return Err(format!("background color must be #rrggbb: {:?}", self.bg_hex));
+    }
+    if !(0.0..=1.0).contains(&self.bg_opacity) {
+      return Err(format!("background opacity out of range: {}", self.bg_opacity));
+    }
+    for scale in [self.scale_x, self.scale_y] {
+      if !(0.6..=2.0).contains(&scale) {
+        return Err(format!("scale out of range: {scale}"));
+      }
+    }
+    if self.font_weight != 0 && !(100..=900).contains(&self.font_weight) {
+      return Err(format!("font weight out of range: {}", self.font_weight));
+    }
+    Ok(())
+  }
+}
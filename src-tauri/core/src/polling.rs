@@ -0,0 +1,107 @@
+//! When to poll the schedule API next. Adaptive mode polls often right before an
+//! event starts (when the API may still correct times or fill in a boss) and
+//! sparingly in between.
+
+use crate::rate_limit::{MAX_REQUESTS_PER_MINUTE, MIN_REQUESTS_PER_MINUTE};
+use crate::schedule::{EventKind, ScheduleResponse};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+pub const MIN_INTERVAL_MINUTES: u32 = 1;
+pub const MAX_INTERVAL_MINUTES: u32 = 60;
+
+/// Bounds of [`PollSettings::cache_ttl_seconds`].
+pub const MIN_CACHE_TTL_SECONDS: u32 = 5;
+pub const MAX_CACHE_TTL_SECONDS: u32 = 600;
+
+/// Adaptive mode polls every minute this close to an event start...
+pub const ADAPTIVE_LEAD: Duration = Duration::from_secs(5 * 60);
+pub const ADAPTIVE_FAST: Duration = Duration::from_secs(60);
+/// ...and stretches the configured interval by this factor otherwise.
+pub const ADAPTIVE_SLOWDOWN: u32 = 2;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PollMode {
+  Fixed,
+  #[default]
+  Adaptive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PollSettings {
+  pub mode: PollMode,
+  pub interval_minutes: u32,
+  /// Courtesy limit for requests to the schedule API, retries included.
+  pub max_requests_per_minute: u32,
+  /// How long a fetched schedule is answered from the cache without asking the API.
+  pub cache_ttl_seconds: u32,
+}
+
+impl Default for PollSettings {
+  fn default() -> Self {
+    Self {
+      mode: PollMode::Adaptive,
+      interval_minutes: 12,
+      max_requests_per_minute: crate::rate_limit::DEFAULT_REQUESTS_PER_MINUTE,
+      cache_ttl_seconds: 30,
+    }
+  }
+}
+
+impl PollSettings {
+  pub fn interval(&self) -> Duration {
+    let minutes = self.interval_minutes.clamp(MIN_INTERVAL_MINUTES, MAX_INTERVAL_MINUTES);
+    Duration::from_secs(u64::from(minutes) * 60)
+  }
+
+  pub fn cache_ttl(&self) -> Duration {
+    Duration::from_secs(u64::from(self.cache_ttl_seconds.clamp(MIN_CACHE_TTL_SECONDS, MAX_CACHE_TTL_SECONDS)))
+  }
+
+  /// Reject values outside what the settings offer before they are saved.
+  pub fn validate(&self) -> Result<(), String> {
+    if !(MIN_INTERVAL_MINUTES..=MAX_INTERVAL_MINUTES).contains(&self.interval_minutes) {
+      return Err(format!(
+        "poll interval must be {MIN_INTERVAL_MINUTES} to {MAX_INTERVAL_MINUTES} minutes, not {}",
+        self.interval_minutes
+      ));
+    }
+    if !(MIN_REQUESTS_PER_MINUTE..=MAX_REQUESTS_PER_MINUTE).contains(&self.max_requests_per_minute) {
+      return Err(format!(
+        "request limit must be {MIN_REQUESTS_PER_MINUTE} to {MAX_REQUESTS_PER_MINUTE} per minute, not {}",
+        self.max_requests_per_minute
+      ));
+    }
+    if !(MIN_CACHE_TTL_SECONDS..=MAX_CACHE_TTL_SECONDS).contains(&self.cache_ttl_seconds) {
+      return Err(format!(
+        "cache lifetime must be {MIN_CACHE_TTL_SECONDS} to {MAX_CACHE_TTL_SECONDS} seconds, not {}",
+        self.cache_ttl_seconds
+      ));
+    }
+    Ok(())
+  }
+
+  /// Delay until the next poll. Without a schedule adaptive mode falls back to the
+  /// plain interval.
+  pub fn next_delay(&self, schedule: Option<&ScheduleResponse>, now: DateTime<Utc>) -> Duration {
+    let interval = self.interval();
+    if self.mode == PollMode::Fixed {
+      return interval;
+    }
+    let Some(schedule) = schedule else { return interval };
+
+    let next_start = EventKind::ALL.iter().filter_map(|kind| schedule.next_event(*kind, now)).map(|e| e.start).min();
+    let Some(next_start) = next_start else { return interval };
+
+    let until_start = (next_start - now).to_std().unwrap_or_default();
+    if until_start <= ADAPTIVE_LEAD {
+      return ADAPTIVE_FAST.min(interval);
+    }
+    let slow = interval * ADAPTIVE_SLOWDOWN;
+    // Wake up in time for the fast window before the next start.
+    slow.min(until_start - ADAPTIVE_LEAD).max(ADAPTIVE_FAST)
+  }
+}
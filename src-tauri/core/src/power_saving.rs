@@ -0,0 +1,76 @@
+//! Power saving on laptops: on battery or over a metered connection the schedule is
+//! polled less often and the overlay stops repainting its countdown every second.
+//! Reading the power source and the connection cost is platform code in the app.
+
+use crate::polling::MAX_INTERVAL_MINUTES;
+use crate::prefs::PowerSaving;
+use serde::Serialize;
+use std::time::Duration;
+
+/// How often the power source and connection cost are checked.
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(120);
+
+/// Poll delays are stretched by this factor while saving power...
+pub const POLL_STRETCH: u32 = 3;
+/// ...but never beyond the longest configurable interval.
+pub const MAX_POLL_DELAY: Duration = Duration::from_secs(MAX_INTERVAL_MINUTES as u64 * 60);
+
+/// Overlay countdown repaint interval while saving power (instead of every second).
+pub const OVERLAY_TICK: Duration = Duration::from_secs(15);
+
+/// What the system reports; unknown counts as plugged in and unmetered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerState {
+  pub on_battery: bool,
+  pub metered: bool,
+}
+
+/// Whether to save power for `setting` in `state`.
+pub fn saving(setting: PowerSaving, state: PowerState) -> bool {
+  match setting {
+    PowerSaving::Auto => state.on_battery || state.metered,
+    PowerSaving::On => true,
+    PowerSaving::Off => false,
+  }
+}
+
+/// The poll `delay`, stretched while saving power.
+pub fn poll_delay(delay: Duration, saving: bool) -> Duration {
+  if saving {
+    (delay * POLL_STRETCH).min(MAX_POLL_DELAY).max(delay)
+  } else {
+    delay
+  }
+}
+
+/// Whether the `/sys/class/power_supply` entries, given as `(type, online)` file
+/// contents, mean running on battery: there is a battery and no mains supply online.
+pub fn sysfs_on_battery<'a>(supplies: impl IntoIterator<Item = (&'a str, Option<&'a str>)>) -> bool {
+  let (mut battery, mut mains_online) = (false, false);
+  for (kind, online) in supplies {
+    match kind.trim() {
+      "Battery" => battery = true,
+      "Mains" | "USB" => mains_online |= online.is_some_and(|o| o.trim() == "1"),
+      _ => {}
+    }
+  }
+  battery && !mains_online
+}
+
+/// NetworkManager's `Metered` property as printed by `busctl get-property`
+/// (`u 1`): yes (1) and guessed yes (3) count as metered.
+pub fn parse_nm_metered(output: &str) -> Option<bool> {
+  let value: u32 = output.trim().strip_prefix("u ")?.trim().parse().ok()?;
+  Some(matches!(value, 1 | 3))
+}
+
+/// `NetworkCostType` of the internet connection profile as printed by PowerShell:
+/// `Fixed` and `Variable` plans are metered, `Unrestricted` is not.
+pub fn parse_network_cost(output: &str) -> Option<bool> {
+  match output.trim() {
+    "Fixed" | "Variable" => Some(true),
+    "Unrestricted" => Some(false),
+    _ => None,
+  }
+}
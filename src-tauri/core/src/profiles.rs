@@ -0,0 +1,125 @@
+//! Named profiles ("Hardcore alt nights", "Casual"): a snapshot of which events are
+//! shown, the reminder lead times and the overlay look, switched from the UI or the
+//! tray. Lead times and overlay style live in the UI's settings and are kept here as
+//! an opaque blob; the backend applies the rest to the preferences.
+
+use crate::json_store::JsonStore;
+use crate::prefs::{OverlayEngine, Preferences};
+use crate::schedule::EventKind;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+pub const MAX_NAME_LEN: usize = 40;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+  pub name: String,
+  pub hidden_events: Vec<EventKind>,
+  pub overlay_enabled: bool,
+  pub overlay_engine: OverlayEngine,
+  /// The UI's settings when the profile was saved.
+  #[serde(default)]
+  pub ui_settings: Option<serde_json::Value>,
+}
+
+impl Profile {
+  /// Snapshot of the current configuration under `name` (trimmed).
+  pub fn capture(name: &str, prefs: &Preferences, ui_settings: Option<serde_json::Value>) -> Result<Self, String> {
+    let name = name.trim();
+    if name.is_empty() {
+      return Err("profile name is empty".into());
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+      return Err(format!("profile name is longer than {MAX_NAME_LEN} characters"));
+    }
+    Ok(Self {
+      name: name.to_string(),
+      hidden_events: prefs.hidden_events.clone(),
+      overlay_enabled: prefs.overlay_enabled,
+      overlay_engine: prefs.overlay_engine,
+      ui_settings,
+    })
+  }
+
+  /// `prefs` with this profile's parts swapped in.
+  pub fn apply(&self, prefs: &Preferences) -> Preferences {
+    Preferences {
+      hidden_events: self.hidden_events.clone(),
+      overlay_enabled: self.overlay_enabled,
+      overlay_engine: self.overlay_engine,
+      ..prefs.clone()
+    }
+  }
+}
+
+/// What `list_profiles` returns.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileList {
+  pub active: Option<String>,
+  pub profiles: Vec<Profile>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Saved {
+  active: Option<String>,
+  profiles: Vec<Profile>,
+}
+
+/// The saved profiles, persisted as JSON, in the order they were created.
+#[derive(Debug, Default)]
+pub struct ProfileStore {
+  saved: JsonStore<Saved>,
+}
+
+impl ProfileStore {
+  pub fn load(path: PathBuf) -> Self {
+    Self { saved: JsonStore::load(path, "profiles") }
+  }
+
+  pub fn list(&self) -> ProfileList {
+    ProfileList { active: self.saved.active.clone(), profiles: self.saved.profiles.clone() }
+  }
+
+  pub fn get(&self, name: &str) -> Option<&Profile> {
+    self.saved.profiles.iter().find(|p| p.name == name)
+  }
+
+  pub fn active(&self) -> Option<&str> {
+    self.saved.active.as_deref()
+  }
+
+  /// Add `profile`, or replace the one with the same name in place. Saving the
+  /// current configuration also makes it the active profile.
+  pub fn put(&mut self, profile: Profile) {
+    self.saved.active = Some(profile.name.clone());
+    match self.saved.profiles.iter_mut().find(|p| p.name == profile.name) {
+      Some(existing) => *existing = profile,
+      None => self.saved.profiles.push(profile),
+    }
+    self.saved.save();
+  }
+
+  pub fn remove(&mut self, name: &str) -> bool {
+    let before = self.saved.profiles.len();
+    self.saved.profiles.retain(|p| p.name != name);
+    if self.saved.profiles.len() == before {
+      return false;
+    }
+    if self.active() == Some(name) {
+      self.saved.active = None;
+    }
+    self.saved.save();
+    true
+  }
+
+  /// Mark `name` active and return it; `None` if there is no such profile.
+  pub fn activate(&mut self, name: &str) -> Option<Profile> {
+    let profile = self.get(name)?.clone();
+    self.saved.active = Some(profile.name.clone());
+    self.saved.save();
+    Some(profile)
+  }
+}
@@ -0,0 +1,235 @@
+//! Reminders forwarded to phones through an ntfy topic or Pushover, so they arrive
+//! without helltime's window or anything else running on the phone's side.
+//! Messages are queued on disk and retried while the service is unreachable; a
+//! reminder that arrives long after it was meant is worse than none, so they expire.
+
+use crate::json_store::JsonStore;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// The public ntfy server, used when no own server is set.
+pub const DEFAULT_NTFY_SERVER: &str = "https://ntfy.sh";
+
+pub const PUSHOVER_URL: &str = "https://api.pushover.net/1/messages.json";
+
+/// How often queued messages are retried.
+pub const RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Queued messages older than this are dropped instead of sent.
+pub const MAX_AGE: ChronoDuration = ChronoDuration::minutes(10);
+
+/// Messages kept while offline; the oldest are dropped beyond this.
+pub const MAX_QUEUED: usize = 50;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NtfySettings {
+  pub enabled: bool,
+  /// Self-hosted server; `None` for [`DEFAULT_NTFY_SERVER`].
+  pub server: Option<String>,
+  /// Anyone who knows the topic can read it, so it should be hard to guess.
+  pub topic: String,
+  /// Access token for protected topics, sent as `Authorization: Bearer`.
+  pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PushoverSettings {
+  pub enabled: bool,
+  /// API token of the Pushover application the user registered.
+  pub app_token: String,
+  /// The user (or group) key the messages go to.
+  pub user_key: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PushSettings {
+  pub ntfy: NtfySettings,
+  pub pushover: PushoverSettings,
+}
+
+impl PushSettings {
+  pub fn validate(&self) -> Result<(), String> {
+    if self.ntfy.enabled {
+      if let Some(server) = self.ntfy.server.as_deref().map(str::trim) {
+        if !server.starts_with("https://") && !server.starts_with("http://") {
+          return Err(format!("ntfy server must be an http(s) URL: {server:?}"));
+        }
+      }
+      let topic = self.ntfy.topic.trim();
+      if topic.is_empty() || !topic.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_') {
+        return Err(format!("ntfy topic may only contain letters, digits, - and _: {topic:?}"));
+      }
+    }
+    if self.pushover.enabled {
+      for (name, key) in [("app token", &self.pushover.app_token), ("user key", &self.pushover.user_key)] {
+        if key.trim().len() != 30 || !key.trim().bytes().all(|b| b.is_ascii_alphanumeric()) {
+          return Err(format!("Pushover {name} must be 30 letters and digits"));
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// The enabled services.
+  pub fn sinks(&self) -> Vec<Sink> {
+    let mut sinks = Vec::new();
+    if self.ntfy.enabled {
+      sinks.push(Sink::Ntfy);
+    }
+    if self.pushover.enabled {
+      sinks.push(Sink::Pushover);
+    }
+    sinks
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sink {
+  Ntfy,
+  Pushover,
+}
+
+impl Sink {
+  pub fn label(self) -> &'static str {
+    match self {
+      Sink::Ntfy => "ntfy",
+      Sink::Pushover => "Pushover",
+    }
+  }
+}
+
+/// A reminder waiting to be sent to one service.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushMessage {
+  pub id: u64,
+  pub sink: Sink,
+  pub title: String,
+  pub body: String,
+  pub queued_at: DateTime<Utc>,
+}
+
+/// One HTTP request delivering a message: a JSON POST, with a bearer token for
+/// protected ntfy topics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushRequest {
+  pub url: String,
+  pub bearer: Option<String>,
+  pub body: serde_json::Value,
+}
+
+/// The request for `message` with the current settings, using the JSON publishing
+/// of ntfy (topic in the body) and Pushover's JSON form of its message API.
+pub fn request(settings: &PushSettings, message: &PushMessage) -> PushRequest {
+  match message.sink {
+    Sink::Ntfy => PushRequest {
+      url: settings.ntfy.server.as_deref().map(str::trim).unwrap_or(DEFAULT_NTFY_SERVER).trim_end_matches('/').to_string(),
+      bearer: settings.ntfy.token.as_deref().map(str::trim).filter(|token| !token.is_empty()).map(str::to_string),
+      body: json!({
+        "topic": settings.ntfy.topic.trim(),
+        "title": message.title,
+        "message": message.body,
+        "tags": ["alarm_clock"],
+        "priority": 4,
+      }),
+    },
+    Sink::Pushover => PushRequest {
+      url: PUSHOVER_URL.to_string(),
+      bearer: None,
+      body: json!({
+        "token": settings.pushover.app_token.trim(),
+        "user": settings.pushover.user_key.trim(),
+        "title": message.title,
+        "message": message.body,
+      }),
+    },
+  }
+}
+
+/// What a response status means for a queued message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+  Sent,
+  /// The service is overloaded or failing; try again later.
+  Retry,
+  /// Wrong topic, token or key; retrying will not help.
+  Rejected,
+}
+
+pub fn delivery(status: u16) -> Delivery {
+  match status {
+    200..=299 => Delivery::Sent,
+    429 | 500..=599 => Delivery::Retry,
+    _ => Delivery::Rejected,
+  }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct Saved {
+  next_id: u64,
+  /// Oldest first.
+  pending: Vec<PushMessage>,
+}
+
+/// Messages not yet delivered, persisted as JSON so a restart while offline does
+/// not lose them.
+#[derive(Debug, Default)]
+pub struct PushQueue {
+  saved: JsonStore<Saved>,
+}
+
+impl PushQueue {
+  pub fn load(path: PathBuf) -> Self {
+    Self { saved: JsonStore::load(path, "push queue") }
+  }
+
+  pub fn save(&self) {
+    self.saved.save();
+  }
+
+  /// Queue one message per sink, dropping the oldest when full.
+  pub fn push(&mut self, sinks: &[Sink], title: &str, body: &str, now: DateTime<Utc>) {
+    for sink in sinks {
+      self.saved.next_id += 1;
+      let id = self.saved.next_id;
+      self.saved.pending.push(PushMessage {
+        id,
+        sink: *sink,
+        title: title.to_string(),
+        body: body.to_string(),
+        queued_at: now,
+      });
+    }
+    let excess = self.saved.pending.len().saturating_sub(MAX_QUEUED);
+    self.saved.pending.drain(..excess);
+  }
+
+  /// Messages to send now, oldest first. Expired ones and those for sinks that
+  /// were turned off are dropped; their count is returned too.
+  pub fn due(&mut self, sinks: &[Sink], now: DateTime<Utc>) -> (Vec<PushMessage>, usize) {
+    let before = self.saved.pending.len();
+    self.saved.pending.retain(|m| now - m.queued_at <= MAX_AGE && sinks.contains(&m.sink));
+    (self.saved.pending.clone(), before - self.saved.pending.len())
+  }
+
+  /// The message was delivered or rejected for good.
+  pub fn remove(&mut self, id: u64) {
+    self.saved.pending.retain(|m| m.id != id);
+  }
+
+  pub fn len(&self) -> usize {
+    self.saved.pending.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.saved.pending.is_empty()
+  }
+}
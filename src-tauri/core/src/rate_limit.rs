@@ -0,0 +1,113 @@
+//! Token bucket keeping requests to the schedule API within a courtesy limit, no
+//! matter how often "refresh" is clicked. Retries count like any other request.
+
+use crate::fetch::{FetchError, HttpFetcher, HttpResponse};
+use serde::Serialize;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_REQUESTS_PER_MINUTE: u32 = 6;
+pub const MIN_REQUESTS_PER_MINUTE: u32 = 1;
+pub const MAX_REQUESTS_PER_MINUTE: u32 = 60;
+
+/// Allows bursts of up to `per_minute` requests and refills at `per_minute` per minute.
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+  per_minute: u32,
+  tokens: f64,
+  refilled_at: Option<Instant>,
+  /// Requests turned away since startup.
+  rejected: u64,
+}
+
+/// Limiter state for diagnostics.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitStatus {
+  pub per_minute: u32,
+  /// Requests that could be made right now.
+  pub available: u32,
+  /// Time until the next request is allowed, 0 when one is available.
+  pub wait_ms: u64,
+  pub rejected: u64,
+}
+
+impl TokenBucket {
+  /// A full bucket; `per_minute` is clamped to the allowed range.
+  pub fn new(per_minute: u32) -> Self {
+    let per_minute = per_minute.clamp(MIN_REQUESTS_PER_MINUTE, MAX_REQUESTS_PER_MINUTE);
+    Self { per_minute, tokens: f64::from(per_minute), refilled_at: None, rejected: 0 }
+  }
+
+  /// Change the rate, keeping what is left in the bucket (capped to the new size).
+  pub fn set_rate(&mut self, per_minute: u32) {
+    self.per_minute = per_minute.clamp(MIN_REQUESTS_PER_MINUTE, MAX_REQUESTS_PER_MINUTE);
+    self.tokens = self.tokens.min(f64::from(self.per_minute));
+  }
+
+  fn refill(&mut self, now: Instant) {
+    if let Some(last) = self.refilled_at {
+      let minutes = now.saturating_duration_since(last).as_secs_f64() / 60.0;
+      self.tokens = (self.tokens + minutes * f64::from(self.per_minute)).min(f64::from(self.per_minute));
+    }
+    self.refilled_at = Some(now);
+  }
+
+  fn wait(&self) -> Duration {
+    if self.tokens >= 1.0 {
+      return Duration::ZERO;
+    }
+    Duration::from_secs_f64((1.0 - self.tokens) * 60.0 / f64::from(self.per_minute))
+  }
+
+  /// Take one token, or say how long until one is available.
+  pub fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+    self.refill(now);
+    if self.tokens >= 1.0 {
+      self.tokens -= 1.0;
+      Ok(())
+    } else {
+      self.rejected += 1;
+      Err(self.wait())
+    }
+  }
+
+  pub fn status(&mut self, now: Instant) -> RateLimitStatus {
+    self.refill(now);
+    RateLimitStatus {
+      per_minute: self.per_minute,
+      available: self.tokens.floor() as u32,
+      wait_ms: self.wait().as_millis() as u64,
+      rejected: self.rejected,
+    }
+  }
+}
+
+/// Wraps a fetcher so every request needs a token from `bucket`. Without one the
+/// request fails right away with [`FetchError::RateLimited`] instead of queueing.
+pub struct RateLimitedFetcher<'a, F> {
+  inner: F,
+  bucket: &'a Mutex<TokenBucket>,
+}
+
+impl<'a, F: HttpFetcher + Sync> RateLimitedFetcher<'a, F> {
+  pub fn new(inner: F, bucket: &'a Mutex<TokenBucket>) -> Self {
+    Self { inner, bucket }
+  }
+}
+
+impl<F: HttpFetcher + Sync> HttpFetcher for RateLimitedFetcher<'_, F> {
+  fn get(&self, url: &str) -> impl Future<Output = Result<HttpResponse, FetchError>> + Send {
+    let acquired = self.bucket.lock().unwrap_or_else(|e| e.into_inner()).try_acquire(Instant::now());
+    let url = url.to_string();
+    async move {
+      acquired.map_err(FetchError::RateLimited)?;
+      self.inner.get(&url).await
+    }
+  }
+
+  fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+    self.inner.sleep(duration)
+  }
+}
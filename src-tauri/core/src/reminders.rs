@@ -0,0 +1,365 @@
+use crate::cadence::HelltideCadence;
+use crate::schedule::{EventKind, ScheduleResponse, ScheduledEvent};
+use chrono::{DateTime, Days, Duration as ChronoDuration, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A reminder counts as due for this long after its fire time (the frontend's
+/// `FIRE_WINDOW_MS` must match, so a slow tick never skips a reminder).
+pub const FIRE_WINDOW: ChronoDuration = ChronoDuration::seconds(30);
+
+/// One reminder for one event occurrence. Fire times are always derived from the
+/// event's UTC start, never from local wall-clock arithmetic.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlannedReminder {
+  pub event: ScheduledEvent,
+  /// Index of the timer within the category (0..3), as used in the frontend's fired map.
+  pub timer: usize,
+  pub minutes_before: i64,
+  pub fire_at: DateTime<Utc>,
+}
+
+impl PlannedReminder {
+  /// Stable key for de-duplication, identical to the frontend's `${type}:${id}:${i}`.
+  pub fn key(&self) -> String {
+    format!("{}:{}:{}", self.event.kind.key(), self.event.id, self.timer)
+  }
+
+  pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+    now >= self.fire_at && now <= self.fire_at + FIRE_WINDOW
+  }
+}
+
+/// Reminders for the next occurrence of `kind` that have not passed their fire window yet.
+pub fn plan_for_next(
+  schedule: &ScheduleResponse,
+  kind: EventKind,
+  minutes_before: &[i64],
+  now: DateTime<Utc>,
+) -> Vec<PlannedReminder> {
+  let Some(event) = schedule.next_event(kind, now) else { return Vec::new() };
+
+  let mut plan: Vec<PlannedReminder> = minutes_before
+    .iter()
+    .enumerate()
+    .map(|(timer, &minutes)| PlannedReminder {
+      event: event.clone(),
+      timer,
+      minutes_before: minutes,
+      fire_at: event.start - ChronoDuration::minutes(minutes),
+    })
+    .filter(|r| now <= r.fire_at + FIRE_WINDOW)
+    .collect();
+  plan.sort_by_key(|r| r.fire_at);
+  plan
+}
+
+/// The UI's reminder timers, mirrored into the preferences so the backend can plan
+/// the same reminders while there is no UI (headless).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ReminderTimes {
+  /// Minutes before the start, one per active timer in the UI's order; empty while
+  /// the category's reminders are off.
+  pub helltide: Vec<i64>,
+  pub legion: Vec<i64>,
+  pub world_boss: Vec<i64>,
+  /// "Helltide ends soon" this many minutes before each end; `None` = off.
+  pub helltide_end: Option<i64>,
+  /// Mystery chest reminder this many minutes before each reset; `None` = off.
+  pub chest: Option<i64>,
+}
+
+impl Default for ReminderTimes {
+  fn default() -> Self {
+    Self {
+      helltide: vec![30, 10, 5],
+      legion: vec![30, 10, 5],
+      world_boss: vec![30, 10, 5],
+      helltide_end: None,
+      chest: None,
+    }
+  }
+}
+
+impl ReminderTimes {
+  pub fn minutes_before(&self, kind: EventKind) -> &[i64] {
+    match kind {
+      EventKind::Helltide => &self.helltide,
+      EventKind::Legion => &self.legion,
+      EventKind::WorldBoss => &self.world_boss,
+    }
+  }
+
+  /// Same bounds as the UI's settings.
+  pub fn validate(&self) -> Result<(), String> {
+    for kind in EventKind::ALL {
+      let minutes = self.minutes_before(kind);
+      if minutes.len() > 3 {
+        return Err(format!("{}: at most 3 reminder timers", kind.label()));
+      }
+      if minutes.iter().any(|m| !(1..=60).contains(m)) {
+        return Err(format!("{}: lead times must be 1–60 minutes", kind.label()));
+      }
+    }
+    if self.helltide_end.is_some_and(|m| !(1..=30).contains(&m)) {
+      return Err("Helltide end reminder must be 1–30 minutes before the end".into());
+    }
+    if self.chest.is_some_and(|m| !(1..=30).contains(&m)) {
+      return Err("chest reminder must be 1–30 minutes before the reset".into());
+    }
+    Ok(())
+  }
+}
+
+/// What one step of an escalation does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EscalationAction {
+  OverlayToast,
+  Notification,
+  Sound,
+  /// A sound every [`REPEAT_INTERVAL`] until the reminder is acknowledged.
+  RepeatSound,
+}
+
+/// At `minutes_before` the start, do `actions`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EscalationRule {
+  pub minutes_before: i64,
+  pub actions: Vec<EscalationAction>,
+}
+
+/// A reminder that gets louder as the event comes closer. While enabled it takes the
+/// place of the per-category timers for every shown category.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Escalation {
+  pub enabled: bool,
+  /// Ordered from the earliest step to the last.
+  pub rules: Vec<EscalationRule>,
+}
+
+impl Default for Escalation {
+  fn default() -> Self {
+    use EscalationAction::*;
+    let rule = |minutes_before, actions: &[EscalationAction]| EscalationRule { minutes_before, actions: actions.to_vec() };
+    Self { enabled: false, rules: vec![rule(10, &[OverlayToast]), rule(5, &[Notification, Sound]), rule(1, &[RepeatSound])] }
+  }
+}
+
+pub const MAX_ESCALATION_RULES: usize = 5;
+
+/// How often a [`EscalationAction::RepeatSound`] step plays again.
+pub const REPEAT_INTERVAL: ChronoDuration = ChronoDuration::seconds(20);
+
+/// Repeating stops this long after the start even if nobody acknowledged it.
+pub const REPEAT_LIMIT_AFTER_START: ChronoDuration = ChronoDuration::minutes(2);
+
+/// Fired steps and acknowledgements are forgotten after this.
+const ESCALATION_RETENTION: ChronoDuration = ChronoDuration::hours(2);
+
+impl Escalation {
+  pub fn validate(&self) -> Result<(), String> {
+    if self.rules.len() > MAX_ESCALATION_RULES {
+      return Err(format!("at most {MAX_ESCALATION_RULES} escalation steps"));
+    }
+    if self.enabled && self.rules.is_empty() {
+      return Err("escalation has no steps".into());
+    }
+    for (i, rule) in self.rules.iter().enumerate() {
+      if !(0..=60).contains(&rule.minutes_before) {
+        return Err(format!("escalation step {}: lead time must be 0–60 minutes", i + 1));
+      }
+      if rule.actions.is_empty() {
+        return Err(format!("escalation step {}: no actions", i + 1));
+      }
+      if i > 0 && rule.minutes_before >= self.rules[i - 1].minutes_before {
+        return Err(format!("escalation step {}: must come after the previous step", i + 1));
+      }
+    }
+    Ok(())
+  }
+
+  pub fn minutes_before(&self) -> Vec<i64> {
+    self.rules.iter().map(|rule| rule.minutes_before).collect()
+  }
+}
+
+/// A step to run now, for the event in `reminder` (whose `timer` is the step index).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EscalationStep {
+  pub reminder: PlannedReminder,
+  pub actions: Vec<EscalationAction>,
+  /// A repetition of a [`EscalationAction::RepeatSound`] step.
+  pub repeat: bool,
+}
+
+fn event_key(kind: EventKind, id: i64) -> String {
+  format!("{}:{}", kind.key(), id)
+}
+
+/// Which escalation steps fired, which still repeat and which events were
+/// acknowledged. Acknowledging stops the repeating and skips the remaining steps.
+#[derive(Debug, Default)]
+pub struct EscalationTracker {
+  fired: HashMap<String, DateTime<Utc>>,
+  acknowledged: HashMap<String, DateTime<Utc>>,
+  /// Repeating steps with the time they last played.
+  repeating: Vec<(PlannedReminder, DateTime<Utc>)>,
+}
+
+impl EscalationTracker {
+  /// Steps of `escalation` due at `now` for the next event of each of `kinds`.
+  pub fn due(
+    &mut self,
+    escalation: &Escalation,
+    schedule: &ScheduleResponse,
+    kinds: &[EventKind],
+    now: DateTime<Utc>,
+  ) -> Vec<EscalationStep> {
+    self.fired.retain(|_, at| now - *at < ESCALATION_RETENTION);
+    self.acknowledged.retain(|_, at| now - *at < ESCALATION_RETENTION);
+    let acknowledged = &self.acknowledged;
+    self.repeating.retain(|(r, _)| {
+      now < r.event.start + REPEAT_LIMIT_AFTER_START && !acknowledged.contains_key(&event_key(r.event.kind, r.event.id))
+    });
+
+    let mut due = Vec::new();
+    for (reminder, last) in &mut self.repeating {
+      if now - *last >= REPEAT_INTERVAL {
+        *last = now;
+        due.push(EscalationStep { reminder: reminder.clone(), actions: vec![EscalationAction::RepeatSound], repeat: true });
+      }
+    }
+
+    let minutes_before = escalation.minutes_before();
+    for &kind in kinds {
+      for reminder in plan_for_next(schedule, kind, &minutes_before, now) {
+        if !reminder.is_due(now)
+          || self.acknowledged.contains_key(&event_key(kind, reminder.event.id))
+          || self.fired.insert(reminder.key(), now).is_some()
+        {
+          continue;
+        }
+        let actions = escalation.rules[reminder.timer].actions.clone();
+        if actions.contains(&EscalationAction::RepeatSound) {
+          self.repeating.push((reminder.clone(), now));
+        }
+        due.push(EscalationStep { reminder, actions, repeat: false });
+      }
+    }
+    due
+  }
+
+  /// Stop escalating the event; returns whether a step was still repeating.
+  pub fn acknowledge(&mut self, kind: EventKind, id: i64, now: DateTime<Utc>) -> bool {
+    self.acknowledged.insert(event_key(kind, id), now);
+    let before = self.repeating.len();
+    self.repeating.retain(|(r, _)| !(r.event.kind == kind && r.event.id == id));
+    self.repeating.len() != before
+  }
+}
+
+/// "Helltide ends in N minutes", so cinders get spent before the zone closes.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndReminder {
+  /// Id of the Helltide that ends.
+  pub helltide_id: i64,
+  pub end: DateTime<Utc>,
+  pub fire_at: DateTime<Utc>,
+}
+
+impl EndReminder {
+  /// Stable key for de-duplication, alongside the event reminder keys.
+  pub fn key(&self) -> String {
+    format!("helltide_end:{}:{}", self.helltide_id, self.end.timestamp())
+  }
+
+  pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+    now >= self.fire_at && now <= self.fire_at + FIRE_WINDOW
+  }
+}
+
+/// End reminders for Helltides that have not passed their fire window yet, soonest
+/// first. Ends come from the API where reported, else from the season's cadence.
+pub fn plan_helltide_end(
+  schedule: &ScheduleResponse,
+  cadence: &HelltideCadence,
+  minutes_before: i64,
+  now: DateTime<Utc>,
+) -> Vec<EndReminder> {
+  let mut plan: Vec<EndReminder> = schedule
+    .events(EventKind::Helltide)
+    .iter()
+    .map(|h| {
+      let end = h.end_or(cadence.duration());
+      // Never before the start: a short Helltide gets its reminder when it opens.
+      let fire_at = (end - ChronoDuration::minutes(minutes_before)).max(h.start);
+      EndReminder { helltide_id: h.id, end, fire_at }
+    })
+    .filter(|r| now < r.end && now <= r.fire_at + FIRE_WINDOW)
+    .collect();
+  plan.sort_by_key(|r| r.fire_at);
+  plan
+}
+
+/// How far ahead the synthetic event of a reminder test starts.
+pub const TEST_LEAD: ChronoDuration = ChronoDuration::minutes(1);
+/// Id of the synthetic event; negative so it never matches a real one.
+pub const TEST_EVENT_ID: i64 = -1;
+
+/// Reminder for a synthetic `kind` event starting [`TEST_LEAD`] after `now`, planned
+/// by [`plan_for_next`] like a real one and due right away.
+pub fn test_reminder(kind: EventKind, now: DateTime<Utc>) -> Option<PlannedReminder> {
+  let mut schedule = ScheduleResponse { world_boss: Vec::new(), legion: Vec::new(), helltide: Vec::new() };
+  let raw = serde_json::json!({ "id": TEST_EVENT_ID, "startTime": (now + TEST_LEAD).to_rfc3339() });
+  match kind {
+    EventKind::Helltide => schedule.helltide.push(raw),
+    EventKind::Legion => schedule.legion.push(raw),
+    EventKind::WorldBoss => schedule.world_boss.push(raw),
+  }
+  plan_for_next(&schedule, kind, &[TEST_LEAD.num_minutes()], now).into_iter().next()
+}
+
+/// "Pause reminders for X": while paused no reminder fires. Nothing is queued;
+/// reminders whose window passes during the pause are simply skipped. Persisted
+/// so a restart does not quietly resume reminders.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReminderPause {
+  until: Option<DateTime<Utc>>,
+}
+
+impl ReminderPause {
+  pub fn pause_for(&mut self, now: DateTime<Utc>, duration: ChronoDuration) {
+    self.until = Some(now + duration);
+  }
+
+  /// Pause until the next local midnight in `now`'s time zone.
+  pub fn pause_until_tomorrow<Tz: TimeZone>(&mut self, now: DateTime<Tz>) {
+    let tz = now.timezone();
+    let tomorrow = now.date_naive().checked_add_days(Days::new(1)).map(|d| d.and_time(NaiveTime::MIN));
+    // A DST gap at midnight has no local 00:00; fall back to 24 h from now.
+    self.until = Some(
+      tomorrow
+        .and_then(|t| tz.from_local_datetime(&t).earliest())
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or_else(|| now.with_timezone(&Utc) + ChronoDuration::hours(24)),
+    );
+  }
+
+  pub fn resume(&mut self) {
+    self.until = None;
+  }
+
+  /// End of the pause, `None` when not paused at `now`.
+  pub fn paused_until(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    self.until.filter(|until| *until > now)
+  }
+
+  pub fn is_paused(&self, now: DateTime<Utc>) -> bool {
+    self.paused_until(now).is_some()
+  }
+}
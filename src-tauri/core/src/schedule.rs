@@ -0,0 +1,212 @@
+use crate::i18n::{self, Locale};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ScheduleResponse {
+  #[serde(default)]
+  pub world_boss: Vec<serde_json::Value>,
+  #[serde(default)]
+  pub legion: Vec<serde_json::Value>,
+  #[serde(default)]
+  pub helltide: Vec<serde_json::Value>,
+}
+
+/// Event category as used by the API and the frontend (`ScheduleType`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+  Helltide,
+  Legion,
+  WorldBoss,
+}
+
+impl EventKind {
+  pub const ALL: [EventKind; 3] = [EventKind::Helltide, EventKind::Legion, EventKind::WorldBoss];
+
+  /// English display name, as shown in the tray and notifications.
+  pub fn label(self) -> &'static str {
+    match self {
+      EventKind::Helltide => "Helltide",
+      EventKind::Legion => "Legion",
+      EventKind::WorldBoss => "World Boss",
+    }
+  }
+
+  /// Stable identifier, identical to the serde representation.
+  pub fn key(self) -> &'static str {
+    match self {
+      EventKind::Helltide => "helltide",
+      EventKind::Legion => "legion",
+      EventKind::WorldBoss => "world_boss",
+    }
+  }
+}
+
+/// A single schedule entry with its start time parsed to UTC.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScheduledEvent {
+  pub kind: EventKind,
+  pub id: i64,
+  pub start: DateTime<Utc>,
+  /// End time when the API reports one (`endTime`); see [`end_or`](Self::end_or).
+  pub end: Option<DateTime<Utc>>,
+  pub boss: Option<String>,
+  /// Set when the API omitted the boss and the rotation predictor filled it in.
+  pub boss_predicted: bool,
+  /// Zone or territory the event spawns in, when the API reports one.
+  pub zone: Option<String>,
+  /// Not reported by the API but extrapolated from the season's cadence.
+  pub predicted: bool,
+}
+
+impl ScheduledEvent {
+  /// When the event closes: the reported end, else `start + duration`.
+  pub fn end_or(&self, duration: Duration) -> DateTime<Utc> {
+    self.end.unwrap_or(self.start + duration)
+  }
+}
+
+impl Titled for ScheduledEvent {
+  fn title_parts(&self) -> (EventKind, Option<&str>, bool, Option<&str>) {
+    (self.kind, self.boss.as_deref(), self.boss_predicted, self.zone.as_deref())
+  }
+}
+
+/// Anything with a display title built by [`describe`].
+pub trait Titled {
+  /// Category, boss, whether the boss is predicted, and zone.
+  fn title_parts(&self) -> (EventKind, Option<&str>, bool, Option<&str>);
+
+  /// Display title, see [`describe`].
+  fn title(&self) -> String {
+    let (kind, boss, boss_predicted, zone) = self.title_parts();
+    describe(kind, boss, boss_predicted, zone)
+  }
+
+  /// [`title`](Self::title) with the category name in `locale`.
+  fn title_in(&self, locale: Locale) -> String {
+    let (kind, boss, boss_predicted, zone) = self.title_parts();
+    describe_as(i18n::event_label(locale, kind), boss, boss_predicted, zone)
+  }
+}
+
+/// Title used in the tray, notifications and integrations: `Legion in Kehjistan`,
+/// `World Boss Ashava? in Dry Steppes`. Predicted boss names are marked with `?`.
+pub fn describe(kind: EventKind, boss: Option<&str>, boss_predicted: bool, zone: Option<&str>) -> String {
+  describe_as(kind.label(), boss, boss_predicted, zone)
+}
+
+/// [`describe`] with a given category name, e.g. a translated one.
+pub fn describe_as(label: &str, boss: Option<&str>, boss_predicted: bool, zone: Option<&str>) -> String {
+  let mut title = label.to_string();
+  if let Some(boss) = boss {
+    title.push(' ');
+    title.push_str(&i18n::isolate(boss));
+    if boss_predicted {
+      title.push('?');
+    }
+  }
+  if let Some(zone) = zone {
+    title.push_str(" in ");
+    title.push_str(&i18n::isolate(zone));
+  }
+  title
+}
+
+/// Parse an event start time. The API sends `startTime` as an ISO-8601 string and
+/// `timestamp` as epoch seconds; the ISO string wins when both are present.
+pub fn parse_event_time(raw: &serde_json::Value) -> Option<DateTime<Utc>> {
+  if let Some(iso) = raw.get("startTime").and_then(|v| v.as_str()) {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(iso) {
+      return Some(dt.with_timezone(&Utc));
+    }
+  }
+
+  let ts = raw.get("timestamp").and_then(|v| v.as_i64())?;
+  // Some mirrors send milliseconds; anything past year ~33658 in seconds is clearly ms.
+  if ts > 1_000_000_000_000 {
+    DateTime::from_timestamp_millis(ts)
+  } else {
+    DateTime::from_timestamp(ts, 0)
+  }
+}
+
+fn parse_event(kind: EventKind, raw: &serde_json::Value) -> Option<ScheduledEvent> {
+  let start = parse_event_time(raw)?;
+  let id = raw.get("id").and_then(|v| v.as_i64()).unwrap_or_else(|| start.timestamp());
+  let name = |key: &str| {
+    raw
+      .get(key)
+      .and_then(|v| v.as_str())
+      .map(str::trim)
+      .filter(|s| !s.is_empty())
+      .map(str::to_string)
+  };
+  let (boss, boss_predicted) = match name("boss") {
+    Some(boss) => (Some(boss), false),
+    None => {
+      let predicted = name("predictedBoss");
+      let is_predicted = predicted.is_some();
+      (predicted, is_predicted)
+    }
+  };
+
+  let end = raw
+    .get("endTime")
+    .and_then(|v| v.as_str())
+    .and_then(|iso| DateTime::parse_from_rfc3339(iso).ok())
+    .map(|dt| dt.with_timezone(&Utc))
+    .filter(|end| *end > start);
+  let predicted = raw.get("predicted").and_then(|v| v.as_bool()).unwrap_or(false);
+  Some(ScheduledEvent { kind, id, start, end, boss, boss_predicted, zone: parse_zone(raw), predicted })
+}
+
+/// The API sends `zone` as a list of `{ id, name, isWhisper }` (world bosses can span
+/// several); some mirrors send a plain string or `location` instead.
+fn parse_zone(raw: &serde_json::Value) -> Option<String> {
+  let names: Vec<&str> = match raw.get("zone") {
+    Some(serde_json::Value::Array(zones)) => zones
+      .iter()
+      .filter_map(|z| z.get("name").and_then(|n| n.as_str()))
+      .collect(),
+    Some(serde_json::Value::String(name)) => vec![name.as_str()],
+    _ => raw.get("location").and_then(|v| v.as_str()).into_iter().collect(),
+  };
+  let names: Vec<&str> = names.into_iter().map(str::trim).filter(|n| !n.is_empty()).collect();
+  (!names.is_empty()).then(|| names.join(" / "))
+}
+
+impl ScheduleResponse {
+  pub fn raw(&self, kind: EventKind) -> &[serde_json::Value] {
+    match kind {
+      EventKind::Helltide => &self.helltide,
+      EventKind::Legion => &self.legion,
+      EventKind::WorldBoss => &self.world_boss,
+    }
+  }
+
+  /// All entries of one category with parseable start times, sorted ascending.
+  pub fn events(&self, kind: EventKind) -> Vec<ScheduledEvent> {
+    let mut events: Vec<ScheduledEvent> = self.raw(kind).iter().filter_map(|raw| parse_event(kind, raw)).collect();
+    events.sort_by_key(|e| e.start);
+    events
+  }
+
+  /// The first event of a category starting strictly after `now`.
+  pub fn next_event(&self, kind: EventKind, now: DateTime<Utc>) -> Option<ScheduledEvent> {
+    self.events(kind).into_iter().find(|e| e.start > now)
+  }
+
+  /// A copy with the categories not in `kinds` left empty, e.g. for
+  /// `Preferences::shown_kinds`.
+  pub fn only(&self, kinds: &[EventKind]) -> ScheduleResponse {
+    let keep = |kind: EventKind| if kinds.contains(&kind) { self.raw(kind).to_vec() } else { Vec::new() };
+    ScheduleResponse {
+      world_boss: keep(EventKind::WorldBoss),
+      legion: keep(EventKind::Legion),
+      helltide: keep(EventKind::Helltide),
+    }
+  }
+}
@@ -0,0 +1,189 @@
+//! Global keyboard shortcuts for the tray actions, so everything the tray offers can
+//! be done without a mouse. Bindings are rebindable in the preferences; actions
+//! missing there use their default, an empty binding turns an action's hotkey off.
+//! Registration is platform code in the app.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutAction {
+  ToggleWindow,
+  PeekNextEvent,
+  RevealOverlay,
+  ToggleOverlay,
+  ToggleReminders,
+  /// Pause reminders for an hour.
+  PauseReminders,
+  ResumeReminders,
+}
+
+impl ShortcutAction {
+  pub const ALL: [ShortcutAction; 7] = [
+    ShortcutAction::ToggleWindow,
+    ShortcutAction::PeekNextEvent,
+    ShortcutAction::RevealOverlay,
+    ShortcutAction::ToggleOverlay,
+    ShortcutAction::ToggleReminders,
+    ShortcutAction::PauseReminders,
+    ShortcutAction::ResumeReminders,
+  ];
+
+  pub fn default_binding(self) -> Option<&'static str> {
+    match self {
+      ShortcutAction::ToggleWindow => Some("Ctrl+Alt+H"),
+      ShortcutAction::PeekNextEvent => Some("Ctrl+Alt+N"),
+      ShortcutAction::RevealOverlay => Some("Ctrl+Alt+O"),
+      ShortcutAction::PauseReminders => Some("Ctrl+Alt+P"),
+      ShortcutAction::ToggleOverlay | ShortcutAction::ToggleReminders | ShortcutAction::ResumeReminders => None,
+    }
+  }
+
+  /// Id of the tray menu item doing the same, for keyboard navigation of the menu.
+  pub fn tray_item(self) -> &'static str {
+    match self {
+      ShortcutAction::ToggleWindow => "restore",
+      ShortcutAction::PeekNextEvent => "peek-next",
+      ShortcutAction::RevealOverlay => "reveal-overlay",
+      ShortcutAction::ToggleOverlay => "toggle-overlay",
+      ShortcutAction::ToggleReminders => "toggle-reminder",
+      ShortcutAction::PauseReminders => "pause-1h",
+      ShortcutAction::ResumeReminders => "resume-reminders",
+    }
+  }
+
+  /// Stable hotkey id (`RegisterHotKey`), 1-based.
+  pub fn hotkey_id(self) -> i32 {
+    ShortcutAction::ALL.iter().position(|a| *a == self).unwrap_or(0) as i32 + 1
+  }
+
+  pub fn from_hotkey_id(id: i32) -> Option<ShortcutAction> {
+    usize::try_from(id - 1).ok().and_then(|i| ShortcutAction::ALL.get(i).copied())
+  }
+}
+
+/// Action → binding such as `Ctrl+Alt+N`; `""` unbinds.
+pub type ShortcutMap = BTreeMap<ShortcutAction, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+  /// `A`–`Z`, upper case.
+  Letter(char),
+  Digit(u8),
+  /// `F1`–`F24`.
+  Function(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+  pub ctrl: bool,
+  pub alt: bool,
+  pub shift: bool,
+  pub win: bool,
+  pub key: Key,
+}
+
+impl Shortcut {
+  /// Parse `Ctrl+Alt+N`, `Shift+Win+F5` and the like (case-insensitive). A global
+  /// shortcut needs Ctrl, Alt or Win, so it does not swallow plain typing.
+  pub fn parse(text: &str) -> Result<Shortcut, String> {
+    let (mut ctrl, mut alt, mut shift, mut win, mut key) = (false, false, false, false, None);
+    for part in text.split('+').map(str::trim) {
+      let flag = match part.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => &mut ctrl,
+        "alt" => &mut alt,
+        "shift" => &mut shift,
+        "win" | "super" | "meta" => &mut win,
+        _ => {
+          if key.is_some() {
+            return Err(format!("\"{text}\" has more than one key"));
+          }
+          key = Some(parse_key(part).ok_or_else(|| format!("unknown key \"{part}\" in \"{text}\""))?);
+          continue;
+        }
+      };
+      if *flag {
+        return Err(format!("\"{text}\" repeats a modifier"));
+      }
+      *flag = true;
+    }
+    let key = key.ok_or_else(|| format!("\"{text}\" has no key"))?;
+    if !(ctrl || alt || win) {
+      return Err(format!("\"{text}\" needs Ctrl, Alt or Win"));
+    }
+    Ok(Shortcut { ctrl, alt, shift, win, key })
+  }
+
+  /// `MOD_*` flags for `RegisterHotKey`, without `MOD_NOREPEAT`.
+  pub fn win32_modifiers(&self) -> u32 {
+    (self.alt as u32) | (self.ctrl as u32) << 1 | (self.shift as u32) << 2 | (self.win as u32) << 3
+  }
+
+  /// Windows virtual-key code.
+  pub fn virtual_key(&self) -> u32 {
+    match self.key {
+      Key::Letter(c) => c as u32,
+      Key::Digit(d) => 0x30 + d as u32,
+      Key::Function(n) => 0x70 + n as u32 - 1,
+    }
+  }
+}
+
+fn parse_key(text: &str) -> Option<Key> {
+  let upper = text.to_ascii_uppercase();
+  let mut chars = upper.chars();
+  match (chars.next()?, chars.as_str()) {
+    (c, "") if c.is_ascii_uppercase() => Some(Key::Letter(c)),
+    (c, "") if c.is_ascii_digit() => Some(Key::Digit(c as u8 - b'0')),
+    ('F', n) => n.parse().ok().filter(|n| (1..=24).contains(n)).map(Key::Function),
+    _ => None,
+  }
+}
+
+/// Canonical spelling, e.g. `Ctrl+Alt+N`.
+impl fmt::Display for Shortcut {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (on, name) in [(self.ctrl, "Ctrl+"), (self.alt, "Alt+"), (self.shift, "Shift+"), (self.win, "Win+")] {
+      if on {
+        f.write_str(name)?;
+      }
+    }
+    match self.key {
+      Key::Letter(c) => write!(f, "{c}"),
+      Key::Digit(d) => write!(f, "{d}"),
+      Key::Function(n) => write!(f, "F{n}"),
+    }
+  }
+}
+
+/// The binding in effect for `action`: its entry in `map`, else its default.
+pub fn binding(map: &ShortcutMap, action: ShortcutAction) -> Option<Shortcut> {
+  let text = map.get(&action).map(String::as_str).or(action.default_binding())?;
+  if text.trim().is_empty() {
+    return None;
+  }
+  Shortcut::parse(text).ok()
+}
+
+/// All bindings in effect, in [`ShortcutAction::ALL`] order.
+pub fn bindings(map: &ShortcutMap) -> Vec<(ShortcutAction, Option<Shortcut>)> {
+  ShortcutAction::ALL.iter().map(|action| (*action, binding(map, *action))).collect()
+}
+
+/// Reject unparsable bindings and two actions on the same shortcut.
+pub fn validate(map: &ShortcutMap) -> Result<(), String> {
+  for text in map.values().filter(|t| !t.trim().is_empty()) {
+    Shortcut::parse(text)?;
+  }
+  let mut seen: Vec<(Shortcut, ShortcutAction)> = Vec::new();
+  for (action, shortcut) in bindings(map) {
+    let Some(shortcut) = shortcut else { continue };
+    if let Some((_, other)) = seen.iter().find(|(s, _)| *s == shortcut) {
+      return Err(format!("{shortcut} is bound to both {other:?} and {action:?}"));
+    }
+    seen.push((shortcut, action));
+  }
+  Ok(())
+}
@@ -0,0 +1,85 @@
+//! Settings sync through a folder the user already syncs (Dropbox, OneDrive, a
+//! network share), no server involved. Every PC writes its shared preferences and
+//! UI settings to [`FILE_NAME`] there; whichever copy was written last wins.
+//!
+//! Device-specific preferences (window placement, network setup, onboarding and the
+//! sync folder itself) never leave the machine.
+
+use crate::error::AppError;
+use crate::prefs::Preferences;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+pub const FILE_NAME: &str = "helltime-sync.json";
+
+/// How often the sync folder is checked for changes from other PCs.
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Contents of the sync file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncDocument {
+  pub updated_at: DateTime<Utc>,
+  /// Host name of the PC that wrote it, for the change notification.
+  pub device: String,
+  /// Shared part of the preferences, see [`shared`].
+  pub preferences: Preferences,
+  /// The UI's settings blob (reminder timers, sounds, overlay look), opaque here.
+  #[serde(default)]
+  pub ui_settings: Option<serde_json::Value>,
+}
+
+/// `prefs` without the device-specific parts (reset to defaults).
+pub fn shared(prefs: &Preferences) -> Preferences {
+  let defaults = Preferences::default();
+  Preferences {
+    network: defaults.network,
+    window: defaults.window,
+    onboarding_completed: defaults.onboarding_completed,
+    sync_dir: defaults.sync_dir,
+    ..prefs.clone()
+  }
+}
+
+/// Take the shared preferences from `remote`, keeping `local`'s device-specific ones.
+pub fn apply(local: &Preferences, remote: &Preferences) -> Preferences {
+  Preferences {
+    network: local.network.clone(),
+    window: local.window,
+    onboarding_completed: local.onboarding_completed,
+    sync_dir: local.sync_dir.clone(),
+    ..remote.clone()
+  }
+}
+
+/// Whether the remote copy should replace local state: it is newer than whatever
+/// this PC last wrote or applied (last write wins).
+pub fn is_newer(remote: &SyncDocument, last_synced: Option<DateTime<Utc>>) -> bool {
+  last_synced.is_none_or(|at| remote.updated_at > at)
+}
+
+/// The sync file in `dir`; `None` if there is none yet.
+pub fn read(dir: &Path) -> Result<Option<SyncDocument>, AppError> {
+  let path = dir.join(FILE_NAME);
+  match std::fs::read(&path) {
+    Ok(bytes) => serde_json::from_slice(&bytes)
+      .map(Some)
+      .map_err(|e| AppError::SettingsCorrupt(format!("{:?} is corrupt: {e}", path))),
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+    Err(e) => Err(AppError::Storage(format!("failed to read {:?}: {e}", path))),
+  }
+}
+
+/// Write-then-rename, so a sync client never picks up half a file.
+pub fn write(dir: &Path, doc: &SyncDocument) -> Result<(), AppError> {
+  if !dir.is_dir() {
+    return Err(AppError::InvalidSettings(format!("sync folder {:?} does not exist", dir)));
+  }
+  let path = dir.join(FILE_NAME);
+  let bytes = serde_json::to_vec_pretty(doc).map_err(|e| AppError::Other(format!("failed to serialize sync file: {e}")))?;
+  let tmp = path.with_extension("json.tmp");
+  std::fs::write(&tmp, bytes).map_err(|e| AppError::Storage(format!("failed to write {:?}: {e}", tmp)))?;
+  std::fs::rename(&tmp, &path).map_err(|e| AppError::Storage(format!("failed to replace {:?}: {e}", path)))
+}
@@ -0,0 +1,32 @@
+use crate::schedule::{EventKind, ScheduleResponse};
+use chrono::{DateTime, Duration, Utc};
+
+/// Helltides start hourly; used when the schedule has no earlier start to measure from.
+const DEFAULT_CYCLE: Duration = Duration::hours(1);
+
+/// How long after its start an event still counts as "active now" for the badge.
+pub const ACTIVE_GRACE: Duration = Duration::minutes(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskbarProgress {
+  /// 0-100: how much of the wait between the previous and the next start has passed.
+  pub percent: u64,
+  /// An event of this kind started less than `ACTIVE_GRACE` ago.
+  pub active: bool,
+}
+
+/// Taskbar progress towards the next event of `kind`, `None` if nothing is scheduled.
+pub fn progress(schedule: &ScheduleResponse, kind: EventKind, now: DateTime<Utc>) -> Option<TaskbarProgress> {
+  let events = schedule.events(kind);
+  let next = events.iter().find(|e| e.start > now)?;
+  let previous = events.iter().rev().find(|e| e.start <= now).map(|e| e.start);
+
+  let from = previous.unwrap_or(next.start - DEFAULT_CYCLE);
+  let total = (next.start - from).num_seconds().max(1);
+  let elapsed = (now - from).num_seconds().clamp(0, total);
+
+  Some(TaskbarProgress {
+    percent: (elapsed * 100 / total) as u64,
+    active: previous.is_some_and(|start| now - start < ACTIVE_GRACE),
+  })
+}
@@ -0,0 +1,172 @@
+//! Opt-in anonymous usage counters: which features are used and which error
+//! categories occur, never schedule content, settings values or anything that
+//! identifies the user or the machine. Counts are batched per send interval and
+//! queued on disk while the endpoint is unreachable.
+
+use crate::json_store::JsonStore;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Set (to anything) to turn telemetry off regardless of the preferences, e.g. in
+/// managed installs.
+pub const ENV_KILL_SWITCH: &str = "HELLTIME_NO_TELEMETRY";
+
+/// How often the counted batch is cut and the queue sent.
+pub const SEND_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Batches kept while offline; the oldest are dropped beyond this.
+pub const MAX_QUEUED_BATCHES: usize = 28;
+
+/// HTTP status with which the endpoint retires telemetry for good.
+pub const STATUS_GONE: u16 = 410;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TelemetrySettings {
+  pub enabled: bool,
+  /// Where batches are posted; without one they are only queued.
+  pub endpoint: Option<String>,
+}
+
+impl TelemetrySettings {
+  pub fn validate(&self) -> Result<(), String> {
+    match self.endpoint.as_deref().map(str::trim) {
+      Some(url) if !url.starts_with("https://") && !url.starts_with("http://") => {
+        Err(format!("telemetry endpoint must be an http(s) URL: {url:?}"))
+      }
+      _ => Ok(()),
+    }
+  }
+}
+
+/// Whether anything may be counted or sent: opted in, not switched off through
+/// [`ENV_KILL_SWITCH`] and not retired by the endpoint.
+pub fn allowed(settings: &TelemetrySettings, kill_switch: Option<&str>, retired: bool) -> bool {
+  settings.enabled && kill_switch.is_none() && !retired
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+  Overlay,
+  Reminder,
+  CalendarExport,
+  ProfileSwitch,
+  Hotkey,
+  LocalApi,
+  DiscordPresence,
+  Sync,
+}
+
+/// Counts for one send interval.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Batch {
+  pub features: BTreeMap<Feature, u64>,
+  /// By `AppError::kind`.
+  pub errors: BTreeMap<String, u64>,
+}
+
+impl Batch {
+  pub fn is_empty(&self) -> bool {
+    self.features.is_empty() && self.errors.is_empty()
+  }
+}
+
+/// What is posted to the endpoint, and what `preview_telemetry_payload` shows.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Payload {
+  pub app_version: String,
+  /// `windows`, `linux` or `macos`.
+  pub os: &'static str,
+  pub batches: Vec<Batch>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct Saved {
+  current: Batch,
+  /// Cut batches waiting to be sent, oldest first.
+  pending: VecDeque<Batch>,
+  retired: bool,
+}
+
+/// The counters and the send queue, persisted as JSON.
+#[derive(Debug, Default)]
+pub struct TelemetryQueue {
+  saved: JsonStore<Saved>,
+}
+
+impl TelemetryQueue {
+  pub fn load(path: PathBuf) -> Self {
+    Self { saved: JsonStore::load(path, "telemetry queue") }
+  }
+
+  pub fn save(&self) {
+    self.saved.save();
+  }
+
+  pub fn record_feature(&mut self, feature: Feature) {
+    *self.saved.current.features.entry(feature).or_default() += 1;
+  }
+
+  pub fn record_error(&mut self, kind: &str) {
+    *self.saved.current.errors.entry(kind.to_string()).or_default() += 1;
+  }
+
+  /// Queue the current counts as a batch, dropping the oldest when full.
+  pub fn cut(&mut self) {
+    let batch = std::mem::take(&mut self.saved.current);
+    if batch.is_empty() {
+      return;
+    }
+    self.saved.pending.push_back(batch);
+    while self.saved.pending.len() > MAX_QUEUED_BATCHES {
+      self.saved.pending.pop_front();
+    }
+  }
+
+  /// The queued batches, or `None` when nothing is waiting.
+  pub fn payload(&self, app_version: &str) -> Option<Payload> {
+    (!self.saved.pending.is_empty()).then(|| Payload {
+      app_version: app_version.to_string(),
+      os: std::env::consts::OS,
+      batches: self.saved.pending.iter().cloned().collect(),
+    })
+  }
+
+  /// Everything that would go out with the next send, the batch still being counted
+  /// included.
+  pub fn preview(&self, app_version: &str) -> Payload {
+    let mut batches: Vec<_> = self.saved.pending.iter().cloned().collect();
+    if !self.saved.current.is_empty() {
+      batches.push(self.saved.current.clone());
+    }
+    Payload { app_version: app_version.to_string(), os: std::env::consts::OS, batches }
+  }
+
+  /// The first `count` batches were delivered.
+  pub fn sent(&mut self, count: usize) {
+    let count = count.min(self.saved.pending.len());
+    self.saved.pending.drain(..count);
+  }
+
+  /// Drop everything counted so far, e.g. after opting out.
+  pub fn clear(&mut self) {
+    self.saved.current = Batch::default();
+    self.saved.pending.clear();
+  }
+
+  /// The endpoint retired telemetry: drop everything and never count again.
+  pub fn retire(&mut self) {
+    self.clear();
+    self.saved.retired = true;
+  }
+
+  pub fn retired(&self) -> bool {
+    self.saved.retired
+  }
+}
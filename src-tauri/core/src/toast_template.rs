@@ -0,0 +1,188 @@
+//! User-defined text of the overlay's event toasts, e.g. `{type} in {minutes}m — {zone}`.
+//!
+//! Placeholders are written in braces; `{{` and `}}` are literal braces. Unknown
+//! placeholders and unbalanced braces are rejected when the preferences are saved,
+//! so a template never fails at the moment a reminder fires. Placeholders without a
+//! value (no zone reported) render empty, and separators left dangling at either end
+//! are dropped.
+
+use crate::clock::format_time_in;
+use crate::countdown::{format_clock, NextEvent};
+use crate::i18n::{self, Locale};
+use crate::schedule::{describe_as, EventKind, ScheduledEvent};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+pub const MAX_LEN: usize = 200;
+
+/// Everything a template can refer to.
+pub const PLACEHOLDERS: [&str; 7] = ["type", "title", "boss", "zone", "minutes", "countdown", "time"];
+
+/// Characters trimmed from the ends of a rendered line, besides whitespace.
+const SEPARATORS: &[char] = &['—', '–', '-', '·', '|', ',', ':', '/'];
+
+/// Title and body line of an event toast.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ToastTemplates {
+  pub title: String,
+  pub body: String,
+}
+
+impl Default for ToastTemplates {
+  fn default() -> Self {
+    Self { title: "{title}".to_string(), body: "{countdown}".to_string() }
+  }
+}
+
+/// The event a toast is about, as far as templates can see it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToastEvent {
+  pub kind: EventKind,
+  pub start: DateTime<Utc>,
+  #[serde(default)]
+  pub boss: Option<String>,
+  #[serde(default)]
+  pub boss_predicted: bool,
+  #[serde(default)]
+  pub zone: Option<String>,
+}
+
+impl From<&ScheduledEvent> for ToastEvent {
+  fn from(event: &ScheduledEvent) -> Self {
+    Self {
+      kind: event.kind,
+      start: event.start,
+      boss: event.boss.clone(),
+      boss_predicted: event.boss_predicted,
+      zone: event.zone.clone(),
+    }
+  }
+}
+
+impl ToastEvent {
+  /// `next` as seen at `now`.
+  pub fn from_next(next: &NextEvent, now: DateTime<Utc>) -> Self {
+    Self {
+      kind: next.kind,
+      start: now + Duration::seconds(next.seconds_until),
+      boss: next.boss.clone(),
+      boss_predicted: next.boss_predicted,
+      zone: next.zone.clone(),
+    }
+  }
+}
+
+/// Rendered toast text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RenderedToast {
+  pub title: String,
+  pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+  Text(String),
+  Field(&'static str),
+}
+
+fn parse(template: &str) -> Result<Vec<Part>, String> {
+  if template.chars().count() > MAX_LEN {
+    return Err(format!("template is longer than {MAX_LEN} characters"));
+  }
+  let mut parts = Vec::new();
+  let mut text = String::new();
+  let mut chars = template.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '{' if chars.peek() == Some(&'{') => {
+        chars.next();
+        text.push('{');
+      }
+      '}' if chars.peek() == Some(&'}') => {
+        chars.next();
+        text.push('}');
+      }
+      '{' => {
+        let mut name = String::new();
+        loop {
+          match chars.next() {
+            Some('}') => break,
+            Some('{') | None => return Err(format!("unclosed placeholder {{{name}")),
+            Some(c) => name.push(c),
+          }
+        }
+        let field = PLACEHOLDERS
+          .iter()
+          .find(|p| **p == name.trim())
+          .ok_or_else(|| format!("unknown placeholder {{{name}}}, use one of {}", PLACEHOLDERS.join(", ")))?;
+        parts.push(Part::Text(std::mem::take(&mut text)));
+        parts.push(Part::Field(field));
+      }
+      '}' => return Err("unmatched } (write }} for a literal brace)".to_string()),
+      c => text.push(c),
+    }
+  }
+  parts.push(Part::Text(text));
+  Ok(parts)
+}
+
+/// Check a single template.
+pub fn validate(template: &str) -> Result<(), String> {
+  parse(template).map(|_| ())
+}
+
+impl ToastTemplates {
+  pub fn validate(&self) -> Result<(), String> {
+    validate(&self.title).map_err(|e| format!("toast title: {e}"))?;
+    validate(&self.body).map_err(|e| format!("toast text: {e}"))
+  }
+
+  /// The toast for `event` at `now`, with times in `tz`. A template that does not
+  /// parse (only possible if validation was skipped) falls back to the default.
+  pub fn render<Tz: TimeZone>(&self, event: &ToastEvent, locale: Locale, now: DateTime<Utc>, tz: &Tz) -> RenderedToast
+  where
+    Tz::Offset: std::fmt::Display,
+  {
+    let defaults = ToastTemplates::default();
+    let line = |template: &str, fallback: &str| {
+      let parts = parse(template).or_else(|_| parse(fallback)).unwrap_or_default();
+      render_parts(&parts, event, locale, now, tz)
+    };
+    RenderedToast { title: line(&self.title, &defaults.title), body: line(&self.body, &defaults.body) }
+  }
+}
+
+fn render_parts<Tz: TimeZone>(parts: &[Part], event: &ToastEvent, locale: Locale, now: DateTime<Utc>, tz: &Tz) -> String
+where
+  Tz::Offset: std::fmt::Display,
+{
+  let seconds = (event.start - now).num_seconds().max(0);
+  let label = i18n::event_label(locale, event.kind);
+  let mut out = String::new();
+  for part in parts {
+    match part {
+      Part::Text(text) => out.push_str(text),
+      Part::Field("type") => out.push_str(label),
+      Part::Field("title") => {
+        out.push_str(&describe_as(label, event.boss.as_deref(), event.boss_predicted, event.zone.as_deref()))
+      }
+      Part::Field("boss") => {
+        if let Some(boss) = &event.boss {
+          out.push_str(&i18n::isolate(boss));
+          if event.boss_predicted {
+            out.push('?');
+          }
+        }
+      }
+      Part::Field("zone") => out.push_str(&i18n::isolate(event.zone.as_deref().unwrap_or_default())),
+      Part::Field("minutes") => out.push_str(&((seconds + 59) / 60).to_string()),
+      Part::Field("countdown") => out.push_str(&format_clock(seconds)),
+      Part::Field("time") => out.push_str(&format_time_in(event.start, tz)),
+      Part::Field(_) => {}
+    }
+  }
+  let collapsed = out.split_whitespace().collect::<Vec<_>>().join(" ");
+  collapsed.trim_matches(|c: char| c.is_whitespace() || SEPARATORS.contains(&c)).to_string()
+}
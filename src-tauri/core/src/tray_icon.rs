@@ -0,0 +1,76 @@
+//! Tray icon variants. The brand-colored hourglass needs a light outline on dark
+//! taskbars and a dark one on light taskbars; the monochrome variants use the
+//! taskbar's text color only.
+//!
+//! Shortly before an event and while a Helltide runs the icon pulses between its
+//! normal and a lit frame, so the tray alerts even with notifications muted.
+
+use crate::cadence::HelltideCadence;
+use crate::countdown::active_events;
+use crate::prefs::TrayIconStyle;
+use crate::schedule::{EventKind, ScheduleResponse};
+use chrono::{DateTime, Duration, Utc};
+
+/// The icon pulses this long before a shown event starts.
+pub const PULSE_LEAD: Duration = Duration::minutes(2);
+
+const BRAND: [u8; 3] = [235, 90, 30];
+const GLOW: [u8; 3] = [255, 200, 60];
+
+/// The Windows taskbar theme (`SystemUsesLightTheme`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskbarTheme {
+  Light,
+  Dark,
+}
+
+/// One tray icon asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrayIcon {
+  /// Colored, for light taskbars.
+  Light,
+  /// Colored, for dark taskbars.
+  Dark,
+  MonochromeLight,
+  MonochromeDark,
+}
+
+/// The icon for `style` on a taskbar with `theme`.
+pub fn pick(style: TrayIconStyle, theme: TaskbarTheme) -> TrayIcon {
+  match (style, theme) {
+    (TrayIconStyle::Light, _) | (TrayIconStyle::Auto, TaskbarTheme::Light) => TrayIcon::Light,
+    (TrayIconStyle::Dark, _) | (TrayIconStyle::Auto, TaskbarTheme::Dark) => TrayIcon::Dark,
+    (TrayIconStyle::Monochrome, TaskbarTheme::Light) => TrayIcon::MonochromeLight,
+    (TrayIconStyle::Monochrome, TaskbarTheme::Dark) => TrayIcon::MonochromeDark,
+  }
+}
+
+/// Whether the icon should pulse: an event of `kinds` starts within [`PULSE_LEAD`],
+/// or a Helltide is running.
+pub fn should_pulse(
+  schedule: &ScheduleResponse,
+  kinds: &[EventKind],
+  cadence: &HelltideCadence,
+  now: DateTime<Utc>,
+) -> bool {
+  let imminent = kinds
+    .iter()
+    .filter_map(|kind| schedule.next_event(*kind, now))
+    .any(|event| event.start - now <= PULSE_LEAD);
+  imminent || active_events(schedule, kinds, cadence, now).iter().any(|e| e.kind == EventKind::Helltide)
+}
+
+/// Turn `rgba` (the pixels of `icon`) into its lit frame: colored icons glow in
+/// the brand colors, monochrome ones fade.
+pub fn light_up(icon: TrayIcon, rgba: &mut [u8]) {
+  for px in rgba.chunks_exact_mut(4) {
+    if px[3] == 0 {
+      continue;
+    }
+    match icon {
+      TrayIcon::Light | TrayIcon::Dark if px[..3] == BRAND => px[..3].copy_from_slice(&GLOW),
+      TrayIcon::Light | TrayIcon::Dark => px[..3].copy_from_slice(&BRAND),
+      TrayIcon::MonochromeLight | TrayIcon::MonochromeDark => px[3] = (px[3] as u16 * 2 / 5) as u8,
+    }
+  }
+}
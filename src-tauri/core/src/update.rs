@@ -0,0 +1,53 @@
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+/// The subset of a GitHub `releases` API entry needed for update checks.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GithubRelease {
+  pub tag_name: String,
+  pub html_url: String,
+  #[serde(default)]
+  pub body: Option<String>,
+  #[serde(default)]
+  pub prerelease: bool,
+  #[serde(default)]
+  pub draft: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
+  pub current: String,
+  pub latest: String,
+  pub url: String,
+  pub notes: Option<String>,
+}
+
+/// Parse a release tag such as `v0.2.0` or `0.2.0-beta.1`.
+pub fn parse_version(tag: &str) -> Option<Version> {
+  let tag = tag.trim();
+  Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()
+}
+
+/// The newest release that is strictly newer than `current`, if any.
+///
+/// Drafts are always ignored; pre-releases only count when `include_prerelease`
+/// is set or the running build is itself a pre-release.
+pub fn newest_update(current: &str, releases: &[GithubRelease], include_prerelease: bool) -> Option<UpdateInfo> {
+  let current_version = parse_version(current)?;
+  let allow_pre = include_prerelease || !current_version.pre.is_empty();
+
+  releases
+    .iter()
+    .filter(|r| !r.draft && (allow_pre || !r.prerelease))
+    .filter_map(|r| parse_version(&r.tag_name).map(|v| (v, r)))
+    .filter(|(v, _)| allow_pre || v.pre.is_empty())
+    .filter(|(v, _)| *v > current_version)
+    .max_by(|(a, _), (b, _)| a.cmp(b))
+    .map(|(v, r)| UpdateInfo {
+      current: current_version.to_string(),
+      latest: v.to_string(),
+      url: r.html_url.clone(),
+      notes: r.body.clone().filter(|b| !b.trim().is_empty()),
+    })
+}
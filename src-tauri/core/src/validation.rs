@@ -0,0 +1,103 @@
+//! Sanity filter for freshly fetched schedules. A glitchy API (a stale mirror, a
+//! timestamp in milliseconds where seconds were expected, the same event twice) must
+//! not turn into absurd countdowns or a burst of reminders, so such entries are dropped
+//! before anything else sees the schedule. What was dropped is counted for diagnostics.
+
+use crate::schedule::{self, EventKind, ScheduleResponse};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// Events that started longer ago than this are dropped. Long enough to keep a running
+/// Helltide, which lasts about an hour.
+pub const PAST_GRACE: Duration = Duration::hours(2);
+
+/// Events starting further out than this are dropped; the API publishes about a day ahead.
+pub const HORIZON: Duration = Duration::hours(48);
+
+/// Start times before the game's release are bogus regardless of the current time.
+fn earliest() -> DateTime<Utc> {
+  Utc.with_ymd_and_hms(2023, 6, 1, 0, 0, 0).unwrap()
+}
+
+/// How many entries one validation pass dropped, by reason.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+  /// Started longer than [`PAST_GRACE`] ago.
+  pub past: usize,
+  /// Starts more than [`HORIZON`] from now.
+  pub too_far: usize,
+  /// Same id or same start as an earlier entry of the same category.
+  pub duplicates: usize,
+  /// No parseable start time, or one before the game existed.
+  pub bogus: usize,
+}
+
+impl ValidationReport {
+  pub fn total(&self) -> usize {
+    self.past + self.too_far + self.duplicates + self.bogus
+  }
+
+  fn add(&mut self, other: &ValidationReport) {
+    self.past += other.past;
+    self.too_far += other.too_far;
+    self.duplicates += other.duplicates;
+    self.bogus += other.bogus;
+  }
+}
+
+/// Dropped counts of the last fetch and since startup, shown in diagnostics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationStats {
+  pub last: ValidationReport,
+  pub since_start: ValidationReport,
+  pub passes: u64,
+}
+
+impl ValidationStats {
+  pub fn record(&mut self, report: ValidationReport) {
+    self.last = report;
+    self.since_start.add(&report);
+    self.passes += 1;
+  }
+}
+
+/// Drop entries that cannot be right at `now`, keeping the first of any duplicates.
+/// The remaining raw entries are left untouched and in their original order.
+pub fn sanitize(schedule: &mut ScheduleResponse, now: DateTime<Utc>) -> ValidationReport {
+  let mut report = ValidationReport::default();
+  for kind in EventKind::ALL {
+    let entries = match kind {
+      EventKind::Helltide => &mut schedule.helltide,
+      EventKind::Legion => &mut schedule.legion,
+      EventKind::WorldBoss => &mut schedule.world_boss,
+    };
+    let mut seen_ids = HashSet::new();
+    let mut seen_starts = HashSet::new();
+    entries.retain(|raw| {
+      let Some(start) = schedule::parse_event_time(raw).filter(|start| *start >= earliest()) else {
+        report.bogus += 1;
+        return false;
+      };
+      if start < now - PAST_GRACE {
+        report.past += 1;
+        return false;
+      }
+      if start > now + HORIZON {
+        report.too_far += 1;
+        return false;
+      }
+      let id = raw.get("id").and_then(|v| v.as_i64());
+      if seen_starts.contains(&start) || id.is_some_and(|id| seen_ids.contains(&id)) {
+        report.duplicates += 1;
+        return false;
+      }
+      seen_starts.insert(start);
+      seen_ids.extend(id);
+      true
+    });
+  }
+  report
+}
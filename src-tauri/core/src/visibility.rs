@@ -0,0 +1,66 @@
+//! Time windows in which the overlay may be on screen, e.g. "18:00–24:00 on
+//! weekdays", for people who leave the PC running all day.
+
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Weekday};
+use serde::{Deserialize, Serialize};
+
+pub const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// Visibility rule set; off or without windows it never hides anything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct VisibilitySchedule {
+  pub enabled: bool,
+  pub windows: Vec<VisibilityWindow>,
+}
+
+/// One local-time window. An end at or before the start runs past midnight into
+/// the next day (`22:00–02:00`); `days` are the days the window starts on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VisibilityWindow {
+  /// Empty means every day.
+  #[serde(default)]
+  pub days: Vec<Weekday>,
+  /// Minutes after local midnight, 0..1440.
+  pub start_minute: u16,
+  /// Minutes after local midnight, 0..=1440 (1440 is 24:00).
+  pub end_minute: u16,
+}
+
+impl VisibilityWindow {
+  fn starts_on(&self, day: Weekday) -> bool {
+    self.days.is_empty() || self.days.contains(&day)
+  }
+
+  pub fn contains(&self, day: Weekday, minute: u16) -> bool {
+    if self.start_minute < self.end_minute {
+      self.starts_on(day) && (self.start_minute..self.end_minute).contains(&minute)
+    } else {
+      (self.starts_on(day) && minute >= self.start_minute) || (self.starts_on(day.pred()) && minute < self.end_minute)
+    }
+  }
+}
+
+impl VisibilitySchedule {
+  /// Whether the overlay may be shown at `at`, judged by its local wall-clock time.
+  pub fn allows<Tz: TimeZone>(&self, at: &DateTime<Tz>) -> bool {
+    if !self.enabled || self.windows.is_empty() {
+      return true;
+    }
+    let minute = (at.hour() * 60 + at.minute()) as u16;
+    self.windows.iter().any(|w| w.contains(at.weekday(), minute))
+  }
+
+  pub fn validate(&self) -> Result<(), String> {
+    for window in &self.windows {
+      if window.start_minute >= MINUTES_PER_DAY || window.end_minute > MINUTES_PER_DAY {
+        return Err(format!(
+          "overlay time window out of range: {}–{} minutes",
+          window.start_minute, window.end_minute
+        ));
+      }
+    }
+    Ok(())
+  }
+}
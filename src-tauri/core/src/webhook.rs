@@ -0,0 +1,272 @@
+//! Generic outgoing webhook for home automation and bots ("flash the lights when a
+//! Helltide starts"): on the chosen triggers a JSON body is posted to the user's
+//! URL, optionally signed with HMAC-SHA256 so the receiver can tell it came from
+//! helltime. Failed deliveries are retried with growing pauses.
+
+use crate::cache::ScheduleDiff;
+use crate::schedule::{EventKind, ScheduleResponse, ScheduledEvent};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// `sha256=<hex>` of the body, keyed with the secret (the scheme GitHub uses).
+pub const SIGNATURE_HEADER: &str = "X-Helltime-Signature";
+
+/// Pauses before the retries; a delivery is given up after the last.
+pub const BACKOFF: [Duration; 4] =
+  [Duration::from_secs(5), Duration::from_secs(30), Duration::from_secs(2 * 60), Duration::from_secs(10 * 60)];
+
+/// The same error category is reported at most once in this period.
+pub const ERROR_REPEAT_AFTER: chrono::Duration = chrono::Duration::minutes(30);
+
+pub const MAX_TEMPLATE_CHARS: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookTrigger {
+  /// An event of a shown category starts.
+  EventStarting,
+  /// A refresh shifted or cancelled upcoming events.
+  ScheduleChanged,
+  /// The schedule could not be fetched or parsed.
+  AppError,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WebhookSettings {
+  pub enabled: bool,
+  pub url: String,
+  pub triggers: Vec<WebhookTrigger>,
+  /// JSON with `{placeholders}`, see [`placeholders`]; `None` posts all fields as
+  /// one object.
+  pub template: Option<String>,
+  /// Signs the body into [`SIGNATURE_HEADER`] when set.
+  pub secret: Option<String>,
+}
+
+impl Default for WebhookSettings {
+  fn default() -> Self {
+    Self {
+      enabled: false,
+      url: String::new(),
+      triggers: vec![WebhookTrigger::EventStarting],
+      template: None,
+      secret: None,
+    }
+  }
+}
+
+impl WebhookSettings {
+  pub fn validate(&self) -> Result<(), String> {
+    if !self.enabled {
+      return Ok(());
+    }
+    let url = self.url.trim();
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+      return Err(format!("webhook URL must be an http(s) URL: {url:?}"));
+    }
+    if self.triggers.is_empty() {
+      return Err("webhook needs at least one trigger".to_string());
+    }
+    if self.secret.as_deref().is_some_and(|secret| secret.trim().is_empty()) {
+      return Err("webhook secret must not be blank".to_string());
+    }
+    if let Some(template) = &self.template {
+      if template.chars().count() > MAX_TEMPLATE_CHARS {
+        return Err(format!("webhook template is longer than {MAX_TEMPLATE_CHARS} characters"));
+      }
+      let sample = WebhookEvent::sample(Utc::now());
+      serde_json::from_str::<Value>(&render(template, &sample))
+        .map_err(|e| format!("webhook template does not produce valid JSON: {e}"))?;
+    }
+    Ok(())
+  }
+
+  pub fn fires_on(&self, trigger: WebhookTrigger) -> bool {
+    self.enabled && self.triggers.contains(&trigger)
+  }
+}
+
+/// Fields available to every trigger, then per trigger.
+pub fn placeholders(trigger: WebhookTrigger) -> &'static [&'static str] {
+  match trigger {
+    WebhookTrigger::EventStarting => &["trigger", "at", "event", "id", "title", "start", "zone", "predicted"],
+    WebhookTrigger::ScheduleChanged => &["trigger", "at", "shifted", "cancelled"],
+    WebhookTrigger::AppError => &["trigger", "at", "kind", "message"],
+  }
+}
+
+/// One occurrence of a trigger with its fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookEvent {
+  pub trigger: WebhookTrigger,
+  fields: Map<String, Value>,
+}
+
+impl WebhookEvent {
+  fn new(trigger: WebhookTrigger, at: DateTime<Utc>, fields: Value) -> Self {
+    let mut all = Map::new();
+    all.insert("trigger".into(), serde_json::to_value(trigger).unwrap_or(Value::Null));
+    all.insert("at".into(), Value::String(at.to_rfc3339()));
+    if let Value::Object(fields) = fields {
+      all.extend(fields);
+    }
+    Self { trigger, fields: all }
+  }
+
+  /// `title` is the event's display name in the user's language.
+  pub fn event_starting(event: &ScheduledEvent, title: &str, at: DateTime<Utc>) -> Self {
+    Self::new(
+      WebhookTrigger::EventStarting,
+      at,
+      json!({
+        "event": event.kind.key(),
+        "id": event.id,
+        "title": title,
+        "start": event.start.to_rfc3339(),
+        "zone": event.zone,
+        "predicted": event.predicted,
+      }),
+    )
+  }
+
+  pub fn schedule_changed(changes: &ScheduleDiff, at: DateTime<Utc>) -> Self {
+    Self::new(
+      WebhookTrigger::ScheduleChanged,
+      at,
+      json!({ "shifted": changes.shifted.len(), "cancelled": changes.cancelled.len() }),
+    )
+  }
+
+  /// `kind` as in `AppError::kind`.
+  pub fn app_error(kind: &str, message: &str, at: DateTime<Utc>) -> Self {
+    Self::new(WebhookTrigger::AppError, at, json!({ "kind": kind, "message": message }))
+  }
+
+  /// A Helltide starting now, for validating templates and the test button.
+  pub fn sample(at: DateTime<Utc>) -> Self {
+    let event = ScheduledEvent {
+      kind: EventKind::Helltide,
+      id: 0,
+      start: at,
+      end: None,
+      boss: None,
+      boss_predicted: false,
+      zone: Some("Kehjistan".to_string()),
+      predicted: false,
+    };
+    Self::event_starting(&event, "Helltide", at)
+  }
+
+  pub fn field(&self, name: &str) -> Option<&Value> {
+    self.fields.get(name)
+  }
+}
+
+/// The body to post: the rendered template, or all fields as one JSON object.
+pub fn body(settings: &WebhookSettings, event: &WebhookEvent) -> String {
+  match &settings.template {
+    Some(template) => render(template, event),
+    None => Value::Object(event.fields.clone()).to_string(),
+  }
+}
+
+/// Replace `{name}` with the field's value. Strings are JSON-escaped without quotes,
+/// so placeholders belong inside string literals (`"text": "{title} started"`);
+/// numbers, booleans and `null` can also stand alone. Unknown names stay as they are.
+pub fn render(template: &str, event: &WebhookEvent) -> String {
+  let mut out = String::with_capacity(template.len());
+  let mut rest = template;
+  while let Some(open) = rest.find('{') {
+    out.push_str(&rest[..open]);
+    let after = &rest[open + 1..];
+    let name = after.find('}').map(|close| &after[..close]).filter(|name| is_name(name));
+    match name.and_then(|name| Some((name, event.fields.get(name)?))) {
+      Some((name, value)) => {
+        match value {
+          Value::String(text) => {
+            let quoted = Value::String(text.clone()).to_string();
+            out.push_str(&quoted[1..quoted.len() - 1]);
+          }
+          other => out.push_str(&other.to_string()),
+        }
+        rest = &after[name.len() + 1..];
+      }
+      None => {
+        out.push('{');
+        rest = after;
+      }
+    }
+  }
+  out.push_str(rest);
+  out
+}
+
+fn is_name(name: &str) -> bool {
+  !name.is_empty() && name.bytes().all(|b| b.is_ascii_lowercase() || b == b'_')
+}
+
+/// [`SIGNATURE_HEADER`] value for `body`.
+pub fn signature(secret: &str, body: &str) -> String {
+  let mac = hmac_sha256(secret.as_bytes(), body.as_bytes());
+  let hex: String = mac.iter().map(|b| format!("{b:02x}")).collect();
+  format!("sha256={hex}")
+}
+
+/// RFC 2104 HMAC with SHA-256.
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+  const BLOCK: usize = 64;
+  let mut block = [0u8; BLOCK];
+  if key.len() > BLOCK {
+    block[..32].copy_from_slice(&Sha256::digest(key));
+  } else {
+    block[..key.len()].copy_from_slice(key);
+  }
+  let pad = |byte: u8| block.map(|k| k ^ byte);
+  let inner = Sha256::new().chain_update(pad(0x36)).chain_update(message).finalize();
+  Sha256::new().chain_update(pad(0x5c)).chain_update(inner).finalize().into()
+}
+
+/// Pause before retry number `retry` (0-based), or `None` to give up.
+pub fn backoff(retry: usize) -> Option<Duration> {
+  BACKOFF.get(retry).copied()
+}
+
+/// Events of `kinds` starting in `(after, until]`, in start order.
+pub fn starting_between(
+  schedule: &ScheduleResponse,
+  kinds: &[EventKind],
+  after: DateTime<Utc>,
+  until: DateTime<Utc>,
+) -> Vec<ScheduledEvent> {
+  let mut events: Vec<ScheduledEvent> = kinds
+    .iter()
+    .flat_map(|kind| schedule.events(*kind))
+    .filter(|e| e.start > after && e.start <= until)
+    .collect();
+  events.sort_by_key(|e| e.start);
+  events
+}
+
+/// Keeps a failing fetch from posting the same error on every poll.
+#[derive(Debug, Default)]
+pub struct ErrorThrottle {
+  last: HashMap<String, DateTime<Utc>>,
+}
+
+impl ErrorThrottle {
+  /// Whether an error of `kind` should be reported at `now`; records it if so.
+  pub fn allow(&mut self, kind: &str, now: DateTime<Utc>) -> bool {
+    match self.last.get(kind) {
+      Some(at) if now - *at < ERROR_REPEAT_AFTER => false,
+      _ => {
+        self.last.insert(kind.to_string(), now);
+        true
+      }
+    }
+  }
+}
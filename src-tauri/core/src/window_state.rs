@@ -0,0 +1,128 @@
+//! Main-window visibility state machine (tray toggle, hide to tray, restore).
+//!
+//! Pure and single-threaded; owned by [`crate::controller::Controller`], which the app
+//! drives from one task, so re-entrant window events queue up instead of deadlocking.
+
+use std::time::{Duration, Instant};
+
+/// Tray clicks closer together than this are one click reported twice (Tauri quirk).
+pub const TRAY_DEBOUNCE: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+  /// Shown and in the taskbar.
+  Visible,
+  /// Hidden, only reachable from the tray (user closed/minimized).
+  Hidden,
+  /// Hidden long enough that its webview was destroyed to free memory; showing it
+  /// again rebuilds the window.
+  Unloaded,
+}
+
+/// What the caller should do after asking for a transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Plan {
+  /// Perform the window operations, then call `finish`.
+  Start,
+  /// Already in the requested state; nothing to do (restore may still focus).
+  AlreadyThere,
+  /// Another transition is running (or this call is re-entrant); skip.
+  Busy,
+}
+
+/// What to do about a resize event, given whether the OS says the window is minimized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinimizeAction {
+  /// Just minimized and "minimize to tray" is on.
+  HideToTray,
+  /// Just minimized; it stays in the taskbar.
+  Minimized,
+  /// Not a minimize (a normal resize, or still minimized from before).
+  None,
+}
+
+#[derive(Debug, Clone)]
+pub struct WindowMachine {
+  visibility: Visibility,
+  transition: Option<Visibility>,
+  last_tray_action: Option<Instant>,
+  minimized: bool,
+}
+
+impl Default for WindowMachine {
+  fn default() -> Self {
+    Self { visibility: Visibility::Visible, transition: None, last_tray_action: None, minimized: false }
+  }
+}
+
+impl WindowMachine {
+  pub fn visibility(&self) -> Visibility {
+    self.visibility
+  }
+
+  pub fn in_transition(&self) -> bool {
+    self.transition.is_some()
+  }
+
+  /// `false` for the duplicate of a click that was just handled.
+  pub fn accept_tray_click(&mut self, now: Instant) -> bool {
+    if let Some(last) = self.last_tray_action {
+      if now.saturating_duration_since(last) < TRAY_DEBOUNCE {
+        return false;
+      }
+    }
+    self.last_tray_action = Some(now);
+    true
+  }
+
+  /// Feed the minimized state queried from the OS (`IsIconic` on Windows) on every
+  /// resize. Only the change into minimized is acted on, so repeated events and
+  /// size changes from DPI or monitor switches are not mistaken for a minimize.
+  pub fn observe_minimized(&mut self, minimized: bool, to_tray: bool) -> MinimizeAction {
+    let entered = minimized && !self.minimized;
+    self.minimized = minimized;
+    match (entered, to_tray) {
+      (false, _) => MinimizeAction::None,
+      (true, true) => MinimizeAction::HideToTray,
+      (true, false) => MinimizeAction::Minimized,
+    }
+  }
+
+  /// Target for a tray toggle.
+  pub fn toggled(&self) -> Visibility {
+    match self.visibility {
+      Visibility::Visible => Visibility::Hidden,
+      Visibility::Hidden | Visibility::Unloaded => Visibility::Visible,
+    }
+  }
+
+  /// Ask to move to `target`. `force` starts the transition even if the state
+  /// already matches (close/minimize events mean the window is on screen anyway).
+  pub fn begin(&mut self, target: Visibility, force: bool) -> Plan {
+    if self.transition.is_some() {
+      return Plan::Busy;
+    }
+    if self.visibility == target && !force {
+      return Plan::AlreadyThere;
+    }
+    self.transition = Some(target);
+    Plan::Start
+  }
+
+  /// Complete the transition started by `begin`; returns the previous visibility.
+  pub fn finish(&mut self) -> Visibility {
+    let previous = self.visibility;
+    if let Some(target) = self.transition.take() {
+      self.visibility = target;
+    }
+    previous
+  }
+
+  /// Set the state without a transition (startup, or syncing with what the OS did).
+  /// Returns `true` if it changed.
+  pub fn set(&mut self, visibility: Visibility) -> bool {
+    let changed = self.visibility != visibility;
+    self.visibility = visibility;
+    changed
+  }
+}
@@ -0,0 +1,26 @@
+use helltime_core::cache::diff;
+
+mod common;
+
+use common::{fixture, utc};
+
+#[test]
+fn diff_reports_shifted_and_cancelled_upcoming_events() {
+  let old = fixture();
+  let mut new = fixture();
+  new.world_boss[0]["startTime"] = "2025-10-16T08:45:00.000Z".into();
+  new.world_boss.remove(1);
+  let now = utc("2025-10-16T08:05:00Z");
+
+  let changes = diff(&old, &new, now);
+  assert_eq!(changes.shifted.len(), 1);
+  assert_eq!(changes.shifted[0].id, 7001);
+  assert_eq!(changes.shifted[0].from, utc("2025-10-16T08:30:00Z"));
+  assert_eq!(changes.shifted[0].to, utc("2025-10-16T08:45:00Z"));
+  let cancelled: Vec<i64> = changes.cancelled.iter().map(|e| e.id).collect();
+  assert_eq!(cancelled, vec![7002]);
+
+  assert!(diff(&old, &fixture(), now).is_empty());
+  // Events that already started drop out of the schedule without being "cancelled".
+  assert!(diff(&old, &new, utc("2025-10-16T13:00:00Z")).cancelled.is_empty());
+}
@@ -0,0 +1,44 @@
+use helltime_core::cadence::{Cadence, CadenceStore};
+
+mod common;
+
+use common::temp_path;
+
+#[test]
+fn bundled_cadence_is_valid() {
+  let cadence = Cadence::bundled();
+  assert_eq!(cadence.helltide.interval_minutes, 60);
+  assert!(cadence.validate().is_ok());
+}
+
+#[test]
+fn nonsense_is_rejected() {
+  let overlapping = br#"{"version": 2, "helltide": {"intervalMinutes": 30, "durationMinutes": 55, "chestOffsetMinutes": 10, "chestIntervalMinutes": 60}}"#;
+  assert!(Cadence::parse(overlapping).is_err());
+  let zero = br#"{"version": 2, "helltide": {"intervalMinutes": 0, "durationMinutes": 0, "chestOffsetMinutes": 0, "chestIntervalMinutes": 0}}"#;
+  assert!(Cadence::parse(zero).is_err());
+  assert!(Cadence::parse(b"{}").is_err());
+}
+
+#[test]
+fn only_newer_versions_are_adopted_and_kept() {
+  let path = temp_path("cadence", "offer", "cadence.json");
+  let mut store = CadenceStore::load(path.clone());
+  assert_eq!(store.get(), &Cadence::bundled());
+
+  let mut newer = Cadence::bundled();
+  newer.version += 1;
+  newer.helltide.interval_minutes = 135;
+  assert!(store.offer(newer.clone()).unwrap());
+  assert!(!store.offer(Cadence::bundled()).unwrap());
+
+  assert_eq!(CadenceStore::load(path).get(), &newer);
+}
+
+#[test]
+fn corrupt_saved_file_falls_back_to_bundled() {
+  let path = temp_path("cadence", "corrupt", "cadence.json");
+  std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+  std::fs::write(&path, b"not json").unwrap();
+  assert_eq!(CadenceStore::load(path).get(), &Cadence::bundled());
+}
@@ -0,0 +1,31 @@
+use helltime_core::coalesce::{Batch, NotificationCoalescer, MAX_PER_MINUTE};
+use std::time::{Duration, Instant};
+
+#[test]
+fn notifications_within_the_window_become_one_summary() {
+  let mut queue = NotificationCoalescer::default();
+  assert!(queue.push("helltide"));
+  assert!(!queue.push("legion"), "joins the open batch");
+  assert!(!queue.push("world_boss"));
+  assert_eq!(queue.flush(Instant::now()), Some(Batch::Summary(vec!["helltide", "legion", "world_boss"])));
+
+  assert!(queue.push("helltide"), "the next one opens a new batch");
+  assert_eq!(queue.flush(Instant::now()), Some(Batch::One("helltide")));
+  assert_eq!(queue.flush(Instant::now()), None);
+}
+
+#[test]
+fn at_most_max_per_minute_are_shown() {
+  let mut queue = NotificationCoalescer::default();
+  let start = Instant::now();
+  for i in 0..MAX_PER_MINUTE {
+    queue.push(i);
+    assert!(queue.flush(start).is_some());
+  }
+  queue.push(99);
+  assert_eq!(queue.flush(start + Duration::from_secs(30)), None);
+  assert_eq!(queue.suppressed(), 1);
+
+  queue.push(100);
+  assert_eq!(queue.flush(start + Duration::from_secs(60)), Some(Batch::One(100)));
+}
@@ -0,0 +1,36 @@
+//! Helpers shared by the integration tests; each test file pulls them in with
+//! `mod common;`. Not every test uses every helper.
+#![allow(dead_code)]
+
+use chrono::{DateTime, Utc};
+use helltime_core::schedule::ScheduleResponse;
+use std::path::PathBuf;
+
+/// `tests/fixtures/schedule.json`, the schedule most tests run against.
+pub fn fixture() -> ScheduleResponse {
+  fixture_named("schedule.json")
+}
+
+pub fn fixture_named(name: &str) -> ScheduleResponse {
+  let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+  let bytes = std::fs::read(&path).unwrap_or_else(|e| panic!("read {path}: {e}"));
+  serde_json::from_slice(&bytes).unwrap_or_else(|e| panic!("parse {path}: {e}"))
+}
+
+pub fn utc(s: &str) -> DateTime<Utc> {
+  DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+}
+
+/// `file` in a fresh, not yet created directory for test `name` of `area`.
+pub fn temp_path(area: &str, name: &str, file: &str) -> PathBuf {
+  let dir = std::env::temp_dir().join(format!("helltime-{}-{}-{}", area, name, std::process::id()));
+  let _ = std::fs::remove_dir_all(&dir);
+  dir.join(file)
+}
+
+/// An empty directory for test `name` of `area`.
+pub fn temp_dir(area: &str, name: &str) -> PathBuf {
+  let dir = temp_path(area, name, "");
+  std::fs::create_dir_all(&dir).unwrap();
+  dir
+}
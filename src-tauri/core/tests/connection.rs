@@ -0,0 +1,52 @@
+use chrono::Duration;
+use helltime_core::connection::{ConnectionState, ConnectionTracker};
+
+mod common;
+
+use common::utc;
+
+#[test]
+fn starts_online() {
+  let tracker = ConnectionTracker::default();
+  let status = tracker.status(utc("2025-10-16T08:00:00Z"));
+  assert_eq!(status.state, ConnectionState::Online);
+  assert_eq!(status.last_success, None);
+}
+
+#[test]
+fn failure_after_success_degrades_then_goes_offline() {
+  let t0 = utc("2025-10-16T08:00:00Z");
+  let mut tracker = ConnectionTracker::default();
+  assert!(!tracker.record_success(t0));
+
+  assert!(tracker.record_failure("request timed out", t0 + Duration::minutes(10)));
+  assert_eq!(tracker.state(t0 + Duration::minutes(10)), ConnectionState::Degraded);
+  assert!(!tracker.record_failure("request timed out", t0 + Duration::minutes(15)));
+
+  let status = tracker.status(t0 + Duration::minutes(31));
+  assert_eq!(status.state, ConnectionState::Offline);
+  assert_eq!(status.consecutive_failures, 2);
+  assert_eq!(status.last_success, Some(t0));
+  assert_eq!(status.last_error.as_deref(), Some("request timed out"));
+}
+
+#[test]
+fn failure_without_any_data_is_offline() {
+  let now = utc("2025-10-16T08:00:00Z");
+  let mut tracker = ConnectionTracker::default();
+  assert!(tracker.record_failure("bad status: 503", now));
+  assert_eq!(tracker.state(now), ConnectionState::Offline);
+}
+
+#[test]
+fn success_recovers() {
+  let now = utc("2025-10-16T08:00:00Z");
+  let mut tracker = ConnectionTracker::default();
+  tracker.record_failure("bad status: 503", now);
+  assert!(tracker.record_success(now + Duration::minutes(1)));
+
+  let status = tracker.status(now + Duration::minutes(1));
+  assert_eq!(status.state, ConnectionState::Online);
+  assert_eq!(status.last_error, None);
+  assert_eq!(status.consecutive_failures, 0);
+}
@@ -0,0 +1,70 @@
+use helltime_core::controller::{Controller, Effect, Message};
+use helltime_core::window_state::{Visibility, TRAY_DEBOUNCE};
+use std::time::Instant;
+
+#[test]
+fn tray_clicks_toggle_and_duplicates_are_dropped() {
+  let mut c = Controller::new(Visibility::Visible);
+  let t0 = Instant::now();
+  assert_eq!(c.handle(Message::TrayClick(t0)), vec![Effect::HideWindow]);
+  assert_eq!(c.handle(Message::TrayClick(t0)), vec![]);
+  assert_eq!(c.handle(Message::TrayClick(t0 + TRAY_DEBOUNCE)), vec![Effect::ShowWindow]);
+  assert_eq!(c.visibility(), Visibility::Visible);
+}
+
+#[test]
+fn keyboard_toggles_are_not_debounced() {
+  let mut c = Controller::new(Visibility::Visible);
+  assert_eq!(c.handle(Message::ToggleWindow), vec![Effect::HideWindow]);
+  assert_eq!(c.handle(Message::ToggleWindow), vec![Effect::ShowWindow]);
+}
+
+#[test]
+fn restoring_a_visible_window_only_focuses_it() {
+  let mut c = Controller::new(Visibility::Hidden);
+  assert_eq!(c.handle(Message::ShowWindow), vec![Effect::ShowWindow]);
+  assert_eq!(c.handle(Message::ShowWindow), vec![Effect::FocusWindow]);
+}
+
+#[test]
+fn close_and_minimize_hide_even_when_the_state_says_hidden() {
+  let mut c = Controller::new(Visibility::Hidden);
+  assert_eq!(c.handle(Message::CloseToTray), vec![Effect::HideWindow]);
+  assert_eq!(c.handle(Message::Resized { minimized: true, to_tray: true }), vec![Effect::HideWindow]);
+  // Still minimized: no second hide.
+  assert_eq!(c.handle(Message::Resized { minimized: true, to_tray: true }), vec![]);
+  assert_eq!(c.handle(Message::Resized { minimized: false, to_tray: true }), vec![]);
+  assert_eq!(c.handle(Message::Resized { minimized: true, to_tray: false }), vec![]);
+}
+
+#[test]
+fn focus_while_hidden_syncs_the_state() {
+  let mut c = Controller::new(Visibility::Hidden);
+  assert_eq!(c.handle(Message::Focused), vec![Effect::ShowInTaskbar]);
+  assert_eq!(c.visibility(), Visibility::Visible);
+  assert_eq!(c.handle(Message::Focused), vec![]);
+}
+
+#[test]
+fn toggles_leave_the_switches_to_the_preferences() {
+  let mut c = Controller::new(Visibility::Visible);
+  assert_eq!(c.handle(Message::ToggleOverlay), vec![Effect::ToggleOverlay]);
+  assert_eq!(c.handle(Message::ToggleReminders), vec![Effect::ToggleReminders]);
+  // The window is not affected.
+  assert_eq!(c.visibility(), Visibility::Visible);
+}
+
+#[test]
+fn hidden_window_unloads_and_restores() {
+  let mut c = Controller::new(Visibility::Visible);
+  // Shown again before the timer ran out: nothing to unload.
+  assert_eq!(c.handle(Message::UnloadIdle), vec![]);
+
+  assert_eq!(c.handle(Message::CloseToTray), vec![Effect::HideWindow]);
+  assert_eq!(c.handle(Message::UnloadIdle), vec![Effect::UnloadWindow]);
+  assert_eq!(c.visibility(), Visibility::Unloaded);
+  assert_eq!(c.handle(Message::UnloadIdle), vec![]);
+
+  assert_eq!(c.handle(Message::TrayClick(Instant::now())), vec![Effect::ShowWindow]);
+  assert_eq!(c.visibility(), Visibility::Visible);
+}
@@ -0,0 +1,192 @@
+use chrono::{Duration as ChronoDuration, FixedOffset};
+use helltime_core::cache::ScheduleCache;
+use helltime_core::clock::SecondaryZone;
+use helltime_core::cadence::Cadence;
+use helltime_core::countdown::{format_clock, format_countdown, next_events, share_text};
+use helltime_core::i18n::Locale;
+use helltime_core::reminders::{plan_for_next, test_reminder, TEST_EVENT_ID};
+use helltime_core::schedule::{EventKind, ScheduleResponse};
+use std::time::{Duration, Instant};
+
+mod common;
+
+use common::{fixture, utc};
+
+#[test]
+fn next_events_are_sorted_by_countdown() {
+  let now = utc("2025-10-16T08:05:00Z");
+  let next = next_events(&fixture(), &EventKind::ALL, now, None);
+  let kinds: Vec<EventKind> = next.iter().map(|e| e.kind).collect();
+  assert_eq!(kinds, vec![EventKind::Legion, EventKind::WorldBoss, EventKind::Helltide]);
+  assert_eq!(next[0].seconds_until, 5 * 60);
+}
+
+#[test]
+fn next_events_only_include_requested_kinds() {
+  let now = utc("2025-10-16T08:05:00Z");
+  let next = next_events(&fixture(), &[EventKind::Helltide, EventKind::WorldBoss], now, None);
+  let kinds: Vec<EventKind> = next.iter().map(|e| e.kind).collect();
+  assert_eq!(kinds, vec![EventKind::WorldBoss, EventKind::Helltide]);
+}
+
+#[test]
+fn next_events_carry_the_secondary_zone_time() {
+  let now = utc("2025-10-16T08:05:00Z");
+  let zone = SecondaryZone { label: "EST".into(), offset_minutes: -5 * 60 };
+  let next = next_events(&fixture(), &[EventKind::Legion], now, Some(&zone));
+  assert_eq!(next[0].start_secondary.as_deref(), Some("03:10 EST"));
+  assert_eq!(next_events(&fixture(), &[EventKind::Legion], now, None)[0].start_secondary, None);
+}
+
+#[test]
+fn secondary_zones_are_validated() {
+  let zone = |label: &str, offset_minutes| SecondaryZone { label: label.into(), offset_minutes };
+  assert!(zone("CET", 60).validate().is_ok());
+  assert!(zone("NZDT", 13 * 60).validate().is_ok());
+  assert!(zone(" ", 60).validate().is_err());
+  assert!(zone("Central European", 60).validate().is_err());
+  assert!(zone("CET", 15 * 60).validate().is_err());
+}
+
+#[test]
+fn countdown_formats() {
+  assert_eq!(format_countdown(30), "<1m");
+  assert_eq!(format_countdown(12 * 60 + 59), "12m");
+  assert_eq!(format_countdown(3600 + 5 * 60), "1h 05m");
+  assert_eq!(format_clock(-5), "00:00");
+  assert_eq!(format_clock(65), "01:05");
+  assert_eq!(format_clock(3661), "01:01:01");
+}
+
+#[test]
+fn cache_respects_ttl_and_invalidation() {
+  let mut cache = ScheduleCache::new(Duration::from_secs(30));
+  let t0 = Instant::now();
+  assert!(cache.fresh_at(t0).is_none());
+
+  cache.store_at(fixture(), t0);
+  assert!(cache.fresh_at(t0 + Duration::from_secs(29)).is_some());
+  assert!(cache.fresh_at(t0 + Duration::from_secs(30)).is_none());
+
+  cache.invalidate();
+  assert!(cache.fresh_at(t0).is_none());
+  assert!(cache.value().is_some(), "stale value survives invalidation");
+}
+
+#[test]
+fn reminders_fire_before_start_in_utc() {
+  let schedule = fixture();
+  let now = utc("2025-10-16T08:01:00Z");
+  let plan = plan_for_next(&schedule, EventKind::Helltide, &[30, 10, 5], now);
+  assert_eq!(plan.len(), 3);
+  assert_eq!(plan[0].fire_at, utc("2025-10-16T08:30:00Z"));
+  assert_eq!(plan[0].key(), "helltide:9002:0");
+  assert!(plan.iter().all(|r| r.fire_at < r.event.start));
+
+  let due_at = plan[2].fire_at + ChronoDuration::seconds(10);
+  assert!(plan[2].is_due(due_at));
+  assert!(!plan[2].is_due(plan[2].fire_at + ChronoDuration::seconds(31)));
+}
+
+#[test]
+fn passed_reminders_are_not_planned() {
+  let schedule = fixture();
+  let now = utc("2025-10-16T08:52:00Z");
+  let plan = plan_for_next(&schedule, EventKind::Helltide, &[30, 10, 5], now);
+  let timers: Vec<usize> = plan.iter().map(|r| r.timer).collect();
+  assert_eq!(timers, vec![2]);
+}
+
+#[test]
+fn reminder_pause_expires() {
+  use helltime_core::reminders::ReminderPause;
+
+  let now = utc("2025-10-16T08:00:00Z");
+  let mut pause = ReminderPause::default();
+  assert!(!pause.is_paused(now));
+
+  pause.pause_for(now, ChronoDuration::minutes(30));
+  assert!(pause.is_paused(now + ChronoDuration::minutes(29)));
+  assert_eq!(pause.paused_until(now + ChronoDuration::minutes(30)), None);
+
+  pause.resume();
+  assert!(!pause.is_paused(now));
+}
+
+#[test]
+fn reminder_pause_survives_a_restart() {
+  use helltime_core::json_store::JsonStore;
+  use helltime_core::reminders::ReminderPause;
+
+  let dir = std::env::temp_dir().join(format!("helltime-pause-{}", std::process::id()));
+  let _ = std::fs::remove_dir_all(&dir);
+  let path = dir.join("reminder_pause.json");
+  let now = utc("2025-10-16T08:00:00Z");
+
+  let mut pause: JsonStore<ReminderPause> = JsonStore::load(path.clone(), "reminder pause");
+  pause.pause_for(now, ChronoDuration::hours(1));
+  pause.save();
+
+  let loaded: JsonStore<ReminderPause> = JsonStore::load(path, "reminder pause");
+  assert_eq!(loaded.paused_until(now), Some(now + ChronoDuration::hours(1)));
+}
+
+#[test]
+fn reminder_pause_until_local_midnight() {
+  use chrono::FixedOffset;
+  use helltime_core::reminders::ReminderPause;
+
+  // 23:10 in UTC+2 -> pause ends at 00:00 local = 22:00 UTC.
+  let berlin = FixedOffset::east_opt(2 * 3600).unwrap();
+  let now = utc("2025-10-16T21:10:00Z").with_timezone(&berlin);
+  let mut pause = ReminderPause::default();
+  pause.pause_until_tomorrow(now);
+  assert_eq!(pause.paused_until(utc("2025-10-16T21:10:00Z")), Some(utc("2025-10-16T22:00:00Z")));
+}
+
+#[test]
+fn active_events_report_remaining_time_and_progress() {
+  use helltime_core::cadence::Cadence;
+  use helltime_core::countdown::active_events;
+
+  let cadence = Cadence::bundled().helltide;
+  // Helltide 08:00-08:55, legion 08:10-08:15.
+  let active = active_events(&fixture(), &EventKind::ALL, &cadence, utc("2025-10-16T08:12:00Z"));
+  let kinds: Vec<EventKind> = active.iter().map(|e| e.kind).collect();
+  assert_eq!(kinds, vec![EventKind::Legion, EventKind::Helltide]);
+  assert_eq!(active[1].seconds_remaining, 43 * 60);
+  assert!((active[0].progress - 0.4).abs() < 1e-9);
+
+  let later = active_events(&fixture(), &EventKind::ALL, &cadence, utc("2025-10-16T08:56:00Z"));
+  assert!(later.iter().all(|e| e.kind != EventKind::Helltide));
+  assert!(active_events(&fixture(), &[EventKind::Legion], &cadence, utc("2025-10-16T08:20:00Z")).is_empty());
+}
+
+#[test]
+fn test_reminder_is_due_immediately() {
+  let now = utc("2025-10-16T08:05:00Z");
+  let reminder = test_reminder(EventKind::WorldBoss, now).unwrap();
+  assert_eq!(reminder.event.kind, EventKind::WorldBoss);
+  assert_eq!(reminder.event.id, TEST_EVENT_ID);
+  assert_eq!(reminder.event.start, utc("2025-10-16T08:06:00Z"));
+  assert_eq!(reminder.minutes_before, 1);
+  assert!(reminder.is_due(now));
+}
+
+#[test]
+fn share_text_lists_running_and_next_events_in_the_given_zone() {
+  let schedule: ScheduleResponse = serde_json::from_value(serde_json::json!({
+    "helltide": [{ "id": 1, "startTime": "2025-10-16T08:00:00Z" }],
+    "legion": [{ "id": 2, "startTime": "2025-10-16T08:30:00Z" }],
+  }))
+  .unwrap();
+  let cadence = Cadence::bundled().helltide;
+  let berlin = FixedOffset::east_opt(2 * 3600).unwrap();
+  let kinds = [EventKind::Helltide, EventKind::Legion];
+
+  let text = share_text(Locale::En, &schedule, &kinds, &cadence, utc("2025-10-16T08:18:00Z"), &berlin);
+  assert_eq!(text, "Next events (UTC+02:00):\nHelltide: active, 37m left\nLegion: 10:30 (in 12m)");
+
+  let text = share_text(Locale::De, &schedule, &kinds, &cadence, utc("2025-10-16T09:30:00Z"), &berlin);
+  assert_eq!(text, "Nächste Events (UTC+02:00):\nKeine kommenden Events bekannt");
+}
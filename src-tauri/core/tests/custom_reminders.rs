@@ -0,0 +1,55 @@
+use helltime_core::cache::diff;
+use helltime_core::custom_reminders::CustomReminderStore;
+use helltime_core::schedule::{EventKind, ScheduleResponse, ScheduledEvent};
+
+mod common;
+
+use common::{fixture, utc};
+
+fn boss(schedule: &ScheduleResponse, id: i64) -> ScheduledEvent {
+  schedule.events(EventKind::WorldBoss).into_iter().find(|e| e.id == id).unwrap()
+}
+
+#[test]
+fn reminder_fires_before_its_event_only() {
+  let mut store = CustomReminderStore::default();
+  let now = utc("2025-10-16T08:00:00Z");
+  let reminder = store.add(&boss(&fixture(), 7001), 25, now).unwrap();
+  assert_eq!(reminder.fire_at, utc("2025-10-16T08:05:00Z"));
+  assert!(reminder.is_due(utc("2025-10-16T08:05:10Z")));
+  assert_eq!(reminder.key(), format!("custom:{}", reminder.id));
+
+  assert_eq!(store.add(&boss(&fixture(), 7001), 25, now).unwrap(), reminder, "duplicates are merged");
+  assert_eq!(store.list(now).len(), 1);
+  assert!(store.list(utc("2025-10-16T08:06:00Z")).is_empty(), "expired reminders are dropped");
+}
+
+#[test]
+fn rejects_passed_or_out_of_range_lead_times() {
+  let mut store = CustomReminderStore::default();
+  let now = utc("2025-10-16T08:10:00Z");
+  assert!(store.add(&boss(&fixture(), 7001), 25, now).is_err());
+  assert!(store.add(&boss(&fixture(), 7001), 0, now).is_err());
+  assert!(store.list(now).is_empty());
+}
+
+#[test]
+fn follows_shifted_events_and_drops_cancelled_ones() {
+  let mut store = CustomReminderStore::default();
+  let now = utc("2025-10-16T08:00:00Z");
+  let old = fixture();
+  let shifted = store.add(&boss(&old, 7001), 10, now).unwrap();
+  let cancelled = store.add(&boss(&old, 7002), 10, now).unwrap();
+
+  let mut new = fixture();
+  new.world_boss[0]["startTime"] = "2025-10-16T08:45:00.000Z".into();
+  new.world_boss.remove(1);
+  assert!(store.reschedule(&diff(&old, &new, now)));
+
+  let list = store.list(now);
+  assert_eq!(list.len(), 1);
+  assert_eq!(list[0].id, shifted.id);
+  assert_eq!(list[0].fire_at, utc("2025-10-16T08:35:00Z"));
+  assert!(!store.remove(cancelled.id));
+  assert!(store.remove(shifted.id));
+}
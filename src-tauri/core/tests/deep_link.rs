@@ -0,0 +1,39 @@
+use helltime_core::deep_link::{find_in_args, parse, DeepLink};
+use helltime_core::schedule::EventKind;
+
+#[test]
+fn routes_show_next() {
+  assert_eq!(parse("helltime://show-next-helltide"), Some(DeepLink::ShowNext { kind: EventKind::Helltide }));
+  assert_eq!(parse("helltime://show-next-world-boss/"), Some(DeepLink::ShowNext { kind: EventKind::WorldBoss }));
+  assert_eq!(parse("HELLTIME://Show-Next-Legion"), Some(DeepLink::ShowNext { kind: EventKind::Legion }));
+  assert_eq!(parse("helltime://show-next-nightmare"), None);
+}
+
+#[test]
+fn routes_settings_sections() {
+  assert_eq!(parse("helltime://settings"), Some(DeepLink::Settings { section: None }));
+  assert_eq!(
+    parse("helltime://settings/overlay?from=discord"),
+    Some(DeepLink::Settings { section: Some("overlay".into()) })
+  );
+  assert_eq!(parse("helltime://settings/<script>"), Some(DeepLink::Settings { section: None }));
+}
+
+#[test]
+fn bare_scheme_shows_window() {
+  assert_eq!(parse("helltime://"), Some(DeepLink::Show));
+  assert_eq!(parse("helltime://show"), Some(DeepLink::Show));
+}
+
+#[test]
+fn rejects_other_schemes() {
+  assert_eq!(parse("https://helltides.com/settings"), None);
+  assert_eq!(parse("settings/overlay"), None);
+}
+
+#[test]
+fn finds_link_in_argv() {
+  let args = vec!["helltime.exe".to_string(), "helltime://settings/overlay".to_string()];
+  assert_eq!(find_in_args(args).as_deref(), Some("helltime://settings/overlay"));
+  assert_eq!(find_in_args(vec!["helltime.exe".to_string()]), None);
+}
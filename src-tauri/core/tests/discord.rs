@@ -0,0 +1,66 @@
+use helltime_core::countdown::NextEvent;
+use helltime_core::discord::{activity_for, decode_header, encode_frame, set_activity, OP_FRAME};
+use helltime_core::i18n::Locale;
+use helltime_core::schedule::EventKind;
+use serde_json::json;
+
+fn event(kind: EventKind, secs: i64, boss: Option<&str>) -> NextEvent {
+  NextEvent {
+    kind,
+    id: 1,
+    start: "2025-10-16T08:12:00+00:00".to_string(),
+    start_local: "10:12".to_string(),
+    start_secondary: None,
+    seconds_until: secs,
+    boss: boss.map(str::to_string),
+    boss_predicted: false,
+    zone: None,
+  }
+}
+
+#[test]
+fn frames_round_trip_header() {
+  let frame = encode_frame(OP_FRAME, &json!({ "a": 1 }));
+  let header: [u8; 8] = frame[..8].try_into().unwrap();
+  assert_eq!(decode_header(header), (OP_FRAME, 7));
+  assert_eq!(&frame[8..], br#"{"a":1}"#);
+}
+
+#[test]
+fn activity_describes_soonest_event() {
+  let next = vec![
+    event(EventKind::Helltide, 12 * 60, None),
+    event(EventKind::WorldBoss, 3900, Some("Ashava")),
+  ];
+  let activity = activity_for(Locale::En, &next).unwrap();
+  assert_eq!(activity["details"], "Next Helltide in 12m");
+  assert_eq!(activity["state"], "World Boss Ashava 1h 05m");
+  assert_eq!(activity["assets"]["large_image"], "helltide");
+  assert_eq!(activity["timestamps"]["end"], 1760602320);
+}
+
+#[test]
+fn activity_follows_locale() {
+  let next = vec![
+    event(EventKind::WorldBoss, 3900, Some("Ashava")),
+    event(EventKind::Helltide, 4000, None),
+  ];
+  let activity = activity_for(Locale::De, &next).unwrap();
+  assert_eq!(activity["details"], "Als Nächstes Weltboss Ashava in 1h 05m");
+  assert_eq!(activity["state"], "Höllenhochwasser 1h 06m");
+  assert_eq!(activity["assets"]["large_text"], "Weltboss");
+}
+
+#[test]
+fn no_activity_without_events() {
+  assert!(activity_for(Locale::En, &[]).is_none());
+}
+
+#[test]
+fn clear_sends_null_activity() {
+  let cmd = set_activity(42, None, 7);
+  assert_eq!(cmd["cmd"], "SET_ACTIVITY");
+  assert_eq!(cmd["args"]["pid"], 42);
+  assert!(cmd["args"]["activity"].is_null());
+  assert_eq!(cmd["nonce"], "7");
+}
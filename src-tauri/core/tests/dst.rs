@@ -0,0 +1,123 @@
+//! Reminders around the 2025 European DST switches (30 March, 26 October, both at
+//! 01:00 UTC). Fire times must stay a fixed real-time distance before the start.
+
+use chrono::{Duration, FixedOffset, MappedLocalTime, NaiveDate, NaiveDateTime, TimeZone};
+use helltime_core::clock::format_time_in;
+use helltime_core::reminders::{plan_for_next, ReminderPause};
+use helltime_core::schedule::{EventKind, ScheduleResponse};
+use serde_json::json;
+
+mod common;
+
+use common::utc;
+
+/// Europe/Berlin for 2025 only: CET (+1), CEST (+2) between the two switches.
+#[derive(Debug, Clone, Copy)]
+struct Berlin2025;
+
+fn cet() -> FixedOffset {
+  FixedOffset::east_opt(3600).unwrap()
+}
+
+fn cest() -> FixedOffset {
+  FixedOffset::east_opt(2 * 3600).unwrap()
+}
+
+fn spring_forward() -> NaiveDateTime {
+  NaiveDate::from_ymd_opt(2025, 3, 30).unwrap().and_hms_opt(1, 0, 0).unwrap()
+}
+
+fn fall_back() -> NaiveDateTime {
+  NaiveDate::from_ymd_opt(2025, 10, 26).unwrap().and_hms_opt(1, 0, 0).unwrap()
+}
+
+impl TimeZone for Berlin2025 {
+  type Offset = FixedOffset;
+
+  fn from_offset(_: &FixedOffset) -> Self {
+    Berlin2025
+  }
+
+  fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<FixedOffset> {
+    self.offset_from_local_datetime(&local.and_hms_opt(12, 0, 0).unwrap())
+  }
+
+  fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> MappedLocalTime<FixedOffset> {
+    let as_cet = *local - Duration::hours(1);
+    let as_cest = *local - Duration::hours(2);
+    let cet_valid = as_cet < spring_forward() || as_cet >= fall_back();
+    let cest_valid = as_cest >= spring_forward() && as_cest < fall_back();
+    match (cest_valid, cet_valid) {
+      (true, true) => MappedLocalTime::Ambiguous(cest(), cet()),
+      (true, false) => MappedLocalTime::Single(cest()),
+      (false, true) => MappedLocalTime::Single(cet()),
+      (false, false) => MappedLocalTime::None,
+    }
+  }
+
+  fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+    self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+  }
+
+  fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+    if *utc >= spring_forward() && *utc < fall_back() {
+      cest()
+    } else {
+      cet()
+    }
+  }
+}
+
+fn helltide_at(start: &str) -> ScheduleResponse {
+  serde_json::from_value(json!({ "helltide": [{ "id": 1, "startTime": start }] })).unwrap()
+}
+
+#[test]
+fn reminder_across_spring_forward_keeps_real_time_distance() {
+  // 03:10 CEST; 30 minutes earlier is 01:40 CET, one wall-clock hour and a half before.
+  let schedule = helltide_at("2025-03-30T01:10:00Z");
+  let plan = plan_for_next(&schedule, EventKind::Helltide, &[30], utc("2025-03-30T00:00:00Z"));
+  assert_eq!(plan[0].fire_at, utc("2025-03-30T00:40:00Z"));
+  assert_eq!(format_time_in(plan[0].fire_at, &Berlin2025), "01:40");
+  assert_eq!(format_time_in(plan[0].event.start, &Berlin2025), "03:10");
+}
+
+#[test]
+fn reminder_across_fall_back_fires_once_before_the_repeated_hour() {
+  // 02:20 CET (second pass through 02:xx); the reminder fires at 02:50 CEST, which
+  // reads later on the wall clock than the event itself.
+  let schedule = helltide_at("2025-10-26T01:20:00Z");
+  let plan = plan_for_next(&schedule, EventKind::Helltide, &[30], utc("2025-10-26T00:00:00Z"));
+  assert_eq!(plan[0].fire_at, utc("2025-10-26T00:50:00Z"));
+  assert_eq!(format_time_in(plan[0].fire_at, &Berlin2025), "02:50");
+  assert_eq!(format_time_in(plan[0].event.start, &Berlin2025), "02:20");
+  assert_eq!(plan[0].event.start - plan[0].fire_at, Duration::minutes(30));
+
+  // Due exactly once: not again when the wall clock shows 02:50 a second time.
+  assert!(plan[0].is_due(utc("2025-10-26T00:50:10Z")));
+  assert!(!plan[0].is_due(utc("2025-10-26T01:50:10Z")));
+}
+
+#[test]
+fn reminders_spanning_the_switch_are_evenly_spaced() {
+  let schedule = helltide_at("2025-03-30T01:30:00Z");
+  let plan = plan_for_next(&schedule, EventKind::Helltide, &[60, 30, 5], utc("2025-03-30T00:00:00Z"));
+  let fire: Vec<_> = plan.iter().map(|r| r.fire_at).collect();
+  assert_eq!(fire, vec![utc("2025-03-30T00:30:00Z"), utc("2025-03-30T01:00:00Z"), utc("2025-03-30T01:25:00Z")]);
+  let local: Vec<_> = fire.iter().map(|at| format_time_in(*at, &Berlin2025)).collect();
+  assert_eq!(local, vec!["01:30", "03:00", "03:25"]);
+}
+
+#[test]
+fn pause_until_tomorrow_on_short_and_long_days() {
+  let mut pause = ReminderPause::default();
+  // 30 March has 23 hours: noon CEST to midnight CEST.
+  let now = utc("2025-03-30T10:00:00Z");
+  pause.pause_until_tomorrow(now.with_timezone(&Berlin2025));
+  assert_eq!(pause.paused_until(now), Some(utc("2025-03-30T22:00:00Z")));
+
+  // 26 October has 25 hours: 01:30 CEST (first pass) to midnight CET.
+  let now = utc("2025-10-25T23:30:00Z");
+  pause.pause_until_tomorrow(now.with_timezone(&Berlin2025));
+  assert_eq!(pause.paused_until(now), Some(utc("2025-10-26T23:00:00Z")));
+}
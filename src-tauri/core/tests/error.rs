@@ -0,0 +1,29 @@
+use helltime_core::error::AppError;
+use helltime_core::fetch::FetchError;
+use serde_json::json;
+use std::time::Duration;
+
+#[test]
+fn serializes_kind_and_message() {
+  let e = AppError::OverlayUnavailable("no webview".into());
+  assert_eq!(serde_json::to_value(&e).unwrap(), json!({ "kind": "overlayUnavailable", "message": "no webview" }));
+
+  let e = AppError::RateLimited { retry_after_secs: 12 };
+  assert_eq!(
+    serde_json::to_value(&e).unwrap(),
+    json!({ "kind": "rateLimited", "message": "rate limited, next request in 12s", "retryAfterSecs": 12 })
+  );
+}
+
+#[test]
+fn fetch_errors_map_to_categories() {
+  assert_eq!(AppError::from(FetchError::Timeout).kind(), "network");
+  assert_eq!(AppError::from(FetchError::Status(503)).kind(), "network");
+  assert_eq!(AppError::from(FetchError::InvalidJson("eof".into())).kind(), "parse");
+  assert_eq!(AppError::from(FetchError::UnexpectedContentType("text/html".into())).kind(), "parse");
+  assert_eq!(
+    AppError::from(FetchError::RateLimited(Duration::from_millis(200))),
+    AppError::RateLimited { retry_after_secs: 1 }
+  );
+  assert_eq!(String::from(AppError::Network("bad status: 503".into())), "bad status: 503");
+}
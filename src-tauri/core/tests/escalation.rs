@@ -0,0 +1,87 @@
+use chrono::Duration;
+use helltime_core::reminders::{
+  Escalation, EscalationAction, EscalationRule, EscalationTracker, REPEAT_INTERVAL, REPEAT_LIMIT_AFTER_START,
+};
+use helltime_core::schedule::EventKind;
+
+mod common;
+
+use common::{fixture, utc};
+
+fn enabled() -> Escalation {
+  Escalation { enabled: true, ..Escalation::default() }
+}
+
+const BOSS: [EventKind; 1] = [EventKind::WorldBoss];
+
+#[test]
+fn steps_fire_in_order_once_each() {
+  let schedule = fixture();
+  let mut tracker = EscalationTracker::default();
+
+  let toast = tracker.due(&enabled(), &schedule, &BOSS, utc("2025-10-16T08:20:00Z"));
+  assert_eq!(toast.len(), 1);
+  assert_eq!(toast[0].reminder.event.id, 7001);
+  assert_eq!(toast[0].actions, [EscalationAction::OverlayToast]);
+  assert!(tracker.due(&enabled(), &schedule, &BOSS, utc("2025-10-16T08:20:10Z")).is_empty());
+
+  let loud = tracker.due(&enabled(), &schedule, &BOSS, utc("2025-10-16T08:25:05Z"));
+  assert_eq!(loud[0].actions, [EscalationAction::Notification, EscalationAction::Sound]);
+  assert_eq!(loud[0].reminder.timer, 1);
+}
+
+#[test]
+fn repeats_until_acknowledged() {
+  let schedule = fixture();
+  let mut tracker = EscalationTracker::default();
+  let first = utc("2025-10-16T08:29:00Z");
+  let step = tracker.due(&enabled(), &schedule, &BOSS, first);
+  assert_eq!(step[0].actions, [EscalationAction::RepeatSound]);
+  assert!(!step[0].repeat);
+
+  assert!(tracker.due(&enabled(), &schedule, &BOSS, first + Duration::seconds(5)).is_empty());
+  let again = tracker.due(&enabled(), &schedule, &BOSS, first + REPEAT_INTERVAL);
+  assert_eq!(again.len(), 1);
+  assert!(again[0].repeat);
+
+  assert!(tracker.acknowledge(EventKind::WorldBoss, 7001, first + REPEAT_INTERVAL));
+  assert!(tracker.due(&enabled(), &schedule, &BOSS, first + REPEAT_INTERVAL * 3).is_empty());
+  assert!(!tracker.acknowledge(EventKind::WorldBoss, 7001, first + REPEAT_INTERVAL * 3));
+}
+
+#[test]
+fn repeating_stops_after_the_start() {
+  let schedule = fixture();
+  let mut tracker = EscalationTracker::default();
+  tracker.due(&enabled(), &schedule, &BOSS, utc("2025-10-16T08:29:00Z"));
+  let late = utc("2025-10-16T08:30:00Z") + REPEAT_LIMIT_AFTER_START;
+  assert!(tracker.due(&enabled(), &schedule, &BOSS, late).iter().all(|s| s.reminder.event.id != 7001));
+}
+
+#[test]
+fn acknowledging_skips_the_remaining_steps() {
+  let schedule = fixture();
+  let mut tracker = EscalationTracker::default();
+  tracker.due(&enabled(), &schedule, &BOSS, utc("2025-10-16T08:20:00Z"));
+  tracker.acknowledge(EventKind::WorldBoss, 7001, utc("2025-10-16T08:21:00Z"));
+  assert!(tracker.due(&enabled(), &schedule, &BOSS, utc("2025-10-16T08:25:05Z")).is_empty());
+}
+
+#[test]
+fn validates_the_rule_list() {
+  assert!(Escalation::default().validate().is_ok());
+  let rule = |minutes_before, actions: &[EscalationAction]| EscalationRule { minutes_before, actions: actions.to_vec() };
+  let with = |rules| Escalation { enabled: true, rules };
+  assert!(with(vec![]).validate().is_err());
+  assert!(with(vec![rule(5, &[EscalationAction::Sound]), rule(10, &[EscalationAction::Sound])]).validate().is_err());
+  assert!(with(vec![rule(5, &[])]).validate().is_err());
+  assert!(with(vec![rule(61, &[EscalationAction::Sound])]).validate().is_err());
+  assert!(with(vec![rule(0, &[EscalationAction::RepeatSound])]).validate().is_ok());
+}
+
+#[test]
+fn serializes_like_the_ui_expects() {
+  let json = serde_json::to_value(Escalation::default()).unwrap();
+  assert_eq!(json["rules"][1]["minutesBefore"], 5);
+  assert_eq!(json["rules"][2]["actions"][0], "repeat_sound");
+}
@@ -0,0 +1,197 @@
+use helltime_core::fetch::{
+  fetch_schedule, is_json, FetchError, HttpFetcher, HttpResponse, RecordingFetcher, RetryPolicy, MAX_BODY_BYTES,
+};
+use helltime_core::schedule::EventKind;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const URL: &str = "https://helltides.com/api/schedule";
+
+/// Replays recorded responses in order and counts requests.
+struct ReplayFetcher {
+  responses: Mutex<VecDeque<Result<HttpResponse, FetchError>>>,
+  requests: Mutex<Vec<String>>,
+  sleeps: Mutex<Vec<Duration>>,
+}
+
+impl ReplayFetcher {
+  fn new(responses: Vec<Result<HttpResponse, FetchError>>) -> Self {
+    Self {
+      responses: Mutex::new(responses.into()),
+      requests: Mutex::new(Vec::new()),
+      sleeps: Mutex::new(Vec::new()),
+    }
+  }
+
+  fn request_count(&self) -> usize {
+    self.requests.lock().unwrap().len()
+  }
+}
+
+impl HttpFetcher for ReplayFetcher {
+  fn get(&self, url: &str) -> impl Future<Output = Result<HttpResponse, FetchError>> + Send {
+    self.requests.lock().unwrap().push(url.to_string());
+    let next = self.responses.lock().unwrap().pop_front().expect("no recorded response left");
+    async move { next }
+  }
+
+  fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+    self.sleeps.lock().unwrap().push(duration);
+    async {}
+  }
+}
+
+fn recorded(status: u16, fixture: &str) -> Result<HttpResponse, FetchError> {
+  let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), fixture);
+  Ok(HttpResponse {
+    status,
+    date: Some("Thu, 16 Oct 2025 07:59:58 GMT".to_string()),
+    headers: vec![("content-type".to_string(), "application/json".to_string())],
+    body: std::fs::read(path).unwrap(),
+  })
+}
+
+fn server_error() -> Result<HttpResponse, FetchError> {
+  Ok(HttpResponse {
+    status: 500,
+    date: None,
+    headers: Vec::new(),
+    body: b"<html>Internal Server Error</html>".to_vec(),
+  })
+}
+
+fn policy() -> RetryPolicy {
+  RetryPolicy { attempts: 3, backoff: Duration::from_millis(100) }
+}
+
+#[tokio::test]
+async fn success_parses_schedule_and_date() {
+  let fetcher = ReplayFetcher::new(vec![recorded(200, "schedule.json")]);
+  let fetched = fetch_schedule(&fetcher, URL, policy()).await.unwrap();
+
+  assert_eq!(fetched.attempts, 1);
+  assert_eq!(fetched.schedule.events(EventKind::Helltide).len(), 3);
+  assert_eq!(fetched.server_date.unwrap().to_rfc3339(), "2025-10-16T07:59:58+00:00");
+  assert!(fetched.sent_at <= fetched.received_at);
+  assert_eq!(fetcher.requests.lock().unwrap().as_slice(), [URL]);
+}
+
+#[tokio::test]
+async fn malformed_json_is_not_retried() {
+  let fetcher = ReplayFetcher::new(vec![recorded(200, "schedule_truncated.json"), recorded(200, "schedule.json")]);
+  let err = fetch_schedule(&fetcher, URL, policy()).await.unwrap_err();
+
+  assert!(matches!(err, FetchError::InvalidJson(_)), "{err:?}");
+  assert_eq!(fetcher.request_count(), 1);
+}
+
+#[tokio::test]
+async fn recording_keeps_unparsable_body() {
+  let fetcher = ReplayFetcher::new(vec![recorded(200, "schedule_truncated.json")]);
+  let recording = RecordingFetcher::new(&fetcher);
+  assert!(fetch_schedule(&recording, URL, policy()).await.is_err());
+
+  let raw = recording.into_last().unwrap();
+  assert_eq!(raw.url, URL);
+  assert_eq!(raw.status, 200);
+  assert_eq!(raw.headers[0].0, "content-type");
+  assert!(raw.body.starts_with('{'));
+  assert!(raw.parse_error.is_some());
+}
+
+#[tokio::test]
+async fn recording_keeps_last_attempt() {
+  let fetcher = ReplayFetcher::new(vec![server_error(), recorded(200, "schedule.json")]);
+  let recording = RecordingFetcher::new(&fetcher);
+  fetch_schedule(&recording, URL, policy()).await.unwrap();
+
+  let raw = recording.into_last().unwrap();
+  assert_eq!(raw.status, 200);
+  assert_eq!(raw.parse_error, None);
+}
+
+#[tokio::test]
+async fn server_errors_are_retried_with_backoff() {
+  let fetcher = ReplayFetcher::new(vec![server_error(), server_error(), recorded(200, "schedule.json")]);
+  let fetched = fetch_schedule(&fetcher, URL, policy()).await.unwrap();
+
+  assert_eq!(fetched.attempts, 3);
+  assert_eq!(
+    fetcher.sleeps.lock().unwrap().as_slice(),
+    [Duration::from_millis(100), Duration::from_millis(200)]
+  );
+}
+
+#[tokio::test]
+async fn gives_up_after_policy_attempts() {
+  let fetcher = ReplayFetcher::new(vec![server_error(), server_error(), server_error(), recorded(200, "schedule.json")]);
+  let err = fetch_schedule(&fetcher, URL, policy()).await.unwrap_err();
+
+  assert_eq!(err, FetchError::Status(500));
+  assert_eq!(fetcher.request_count(), 3);
+}
+
+#[tokio::test]
+async fn timeouts_are_retried() {
+  let fetcher = ReplayFetcher::new(vec![Err(FetchError::Timeout), recorded(200, "schedule.json")]);
+  let fetched = fetch_schedule(&fetcher, URL, policy()).await.unwrap();
+  assert_eq!(fetched.attempts, 2);
+}
+
+#[tokio::test]
+async fn client_errors_fail_immediately() {
+  let fetcher = ReplayFetcher::new(vec![recorded(404, "schedule.json")]);
+  let err = fetch_schedule(&fetcher, URL, policy()).await.unwrap_err();
+  assert_eq!(err, FetchError::Status(404));
+  assert_eq!(err.to_string(), "bad status: 404");
+}
+
+#[tokio::test]
+async fn failed_fetch_leaves_cached_value_usable() {
+  use helltime_core::cache::ScheduleCache;
+
+  let mut cache = ScheduleCache::new(Duration::from_secs(30));
+  let ok = ReplayFetcher::new(vec![recorded(200, "schedule.json")]);
+  cache.store(fetch_schedule(&ok, URL, policy()).await.unwrap().schedule);
+  cache.invalidate();
+
+  let failing = ReplayFetcher::new(vec![Err(FetchError::Timeout); 3]);
+  assert!(fetch_schedule(&failing, URL, policy()).await.is_err());
+  assert!(cache.fresh().is_none());
+  assert_eq!(cache.value().unwrap().events(EventKind::Legion).len(), 3);
+}
+
+#[tokio::test]
+async fn non_json_success_is_refused() {
+  let portal = Ok(HttpResponse {
+    status: 200,
+    date: None,
+    headers: vec![("Content-Type".to_string(), "text/html; charset=utf-8".to_string())],
+    body: b"<html>Please log in</html>".to_vec(),
+  });
+  let fetcher = ReplayFetcher::new(vec![portal]);
+  let err = fetch_schedule(&fetcher, URL, policy()).await.unwrap_err();
+  assert_eq!(err, FetchError::UnexpectedContentType("text/html; charset=utf-8".into()));
+  assert_eq!(fetcher.request_count(), 1);
+
+  assert!(is_json("application/json; charset=utf-8"));
+  assert!(is_json("application/vnd.api+json"));
+  assert!(!is_json("text/html"));
+}
+
+#[tokio::test]
+async fn oversized_bodies_are_refused() {
+  let huge = Ok(HttpResponse {
+    status: 200,
+    date: None,
+    headers: Vec::new(),
+    body: vec![b' '; MAX_BODY_BYTES + 1],
+  });
+  let fetcher = ReplayFetcher::new(vec![huge]);
+  let err = fetch_schedule(&fetcher, URL, policy()).await.unwrap_err();
+  assert_eq!(err, FetchError::TooLarge(MAX_BODY_BYTES));
+  assert!(!err.is_retryable());
+  assert!(FetchError::BodyTimeout.is_retryable());
+}
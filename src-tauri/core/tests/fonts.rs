@@ -0,0 +1,13 @@
+use helltime_core::fonts::{normalize, parse_fc_list};
+
+#[test]
+fn normalizes_family_names() {
+  let names = ["Segoe UI", "@Malgun Gothic", "arial", " Arial ", "", "Bahnschrift"].map(String::from);
+  assert_eq!(normalize(names), vec!["arial", "Bahnschrift", "Segoe UI"]);
+}
+
+#[test]
+fn parses_fc_list_output() {
+  let output = "DejaVu Sans\nNoto Sans CJK JP,Noto Sans CJK JP Regular\nDejaVu Sans\nC059\nURW Bookman\\-L\n";
+  assert_eq!(parse_fc_list(output), vec!["C059", "DejaVu Sans", "Noto Sans CJK JP", "URW Bookman-L"]);
+}
@@ -0,0 +1,25 @@
+use helltime_core::game_mode::{is_game_process, tasklist_has_game, throttled, THROTTLE_FACTOR};
+use std::time::Duration;
+
+#[test]
+fn recognizes_the_game_executable() {
+  assert!(is_game_process("Diablo IV.exe"));
+  assert!(is_game_process("diablo iv.exe\n"));
+  assert!(!is_game_process("Diablo IV Launcher.exe"));
+  assert!(!is_game_process("helltime.exe"));
+}
+
+#[test]
+fn parses_tasklist_csv() {
+  let running = "\"explorer.exe\",\"4711\",\"Console\",\"1\",\"120.000 K\"\r\n\"Diablo IV.exe\",\"9001\",\"Console\",\"1\",\"6.512.345 K\"\r\n";
+  assert!(tasklist_has_game(running));
+  assert!(!tasklist_has_game("INFO: No tasks are running which match the specified criteria.\r\n"));
+  assert!(!tasklist_has_game(""));
+}
+
+#[test]
+fn throttles_only_while_the_game_runs() {
+  let interval = Duration::from_secs(30);
+  assert_eq!(throttled(interval, false), interval);
+  assert_eq!(throttled(interval, true), interval * THROTTLE_FACTOR);
+}
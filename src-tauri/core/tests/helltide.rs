@@ -0,0 +1,80 @@
+use helltime_core::cadence::{Cadence, HelltideCadence};
+use helltime_core::helltide::{active_helltide, extrapolate, upcoming_resets, ChestReset};
+use helltime_core::reminders::plan_helltide_end;
+use helltime_core::schedule::EventKind;
+
+mod common;
+
+use common::{fixture, utc};
+
+fn cadence() -> HelltideCadence {
+  Cadence::bundled().helltide
+}
+
+#[test]
+fn active_only_while_open() {
+  let schedule = fixture();
+  assert_eq!(active_helltide(&schedule, &cadence(), utc("2025-10-16T08:20:00Z")).unwrap().id, 9001);
+  assert!(active_helltide(&schedule, &cadence(), utc("2025-10-16T08:56:00Z")).is_none());
+  assert!(active_helltide(&schedule, &cadence(), utc("2025-10-16T07:59:00Z")).is_none());
+}
+
+#[test]
+fn resets_follow_hourly_cadence_from_start() {
+  let resets = upcoming_resets(&fixture(), &cadence(), utc("2025-10-16T08:10:00Z"));
+  assert_eq!(resets[0], ChestReset { helltide_id: 9001, at: utc("2025-10-16T08:30:00Z") });
+  assert_eq!(resets[1], ChestReset { helltide_id: 9002, at: utc("2025-10-16T09:30:00Z") });
+  assert_eq!(resets[0].key(), format!("chest:9001:{}", utc("2025-10-16T08:30:00Z").timestamp()));
+}
+
+#[test]
+fn passed_resets_are_skipped() {
+  let resets = upcoming_resets(&fixture(), &cadence(), utc("2025-10-16T08:40:00Z"));
+  assert_eq!(resets[0].helltide_id, 9002);
+}
+
+#[test]
+fn predictor_extends_schedule_with_cadence() {
+  let mut schedule = fixture();
+  // Scheduled: 08:00, 09:00, 10:00. At 09:30 only 10:00 is upcoming.
+  extrapolate(&mut schedule, &cadence(), utc("2025-10-16T09:30:00Z"));
+  let events = schedule.events(EventKind::Helltide);
+  let predicted: Vec<_> = events.iter().filter(|h| h.predicted).map(|h| h.start).collect();
+  assert_eq!(predicted, vec![utc("2025-10-16T11:00:00Z"), utc("2025-10-16T12:00:00Z")]);
+  assert!(!events[0].predicted);
+}
+
+#[test]
+fn predictor_follows_a_slower_season() {
+  let mut schedule = fixture();
+  let slow = HelltideCadence { interval_minutes: 135, ..cadence() };
+  extrapolate(&mut schedule, &slow, utc("2025-10-16T13:00:00Z"));
+  let events = schedule.events(EventKind::Helltide);
+  let predicted: Vec<_> = events.iter().filter(|h| h.predicted).map(|h| h.start).collect();
+  // 10:00 + 2h15m steps, skipping 12:15 which already passed.
+  assert_eq!(predicted, vec![utc("2025-10-16T14:30:00Z"), utc("2025-10-16T16:45:00Z"), utc("2025-10-16T19:00:00Z")]);
+}
+
+#[test]
+fn end_reminders_fire_before_the_cadence_end() {
+  let plan = plan_helltide_end(&fixture(), &cadence(), 10, utc("2025-10-16T08:20:00Z"));
+  assert_eq!(plan[0].helltide_id, 9001);
+  assert_eq!(plan[0].end, utc("2025-10-16T08:55:00Z"));
+  assert_eq!(plan[0].fire_at, utc("2025-10-16T08:45:00Z"));
+  assert_eq!(plan[0].key(), format!("helltide_end:9001:{}", utc("2025-10-16T08:55:00Z").timestamp()));
+  assert!(plan[0].is_due(utc("2025-10-16T08:45:10Z")));
+
+  let later = plan_helltide_end(&fixture(), &cadence(), 10, utc("2025-10-16T08:46:00Z"));
+  assert_eq!(later[0].helltide_id, 9002, "passed fire window is skipped");
+}
+
+#[test]
+fn reported_end_times_win_over_the_cadence() {
+  let mut schedule = fixture();
+  schedule.helltide[0]["endTime"] = "2025-10-16T08:40:00.000Z".into();
+  assert_eq!(schedule.events(EventKind::Helltide)[0].end, Some(utc("2025-10-16T08:40:00Z")));
+  assert!(active_helltide(&schedule, &cadence(), utc("2025-10-16T08:41:00Z")).is_none());
+
+  let plan = plan_helltide_end(&schedule, &cadence(), 10, utc("2025-10-16T08:20:00Z"));
+  assert_eq!(plan[0].fire_at, utc("2025-10-16T08:30:00Z"));
+}
@@ -0,0 +1,110 @@
+use chrono::{DateTime, Duration, Utc};
+use helltime_core::history::{EventHistory, HistoryRange};
+use helltime_core::schedule::{EventKind, ScheduledEvent};
+
+mod common;
+
+use common::{fixture, utc};
+
+fn observed() -> EventHistory {
+  let schedule = fixture();
+  let events: Vec<_> = EventKind::ALL.into_iter().flat_map(|kind| schedule.events(kind)).collect();
+  let mut history = EventHistory::default();
+  history.record(&events, utc("2025-10-16T08:00:00Z"));
+  history
+}
+
+fn boss(id: i64, start: DateTime<Utc>, boss: Option<&str>, zone: Option<&str>) -> ScheduledEvent {
+  ScheduledEvent {
+    kind: EventKind::WorldBoss,
+    id,
+    start,
+    end: None,
+    boss: boss.map(Into::into),
+    boss_predicted: false,
+    zone: zone.map(Into::into),
+    predicted: false,
+  }
+}
+
+#[test]
+fn known_occurrences_are_not_duplicated() {
+  let mut history = observed();
+  let count = history.query(&HistoryRange::default()).len();
+  assert!(count > 0);
+
+  let schedule = fixture();
+  let events: Vec<_> = EventKind::ALL.into_iter().flat_map(|kind| schedule.events(kind)).collect();
+  history.record(&events, utc("2025-10-16T08:05:00Z"));
+  assert_eq!(history.query(&HistoryRange::default()).len(), count);
+}
+
+#[test]
+fn later_fetches_fill_in_known_occurrences() {
+  let mut history = EventHistory::default();
+  let start = utc("2025-10-16T12:00:00Z");
+  history.record(&[boss(1, start, None, None)], utc("2025-10-16T08:00:00Z"));
+  assert!(history.boss_sequence().is_empty());
+
+  let moved = start + Duration::minutes(15);
+  history.record(&[boss(1, moved, Some("Avarice"), Some("Fractured Peaks"))], utc("2025-10-16T09:00:00Z"));
+  let entries = history.query(&HistoryRange::default());
+  assert_eq!(entries.len(), 1);
+  assert_eq!(entries[0].start, moved);
+  assert_eq!(entries[0].boss.as_deref(), Some("Avarice"));
+  assert_eq!(entries[0].zone.as_deref(), Some("Fractured Peaks"));
+  assert_eq!(entries[0].observed_at, utc("2025-10-16T08:00:00Z"));
+  assert_eq!(history.boss_sequence(), vec![(moved, "Avarice".to_string())]);
+
+  // A later fetch that omits the name or guesses one doesn't erase what was seen.
+  let guessed = ScheduledEvent { boss: Some("Ashava".into()), boss_predicted: true, ..boss(1, moved, None, None) };
+  history.record(&[guessed], utc("2025-10-16T10:00:00Z"));
+  let entries = history.query(&HistoryRange::default());
+  assert_eq!(entries[0].boss.as_deref(), Some("Avarice"));
+  assert_eq!(entries[0].zone.as_deref(), Some("Fractured Peaks"));
+}
+
+#[test]
+fn stats_count_and_space_past_occurrences() {
+  let now = utc("2025-10-16T12:00:00Z");
+  let mut history = EventHistory::default();
+  let events = [
+    boss(1, now - Duration::hours(72), Some("Ashava"), None),
+    boss(2, now - Duration::hours(36), None, None),
+    boss(3, now, Some("Wandering Death"), None),
+    boss(4, now + Duration::hours(36), Some("Avarice"), None),
+  ];
+  history.record(&events, now - Duration::hours(80));
+
+  let stats = history.stats(now);
+  assert_eq!(stats.total[&EventKind::WorldBoss], 3, "future occurrences are not counted");
+  assert_eq!(stats.today[&EventKind::WorldBoss], 1);
+  assert_eq!(stats.average_interval_secs[&EventKind::WorldBoss], 36 * 3600);
+  assert_eq!(stats.total[&EventKind::Legion], 0);
+  assert!(!stats.average_interval_secs.contains_key(&EventKind::Legion));
+  let rotation: Vec<_> = stats.boss_rotation.iter().map(|b| b.boss.as_str()).collect();
+  assert_eq!(rotation, ["Ashava", "Wandering Death"]);
+}
+
+#[test]
+fn attendance_is_recorded_and_counted() {
+  let mut history = observed();
+  assert!(!history.attended(EventKind::WorldBoss, 7001));
+
+  let entry = history.mark_attended(Some(EventKind::WorldBoss), 7001).unwrap();
+  assert!(entry.attended);
+  assert!(history.attended(EventKind::WorldBoss, 7001));
+  // Marking twice is harmless.
+  history.mark_attended(None, 7001).unwrap();
+
+  let stats = history.stats(utc("2025-10-16T12:00:00Z"));
+  assert_eq!(stats.attended[&EventKind::WorldBoss], 1);
+  assert_eq!(stats.attended[&EventKind::Legion], 0);
+}
+
+#[test]
+fn unknown_ids_are_rejected() {
+  let mut history = observed();
+  assert!(history.mark_attended(None, 424242).is_err());
+  assert!(history.mark_attended(Some(EventKind::Legion), 7001).is_err());
+}
@@ -0,0 +1,49 @@
+use helltime_core::hover_fade::{HoverFade, DELAY, POLL_INTERVAL};
+use helltime_core::hud_layout::Rect;
+use std::time::Instant;
+
+#[test]
+fn fades_after_the_cursor_rests_on_the_overlay() {
+  let start = Instant::now();
+  let mut fade = HoverFade::default();
+  assert_eq!(fade.observe(true, start), None);
+  assert_eq!(fade.observe(true, start + POLL_INTERVAL), None);
+  assert_eq!(fade.observe(true, start + DELAY), Some(true));
+  assert_eq!(fade.observe(true, start + DELAY + POLL_INTERVAL), None);
+  assert!(fade.faded());
+
+  assert_eq!(fade.observe(false, start + DELAY * 2), Some(false));
+  assert_eq!(fade.observe(false, start + DELAY * 3), None);
+}
+
+#[test]
+fn passing_over_does_not_fade() {
+  let start = Instant::now();
+  let mut fade = HoverFade::default();
+  fade.observe(true, start);
+  fade.observe(false, start + POLL_INTERVAL);
+  // The delay starts over on re-entering.
+  assert_eq!(fade.observe(true, start + DELAY), None);
+  assert_eq!(fade.observe(true, start + DELAY + POLL_INTERVAL), None);
+  assert!(!fade.faded());
+}
+
+#[test]
+fn reset_unfades() {
+  let start = Instant::now();
+  let mut fade = HoverFade::default();
+  assert_eq!(fade.reset(), None);
+  fade.observe(true, start);
+  fade.observe(true, start + DELAY);
+  assert_eq!(fade.reset(), Some(false));
+  assert_eq!(fade.observe(true, start + DELAY * 2), None);
+}
+
+#[test]
+fn cursor_inside_the_window_rectangle() {
+  let window = Rect { x: -300, y: 40, width: 260, height: 130 };
+  assert!(window.contains(-300.0, 40.0));
+  assert!(window.contains(-41.5, 169.5));
+  assert!(!window.contains(-40.0, 100.0));
+  assert!(!window.contains(-100.0, 170.0));
+}
@@ -0,0 +1,51 @@
+use helltime_core::hud_layout::{self, HudLayout, Rect, Suggestion};
+
+const FULL_HD: Rect = Rect { x: 0, y: 0, width: 1920, height: 1080 };
+
+fn overlay_at(suggestion: &Suggestion, size: (u32, u32)) -> Rect {
+  Rect { x: suggestion.x, y: suggestion.y, width: size.0, height: size.1 }
+}
+
+#[test]
+fn suggestions_avoid_the_hud() {
+  for layout in [HudLayout::Default, HudLayout::LeftSkillBar] {
+    for monitor in [FULL_HD, Rect { x: -2560, y: 0, width: 2560, height: 1440 }] {
+      let size = (260, 130);
+      let suggestions = hud_layout::suggest(layout, monitor, size);
+      assert_eq!(suggestions.len(), layout.safe_zones().len(), "{layout:?}");
+      for suggestion in &suggestions {
+        let overlay = overlay_at(suggestion, size);
+        assert!(hud_layout::covered(layout, monitor, overlay).is_empty(), "{layout:?} {suggestion:?}");
+        assert!(overlay.x >= monitor.x && overlay.x + size.0 as i32 <= monitor.x + monitor.width as i32);
+      }
+    }
+  }
+}
+
+#[test]
+fn zones_place_by_their_alignment() {
+  let suggestions = hud_layout::suggest(HudLayout::Default, FULL_HD, (260, 130));
+  let at = |zone| suggestions.iter().find(|s| s.zone == zone).copied().unwrap();
+  assert_eq!(at("top_left"), Suggestion { zone: "top_left", x: 19, y: 22 });
+  assert_eq!(at("top_center"), Suggestion { zone: "top_center", x: 830, y: 22 });
+  assert_eq!(at("bottom_right"), Suggestion { zone: "bottom_right", x: 1641, y: 928 });
+}
+
+#[test]
+fn zones_too_small_for_the_overlay_are_left_out() {
+  let zones: Vec<_> = hud_layout::suggest(HudLayout::Default, FULL_HD, (400, 200)).into_iter().map(|s| s.zone).collect();
+  assert_eq!(zones, ["top_left"]);
+}
+
+#[test]
+fn reports_covered_elements_per_layout() {
+  let bottom_left = Rect { x: 100, y: 950, width: 260, height: 100 };
+  assert_eq!(hud_layout::covered(HudLayout::Default, FULL_HD, bottom_left), ["chat"]);
+  assert_eq!(hud_layout::covered(HudLayout::LeftSkillBar, FULL_HD, bottom_left), ["health_globe", "skill_bar"]);
+}
+
+#[test]
+fn layout_names_are_snake_case() {
+  assert_eq!(serde_json::to_string(&HudLayout::LeftSkillBar).unwrap(), "\"left_skill_bar\"");
+  assert_eq!(serde_json::from_str::<HudLayout>("\"default\"").unwrap(), HudLayout::Default);
+}
@@ -0,0 +1,79 @@
+use helltime_core::i18n::{self, Locale, Msg};
+use helltime_core::schedule::{describe, EventKind};
+
+#[test]
+fn locale_tags_match_by_language() {
+  assert_eq!(Locale::from_tag("de_DE.UTF-8"), Some(Locale::De));
+  assert_eq!(Locale::from_tag("de-AT"), Some(Locale::De));
+  assert_eq!(Locale::from_tag("EN-us"), Some(Locale::En));
+  assert_eq!(Locale::from_tag("C"), None);
+  assert_eq!(Locale::from_tag("fr_FR"), None);
+}
+
+#[test]
+fn detect_takes_first_supported_locale() {
+  assert_eq!(Locale::detect(["C.UTF-8", "fr_FR", "de-CH"]), Locale::De);
+  assert_eq!(Locale::detect(["C"]), Locale::En);
+  assert_eq!(Locale::detect([]), Locale::En);
+}
+
+#[test]
+fn every_message_is_in_every_bundle() {
+  for locale in Locale::ALL {
+    let bundle = i18n::bundle(locale);
+    for msg in Msg::ALL {
+      assert!(bundle.contains_key(msg.key()), "{:?} has no {}", locale, msg.key());
+    }
+    for kind in EventKind::ALL {
+      assert!(bundle.contains_key(&format!("event_{}", kind.key())), "{:?} has no label for {:?}", locale, kind);
+    }
+  }
+}
+
+#[test]
+fn placeholders_are_filled() {
+  let text = i18n::format(Locale::De, Msg::TooltipRemindersPaused, &[("remaining", "12m")]);
+  assert_eq!(text, "Erinnerungen pausiert: 12m");
+  assert_eq!(i18n::text(Locale::En, Msg::MenuExit), "Exit");
+}
+
+#[test]
+fn event_labels_are_translated() {
+  assert_eq!(i18n::event_label(Locale::De, EventKind::WorldBoss), "Weltboss");
+  assert_eq!(i18n::event_label(Locale::De, EventKind::Legion), "Legion");
+  assert_eq!(i18n::event_label(Locale::En, EventKind::Helltide), "Helltide");
+  // The same word the UI's default spoken name uses.
+  assert_eq!(i18n::event_label(Locale::De, EventKind::Helltide), "Höllenhochwasser");
+}
+
+#[test]
+fn overlay_texts_follow_the_locale() {
+  let texts = i18n::OverlayTexts::new(Locale::De);
+  assert_eq!(texts.drag_hint, "Ziehen zum Verschieben");
+  assert_eq!(texts.labels[&EventKind::WorldBoss], "Weltboss");
+  let json = serde_json::to_value(i18n::OverlayTexts::new(Locale::En)).unwrap();
+  assert_eq!(json["moveLabel"], "Move overlay");
+  assert_eq!(json["labels"]["world_boss"], "World Boss");
+}
+
+#[test]
+fn truncation_counts_utf16_units() {
+  assert_eq!(i18n::truncate_utf16("Höllenhochwasser", 16), "Höllenhochwasser");
+  assert_eq!(i18n::truncate_utf16("Höllenhochwasser", 5), "Höll…");
+  // The emoji takes two units and must not be split.
+  assert_eq!(i18n::truncate_utf16("ab😀cd", 4), "ab…");
+  assert_eq!(i18n::truncate_utf16("ab😀cd", 5), "ab😀…");
+}
+
+#[test]
+fn right_to_left_names_are_isolated() {
+  assert_eq!(i18n::isolate("Kehjistan"), "Kehjistan");
+  assert_eq!(i18n::isolate("北方荒原"), "北方荒原");
+  assert_eq!(i18n::isolate("كهجستان"), "\u{2068}كهجستان\u{2069}");
+  assert_eq!(
+    describe(EventKind::Legion, None, false, Some("שלג")),
+    "Legion in \u{2068}שלג\u{2069}"
+  );
+  // A cut inside an isolate still closes it.
+  assert_eq!(i18n::truncate_utf16("Legion in \u{2068}كهجستان\u{2069}", 15), "Legion in \u{2068}كه…\u{2069}");
+}
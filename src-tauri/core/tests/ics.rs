@@ -0,0 +1,74 @@
+use helltime_core::cadence::Cadence;
+use helltime_core::clock::SecondaryZone;
+use helltime_core::i18n::Locale;
+use helltime_core::ics::{calendar, feed, upcoming};
+use helltime_core::schedule::EventKind;
+
+mod common;
+
+use common::{fixture, utc};
+
+#[test]
+fn upcoming_is_limited_to_the_window() {
+  let events = upcoming(&fixture(), &EventKind::ALL, utc("2025-10-16T08:05:00Z"), 1);
+  let ids: Vec<i64> = events.iter().map(|e| e.id).collect();
+  assert_eq!(ids, vec![5001, 7001, 5002, 9002, 5003]);
+}
+
+#[test]
+fn calendar_has_one_event_with_alarm_per_entry() {
+  let now = utc("2025-10-16T08:05:00Z");
+  let cadence = Cadence::bundled().helltide;
+  let events = upcoming(&fixture(), &[EventKind::WorldBoss], now, 24);
+  let ics = calendar(&events, Locale::En, &cadence, now, None);
+
+  assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+  assert!(ics.ends_with("END:VCALENDAR\r\n"));
+  assert_eq!(ics.matches("BEGIN:VEVENT").count(), 3);
+  assert_eq!(ics.matches("BEGIN:VALARM").count(), 3);
+  assert!(ics.contains("UID:world_boss-7001@helltime\r\n"));
+  assert!(ics.contains("DTSTART:20251016T083000Z\r\nDTEND:20251016T084500Z\r\n"));
+  assert!(ics.contains("SUMMARY:World Boss Ashava in Kehjistan\r\n"));
+  assert!(ics.contains("TRIGGER:-PT10M\r\n"));
+  assert!(ics.lines().all(|line| line.len() <= 75));
+}
+
+#[test]
+fn calendar_escapes_and_folds_text() {
+  let now = utc("2025-10-16T08:05:00Z");
+  let cadence = Cadence::bundled().helltide;
+  let mut events = upcoming(&fixture(), &[EventKind::Legion], now, 1);
+  events.truncate(1);
+  events[0].zone = Some("Kehjistan, Scosglen; Nahantu — Höllenfeuer-Zitadelle der Verdammten".to_string());
+  let ics = calendar(&events, Locale::De, &cadence, now, None);
+
+  assert!(ics.contains("SUMMARY:Legion in Kehjistan\\, Scosglen\\; Nahantu"));
+  assert!(ics.contains("\r\n "), "long lines must be folded");
+  assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+  let unfolded = ics.replace("\r\n ", "");
+  assert!(unfolded.contains("Höllenfeuer-Zitadelle der Verdammten\r\n"));
+}
+
+#[test]
+fn feed_asks_subscribers_to_refresh() {
+  let now = utc("2025-10-16T08:05:00Z");
+  let cadence = Cadence::bundled().helltide;
+  let events = upcoming(&fixture(), &EventKind::ALL, now, 48);
+  assert!(feed(&events, Locale::En, &cadence, now, None).contains("REFRESH-INTERVAL;VALUE=DURATION:PT30M\r\n"));
+  assert!(!calendar(&events, Locale::En, &cadence, now, None).contains("REFRESH-INTERVAL"));
+}
+
+#[test]
+fn calendar_uses_the_secondary_zone() {
+  let now = utc("2025-10-16T08:05:00Z");
+  let cadence = Cadence::bundled().helltide;
+  let events = upcoming(&fixture(), &[EventKind::WorldBoss], now, 24);
+  let zone = SecondaryZone { label: "IST".into(), offset_minutes: 5 * 60 + 30 };
+  let ics = calendar(&events, Locale::En, &cadence, now, Some(&zone));
+
+  assert_eq!(ics.matches("BEGIN:VTIMEZONE").count(), 1);
+  assert!(ics.contains("TZID:UTC+0530\r\n"));
+  assert!(ics.contains("TZOFFSETFROM:+0530\r\nTZOFFSETTO:+0530\r\nTZNAME:IST\r\n"));
+  assert!(ics.contains("DTSTART;TZID=UTC+0530:20251016T140000\r\nDTEND;TZID=UTC+0530:20251016T141500\r\n"));
+  assert!(!ics.contains("DTSTART:2025"));
+}
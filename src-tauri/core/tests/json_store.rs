@@ -0,0 +1,35 @@
+use helltime_core::json_store::JsonStore;
+
+mod common;
+
+use common::temp_path;
+
+#[test]
+fn saves_and_loads_the_value() {
+  let path = temp_path("json-store", "roundtrip", "store.json");
+  let mut store: JsonStore<Vec<u32>> = JsonStore::load(path.clone(), "numbers");
+  assert!(store.is_empty());
+  store.push(7);
+  store.save();
+
+  let loaded: JsonStore<Vec<u32>> = JsonStore::load(path.clone(), "numbers");
+  assert_eq!(*loaded, vec![7]);
+  assert!(!path.with_extension("json.tmp").exists());
+}
+
+#[test]
+fn corrupt_file_starts_fresh() {
+  let path = temp_path("json-store", "corrupt", "store.json");
+  std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+  std::fs::write(&path, b"{not json").unwrap();
+  let store: JsonStore<Vec<u32>> = JsonStore::load(path, "numbers");
+  assert!(store.is_empty());
+}
+
+#[test]
+fn default_store_never_writes() {
+  let mut store: JsonStore<Vec<u32>> = JsonStore::default();
+  store.push(1);
+  store.save();
+  assert_eq!(*store, vec![1]);
+}
@@ -0,0 +1,58 @@
+use helltime_core::local_api::{route, sse_event, sse_head, Reply, Route, OBS_PAGE};
+
+#[test]
+fn routes_known_endpoints() {
+  assert_eq!(route("GET /schedule HTTP/1.1\r\nHost: localhost\r\n\r\n"), Route::Schedule);
+  assert_eq!(route("GET /next?kind=legion HTTP/1.1\r\n\r\n"), Route::Next);
+  assert_eq!(route("GET /status/ HTTP/1.0\r\n\r\n"), Route::Status);
+}
+
+#[test]
+fn rejects_unknown_requests() {
+  assert_eq!(route("GET /../secret HTTP/1.1\r\n\r\n"), Route::NotFound);
+  assert_eq!(route("POST /next HTTP/1.1\r\n\r\n"), Route::MethodNotAllowed);
+  assert_eq!(route("HEAD /next HTTP/1.1\r\n\r\n"), Route::MethodNotAllowed);
+  assert_eq!(route("garbage"), Route::BadRequest);
+  assert_eq!(route(""), Route::BadRequest);
+}
+
+#[test]
+fn frames_json_replies() {
+  let reply = Reply::json(200, &serde_json::json!({ "ok": true }));
+  let text = String::from_utf8(reply.to_bytes()).unwrap();
+  assert!(text.starts_with("HTTP/1.1 200 OK\r\n"));
+  assert!(text.contains("Content-Type: application/json\r\n"));
+  assert!(text.contains("Content-Length: 11\r\n"));
+  assert!(text.ends_with("\r\n\r\n{\"ok\":true}"));
+}
+
+#[test]
+fn errors_carry_message() {
+  let reply = Reply::error(503, "no schedule yet");
+  assert_eq!(reply.status, 503);
+  assert_eq!(reply.body, br#"{"error":"no schedule yet"}"#);
+  assert!(String::from_utf8(reply.to_bytes()).unwrap().starts_with("HTTP/1.1 503 Service Unavailable"));
+}
+
+#[test]
+fn serves_obs_page_and_event_stream() {
+  assert_eq!(route("GET /obs?kind=helltide HTTP/1.1\r\n\r\n"), Route::ObsPage);
+  assert_eq!(route("GET /events HTTP/1.1\r\n\r\n"), Route::Events);
+  assert!(OBS_PAGE.contains("new EventSource(\"/events\")"));
+
+  let reply = Reply::html(OBS_PAGE);
+  assert!(String::from_utf8(reply.to_bytes()).unwrap().contains("Content-Type: text/html; charset=utf-8\r\n"));
+}
+
+#[test]
+fn frames_sse_messages() {
+  assert!(std::str::from_utf8(sse_head()).unwrap().contains("Content-Type: text/event-stream"));
+  assert_eq!(sse_event(&serde_json::json!([1, 2])), b"data: [1,2]\n\n");
+}
+
+#[test]
+fn serves_calendar_feed() {
+  assert_eq!(route("GET /calendar.ics HTTP/1.1\r\n\r\n"), Route::Calendar);
+  let reply = Reply::calendar("BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n".to_string());
+  assert!(String::from_utf8(reply.to_bytes()).unwrap().contains("Content-Type: text/calendar; charset=utf-8\r\n"));
+}
@@ -0,0 +1,39 @@
+use helltime_core::metrics::{Metrics, MetricsSnapshot};
+use std::time::Duration;
+
+#[test]
+fn starts_empty() {
+  let metrics = Metrics::default();
+  assert_eq!(metrics.snapshot(), MetricsSnapshot::default());
+  assert_eq!(metrics.summary(), "fetches 0/0 ok (0 failed, avg —), cache hits 0, reminders 0, overlay shows 0");
+}
+
+#[test]
+fn counts_and_averages_fetches() {
+  let metrics = Metrics::default();
+  for (latency, ok) in [(100, true), (300, false)] {
+    metrics.fetch_started();
+    metrics.fetch_finished(Duration::from_millis(latency), ok);
+  }
+  // Still running.
+  metrics.fetch_started();
+  metrics.cache_hit();
+  metrics.cache_hit();
+  metrics.reminder_fired();
+  metrics.overlay_shown();
+
+  assert_eq!(
+    metrics.snapshot(),
+    MetricsSnapshot {
+      fetch_attempts: 3,
+      fetch_successes: 1,
+      fetch_failures: 1,
+      average_fetch_latency_ms: Some(200),
+      cache_hits: 2,
+      reminders_fired: 1,
+      overlay_shows: 1,
+    }
+  );
+  let json = serde_json::to_value(metrics.snapshot()).unwrap();
+  assert_eq!(json["averageFetchLatencyMs"], 200);
+}
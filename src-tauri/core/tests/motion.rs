@@ -0,0 +1,17 @@
+use helltime_core::motion::{self, parse_gsettings_bool};
+use helltime_core::prefs::ReducedMotion;
+
+#[test]
+fn auto_follows_the_system_setting() {
+  assert!(!motion::reduced(ReducedMotion::Auto, true));
+  assert!(motion::reduced(ReducedMotion::Auto, false));
+  assert!(motion::reduced(ReducedMotion::On, true));
+  assert!(!motion::reduced(ReducedMotion::Off, false));
+}
+
+#[test]
+fn parses_gsettings_output() {
+  assert_eq!(parse_gsettings_bool("false\n"), Some(false));
+  assert_eq!(parse_gsettings_bool("true"), Some(true));
+  assert_eq!(parse_gsettings_bool("No such schema “org.gnome.desktop.interface”"), None);
+}
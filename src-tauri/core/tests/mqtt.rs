@@ -0,0 +1,134 @@
+use helltime_core::countdown::{ActiveEvent, NextEvent};
+use helltime_core::mqtt::{
+  check_connack, connect, disconnect, discovery, publish, reconnect_delay, states, Message, MqttSettings,
+  MAX_RECONNECT_DELAY, RECONNECT_DELAY,
+};
+use helltime_core::schedule::EventKind;
+use serde_json::Value;
+
+fn settings() -> MqttSettings {
+  MqttSettings { enabled: true, host: "broker.local".into(), ..MqttSettings::default() }
+}
+
+fn next(kind: EventKind, seconds_until: i64) -> NextEvent {
+  NextEvent {
+    kind,
+    id: 1,
+    start: "2025-10-16T08:30:00+00:00".into(),
+    start_local: "10:30".into(),
+    start_secondary: None,
+    seconds_until,
+    boss: None,
+    boss_predicted: false,
+    zone: None,
+  }
+}
+
+fn active(kind: EventKind) -> ActiveEvent {
+  ActiveEvent {
+    kind,
+    id: 2,
+    start: "2025-10-16T08:00:00+00:00".into(),
+    end: "2025-10-16T09:00:00+00:00".into(),
+    seconds_remaining: 600,
+    progress: 0.8,
+    boss: None,
+    boss_predicted: false,
+    zone: None,
+  }
+}
+
+#[test]
+fn connect_carries_will_and_credentials() {
+  let mut settings = settings();
+  settings.username = Some("u".into());
+  settings.password = Some("p".into());
+  let packet = connect(&settings, "c");
+  let mut expected = vec![0x10, 0];
+  expected.extend_from_slice(b"\x00\x04MQTT\x04");
+  expected.push(0x80 | 0x40 | 0x20 | 0x04 | 0x02);
+  expected.extend_from_slice(&60u16.to_be_bytes());
+  expected.extend_from_slice(b"\x00\x01c");
+  expected.extend_from_slice(b"\x00\x0fhelltime/status");
+  expected.extend_from_slice(b"\x00\x07offline");
+  expected.extend_from_slice(b"\x00\x01u\x00\x01p");
+  expected[1] = (expected.len() - 2) as u8;
+  assert_eq!(packet, expected);
+}
+
+#[test]
+fn publish_is_retained_with_a_multi_byte_length() {
+  let message = Message { topic: "t".into(), payload: "x".repeat(200) };
+  let packet = publish(&message);
+  // 2 + 1 topic bytes + 200 payload bytes = 203 = 0xcb 0x01.
+  assert_eq!(&packet[..6], &[0x31, 0xcb, 0x01, 0x00, 0x01, b't']);
+  assert_eq!(packet.len(), 3 + 203);
+  assert_eq!(disconnect(), vec![0xe0, 0x00]);
+}
+
+#[test]
+fn connack_codes_are_explained() {
+  assert!(check_connack([0x20, 0x02, 0x00, 0x00]).is_ok());
+  assert_eq!(check_connack([0x20, 0x02, 0x00, 0x04]), Err("bad user name or password".to_string()));
+  assert!(check_connack([0xd0, 0x00, 0x00, 0x00]).is_err());
+}
+
+#[test]
+fn states_cover_next_start_countdown_and_activity() {
+  let messages = states(
+    &settings(),
+    &[EventKind::Helltide, EventKind::Legion],
+    &[next(EventKind::Legion, 300)],
+    &[active(EventKind::Helltide)],
+  );
+  let pairs: Vec<(&str, &str)> = messages.iter().map(|m| (m.topic.as_str(), m.payload.as_str())).collect();
+  assert_eq!(
+    pairs,
+    [
+      ("helltime/helltide/active", "ON"),
+      ("helltime/legion/next_start", "2025-10-16T08:30:00+00:00"),
+      ("helltime/legion/seconds_until", "300"),
+      ("helltime/legion/active", "OFF"),
+    ]
+  );
+}
+
+#[test]
+fn discovery_configures_shown_and_removes_hidden_categories() {
+  let messages = discovery(&settings(), &[EventKind::Helltide]);
+  assert_eq!(messages.len(), 9);
+
+  let config = messages.iter().find(|m| m.topic == "homeassistant/binary_sensor/helltime_helltide_active/config").unwrap();
+  let config: Value = serde_json::from_str(&config.payload).unwrap();
+  assert_eq!(config["state_topic"], "helltime/helltide/active");
+  assert_eq!(config["availability_topic"], "helltime/status");
+  assert_eq!(config["unique_id"], "helltime_helltide_active");
+
+  let hidden = messages.iter().find(|m| m.topic == "homeassistant/sensor/helltime_legion_next/config").unwrap();
+  assert_eq!(hidden.payload, "");
+}
+
+#[test]
+fn settings_are_validated_only_when_enabled() {
+  assert!(MqttSettings::default().validate().is_ok());
+  assert!(settings().validate().is_ok());
+
+  let mut url = settings();
+  url.host = "mqtt://broker.local".into();
+  assert!(url.validate().is_err());
+
+  let mut wildcard = settings();
+  wildcard.topic_prefix = "helltime/#".into();
+  assert!(wildcard.validate().is_err());
+
+  let mut password_only = settings();
+  password_only.password = Some("p".into());
+  assert!(password_only.validate().is_err());
+}
+
+#[test]
+fn reconnect_delay_doubles_up_to_the_cap() {
+  assert_eq!(reconnect_delay(0), RECONNECT_DELAY);
+  assert_eq!(reconnect_delay(1), RECONNECT_DELAY * 2);
+  assert_eq!(reconnect_delay(30), MAX_RECONNECT_DELAY);
+}
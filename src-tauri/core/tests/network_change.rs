@@ -0,0 +1,36 @@
+use helltime_core::network_change::{self, NetworkWatch};
+
+const HEADER: &str = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT\n";
+
+fn table(rows: &[&str]) -> String {
+  format!("{HEADER}{}", rows.join("\n"))
+}
+
+#[test]
+fn finds_default_routes() {
+  let online = table(&[
+    "wlp2s0\t00000000\t0100A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0",
+    "wlp2s0\t0000A8C0\t00000000\t0001\t0\t0\t600\t00FFFFFF\t0\t0\t0",
+  ]);
+  assert_eq!(network_change::default_routes(&online), ["wlp2s0 0100A8C0"]);
+
+  // Only the local subnet, or a default route that is down.
+  let offline = table(&[
+    "wlp2s0\t0000A8C0\t00000000\t0001\t0\t0\t600\t00FFFFFF\t0\t0\t0",
+    "eth0\t00000000\t0101A8C0\t0002\t0\t0\t100\t00000000\t0\t0\t0",
+  ]);
+  assert!(network_change::default_routes(&offline).is_empty());
+  assert!(network_change::default_routes("").is_empty());
+}
+
+#[test]
+fn refreshes_when_back_online_or_on_another_network() {
+  let home = || vec!["wlp2s0 0100A8C0".to_string()];
+  let mut watch = NetworkWatch::default();
+  assert!(!watch.observe(home()), "the first sample is the baseline");
+  assert!(!watch.observe(home()));
+  assert!(!watch.observe(Vec::new()), "going offline");
+  assert!(watch.observe(home()), "back online");
+  assert!(watch.observe(vec!["eth0 0101A8C0".to_string()]), "switched networks");
+  assert!(!watch.observe(vec!["eth0 0101A8C0".to_string()]));
+}
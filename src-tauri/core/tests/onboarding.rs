@@ -0,0 +1,17 @@
+use helltime_core::onboarding::{OnboardingStatus, Permission, Step};
+
+#[test]
+fn steps_cover_only_what_is_missing() {
+  let status = OnboardingStatus::new(false, Permission::Prompt, Some(false), true);
+  assert_eq!(status.steps, vec![Step::Notifications, Step::Autostart, Step::OverlayPosition]);
+
+  let status = OnboardingStatus::new(false, Permission::Granted, Some(true), false);
+  assert!(status.steps.is_empty());
+}
+
+#[test]
+fn unsupported_autostart_is_not_offered() {
+  let status = OnboardingStatus::new(true, Permission::Granted, None, false);
+  assert!(status.completed);
+  assert!(!status.steps.contains(&Step::Autostart));
+}
@@ -0,0 +1,21 @@
+use helltime_core::overlay_compat::{self, DegradedFeature, OverlayCompat};
+
+#[test]
+fn wine_is_detected_and_can_be_overridden() {
+  assert_eq!(overlay_compat::detect(None, None), OverlayCompat::Standard);
+  assert_eq!(overlay_compat::detect(Some("9.0"), None), OverlayCompat::Wine);
+  assert_eq!(overlay_compat::detect(Some("9.0"), Some("0")), OverlayCompat::Standard);
+  assert_eq!(overlay_compat::detect(None, Some(" 1 ")), OverlayCompat::Wine);
+  assert_eq!(overlay_compat::detect(None, Some("yes")), OverlayCompat::Standard);
+}
+
+#[test]
+fn wine_mode_drops_layered_styles() {
+  assert!(OverlayCompat::Standard.degraded().is_empty());
+  assert!(OverlayCompat::Standard.transparent() && OverlayCompat::Standard.layered_click_through());
+
+  let wine = OverlayCompat::Wine;
+  assert!(!wine.transparent() && !wine.layered_click_through());
+  assert_eq!(wine.degraded(), [DegradedFeature::Transparency, DegradedFeature::ClickThroughHitTest]);
+  assert_eq!(serde_json::to_value(wine.degraded()).unwrap(), serde_json::json!(["transparency", "click_through_hit_test"]));
+}
@@ -0,0 +1,52 @@
+use helltime_core::overlay_style::{OverlayMode, OverlayStyle, OverlayTheme, SizePreset, TextShadow};
+use helltime_core::schedule::EventKind;
+
+#[test]
+fn parses_the_ui_payload() {
+  let style: OverlayStyle = serde_json::from_value(serde_json::json!({
+    "enabled": true,
+    "mode": "minibar",
+    "categories": { "helltide": true, "legion": false, "world_boss": true },
+    "bgHex": "#102030",
+    "bgOpacity": 0.5,
+    "scaleX": 1.2,
+    "scaleY": 0.8,
+    "textShadow": "outline8",
+    "theme": "highContrast",
+    "fontFamily": "Segoe UI",
+    "fontWeight": 600,
+    "sizePreset": "large",
+    "wrapText": true,
+    "width": 0,
+    "height": 0
+  }))
+  .unwrap();
+
+  assert_eq!(style.mode, OverlayMode::Minibar);
+  assert_eq!(style.text_shadow, TextShadow::Outline8);
+  assert_eq!(style.theme, OverlayTheme::HighContrast);
+  assert_eq!(style.size_preset, SizePreset::Large);
+  assert!(style.shows(EventKind::Helltide) && !style.shows(EventKind::Legion));
+  assert!(style.validate().is_ok());
+}
+
+#[test]
+fn missing_fields_use_defaults() {
+  let style: OverlayStyle = serde_json::from_str(r#"{ "mode": "toast" }"#).unwrap();
+  assert_eq!(style, OverlayStyle { mode: OverlayMode::Toast, ..OverlayStyle::default() });
+  assert!(style.shows(EventKind::WorldBoss));
+  assert!(OverlayStyle::default().validate().is_ok());
+}
+
+#[test]
+fn rejects_values_an_engine_cannot_draw() {
+  let invalid = [
+    OverlayStyle { bg_hex: "red".into(), ..OverlayStyle::default() },
+    OverlayStyle { bg_opacity: 1.5, ..OverlayStyle::default() },
+    OverlayStyle { scale_y: 3.0, ..OverlayStyle::default() },
+    OverlayStyle { font_weight: 50, ..OverlayStyle::default() },
+  ];
+  for style in invalid {
+    assert!(style.validate().is_err(), "{style:?}");
+  }
+}
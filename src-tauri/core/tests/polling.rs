@@ -0,0 +1,55 @@
+use helltime_core::polling::{PollMode, PollSettings};
+use std::time::Duration;
+
+mod common;
+
+use common::{fixture, utc};
+
+const MINUTE: Duration = Duration::from_secs(60);
+
+#[test]
+fn fixed_mode_ignores_schedule() {
+  let settings = PollSettings { mode: PollMode::Fixed, interval_minutes: 10, ..PollSettings::default() };
+  assert_eq!(settings.next_delay(Some(&fixture()), utc("2025-10-16T08:08:00Z")), 10 * MINUTE);
+}
+
+#[test]
+fn adaptive_polls_fast_right_before_an_event() {
+  let settings = PollSettings { mode: PollMode::Adaptive, interval_minutes: 10, ..PollSettings::default() };
+  // Legion at 08:10.
+  assert_eq!(settings.next_delay(Some(&fixture()), utc("2025-10-16T08:07:00Z")), MINUTE);
+}
+
+#[test]
+fn adaptive_waits_until_the_fast_window() {
+  let settings = PollSettings { mode: PollMode::Adaptive, interval_minutes: 10, ..PollSettings::default() };
+  // Next start 08:30 (world boss): wake at 08:25.
+  assert_eq!(settings.next_delay(Some(&fixture()), utc("2025-10-16T08:11:00Z")), 14 * MINUTE);
+}
+
+#[test]
+fn adaptive_slows_down_between_events() {
+  let settings = PollSettings { mode: PollMode::Adaptive, interval_minutes: 5, ..PollSettings::default() };
+  // Last scheduled start is 15:30; far away from anything but capped at 2x the interval.
+  assert_eq!(settings.next_delay(Some(&fixture()), utc("2025-10-16T12:01:00Z")), 10 * MINUTE);
+}
+
+#[test]
+fn interval_is_clamped_and_defaults_apply() {
+  let settings = PollSettings { mode: PollMode::Fixed, interval_minutes: 0, ..PollSettings::default() };
+  assert_eq!(settings.interval(), MINUTE);
+  let parsed: PollSettings = serde_json::from_str(r#"{"intervalMinutes": 20}"#).unwrap();
+  assert_eq!(parsed.mode, PollMode::Adaptive);
+  assert_eq!(settings.next_delay(None, utc("2025-10-16T08:00:00Z")), MINUTE);
+}
+
+#[test]
+fn cache_ttl_is_validated() {
+  assert!(PollSettings::default().validate().is_ok());
+  assert_eq!(PollSettings::default().cache_ttl(), Duration::from_secs(30));
+  let long = PollSettings { cache_ttl_seconds: 3600, ..PollSettings::default() };
+  assert!(long.validate().is_err());
+  assert_eq!(long.cache_ttl(), Duration::from_secs(600));
+  let unlimited = PollSettings { max_requests_per_minute: 0, ..PollSettings::default() };
+  assert!(unlimited.validate().is_err());
+}
@@ -0,0 +1,47 @@
+use helltime_core::power_saving::{self, PowerState, MAX_POLL_DELAY};
+use helltime_core::prefs::PowerSaving;
+use std::time::Duration;
+
+#[test]
+fn auto_saves_on_battery_or_metered_connections() {
+  let plugged = PowerState::default();
+  let battery = PowerState { on_battery: true, metered: false };
+  let metered = PowerState { on_battery: false, metered: true };
+
+  assert!(!power_saving::saving(PowerSaving::Auto, plugged));
+  assert!(power_saving::saving(PowerSaving::Auto, battery));
+  assert!(power_saving::saving(PowerSaving::Auto, metered));
+  assert!(power_saving::saving(PowerSaving::On, plugged));
+  assert!(!power_saving::saving(PowerSaving::Off, battery));
+}
+
+#[test]
+fn stretches_poll_delays_up_to_the_longest_interval() {
+  let minute = Duration::from_secs(60);
+  assert_eq!(power_saving::poll_delay(minute, false), minute);
+  assert_eq!(power_saving::poll_delay(minute, true), 3 * minute);
+  assert_eq!(power_saving::poll_delay(30 * minute, true), MAX_POLL_DELAY);
+  assert_eq!(power_saving::poll_delay(2 * MAX_POLL_DELAY, true), 2 * MAX_POLL_DELAY);
+}
+
+#[test]
+fn reads_the_power_source_from_sysfs() {
+  assert!(power_saving::sysfs_on_battery([("Battery\n", None), ("Mains\n", Some("0\n"))]));
+  assert!(!power_saving::sysfs_on_battery([("Battery\n", None), ("Mains\n", Some("1\n"))]));
+  assert!(!power_saving::sysfs_on_battery([("Mains", Some("1"))]));
+  // Desktop without a battery, or a UPS-less unknown supply.
+  assert!(!power_saving::sysfs_on_battery([("Mains", Some("0"))]));
+  assert!(!power_saving::sysfs_on_battery([]));
+}
+
+#[test]
+fn parses_connection_costs() {
+  assert_eq!(power_saving::parse_nm_metered("u 1\n"), Some(true));
+  assert_eq!(power_saving::parse_nm_metered("u 3"), Some(true));
+  assert_eq!(power_saving::parse_nm_metered("u 4"), Some(false));
+  assert_eq!(power_saving::parse_nm_metered("Failed to get property"), None);
+
+  assert_eq!(power_saving::parse_network_cost("Variable\r\n"), Some(true));
+  assert_eq!(power_saving::parse_network_cost("Unrestricted"), Some(false));
+  assert_eq!(power_saving::parse_network_cost("Unknown"), None);
+}
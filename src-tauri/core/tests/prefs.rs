@@ -0,0 +1,237 @@
+use helltime_core::prefs::{
+  CloseBehavior, MonitorRect, NetworkSettings, OverlayEngine, PowerSaving, Preferences, PrefsStore, ProxyMode,
+  ReducedMotion, RequestHeader, TrayIconStyle, WindowGeometry,
+};
+use helltime_core::clock::SecondaryZone;
+use helltime_core::i18n::Locale;
+use helltime_core::mqtt::MqttSettings;
+use helltime_core::polling::{PollMode, PollSettings};
+use helltime_core::push::{NtfySettings, PushSettings};
+use helltime_core::reminders::{Escalation, EscalationAction, EscalationRule, ReminderTimes};
+use helltime_core::schedule::EventKind;
+use helltime_core::shortcuts::{ShortcutAction, ShortcutMap};
+use helltime_core::telemetry::TelemetrySettings;
+use helltime_core::toast_template::ToastTemplates;
+use helltime_core::visibility::{VisibilitySchedule, VisibilityWindow};
+use helltime_core::webhook::{WebhookSettings, WebhookTrigger};
+use chrono::Weekday;
+use std::path::PathBuf;
+
+mod common;
+
+use common::temp_path;
+
+#[test]
+fn defaults_when_missing() {
+  let store = PrefsStore::load(temp_path("prefs", "missing", "preferences.json"));
+  assert_eq!(store.get(), &Preferences::default());
+  assert_eq!(store.get().close_behavior, CloseBehavior::HideToTray);
+  assert!(store.get().minimize_to_tray);
+}
+
+#[test]
+fn round_trips_through_disk() {
+  let path = temp_path("prefs", "roundtrip", "preferences.json");
+  let mut store = PrefsStore::load(path.clone());
+  let prefs = Preferences {
+    close_behavior: CloseBehavior::Exit,
+    minimize_to_tray: false,
+    start_minimized: true,
+    hidden_events: vec![EventKind::Legion],
+    poll: PollSettings { mode: PollMode::Fixed, interval_minutes: 5, max_requests_per_minute: 10, cache_ttl_seconds: 120 },
+    cadence_updates: false,
+    network: NetworkSettings {
+      proxy_mode: ProxyMode::Manual,
+      proxy_url: Some("http://proxy.corp:3128".to_string()),
+      ca_bundle: None,
+      insecure_tls: false,
+      user_agent: Some("helltime-test".to_string()),
+      api_token: Some("secret".to_string()),
+      extra_headers: vec![RequestHeader { name: "X-Api-Key".to_string(), value: "k".to_string() }],
+    },
+    overlay_engine: OverlayEngine::Native,
+    overlay_enabled: false,
+    overlay_visibility: VisibilitySchedule {
+      enabled: true,
+      windows: vec![VisibilityWindow { days: vec![Weekday::Sat], start_minute: 18 * 60, end_minute: 24 * 60 }],
+    },
+    overlay_capture_hidden: true,
+    overlay_hover_fade: true,
+    reminders_enabled: false,
+    reminder_times: ReminderTimes {
+      helltide: vec![20],
+      legion: Vec::new(),
+      world_boss: vec![45, 15],
+      helltide_end: Some(5),
+      chest: None,
+    },
+    reminder_escalation: Escalation {
+      enabled: true,
+      rules: vec![EscalationRule { minutes_before: 3, actions: vec![EscalationAction::Notification, EscalationAction::RepeatSound] }],
+    },
+    game_mode: false,
+    power_saving: PowerSaving::Off,
+    locale: Some(Locale::De),
+    window: Some(WindowGeometry { x: 10, y: 20, width: 380, height: 720, maximized: false }),
+    onboarding_completed: true,
+    sync_dir: Some(PathBuf::from("/mnt/share/helltime")),
+    headless: true,
+    unload_after_minutes: Some(15),
+    tray_icon_style: TrayIconStyle::Monochrome,
+    tray_pulse: false,
+    toast_templates: ToastTemplates { title: "{type} — {zone}".into(), body: "in {minutes}m".into() },
+    reduced_motion: ReducedMotion::On,
+    shortcuts: ShortcutMap::from([(ShortcutAction::PeekNextEvent, "Ctrl+Shift+F9".into()), (ShortcutAction::ToggleWindow, String::new())]),
+    telemetry: TelemetrySettings { enabled: true, endpoint: Some("https://telemetry.example.com/v1".into()) },
+    secondary_zone: Some(SecondaryZone { label: "CET".into(), offset_minutes: 60 }),
+    push: PushSettings {
+      ntfy: NtfySettings { enabled: true, server: None, topic: "helltime-3f9a".into(), token: Some("tk_abc".into()) },
+      ..PushSettings::default()
+    },
+    webhook: WebhookSettings {
+      enabled: true,
+      url: "http://homeassistant.local:8123/api/webhook/helltime".into(),
+      triggers: vec![WebhookTrigger::EventStarting, WebhookTrigger::AppError],
+      template: Some(r#"{"text": "{title}"}"#.into()),
+      secret: Some("s3cret".into()),
+    },
+    mqtt: MqttSettings {
+      enabled: true,
+      host: "192.168.1.10".into(),
+      username: Some("helltime".into()),
+      password: Some("pw".into()),
+      ..MqttSettings::default()
+    },
+  };
+  store.set(prefs.clone()).unwrap();
+
+  assert_eq!(PrefsStore::load(path.clone()).get(), &prefs);
+  assert!(!path.with_extension("json.tmp").exists());
+}
+
+#[test]
+fn partial_and_corrupt_files_fall_back() {
+  let path = temp_path("prefs", "partial", "preferences.json");
+  std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+  std::fs::write(&path, r#"{"startMinimized": true}"#).unwrap();
+  let store = PrefsStore::load(path.clone());
+  assert!(store.get().start_minimized);
+  assert!(store.get().minimize_to_tray, "missing fields use defaults");
+  assert!(store.get().overlay_enabled && store.get().reminders_enabled);
+
+  std::fs::write(&path, b"{not json").unwrap();
+  assert_eq!(PrefsStore::load(path).get(), &Preferences::default());
+}
+
+#[test]
+fn hidden_events_filter_kinds() {
+  let prefs: Preferences = serde_json::from_str(r#"{"hiddenEvents": ["world_boss"]}"#).unwrap();
+  assert!(prefs.shows(EventKind::Helltide));
+  assert!(!prefs.shows(EventKind::WorldBoss));
+  assert_eq!(prefs.shown_kinds(), vec![EventKind::Helltide, EventKind::Legion]);
+  assert_eq!(Preferences::default().shown_kinds(), EventKind::ALL.to_vec());
+}
+
+#[test]
+fn reminder_times_are_validated() {
+  let defaults = ReminderTimes::default();
+  assert!(defaults.validate().is_ok());
+  assert_eq!(defaults.minutes_before(EventKind::Legion), [30, 10, 5]);
+
+  let off = ReminderTimes { legion: Vec::new(), ..ReminderTimes::default() };
+  assert!(off.validate().is_ok());
+  assert!(off.minutes_before(EventKind::Legion).is_empty());
+
+  assert!(ReminderTimes { helltide: vec![30, 20, 10, 5], ..ReminderTimes::default() }.validate().is_err());
+  assert!(ReminderTimes { world_boss: vec![0], ..ReminderTimes::default() }.validate().is_err());
+  assert!(ReminderTimes { helltide_end: Some(45), ..ReminderTimes::default() }.validate().is_err());
+  assert!(ReminderTimes { chest: Some(5), ..ReminderTimes::default() }.validate().is_ok());
+}
+
+#[test]
+fn network_settings_are_validated() {
+  let manual = |url: &str| NetworkSettings {
+    proxy_mode: ProxyMode::Manual,
+    proxy_url: Some(url.to_string()),
+    ..NetworkSettings::default()
+  };
+  assert!(NetworkSettings::default().validate().is_ok());
+  assert!(manual("http://user:pw@proxy.corp:3128").validate().is_ok());
+  assert!(manual("").validate().is_err());
+  assert!(manual("proxy.corp:3128").validate().is_err());
+  assert!(manual("https://").validate().is_err());
+
+  let missing_ca = NetworkSettings { ca_bundle: Some("/nonexistent/ca.pem".into()), ..NetworkSettings::default() };
+  assert!(missing_ca.validate().unwrap_err().contains("CA bundle"));
+
+  let header = |name: &str, value: &str| NetworkSettings {
+    extra_headers: vec![RequestHeader { name: name.to_string(), value: value.to_string() }],
+    ..NetworkSettings::default()
+  };
+  assert!(header("X-Api-Key", "abc").validate().is_ok());
+  assert!(header("X Api Key", "abc").validate().is_err());
+  assert!(header("X-Api-Key", "abc\r\nHost: evil").validate().is_err());
+  let token = NetworkSettings { api_token: Some("a\nb".to_string()), ..NetworkSettings::default() };
+  assert!(token.validate().is_err());
+}
+
+#[test]
+fn network_summary_redacts_secrets() {
+  let network = NetworkSettings {
+    proxy_mode: ProxyMode::Manual,
+    proxy_url: Some("http://user:pw@proxy.corp:3128".to_string()),
+    api_token: Some("token-123".to_string()),
+    extra_headers: vec![RequestHeader { name: "X-Api-Key".to_string(), value: "key-456".to_string() }],
+    ..NetworkSettings::default()
+  };
+  let summary = network.summary();
+  assert!(summary.contains("proxy.corp:3128"));
+  assert!(summary.contains("X-Api-Key"));
+  for secret in ["pw", "token-123", "key-456"] {
+    assert!(!summary.contains(secret), "{secret} leaked: {summary}");
+  }
+}
+
+#[test]
+fn update_keeps_other_fields() {
+  let path = temp_path("prefs", "update", "preferences.json");
+  let mut store = PrefsStore::load(path.clone());
+  store.update(|p| p.start_minimized = true).unwrap();
+  store.update(|p| p.window = Some(WindowGeometry { x: 1, y: 2, width: 3, height: 4, maximized: true })).unwrap();
+
+  let loaded = PrefsStore::load(path);
+  assert!(loaded.get().start_minimized);
+  assert!(loaded.get().window.unwrap().maximized);
+}
+
+const PRIMARY: MonitorRect = MonitorRect { x: 0, y: 0, width: 1920, height: 1040 };
+const LEFT: MonitorRect = MonitorRect { x: -1280, y: 0, width: 1280, height: 1024 };
+
+fn geom(x: i32, y: i32, width: u32, height: u32) -> WindowGeometry {
+  WindowGeometry { x, y, width, height, maximized: false }
+}
+
+#[test]
+fn keeps_visible_placement() {
+  assert_eq!(geom(100, 50, 380, 720).clamp_to(&[PRIMARY, LEFT]), geom(100, 50, 380, 720));
+  assert_eq!(geom(-900, 100, 380, 720).clamp_to(&[PRIMARY, LEFT]), geom(-900, 100, 380, 720));
+}
+
+#[test]
+fn recenters_when_monitor_is_gone() {
+  // Saved on the left monitor, which has since been unplugged.
+  assert_eq!(geom(-900, 100, 380, 720).clamp_to(&[PRIMARY]), geom(770, 160, 380, 720));
+}
+
+#[test]
+fn recenters_when_title_bar_is_off_screen() {
+  assert_eq!(geom(100, -600, 380, 720).clamp_to(&[PRIMARY]), geom(770, 160, 380, 720));
+  assert_eq!(geom(1900, 50, 380, 720).clamp_to(&[PRIMARY]), geom(770, 160, 380, 720));
+}
+
+#[test]
+fn shrinks_oversized_windows() {
+  assert_eq!(geom(0, 0, 4000, 3000).clamp_to(&[PRIMARY]), geom(0, 0, 1920, 1040));
+  assert_eq!(geom(5, 5, 10, 10).clamp_to(&[]), geom(5, 5, 10, 10));
+}
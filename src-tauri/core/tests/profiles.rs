@@ -0,0 +1,63 @@
+use helltime_core::prefs::{OverlayEngine, Preferences};
+use helltime_core::profiles::{Profile, ProfileStore};
+use helltime_core::schedule::EventKind;
+
+mod common;
+
+use common::temp_path;
+
+fn hardcore() -> Preferences {
+  Preferences {
+    hidden_events: vec![EventKind::Legion],
+    overlay_engine: OverlayEngine::Native,
+    ..Preferences::default()
+  }
+}
+
+#[test]
+fn captures_and_applies_only_profile_parts() {
+  let settings = serde_json::json!({ "soundEnabled": false });
+  let profile = Profile::capture("  Hardcore alt nights ", &hardcore(), Some(settings.clone())).unwrap();
+  assert_eq!(profile.name, "Hardcore alt nights");
+  assert_eq!(profile.ui_settings, Some(settings));
+
+  let current = Preferences { game_mode: false, ..Preferences::default() };
+  let applied = profile.apply(&current);
+  assert_eq!(applied.hidden_events, vec![EventKind::Legion]);
+  assert_eq!(applied.overlay_engine, OverlayEngine::Native);
+  assert!(!applied.game_mode, "other preferences are left alone");
+
+  assert!(Profile::capture("   ", &hardcore(), None).is_err());
+  assert!(Profile::capture(&"x".repeat(41), &hardcore(), None).is_err());
+}
+
+#[test]
+fn store_replaces_by_name_and_persists() {
+  let path = temp_path("profiles", "store", "profiles.json");
+  let mut store = ProfileStore::load(path.clone());
+  store.put(Profile::capture("Casual", &Preferences::default(), None).unwrap());
+  store.put(Profile::capture("Hardcore", &hardcore(), None).unwrap());
+  store.put(Profile::capture("Casual", &hardcore(), None).unwrap());
+  assert_eq!(store.active(), Some("Casual"));
+
+  let list = ProfileStore::load(path).list();
+  assert_eq!(list.profiles.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(), ["Casual", "Hardcore"]);
+  assert_eq!(list.profiles[0].hidden_events, vec![EventKind::Legion]);
+  assert_eq!(list.active.as_deref(), Some("Casual"));
+}
+
+#[test]
+fn activating_and_removing() {
+  let mut store = ProfileStore::default();
+  store.put(Profile::capture("Casual", &Preferences::default(), None).unwrap());
+  store.put(Profile::capture("Hardcore", &hardcore(), None).unwrap());
+
+  assert_eq!(store.activate("Casual").map(|p| p.name), Some("Casual".to_string()));
+  assert_eq!(store.activate("Missing"), None);
+  assert_eq!(store.active(), Some("Casual"));
+
+  assert!(store.remove("Casual"));
+  assert!(!store.remove("Casual"));
+  assert_eq!(store.active(), None);
+  assert_eq!(store.list().profiles.len(), 1);
+}
@@ -0,0 +1,182 @@
+//! Property tests for the timing math: reminders, clock skew and the Helltide cadence,
+//! each checked against many generated cases. Cases come from a seeded generator so
+//! failures reproduce; `HELLTIME_PROPERTY_CASES` raises the count and
+//! `HELLTIME_REMINDER_TOLERANCE_SECS` tightens how far from its lead time a reminder
+//! may fire (default: the reminder fire window).
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use helltime_core::cadence::HelltideCadence;
+use helltime_core::clock::ClockSkew;
+use helltime_core::helltide::{self, PREDICT_AHEAD};
+use helltime_core::reminders::{plan_for_next, FIRE_WINDOW};
+use helltime_core::schedule::{EventKind, ScheduleResponse};
+use serde_json::json;
+
+mod common;
+
+use common::utc;
+
+/// xorshift64*: small, deterministic, good enough to spread test inputs.
+struct Gen(u64);
+
+impl Gen {
+  fn new(seed: u64) -> Self {
+    Self(seed.max(1))
+  }
+
+  fn next(&mut self) -> u64 {
+    self.0 ^= self.0 >> 12;
+    self.0 ^= self.0 << 25;
+    self.0 ^= self.0 >> 27;
+    self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+  }
+
+  /// Uniform in `lo..=hi`.
+  fn range(&mut self, lo: i64, hi: i64) -> i64 {
+    lo + (self.next() % (hi - lo + 1) as u64) as i64
+  }
+}
+
+fn cases() -> usize {
+  std::env::var("HELLTIME_PROPERTY_CASES").ok().and_then(|v| v.parse().ok()).unwrap_or(2_000)
+}
+
+fn tolerance() -> Duration {
+  std::env::var("HELLTIME_REMINDER_TOLERANCE_SECS")
+    .ok()
+    .and_then(|v| v.parse().ok())
+    .map(Duration::seconds)
+    .unwrap_or(FIRE_WINDOW)
+}
+
+fn schedule_with(kind: EventKind, start: &str) -> ScheduleResponse {
+  let key = match kind {
+    EventKind::Helltide => "helltide",
+    EventKind::Legion => "legion",
+    EventKind::WorldBoss => "world_boss",
+  };
+  serde_json::from_value(json!({ key: [{ "id": 1, "startTime": start }] })).unwrap()
+}
+
+/// Interesting instants to build cases around: the 2025 EU and US DST switches,
+/// a year boundary and the last leap second (2016-12-31T23:59:60Z).
+fn anchors() -> Vec<DateTime<Utc>> {
+  vec![
+    utc("2025-03-30T01:00:00Z"),
+    utc("2025-10-26T01:00:00Z"),
+    utc("2025-03-09T10:00:00Z"),
+    utc("2025-11-02T09:00:00Z"),
+    utc("2025-12-31T23:59:59Z"),
+    utc("2016-12-31T23:59:60Z"),
+  ]
+}
+
+/// Every planned reminder is due only before its event starts, and only within the
+/// tolerance of its lead time, wherever the event falls.
+#[test]
+fn reminders_fire_before_the_start_within_tolerance() {
+  let mut g = Gen::new(0x5EED_0001);
+  let anchors = anchors();
+  let tolerance = tolerance();
+  for _ in 0..cases() {
+    let kind = EventKind::ALL[g.range(0, 2) as usize];
+    let anchor = anchors[g.range(0, anchors.len() as i64 - 1) as usize];
+    let start = anchor + Duration::seconds(g.range(-3 * 3600, 3 * 3600));
+    let leads: Vec<i64> = (0..g.range(1, 3)).map(|_| g.range(1, 60)).collect();
+    let now = start - Duration::seconds(g.range(1, 2 * 3600));
+
+    let schedule = schedule_with(kind, &start.to_rfc3339());
+    for reminder in plan_for_next(&schedule, kind, &leads, now) {
+      assert_eq!(reminder.event.start, start);
+      let lead = Duration::minutes(reminder.minutes_before);
+      // Walk the fire window in steps that include both edges.
+      for offset in (0..=FIRE_WINDOW.num_seconds()).step_by(5) {
+        let at = reminder.fire_at + Duration::seconds(offset);
+        if !reminder.is_due(at) {
+          continue;
+        }
+        assert!(at < start, "{kind:?} reminder {lead} before {start} due at {at}, after the start");
+        let early_by = (start - at) - lead;
+        assert!(early_by.abs() <= tolerance, "due at {at}, {early_by} off its lead time {lead} (start {start})");
+      }
+    }
+  }
+}
+
+/// Reminders of one event are never due twice: their windows do not outlast the next one.
+#[test]
+fn planned_reminders_are_ordered_and_due_once() {
+  let mut g = Gen::new(0x5EED_0002);
+  for _ in 0..cases() {
+    let start = utc("2025-10-26T01:00:00Z") + Duration::minutes(g.range(-600, 600));
+    let leads: Vec<i64> = (0..3).map(|_| g.range(1, 60)).collect();
+    let schedule = schedule_with(EventKind::Helltide, &start.to_rfc3339());
+    let plan = plan_for_next(&schedule, EventKind::Helltide, &leads, start - Duration::hours(2));
+    assert_eq!(plan.len(), leads.len());
+    assert!(plan.windows(2).all(|pair| pair[0].fire_at <= pair[1].fire_at));
+    for reminder in &plan {
+      assert!(!reminder.is_due(reminder.fire_at - Duration::seconds(1)));
+      assert!(!reminder.is_due(reminder.fire_at + FIRE_WINDOW + Duration::seconds(1)));
+    }
+  }
+}
+
+/// The skew estimate stays within what the `Date` header's one-second resolution,
+/// the round trip and the significance threshold allow.
+#[test]
+fn clock_skew_estimate_is_bounded_by_round_trip_and_resolution() {
+  let mut g = Gen::new(0x5EED_0003);
+  let base = utc("2025-10-16T08:00:00Z");
+  for _ in 0..cases() {
+    let true_offset = Duration::milliseconds(g.range(-600_000, 600_000));
+    let mut skew = ClockSkew::default();
+    let mut max_rtt = Duration::zero();
+    for sample in 0..g.range(1, 8) {
+      let sent = base + Duration::seconds(sample * 60) + Duration::milliseconds(g.range(0, 999));
+      let rtt = Duration::milliseconds(g.range(0, 4_000));
+      max_rtt = max_rtt.max(rtt);
+      // The server stamps its reply somewhere during the round trip, in whole seconds.
+      let stamped = sent + Duration::milliseconds(g.range(0, rtt.num_milliseconds())) + true_offset;
+      let header = Utc.timestamp_opt(stamped.timestamp(), 0).unwrap();
+      skew.record(header, sent, sent + rtt);
+    }
+    let error = (Duration::milliseconds(skew.offset_ms()) - true_offset).abs();
+    let bound = max_rtt / 2 + Duration::milliseconds(2_500);
+    assert!(error <= bound, "offset {} vs true {true_offset}: off by {error}, bound {bound}", skew.offset_ms());
+  }
+}
+
+/// Predictions continue the cadence from the last known Helltide without drifting,
+/// and always leave `PREDICT_AHEAD` upcoming ones.
+#[test]
+fn extrapolated_helltides_follow_the_cadence_without_drift() {
+  let mut g = Gen::new(0x5EED_0004);
+  for _ in 0..cases() {
+    let cadence = HelltideCadence {
+      interval_minutes: g.range(30, 180),
+      duration_minutes: g.range(10, 60),
+      chest_offset_minutes: g.range(0, 30),
+      chest_interval_minutes: g.range(15, 60),
+    };
+    let last = utc("2025-10-26T01:00:00Z") + Duration::minutes(g.range(-24 * 60, 24 * 60));
+    let now = last + Duration::minutes(g.range(-6 * 60, 24 * 60));
+    let mut schedule = schedule_with(EventKind::Helltide, &last.to_rfc3339());
+    helltide::extrapolate(&mut schedule, &cadence, now);
+
+    let events = schedule.events(EventKind::Helltide);
+    assert!(events.iter().filter(|h| h.start > now).count() >= PREDICT_AHEAD);
+    for predicted in events.iter().filter(|h| h.predicted) {
+      let since = predicted.start - last;
+      assert!(predicted.start > now);
+      assert_eq!(since.num_seconds() % cadence.interval().num_seconds(), 0, "{} drifted from {last}", predicted.start);
+    }
+    for h in &events {
+      let end = h.end_or(cadence.duration());
+      assert!(helltide::is_active(h, &cadence, h.start));
+      assert!(!helltide::is_active(h, &cadence, end));
+      for reset in helltide::resets_of(h, &cadence) {
+        assert!(reset.at >= h.start && reset.at < end);
+      }
+    }
+  }
+}
@@ -0,0 +1,113 @@
+use chrono::Duration;
+use helltime_core::push::{
+  delivery, request, Delivery, NtfySettings, PushQueue, PushSettings, PushoverSettings, Sink, MAX_QUEUED, PUSHOVER_URL,
+};
+use serde_json::json;
+
+mod common;
+
+use common::{temp_path, utc};
+
+fn settings() -> PushSettings {
+  PushSettings {
+    ntfy: NtfySettings { enabled: true, server: None, topic: "helltime-3f9a".into(), token: None },
+    pushover: PushoverSettings {
+      enabled: true,
+      app_token: "azGDORePK8gMaC0QOYAMyEEuzJnyUi".into(),
+      user_key: "uQiRzpo4DXghDmr9QzzfQu27cmVRsG".into(),
+    },
+  }
+}
+
+#[test]
+fn settings_are_validated_only_when_enabled() {
+  assert!(settings().validate().is_ok());
+  assert!(PushSettings::default().validate().is_ok());
+
+  let mut bad_topic = settings();
+  bad_topic.ntfy.topic = "my topic/x".into();
+  assert!(bad_topic.validate().is_err());
+  bad_topic.ntfy.enabled = false;
+  assert!(bad_topic.validate().is_ok());
+
+  let mut bad_key = settings();
+  bad_key.pushover.user_key = "short".into();
+  assert!(bad_key.validate().is_err());
+
+  let mut bad_server = settings();
+  bad_server.ntfy.server = Some("ntfy.example.com".into());
+  assert!(bad_server.validate().is_err());
+}
+
+#[test]
+fn requests_use_each_services_json_api() {
+  let mut queue = PushQueue::default();
+  let now = utc("2025-10-16T08:00:00Z");
+  queue.push(&[Sink::Ntfy, Sink::Pushover], "Legion", "in 5 min", now);
+  let (due, _) = queue.due(&[Sink::Ntfy, Sink::Pushover], now);
+
+  let mut settings = settings();
+  settings.ntfy.server = Some("https://ntfy.example.com/".into());
+  settings.ntfy.token = Some("tk_secret".into());
+  let ntfy = request(&settings, &due[0]);
+  assert_eq!(ntfy.url, "https://ntfy.example.com");
+  assert_eq!(ntfy.bearer.as_deref(), Some("tk_secret"));
+  assert_eq!(ntfy.body["topic"], "helltime-3f9a");
+  assert_eq!(ntfy.body["title"], "Legion");
+  assert_eq!(ntfy.body["message"], "in 5 min");
+
+  let pushover = request(&settings, &due[1]);
+  assert_eq!(pushover.url, PUSHOVER_URL);
+  assert_eq!(pushover.bearer, None);
+  assert_eq!(
+    pushover.body,
+    json!({
+      "token": "azGDORePK8gMaC0QOYAMyEEuzJnyUi",
+      "user": "uQiRzpo4DXghDmr9QzzfQu27cmVRsG",
+      "title": "Legion",
+      "message": "in 5 min",
+    })
+  );
+}
+
+#[test]
+fn statuses_decide_between_retry_and_drop() {
+  assert_eq!(delivery(200), Delivery::Sent);
+  assert_eq!(delivery(429), Delivery::Retry);
+  assert_eq!(delivery(503), Delivery::Retry);
+  assert_eq!(delivery(400), Delivery::Rejected);
+  assert_eq!(delivery(401), Delivery::Rejected);
+}
+
+#[test]
+fn old_and_disabled_messages_are_dropped() {
+  let mut queue = PushQueue::default();
+  let now = utc("2025-10-16T08:00:00Z");
+  queue.push(&[Sink::Ntfy], "Helltide", "in 10 min", now);
+  queue.push(&[Sink::Ntfy, Sink::Pushover], "Legion", "in 5 min", now + Duration::minutes(6));
+
+  let (due, dropped) = queue.due(&[Sink::Ntfy], now + Duration::minutes(12));
+  assert_eq!(dropped, 2, "the first is too old, the Pushover one disabled");
+  assert_eq!(due.len(), 1);
+  assert_eq!(due[0].title, "Legion");
+
+  queue.remove(due[0].id);
+  assert!(queue.is_empty());
+}
+
+#[test]
+fn queue_is_bounded_and_survives_a_restart() {
+  let path = temp_path("push", "restart", "push-queue.json");
+  let now = utc("2025-10-16T08:00:00Z");
+  let mut queue = PushQueue::load(path.clone());
+  for i in 0..MAX_QUEUED + 3 {
+    queue.push(&[Sink::Pushover], &format!("#{i}"), "", now);
+  }
+  assert_eq!(queue.len(), MAX_QUEUED);
+  queue.save();
+
+  let mut loaded = PushQueue::load(path);
+  let (due, _) = loaded.due(&[Sink::Pushover], now);
+  assert_eq!(due.len(), MAX_QUEUED);
+  assert_eq!(due[0].title, "#3", "the oldest were dropped");
+}
@@ -0,0 +1,75 @@
+use helltime_core::fetch::{fetch_schedule, FetchError, HttpFetcher, HttpResponse, RetryPolicy};
+use helltime_core::rate_limit::{RateLimitedFetcher, TokenBucket, MAX_REQUESTS_PER_MINUTE};
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const URL: &str = "https://helltides.com/api/schedule";
+
+/// Answers every request with a server error and counts them.
+#[derive(Default)]
+struct FailingFetcher {
+  requests: Arc<AtomicUsize>,
+}
+
+impl HttpFetcher for FailingFetcher {
+  fn get(&self, _url: &str) -> impl Future<Output = Result<HttpResponse, FetchError>> + Send {
+    self.requests.fetch_add(1, Ordering::SeqCst);
+    async { Ok(HttpResponse { status: 503, date: None, headers: Vec::new(), body: Vec::new() }) }
+  }
+}
+
+#[test]
+fn allows_a_burst_up_to_the_rate_then_refuses() {
+  let mut bucket = TokenBucket::new(3);
+  let now = Instant::now();
+  for _ in 0..3 {
+    assert_eq!(bucket.try_acquire(now), Ok(()));
+  }
+  assert_eq!(bucket.try_acquire(now), Err(Duration::from_secs(20)));
+  assert_eq!(bucket.status(now).rejected, 1);
+}
+
+#[test]
+fn refills_over_time() {
+  let mut bucket = TokenBucket::new(6);
+  let start = Instant::now();
+  for _ in 0..6 {
+    bucket.try_acquire(start).unwrap();
+  }
+  assert!(bucket.try_acquire(start + Duration::from_secs(5)).is_err());
+  assert_eq!(bucket.try_acquire(start + Duration::from_secs(10)), Ok(()));
+
+  let status = bucket.status(start + Duration::from_secs(600));
+  assert_eq!(status.available, 6, "never holds more than one minute's worth");
+  assert_eq!(status.wait_ms, 0);
+}
+
+#[test]
+fn rate_is_clamped_and_shrinking_caps_the_bucket() {
+  let mut bucket = TokenBucket::new(0);
+  let now = Instant::now();
+  assert_eq!(bucket.status(now).per_minute, 1);
+
+  bucket.set_rate(1000);
+  assert_eq!(bucket.status(now).per_minute, MAX_REQUESTS_PER_MINUTE);
+  assert_eq!(bucket.status(now).available, 1, "raising the rate does not grant a burst");
+
+  let mut bucket = TokenBucket::new(10);
+  bucket.set_rate(2);
+  assert_eq!(bucket.status(now).available, 2);
+}
+
+#[tokio::test]
+async fn retries_count_against_the_limit() {
+  let inner = FailingFetcher::default();
+  let requests = inner.requests.clone();
+  let bucket = Mutex::new(TokenBucket::new(2));
+  let fetcher = RateLimitedFetcher::new(inner, &bucket);
+
+  let err = fetch_schedule(&fetcher, URL, RetryPolicy::default()).await.unwrap_err();
+  assert!(matches!(err, FetchError::RateLimited(_)), "{err:?}");
+  assert!(!err.is_retryable());
+  assert_eq!(requests.load(Ordering::SeqCst), 2, "the limited attempt never reached the network");
+}
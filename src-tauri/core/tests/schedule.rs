@@ -0,0 +1,94 @@
+use chrono::{TimeZone, Utc};
+use helltime_core::boss_rotation;
+use helltime_core::schedule::{parse_event_time, EventKind, ScheduleResponse, Titled};
+
+mod common;
+
+use common::{fixture_named, utc};
+
+#[test]
+fn parses_all_categories_sorted() {
+  let schedule = fixture_named("schedule.json");
+  let helltides = schedule.events(EventKind::Helltide);
+  assert_eq!(helltides.len(), 3);
+  assert_eq!(helltides[0].start, utc("2025-10-16T08:00:00Z"));
+  assert!(helltides.windows(2).all(|w| w[0].start < w[1].start));
+
+  assert_eq!(schedule.events(EventKind::Legion).len(), 3);
+  let bosses = schedule.events(EventKind::WorldBoss);
+  assert_eq!(bosses[0].boss.as_deref(), Some("Ashava"));
+  assert_eq!(bosses[1].boss, None, "empty boss names are treated as missing");
+}
+
+#[test]
+fn missing_sections_default_to_empty() {
+  let schedule = fixture_named("schedule_partial.json");
+  assert!(schedule.world_boss.is_empty());
+  assert!(schedule.legion.is_empty());
+}
+
+#[test]
+fn start_time_falls_back_to_timestamp() {
+  let schedule = fixture_named("schedule_partial.json");
+  let events = schedule.events(EventKind::Helltide);
+  let ids: Vec<i64> = events.iter().map(|e| e.id).collect();
+  // id 3 has neither field and is dropped; id 2 sends milliseconds.
+  assert_eq!(ids, vec![1, 2, 4]);
+  assert_eq!(events[0].start, utc("2025-10-16T08:00:00Z"));
+  assert_eq!(events[1].start, utc("2025-10-16T09:00:00Z"));
+}
+
+#[test]
+fn iso_start_time_wins_over_timestamp() {
+  let raw = serde_json::json!({ "timestamp": 0, "startTime": "2025-10-16T10:15:00+02:00" });
+  assert_eq!(parse_event_time(&raw), Some(Utc.with_ymd_and_hms(2025, 10, 16, 8, 15, 0).unwrap()));
+}
+
+#[test]
+fn next_event_is_strictly_after_now() {
+  let schedule = fixture_named("schedule.json");
+  let next = schedule.next_event(EventKind::Helltide, utc("2025-10-16T08:00:00Z")).unwrap();
+  assert_eq!(next.id, 9002);
+  assert!(schedule.next_event(EventKind::Helltide, utc("2025-10-16T11:00:00Z")).is_none());
+}
+
+#[test]
+fn only_keeps_the_given_categories() {
+  let schedule = fixture_named("schedule.json");
+  let shown = schedule.only(&[EventKind::Helltide, EventKind::WorldBoss]);
+  assert!(shown.legion.is_empty());
+  assert_eq!(shown.helltide, schedule.helltide);
+  assert_eq!(shown.world_boss, schedule.world_boss);
+  assert!(schedule.only(&[]).raw(EventKind::Helltide).is_empty());
+}
+
+#[test]
+fn zone_names_are_captured() {
+  let schedule = fixture_named("schedule.json");
+  let legion = schedule.events(EventKind::Legion);
+  assert_eq!(legion[0].zone.as_deref(), Some("Kehjistan"));
+  assert_eq!(legion[0].title(), "Legion in Kehjistan");
+  assert_eq!(legion[1].zone, None);
+  assert_eq!(schedule.events(EventKind::WorldBoss)[0].title(), "World Boss Ashava in Kehjistan");
+
+  let raw = serde_json::json!({ "startTime": "2025-10-16T08:00:00Z", "location": " Scosglen " });
+  let schedule: ScheduleResponse = serde_json::from_value(serde_json::json!({ "helltide": [raw] })).unwrap();
+  assert_eq!(schedule.events(EventKind::Helltide)[0].zone.as_deref(), Some("Scosglen"));
+}
+
+#[test]
+fn predicts_missing_boss_from_rotation() {
+  let mut schedule = fixture_named("schedule.json");
+  boss_rotation::annotate(&mut schedule, &[]);
+  let bosses = schedule.events(EventKind::WorldBoss);
+  assert_eq!(bosses[1].boss.as_deref(), Some("Wandering Death"));
+  assert!(bosses[1].boss_predicted);
+  assert!(!bosses[0].boss_predicted);
+}
+
+#[test]
+fn prediction_prefers_observed_successors() {
+  let history: Vec<String> = ["Ashava", "Avarice", "Ashava", "Avarice", "Ashava"].iter().map(|s| s.to_string()).collect();
+  assert_eq!(boss_rotation::predict_next(&history).as_deref(), Some("Avarice"));
+  assert_eq!(boss_rotation::predict_next(&[]), None);
+}
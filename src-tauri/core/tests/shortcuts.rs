@@ -0,0 +1,48 @@
+use helltime_core::shortcuts::{self, Key, Shortcut, ShortcutAction, ShortcutMap};
+
+#[test]
+fn parses_and_formats_shortcuts() {
+  let s = Shortcut::parse(" ctrl + alt + n ").unwrap();
+  assert_eq!(s, Shortcut { ctrl: true, alt: true, shift: false, win: false, key: Key::Letter('N') });
+  assert_eq!(s.to_string(), "Ctrl+Alt+N");
+  assert_eq!((s.win32_modifiers(), s.virtual_key()), (0x3, 0x4E));
+
+  let s = Shortcut::parse("Win+Shift+F12").unwrap();
+  assert_eq!(s.to_string(), "Shift+Win+F12");
+  assert_eq!((s.win32_modifiers(), s.virtual_key()), (0xC, 0x7B));
+  assert_eq!(Shortcut::parse("Alt+7").unwrap().virtual_key(), 0x37);
+}
+
+#[test]
+fn rejects_unusable_shortcuts() {
+  for bad in ["N", "Shift+N", "Ctrl+Alt", "Ctrl+Ctrl+N", "Ctrl+N+M", "Ctrl+F25", "Ctrl+Esc", ""] {
+    assert!(Shortcut::parse(bad).is_err(), "{bad}");
+  }
+}
+
+#[test]
+fn missing_entries_use_defaults_and_empty_ones_unbind() {
+  let map = ShortcutMap::from([(ShortcutAction::ToggleWindow, String::new()), (ShortcutAction::ToggleOverlay, "Ctrl+Alt+V".into())]);
+  let bound = |action| shortcuts::binding(&map, action).map(|s| s.to_string());
+  assert_eq!(bound(ShortcutAction::ToggleWindow), None);
+  assert_eq!(bound(ShortcutAction::ToggleOverlay).as_deref(), Some("Ctrl+Alt+V"));
+  assert_eq!(bound(ShortcutAction::PeekNextEvent).as_deref(), Some("Ctrl+Alt+N"));
+  assert_eq!(shortcuts::bindings(&map).len(), ShortcutAction::ALL.len());
+}
+
+#[test]
+fn validation_catches_conflicts_and_typos() {
+  assert!(shortcuts::validate(&ShortcutMap::new()).is_ok());
+  let clash = ShortcutMap::from([(ShortcutAction::ToggleOverlay, "alt+ctrl+n".into())]);
+  assert!(shortcuts::validate(&clash).unwrap_err().contains("Ctrl+Alt+N"));
+  let typo = ShortcutMap::from([(ShortcutAction::ToggleOverlay, "Ctrl+Alt+Nope".into())]);
+  assert!(shortcuts::validate(&typo).is_err());
+}
+
+#[test]
+fn hotkey_ids_round_trip() {
+  for action in ShortcutAction::ALL {
+    assert_eq!(ShortcutAction::from_hotkey_id(action.hotkey_id()), Some(action));
+  }
+  assert_eq!(ShortcutAction::from_hotkey_id(0), None);
+}
@@ -0,0 +1,67 @@
+use helltime_core::error::AppError;
+use helltime_core::prefs::{Preferences, ProxyMode, WindowGeometry};
+use helltime_core::sync::{self, SyncDocument};
+use std::path::PathBuf;
+
+mod common;
+
+use common::{temp_dir, utc};
+
+fn local() -> Preferences {
+  let mut prefs = Preferences::default();
+  prefs.network.proxy_mode = ProxyMode::Direct;
+  prefs.window = Some(WindowGeometry { x: 10, y: 20, width: 380, height: 720, maximized: false });
+  prefs.onboarding_completed = true;
+  prefs.sync_dir = Some(PathBuf::from("/mnt/share"));
+  prefs
+}
+
+#[test]
+fn device_specific_preferences_stay_local() {
+  let shared = sync::shared(&local());
+  assert_eq!(shared.window, None);
+  assert_eq!(shared.network, Default::default());
+  assert!(!shared.onboarding_completed);
+  assert_eq!(shared.sync_dir, None);
+
+  let remote = Preferences { game_mode: false, minimize_to_tray: false, ..Preferences::default() };
+  let merged = sync::apply(&local(), &remote);
+  assert!(!merged.game_mode && !merged.minimize_to_tray);
+  assert_eq!(merged.window, local().window);
+  assert_eq!(merged.network, local().network);
+  assert_eq!(merged.sync_dir, local().sync_dir);
+  assert!(merged.onboarding_completed);
+}
+
+#[test]
+fn last_write_wins() {
+  let doc = SyncDocument {
+    updated_at: utc("2025-10-16T08:00:00Z"),
+    device: "desk".into(),
+    preferences: Preferences::default(),
+    ui_settings: None,
+  };
+  assert!(sync::is_newer(&doc, None));
+  assert!(sync::is_newer(&doc, Some(utc("2025-10-16T07:59:59Z"))));
+  assert!(!sync::is_newer(&doc, Some(utc("2025-10-16T08:00:00Z"))));
+}
+
+#[test]
+fn round_trips_through_the_folder() {
+  let dir = temp_dir("sync", "roundtrip");
+  assert_eq!(sync::read(&dir).unwrap(), None);
+
+  let doc = SyncDocument {
+    updated_at: utc("2025-10-16T08:00:00Z"),
+    device: "laptop".into(),
+    preferences: sync::shared(&local()),
+    ui_settings: Some(serde_json::json!({ "soundEnabled": false })),
+  };
+  sync::write(&dir, &doc).unwrap();
+  assert_eq!(sync::read(&dir).unwrap(), Some(doc.clone()));
+  assert!(!dir.join(sync::FILE_NAME).with_extension("json.tmp").exists());
+
+  std::fs::write(dir.join(sync::FILE_NAME), "{").unwrap();
+  assert!(matches!(sync::read(&dir), Err(AppError::SettingsCorrupt(_))));
+  assert!(matches!(sync::write(&dir.join("missing"), &doc), Err(AppError::InvalidSettings(_))));
+}
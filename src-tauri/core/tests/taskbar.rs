@@ -0,0 +1,30 @@
+use helltime_core::schedule::EventKind;
+use helltime_core::taskbar::{progress, TaskbarProgress};
+
+mod common;
+
+use common::{fixture, utc};
+
+#[test]
+fn measures_between_consecutive_starts() {
+  let p = progress(&fixture(), EventKind::Helltide, utc("2025-10-16T08:45:00Z")).unwrap();
+  assert_eq!(p, TaskbarProgress { percent: 75, active: false });
+}
+
+#[test]
+fn active_shortly_after_start() {
+  let p = progress(&fixture(), EventKind::Helltide, utc("2025-10-16T09:02:00Z")).unwrap();
+  assert!(p.active);
+  assert_eq!(p.percent, 3);
+}
+
+#[test]
+fn assumes_hourly_cycle_before_first_event() {
+  let p = progress(&fixture(), EventKind::Helltide, utc("2025-10-16T07:30:00Z")).unwrap();
+  assert_eq!(p, TaskbarProgress { percent: 50, active: false });
+}
+
+#[test]
+fn none_after_last_event() {
+  assert!(progress(&fixture(), EventKind::Helltide, utc("2025-10-16T12:00:00Z")).is_none());
+}
@@ -0,0 +1,74 @@
+use helltime_core::telemetry::{self, Feature, TelemetryQueue, TelemetrySettings, MAX_QUEUED_BATCHES};
+
+mod common;
+
+use common::temp_path;
+
+#[test]
+fn only_allowed_when_opted_in_and_not_switched_off() {
+  let on = TelemetrySettings { enabled: true, endpoint: None };
+  assert!(telemetry::allowed(&on, None, false));
+  assert!(!telemetry::allowed(&TelemetrySettings::default(), None, false));
+  assert!(!telemetry::allowed(&on, Some("1"), false));
+  assert!(!telemetry::allowed(&on, None, true));
+}
+
+#[test]
+fn endpoint_must_be_http() {
+  let with = |url: &str| TelemetrySettings { enabled: true, endpoint: Some(url.into()) };
+  assert!(with("https://telemetry.example.com/v1").validate().is_ok());
+  assert!(with("ftp://telemetry.example.com").validate().is_err());
+  assert!(TelemetrySettings::default().validate().is_ok());
+}
+
+#[test]
+fn batches_counts_and_previews_the_running_batch() {
+  let mut queue = TelemetryQueue::default();
+  assert!(queue.payload("1.0.0").is_none());
+  queue.record_feature(Feature::Overlay);
+  queue.record_feature(Feature::Overlay);
+  queue.record_error("network");
+
+  // Not cut yet: only the preview shows it.
+  assert!(queue.payload("1.0.0").is_none());
+  let preview = serde_json::to_value(queue.preview("1.0.0")).unwrap();
+  assert_eq!(preview["appVersion"], "1.0.0");
+  assert_eq!(preview["batches"][0]["features"]["overlay"], 2);
+  assert_eq!(preview["batches"][0]["errors"]["network"], 1);
+
+  queue.cut();
+  queue.cut();
+  let payload = queue.payload("1.0.0").unwrap();
+  assert_eq!(payload.batches.len(), 1, "empty batches are not queued");
+  queue.sent(payload.batches.len());
+  assert!(queue.payload("1.0.0").is_none());
+}
+
+#[test]
+fn drops_the_oldest_batches_when_offline_too_long() {
+  let mut queue = TelemetryQueue::default();
+  for _ in 0..MAX_QUEUED_BATCHES + 3 {
+    queue.record_feature(Feature::Reminder);
+    queue.cut();
+  }
+  assert_eq!(queue.payload("1.0.0").unwrap().batches.len(), MAX_QUEUED_BATCHES);
+}
+
+#[test]
+fn queue_and_retirement_survive_a_restart() {
+  let path = temp_path("telemetry", "persist", "telemetry.json");
+  let mut queue = TelemetryQueue::load(path.clone());
+  queue.record_feature(Feature::CalendarExport);
+  queue.cut();
+  queue.record_error("parse");
+  queue.save();
+
+  let mut loaded = TelemetryQueue::load(path.clone());
+  assert_eq!(loaded.preview("1.0.0").batches.len(), 2);
+
+  loaded.retire();
+  loaded.save();
+  let loaded = TelemetryQueue::load(path);
+  assert!(loaded.retired());
+  assert!(loaded.preview("1.0.0").batches.is_empty());
+}
@@ -0,0 +1,84 @@
+use chrono::{FixedOffset, Utc};
+use helltime_core::i18n::Locale;
+use helltime_core::schedule::EventKind;
+use helltime_core::toast_template::{self, RenderedToast, ToastEvent, ToastTemplates};
+
+mod common;
+
+use common::utc;
+
+fn templates(title: &str, body: &str) -> ToastTemplates {
+  ToastTemplates { title: title.into(), body: body.into() }
+}
+
+fn legion(zone: Option<&str>) -> ToastEvent {
+  ToastEvent {
+    kind: EventKind::Legion,
+    start: utc("2025-10-16T09:00:00Z"),
+    boss: None,
+    boss_predicted: false,
+    zone: zone.map(str::to_string),
+  }
+}
+
+#[test]
+fn renders_placeholders_against_the_event() {
+  let now = utc("2025-10-16T08:47:30Z");
+  let cest = FixedOffset::east_opt(2 * 3600).unwrap();
+  let boss = ToastEvent {
+    kind: EventKind::WorldBoss,
+    boss: Some("Ashava".into()),
+    boss_predicted: true,
+    zone: Some("Dry Steppes".into()),
+    ..legion(None)
+  };
+
+  let rendered = templates("{type}: {boss}", "in {minutes}m ({countdown}) at {time} — {zone}").render(&boss, Locale::En, now, &cest);
+  assert_eq!(
+    rendered,
+    RenderedToast { title: "World Boss: Ashava?".into(), body: "in 13m (12:30) at 11:00 — Dry Steppes".into() }
+  );
+
+  let rendered = ToastTemplates::default().render(&boss, Locale::De, now, &Utc);
+  assert_eq!(rendered.title, "Weltboss Ashava? in Dry Steppes");
+  assert_eq!(rendered.body, "12:30");
+}
+
+#[test]
+fn missing_values_leave_no_dangling_separators() {
+  let now = utc("2025-10-16T08:50:00Z");
+  let rendered = templates("{type} — {zone}", "{zone} · {minutes}m   left").render(&legion(None), Locale::En, now, &Utc);
+  assert_eq!(rendered, RenderedToast { title: "Legion".into(), body: "10m left".into() });
+}
+
+#[test]
+fn braces_can_be_escaped() {
+  let rendered = templates("{{{type}}}", "}}").render(&legion(None), Locale::En, utc("2025-10-16T08:50:00Z"), &Utc);
+  assert_eq!(rendered, RenderedToast { title: "{Legion}".into(), body: "}".into() });
+}
+
+#[test]
+fn rejects_invalid_templates() {
+  assert!(toast_template::validate("{type} in {minutes}m").is_ok());
+  assert!(toast_template::validate("{ zone }").is_ok());
+  assert!(toast_template::validate("{typ}").unwrap_err().contains("unknown placeholder {typ}"));
+  assert!(toast_template::validate("{type").unwrap_err().contains("unclosed"));
+  assert!(toast_template::validate("{ty{pe}").unwrap_err().contains("unclosed"));
+  assert!(toast_template::validate("type}").unwrap_err().contains("unmatched"));
+  assert!(toast_template::validate(&"x".repeat(toast_template::MAX_LEN + 1)).is_err());
+
+  let err = templates("{title}", "{nope}").validate().unwrap_err();
+  assert!(err.starts_with("toast text:"), "{err}");
+}
+
+#[test]
+fn unvalidated_templates_fall_back_to_the_default() {
+  let rendered = templates("{nope}", "{countdown").render(&legion(Some("Kehjistan")), Locale::En, utc("2025-10-16T08:59:00Z"), &Utc);
+  assert_eq!(rendered, RenderedToast { title: "Legion in Kehjistan".into(), body: "01:00".into() });
+}
+
+#[test]
+fn templates_deserialize_with_defaults() {
+  let parsed: ToastTemplates = serde_json::from_str(r#"{ "body": "{minutes}m" }"#).unwrap();
+  assert_eq!(parsed, templates("{title}", "{minutes}m"));
+}
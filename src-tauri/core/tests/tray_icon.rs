@@ -0,0 +1,57 @@
+use helltime_core::cadence::Cadence;
+use helltime_core::prefs::TrayIconStyle;
+use helltime_core::schedule::{EventKind, ScheduleResponse};
+use helltime_core::tray_icon::{light_up, pick, should_pulse, TaskbarTheme, TrayIcon};
+
+mod common;
+
+use common::utc;
+
+#[test]
+fn auto_and_monochrome_follow_the_taskbar() {
+  assert_eq!(pick(TrayIconStyle::Auto, TaskbarTheme::Light), TrayIcon::Light);
+  assert_eq!(pick(TrayIconStyle::Auto, TaskbarTheme::Dark), TrayIcon::Dark);
+  assert_eq!(pick(TrayIconStyle::Monochrome, TaskbarTheme::Light), TrayIcon::MonochromeLight);
+  assert_eq!(pick(TrayIconStyle::Monochrome, TaskbarTheme::Dark), TrayIcon::MonochromeDark);
+}
+
+#[test]
+fn manual_override_ignores_the_taskbar() {
+  for theme in [TaskbarTheme::Light, TaskbarTheme::Dark] {
+    assert_eq!(pick(TrayIconStyle::Light, theme), TrayIcon::Light);
+    assert_eq!(pick(TrayIconStyle::Dark, theme), TrayIcon::Dark);
+  }
+}
+
+fn schedule() -> ScheduleResponse {
+  serde_json::from_value(serde_json::json!({
+    "helltide": [{ "id": 1, "startTime": "2025-10-16T08:00:00Z" }],
+    "legion": [{ "id": 2, "startTime": "2025-10-16T09:30:00Z" }],
+  }))
+  .unwrap()
+}
+
+#[test]
+fn pulses_before_shown_events_and_during_helltides() {
+  let cadence = Cadence::bundled().helltide;
+  let kinds = [EventKind::Helltide, EventKind::Legion];
+  let pulse = |kinds: &[EventKind], now| should_pulse(&schedule(), kinds, &cadence, utc(now));
+
+  assert!(!pulse(&kinds, "2025-10-16T07:57:59Z"));
+  assert!(pulse(&kinds, "2025-10-16T07:58:00Z"));
+  assert!(pulse(&kinds, "2025-10-16T08:30:00Z"), "Helltide running");
+  assert!(!pulse(&kinds, "2025-10-16T09:20:00Z"));
+  assert!(pulse(&kinds, "2025-10-16T09:29:00Z"));
+  assert!(!pulse(&[EventKind::Helltide], "2025-10-16T09:29:00Z"), "hidden categories do not count");
+}
+
+#[test]
+fn lit_frame_glows_or_fades() {
+  let mut colored = vec![235, 90, 30, 255, 240, 240, 240, 255, 0, 0, 0, 0];
+  light_up(TrayIcon::Dark, &mut colored);
+  assert_eq!(colored, [255, 200, 60, 255, 235, 90, 30, 255, 0, 0, 0, 0]);
+
+  let mut mono = vec![240, 240, 240, 255];
+  light_up(TrayIcon::MonochromeDark, &mut mono);
+  assert_eq!(mono, [240, 240, 240, 102]);
+}
@@ -0,0 +1,56 @@
+use helltime_core::update::{newest_update, parse_version, GithubRelease};
+
+fn release(tag: &str, prerelease: bool, draft: bool) -> GithubRelease {
+  GithubRelease {
+    tag_name: tag.to_string(),
+    html_url: format!("https://github.com/Trissilein/helltime/releases/tag/{tag}"),
+    body: Some(format!("notes for {tag}")),
+    prerelease,
+    draft,
+  }
+}
+
+#[test]
+fn parses_tags_with_and_without_prefix() {
+  assert_eq!(parse_version("v1.2.3").unwrap().to_string(), "1.2.3");
+  assert_eq!(parse_version("0.2.0-beta.1").unwrap().pre.as_str(), "beta.1");
+  assert!(parse_version("nightly").is_none());
+}
+
+#[test]
+fn picks_newest_stable_release() {
+  let releases = vec![
+    release("v0.1.0", false, false),
+    release("v0.3.0", false, false),
+    release("v0.10.0-rc.1", true, false),
+    release("v0.2.5", false, false),
+    release("v0.9.0", false, true),
+  ];
+  let update = newest_update("0.1.0", &releases, false).unwrap();
+  assert_eq!(update.latest, "0.3.0");
+  assert!(update.url.ends_with("/v0.3.0"));
+  assert_eq!(update.notes.as_deref(), Some("notes for v0.3.0"));
+}
+
+#[test]
+fn prereleases_are_opt_in() {
+  let releases = vec![release("v0.2.0", false, false), release("v0.3.0-beta.2", true, false)];
+  assert_eq!(newest_update("0.1.0", &releases, true).unwrap().latest, "0.3.0-beta.2");
+  // A pre-release build is offered newer pre-releases without opting in.
+  assert_eq!(newest_update("0.3.0-beta.1", &releases, false).unwrap().latest, "0.3.0-beta.2");
+}
+
+#[test]
+fn no_update_when_current_is_newest() {
+  let releases = vec![release("v0.1.0", false, false), release("v0.0.9", false, false)];
+  assert!(newest_update("0.1.0", &releases, false).is_none());
+  assert!(newest_update("not-a-version", &releases, false).is_none());
+}
+
+#[test]
+fn parses_github_api_payload() {
+  let json = r#"[{"tag_name":"v0.2.0","html_url":"https://example.invalid/r","body":"","prerelease":false,"draft":false,"assets":[]}]"#;
+  let releases: Vec<GithubRelease> = serde_json::from_str(json).unwrap();
+  let update = newest_update("0.1.0", &releases, false).unwrap();
+  assert_eq!(update.notes, None, "blank release notes are dropped");
+}
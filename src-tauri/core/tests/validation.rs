@@ -0,0 +1,54 @@
+use helltime_core::schedule::{EventKind, ScheduleResponse};
+use helltime_core::validation::{self, ValidationReport, ValidationStats};
+use serde_json::json;
+
+mod common;
+
+use common::utc;
+
+#[test]
+fn drops_stale_far_duplicate_and_bogus_entries() {
+  let mut schedule: ScheduleResponse = serde_json::from_value(json!({
+    "helltide": [
+      { "id": 1, "startTime": "2025-10-16T07:30:00Z" },
+      { "id": 2, "startTime": "2025-10-16T09:00:00Z" },
+      { "id": 2, "startTime": "2025-10-16T09:15:00Z" },
+      { "id": 3, "startTime": "2025-10-16T09:00:00Z" },
+      { "id": 4, "startTime": "2025-10-16T05:00:00Z" },
+    ],
+    "legion": [
+      { "id": 5, "startTime": "2025-10-19T08:00:00Z" },
+      { "id": 6, "startTime": "not a date" },
+      { "id": 7, "timestamp": 0 },
+      { "id": 8, "startTime": "2025-10-17T07:00:00Z" },
+    ],
+  }))
+  .unwrap();
+
+  let report = validation::sanitize(&mut schedule, utc("2025-10-16T08:00:00Z"));
+  assert_eq!(report, ValidationReport { past: 1, too_far: 1, duplicates: 2, bogus: 2 });
+  assert_eq!(report.total(), 6);
+
+  let ids = |kind| schedule.events(kind).iter().map(|e| e.id).collect::<Vec<_>>();
+  assert_eq!(ids(EventKind::Helltide), [1, 2], "a running Helltide is kept");
+  assert_eq!(ids(EventKind::Legion), [8]);
+}
+
+#[test]
+fn valid_schedules_pass_unchanged() {
+  let raw = json!({ "world_boss": [{ "id": 1, "startTime": "2025-10-16T09:00:00Z", "boss": "Ashava" }] });
+  let mut schedule: ScheduleResponse = serde_json::from_value(raw.clone()).unwrap();
+  let report = validation::sanitize(&mut schedule, utc("2025-10-16T08:00:00Z"));
+  assert_eq!(report.total(), 0);
+  assert_eq!(schedule.world_boss, raw["world_boss"].as_array().unwrap().clone());
+}
+
+#[test]
+fn stats_keep_the_last_pass_and_a_running_total() {
+  let mut stats = ValidationStats::default();
+  stats.record(ValidationReport { duplicates: 2, ..Default::default() });
+  stats.record(ValidationReport { bogus: 1, ..Default::default() });
+  assert_eq!(stats.last.total(), 1);
+  assert_eq!(stats.since_start, ValidationReport { duplicates: 2, bogus: 1, ..Default::default() });
+  assert_eq!(stats.passes, 2);
+}
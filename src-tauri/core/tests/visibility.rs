@@ -0,0 +1,58 @@
+use chrono::{DateTime, FixedOffset, Weekday};
+use helltime_core::visibility::{VisibilitySchedule, VisibilityWindow};
+
+fn local(s: &str) -> DateTime<FixedOffset> {
+  DateTime::parse_from_rfc3339(s).unwrap()
+}
+
+fn evenings_on_weekdays() -> VisibilitySchedule {
+  VisibilitySchedule {
+    enabled: true,
+    windows: vec![VisibilityWindow {
+      days: vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri],
+      start_minute: 18 * 60,
+      end_minute: 24 * 60,
+    }],
+  }
+}
+
+#[test]
+fn disabled_or_empty_schedule_always_allows() {
+  let at = local("2025-10-16T03:00:00+02:00");
+  assert!(VisibilitySchedule::default().allows(&at));
+  assert!(VisibilitySchedule { enabled: true, windows: Vec::new() }.allows(&at));
+  assert!(VisibilitySchedule { enabled: false, ..evenings_on_weekdays() }.allows(&at));
+}
+
+#[test]
+fn window_uses_local_wall_clock_and_weekday() {
+  let schedule = evenings_on_weekdays();
+  // Thursday 2025-10-16.
+  assert!(!schedule.allows(&local("2025-10-16T17:59:00+02:00")));
+  assert!(schedule.allows(&local("2025-10-16T18:00:00+02:00")));
+  assert!(schedule.allows(&local("2025-10-16T23:59:00+02:00")));
+  // Same instant as Thursday 19:00 in Berlin, but 17:00 UTC.
+  assert!(!schedule.allows(&local("2025-10-16T17:00:00+00:00")));
+  // Saturday evening.
+  assert!(!schedule.allows(&local("2025-10-18T20:00:00+02:00")));
+}
+
+#[test]
+fn window_past_midnight_belongs_to_its_start_day() {
+  let schedule = VisibilitySchedule {
+    enabled: true,
+    windows: vec![VisibilityWindow { days: vec![Weekday::Fri], start_minute: 22 * 60, end_minute: 2 * 60 }],
+  };
+  assert!(schedule.allows(&local("2025-10-17T23:00:00+02:00")));
+  assert!(schedule.allows(&local("2025-10-18T01:30:00+02:00")));
+  assert!(!schedule.allows(&local("2025-10-18T02:00:00+02:00")));
+  assert!(!schedule.allows(&local("2025-10-17T01:00:00+02:00")), "Thursday night is not covered");
+}
+
+#[test]
+fn rejects_out_of_range_times() {
+  let mut schedule = evenings_on_weekdays();
+  assert!(schedule.validate().is_ok());
+  schedule.windows[0].end_minute = 24 * 60 + 1;
+  assert!(schedule.validate().is_err());
+}
@@ -0,0 +1,108 @@
+use chrono::Duration;
+use helltime_core::cache::ScheduleDiff;
+use helltime_core::schedule::EventKind;
+use helltime_core::webhook::{
+  self, backoff, placeholders, render, signature, starting_between, ErrorThrottle, WebhookEvent, WebhookSettings,
+  WebhookTrigger, BACKOFF,
+};
+use serde_json::{json, Value};
+
+mod common;
+
+use common::{fixture, utc};
+
+fn enabled(template: Option<&str>) -> WebhookSettings {
+  WebhookSettings {
+    enabled: true,
+    url: "http://homeassistant.local:8123/api/webhook/helltime".into(),
+    template: template.map(str::to_string),
+    ..WebhookSettings::default()
+  }
+}
+
+#[test]
+fn signature_is_hmac_sha256() {
+  // RFC 4231, test case 2.
+  assert_eq!(
+    signature("Jefe", "what do ya want for nothing?"),
+    "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+  );
+}
+
+#[test]
+fn default_body_has_all_fields() {
+  let at = utc("2025-10-16T08:30:00Z");
+  let event = WebhookEvent::sample(at);
+  let body: Value = serde_json::from_str(&webhook::body(&enabled(None), &event)).unwrap();
+  assert_eq!(
+    body,
+    json!({
+      "trigger": "event_starting",
+      "at": "2025-10-16T08:30:00+00:00",
+      "event": "helltide",
+      "id": 0,
+      "title": "Helltide",
+      "start": "2025-10-16T08:30:00+00:00",
+      "zone": "Kehjistan",
+      "predicted": false,
+    })
+  );
+  for name in placeholders(WebhookTrigger::EventStarting) {
+    assert!(event.field(name).is_some(), "{name}");
+  }
+}
+
+#[test]
+fn templates_escape_strings_and_keep_unknown_braces() {
+  let event = WebhookEvent::app_error("network", "bad status: 503 \"Service\"", utc("2025-10-16T08:30:00Z"));
+  let rendered = render(r#"{"text": "{kind}: {message} {unknown}", "n": {nope}}"#, &event);
+  assert_eq!(rendered, r#"{"text": "network: bad status: 503 \"Service\" {unknown}", "n": {nope}}"#);
+
+  let changed = WebhookEvent::schedule_changed(&ScheduleDiff::default(), utc("2025-10-16T08:30:00Z"));
+  let body: Value = serde_json::from_str(&render(r#"{"moved": {shifted}}"#, &changed)).unwrap();
+  assert_eq!(body, json!({ "moved": 0 }));
+}
+
+#[test]
+fn settings_are_validated_only_when_enabled() {
+  assert!(WebhookSettings::default().validate().is_ok());
+  assert!(enabled(None).validate().is_ok());
+  assert!(enabled(Some(r#"{"text": "{title} started"}"#)).validate().is_ok());
+  assert!(enabled(Some(r#"{"text": {title}}"#)).validate().is_err(), "a bare string placeholder is not JSON");
+
+  let mut no_url = enabled(None);
+  no_url.url = "homeassistant.local".into();
+  assert!(no_url.validate().is_err());
+
+  let mut no_triggers = enabled(None);
+  no_triggers.triggers.clear();
+  assert!(no_triggers.validate().is_err());
+  no_triggers.enabled = false;
+  assert!(!no_triggers.fires_on(WebhookTrigger::EventStarting));
+}
+
+#[test]
+fn finds_events_starting_in_the_window() {
+  let after = utc("2025-10-16T08:29:55Z");
+  let started = starting_between(&fixture(), &EventKind::ALL, after, after + Duration::seconds(5));
+  let ids: Vec<i64> = started.iter().map(|e| e.id).collect();
+  assert_eq!(ids, vec![7001]);
+  assert!(starting_between(&fixture(), &[EventKind::Legion], after, after + Duration::seconds(5)).is_empty());
+}
+
+#[test]
+fn repeated_errors_are_throttled_per_kind() {
+  let mut throttle = ErrorThrottle::default();
+  let now = utc("2025-10-16T08:00:00Z");
+  assert!(throttle.allow("network", now));
+  assert!(!throttle.allow("network", now + Duration::minutes(10)));
+  assert!(throttle.allow("parse", now + Duration::minutes(10)));
+  assert!(throttle.allow("network", now + Duration::minutes(31)));
+}
+
+#[test]
+fn backoff_grows_then_gives_up() {
+  assert_eq!(backoff(0), Some(BACKOFF[0]));
+  assert!(BACKOFF.windows(2).all(|w| w[0] < w[1]));
+  assert_eq!(backoff(BACKOFF.len()), None);
+}
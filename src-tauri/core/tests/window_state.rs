@@ -0,0 +1,74 @@
+use helltime_core::window_state::{MinimizeAction, Plan, Visibility, WindowMachine, TRAY_DEBOUNCE};
+use std::time::{Duration, Instant};
+
+#[test]
+fn starts_visible_and_toggles() {
+  let mut m = WindowMachine::default();
+  assert_eq!(m.visibility(), Visibility::Visible);
+  assert_eq!(m.toggled(), Visibility::Hidden);
+
+  assert_eq!(m.begin(Visibility::Hidden, false), Plan::Start);
+  assert!(m.in_transition());
+  assert_eq!(m.finish(), Visibility::Visible);
+  assert_eq!(m.visibility(), Visibility::Hidden);
+  assert_eq!(m.toggled(), Visibility::Visible);
+}
+
+#[test]
+fn reentrant_requests_are_busy() {
+  let mut m = WindowMachine::default();
+  assert_eq!(m.begin(Visibility::Hidden, false), Plan::Start);
+  // window.hide() fires events that ask to hide/restore again while we are mid-transition.
+  assert_eq!(m.begin(Visibility::Hidden, true), Plan::Busy);
+  assert_eq!(m.begin(Visibility::Visible, false), Plan::Busy);
+  m.finish();
+  assert!(!m.in_transition());
+  assert_eq!(m.begin(Visibility::Visible, false), Plan::Start);
+}
+
+#[test]
+fn same_state_is_a_noop_unless_forced() {
+  let mut m = WindowMachine::default();
+  assert_eq!(m.begin(Visibility::Visible, false), Plan::AlreadyThere);
+  assert!(!m.in_transition());
+  assert_eq!(m.begin(Visibility::Visible, true), Plan::Start);
+  m.finish();
+  assert_eq!(m.visibility(), Visibility::Visible);
+}
+
+#[test]
+fn finish_without_begin_keeps_state() {
+  let mut m = WindowMachine::default();
+  assert_eq!(m.finish(), Visibility::Visible);
+  assert_eq!(m.visibility(), Visibility::Visible);
+}
+
+#[test]
+fn set_reports_changes() {
+  let mut m = WindowMachine::default();
+  assert!(m.set(Visibility::Hidden));
+  assert!(!m.set(Visibility::Hidden));
+  assert!(m.set(Visibility::Visible));
+}
+
+#[test]
+fn debounces_duplicate_tray_clicks() {
+  let mut m = WindowMachine::default();
+  let t0 = Instant::now();
+  assert!(m.accept_tray_click(t0));
+  assert!(!m.accept_tray_click(t0 + Duration::from_millis(10)));
+  assert!(m.accept_tray_click(t0 + TRAY_DEBOUNCE));
+  // Clock going backwards (shouldn't happen with Instant) is treated as a duplicate.
+  assert!(!m.accept_tray_click(t0));
+}
+
+#[test]
+fn only_entering_minimized_counts_as_a_minimize() {
+  let mut m = WindowMachine::default();
+  // A DPI or monitor change resizes without minimizing.
+  assert_eq!(m.observe_minimized(false, true), MinimizeAction::None);
+  assert_eq!(m.observe_minimized(true, true), MinimizeAction::HideToTray);
+  assert_eq!(m.observe_minimized(true, true), MinimizeAction::None);
+  assert_eq!(m.observe_minimized(false, true), MinimizeAction::None);
+  assert_eq!(m.observe_minimized(true, false), MinimizeAction::Minimized);
+}
@@ -0,0 +1,129 @@
+//! Version, build and environment facts for the about dialog and diagnostics reports.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AboutInfo {
+  pub version: &'static str,
+  /// Short commit hash; empty when built outside a git checkout.
+  pub git_hash: &'static str,
+  /// RFC 3339, UTC.
+  pub build_date: Option<String>,
+  pub tauri_version: &'static str,
+  /// WebView2 on Windows, WebKitGTK on Linux; `None` if the runtime is missing.
+  pub webview_version: Option<String>,
+  pub os: String,
+  pub arch: &'static str,
+  pub monitors: Vec<MonitorInfo>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorInfo {
+  pub name: Option<String>,
+  pub x: i32,
+  pub y: i32,
+  pub width: u32,
+  pub height: u32,
+  pub scale_factor: f64,
+  pub primary: bool,
+}
+
+impl AboutInfo {
+  /// Plain-text rendering, one fact per line.
+  pub fn to_text(&self) -> String {
+    let mut lines = vec![
+      format!(
+        "helltime {}{}",
+        self.version,
+        if self.git_hash.is_empty() { String::new() } else { format!(" ({})", self.git_hash) }
+      ),
+      format!("built: {}", self.build_date.as_deref().unwrap_or("?")),
+      format!("tauri: {}, webview: {}", self.tauri_version, self.webview_version.as_deref().unwrap_or("missing")),
+      format!("os: {} ({})", self.os, self.arch),
+    ];
+    for m in &self.monitors {
+      lines.push(format!(
+        "monitor: {} {}x{} at {},{} scale {}{}",
+        m.name.as_deref().unwrap_or("?"),
+        m.width,
+        m.height,
+        m.x,
+        m.y,
+        m.scale_factor,
+        if m.primary { " (primary)" } else { "" }
+      ));
+    }
+    lines.join("\n")
+  }
+}
+
+#[cfg(windows)]
+fn os_version() -> String {
+  use std::os::windows::process::CommandExt;
+  const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+  // "Microsoft Windows [Version 10.0.22631.4317]"
+  std::process::Command::new("cmd")
+    .args(["/C", "ver"])
+    .creation_flags(CREATE_NO_WINDOW)
+    .output()
+    .ok()
+    .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+    .filter(|v| !v.is_empty())
+    .unwrap_or_else(|| "Windows".to_string())
+}
+
+#[cfg(not(windows))]
+fn os_version() -> String {
+  let name = std::fs::read_to_string("/etc/os-release").ok().and_then(|release| {
+    release
+      .lines()
+      .find_map(|line| line.strip_prefix("PRETTY_NAME="))
+      .map(|name| name.trim_matches('"').to_string())
+  });
+  let kernel = std::fs::read_to_string("/proc/sys/kernel/osrelease").ok().map(|k| k.trim().to_string());
+  match (name, kernel) {
+    (Some(name), Some(kernel)) => format!("{name}, kernel {kernel}"),
+    (Some(name), None) => name,
+    (None, Some(kernel)) => format!("{}, kernel {kernel}", std::env::consts::OS),
+    (None, None) => std::env::consts::OS.to_string(),
+  }
+}
+
+fn monitors(app: &tauri::AppHandle) -> Vec<MonitorInfo> {
+  let primary = app.primary_monitor().ok().flatten().map(|m| *m.position());
+  app
+    .available_monitors()
+    .unwrap_or_default()
+    .iter()
+    .map(|m| MonitorInfo {
+      name: m.name().cloned(),
+      x: m.position().x,
+      y: m.position().y,
+      width: m.size().width,
+      height: m.size().height,
+      scale_factor: m.scale_factor(),
+      primary: primary == Some(*m.position()),
+    })
+    .collect()
+}
+
+pub fn collect(app: &tauri::AppHandle) -> AboutInfo {
+  let build_date = env!("HELLTIME_BUILD_TIME")
+    .parse::<i64>()
+    .ok()
+    .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+    .map(|at| at.to_rfc3339());
+  AboutInfo {
+    version: env!("CARGO_PKG_VERSION"),
+    git_hash: env!("HELLTIME_GIT_HASH"),
+    build_date,
+    tauri_version: tauri::VERSION,
+    webview_version: tauri::webview_version().ok(),
+    os: os_version(),
+    arch: std::env::consts::ARCH,
+    monitors: monitors(app),
+  }
+}
@@ -0,0 +1,88 @@
+//! Start helltime with the system: the `Run` registry key on Windows, an XDG
+//! autostart entry on Linux. Other platforms are not supported.
+
+pub const SUPPORTED: bool = cfg!(any(windows, target_os = "linux"));
+
+#[cfg(windows)]
+const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+
+#[cfg(windows)]
+fn reg(args: &[&str]) -> Result<std::process::Output, String> {
+  use std::os::windows::process::CommandExt;
+  const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+  std::process::Command::new("reg")
+    .args(args)
+    .creation_flags(CREATE_NO_WINDOW)
+    .output()
+    .map_err(|e| format!("registry call failed: {e}"))
+}
+
+#[cfg(windows)]
+pub fn is_enabled() -> Result<bool, String> {
+  Ok(reg(&["query", RUN_KEY, "/v", "helltime"])?.status.success())
+}
+
+#[cfg(windows)]
+pub fn set(enabled: bool) -> Result<(), String> {
+  let out = if enabled {
+    let exe = std::env::current_exe().map_err(|e| format!("current exe: {e}"))?;
+    let command = format!("\"{}\"", exe.display());
+    reg(&["add", RUN_KEY, "/v", "helltime", "/t", "REG_SZ", "/d", &command, "/f"])?
+  } else {
+    if !is_enabled()? {
+      return Ok(());
+    }
+    reg(&["delete", RUN_KEY, "/v", "helltime", "/f"])?
+  };
+  if out.status.success() {
+    Ok(())
+  } else {
+    Err(format!("registry update failed: {}", String::from_utf8_lossy(&out.stderr).trim()))
+  }
+}
+
+#[cfg(target_os = "linux")]
+fn desktop_entry() -> Result<std::path::PathBuf, String> {
+  let config = std::env::var_os("XDG_CONFIG_HOME")
+    .filter(|dir| !dir.is_empty())
+    .map(std::path::PathBuf::from)
+    .or_else(|| std::env::var_os("HOME").map(|home| std::path::PathBuf::from(home).join(".config")))
+    .ok_or("neither XDG_CONFIG_HOME nor HOME is set")?;
+  Ok(config.join("autostart").join("helltime.desktop"))
+}
+
+#[cfg(target_os = "linux")]
+pub fn is_enabled() -> Result<bool, String> {
+  Ok(desktop_entry()?.exists())
+}
+
+#[cfg(target_os = "linux")]
+pub fn set(enabled: bool) -> Result<(), String> {
+  let path = desktop_entry()?;
+  if !enabled {
+    return match std::fs::remove_file(&path) {
+      Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(format!("remove {}: {e}", path.display())),
+      _ => Ok(()),
+    };
+  }
+  let exe = std::env::current_exe().map_err(|e| format!("current exe: {e}"))?;
+  let entry = format!(
+    "[Desktop Entry]\nType=Application\nName=helltime\nExec=\"{}\"\nX-GNOME-Autostart-enabled=true\n",
+    exe.display()
+  );
+  if let Some(dir) = path.parent() {
+    std::fs::create_dir_all(dir).map_err(|e| format!("create {}: {e}", dir.display()))?;
+  }
+  std::fs::write(&path, entry).map_err(|e| format!("write {}: {e}", path.display()))
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+pub fn is_enabled() -> Result<bool, String> {
+  Ok(false)
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+pub fn set(_enabled: bool) -> Result<(), String> {
+  Err("autostart is not supported on this platform".into())
+}
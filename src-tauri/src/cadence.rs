@@ -0,0 +1,52 @@
+//! Keeps the season cadence current: the bundled `cadence.json`, replaced by a newer
+//! copy from the repository when one is published.
+
+use crate::AppState;
+use helltime_core::cadence::Cadence;
+use helltime_core::fetch::HttpFetcher;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+const CADENCE_URL: &str = "https://raw.githubusercontent.com/Trissilein/helltime/main/src-tauri/core/cadence.json";
+
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(60);
+const CHECK_INTERVAL: Duration = Duration::from_secs(12 * 60 * 60);
+
+pub fn current(app: &tauri::AppHandle) -> Cadence {
+  let state = app.state::<AppState>();
+  let cadence = state.inner().cadence.lock().unwrap_or_else(|e| e.into_inner()).get().clone();
+  cadence
+}
+
+/// Fetch the published cadence and adopt it if newer. Emits `cadence:changed` and
+/// drops the cached schedule so predictions are redone with the new rhythm.
+pub async fn check(app: &tauri::AppHandle) -> Result<bool, String> {
+  let state = app.state::<AppState>();
+  let resp = state.inner().http().plain().get(CADENCE_URL).await.map_err(|e| e.to_string())?;
+  if !(200..300).contains(&resp.status) {
+    return Err(format!("bad status: {}", resp.status));
+  }
+  let cadence = Cadence::parse(&resp.body)?;
+  let adopted = state.inner().cadence.lock().unwrap_or_else(|e| e.into_inner()).offer(cadence.clone())?;
+  if adopted {
+    applog!("🗓 Cadence updated to version {} ({})", cadence.version, cadence.season.as_deref().unwrap_or("?"));
+    state.inner().cache.lock().await.invalidate();
+    let _ = app.emit("cadence:changed", &cadence);
+  }
+  Ok(adopted)
+}
+
+pub fn spawn_checker(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    tokio::time::sleep(FIRST_CHECK_DELAY).await;
+    loop {
+      // Read each round so turning the preference off stops the next fetch.
+      if crate::preferences(&app).cadence_updates {
+        if let Err(e) = check(&app).await {
+          applog!("⚠ Cadence check failed: {}", e);
+        }
+      }
+      tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+  });
+}
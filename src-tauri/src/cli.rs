@@ -0,0 +1,221 @@
+//! Headless mode: `helltime --next [--json] [--watch]` prints upcoming events to
+//! stdout without creating any window, for scripts, terminals and Stream Deck plugins.
+
+use crate::http::ReqwestFetcher;
+use crate::SCHEDULE_URL;
+use helltime_core::boss_rotation;
+use helltime_core::cache::ScheduleCache;
+use helltime_core::cadence::Cadence;
+use helltime_core::clock::ClockSkew;
+use helltime_core::countdown::{self, format_clock, NextEvent};
+use helltime_core::fetch::{fetch_schedule, RetryPolicy};
+use helltime_core::helltide;
+use helltime_core::polling::PollSettings;
+use helltime_core::schedule::EventKind;
+use std::io::Write;
+use std::time::Duration;
+
+const USAGE: &str = "\
+Usage: helltime [--next] [--json] [--watch]
+       helltime [--headless]
+
+  --next      Print the next Helltide, Legion and World Boss (default in CLI mode)
+  --json      Print machine-readable JSON instead of text
+  --watch     Keep running and refresh (text: every second, JSON: one line per fetch)
+  --headless  Start the tray app without its window: tray, tooltip and reminders only
+  --help      Show this help
+
+Without any of these flags helltime starts the tray app.";
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Options {
+  json: bool,
+  watch: bool,
+}
+
+enum Parsed {
+  Gui,
+  Help,
+  Run(Options),
+  Invalid(String),
+}
+
+fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Parsed {
+  let mut opts = Options::default();
+  let mut cli = false;
+  for arg in args {
+    match arg.as_str() {
+      "--next" => cli = true,
+      "--json" => {
+        cli = true;
+        opts.json = true;
+      }
+      "--watch" => {
+        cli = true;
+        opts.watch = true;
+      }
+      "--help" | "-h" => return Parsed::Help,
+      "--headless" => {}
+      // Deep links and anything the OS/installer passes belong to the GUI.
+      other if other.starts_with("--") && cli => return Parsed::Invalid(other.to_string()),
+      _ => {}
+    }
+  }
+  if cli {
+    Parsed::Run(opts)
+  } else {
+    Parsed::Gui
+  }
+}
+
+/// `--headless`: start the tray app without creating the main window.
+pub fn headless_requested() -> bool {
+  std::env::args().skip(1).any(|arg| arg == "--headless")
+}
+
+/// Release builds use the Windows GUI subsystem and have no console; reuse the
+/// terminal we were started from so stdout is visible.
+#[cfg(windows)]
+fn attach_parent_console() {
+  #[link(name = "kernel32")]
+  extern "system" {
+    fn AttachConsole(process_id: u32) -> i32;
+  }
+  const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+  unsafe {
+    AttachConsole(ATTACH_PARENT_PROCESS);
+  }
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
+
+fn describe(event: &NextEvent) -> String {
+  let boss = match (&event.boss, event.boss_predicted) {
+    (Some(boss), false) => format!("  {}", boss),
+    (Some(boss), true) => format!("  {}?", boss),
+    (None, _) => String::new(),
+  };
+  let zone = event.zone.as_deref().map(|z| format!("  ({})", z)).unwrap_or_default();
+  format!(
+    "{:<11} {:>8}  {}{}{}",
+    event.kind.label(),
+    format_clock(event.seconds_until),
+    event.start_local,
+    boss,
+    zone
+  )
+}
+
+struct Session {
+  fetcher: ReqwestFetcher,
+  cache: ScheduleCache,
+  clock: ClockSkew,
+}
+
+impl Session {
+  /// Refetch once the cache is stale; returns whether new data was fetched.
+  async fn refresh(&mut self) -> Result<bool, String> {
+    if self.cache.fresh().is_some() {
+      return Ok(false);
+    }
+    let fetched = fetch_schedule(&self.fetcher, SCHEDULE_URL, RetryPolicy::default())
+      .await
+      .map_err(|e| e.to_string())?;
+    if let Some(server_date) = fetched.server_date {
+      self.clock.record(server_date, fetched.sent_at, fetched.received_at);
+    }
+    let mut schedule = fetched.schedule;
+    boss_rotation::annotate(&mut schedule, &[]);
+    helltide::extrapolate(&mut schedule, &Cadence::bundled().helltide, chrono::Utc::now());
+    self.cache.store(schedule);
+    Ok(true)
+  }
+
+  fn next_events(&self) -> Vec<NextEvent> {
+    self
+      .cache
+      .value()
+      .map(|schedule| countdown::next_events(schedule, &EventKind::ALL, self.clock.server_now(), None))
+      .unwrap_or_default()
+  }
+}
+
+fn print(events: &[NextEvent], json: bool) {
+  let mut out = std::io::stdout().lock();
+  if json {
+    let _ = writeln!(out, "{}", serde_json::to_string(events).unwrap_or_else(|_| "[]".into()));
+  } else {
+    for event in events {
+      let _ = writeln!(out, "{}", describe(event));
+    }
+  }
+  let _ = out.flush();
+}
+
+async fn run(opts: Options) -> i32 {
+  let mut session = Session {
+    fetcher: ReqwestFetcher::default(),
+    cache: ScheduleCache::new(PollSettings::default().cache_ttl()),
+    clock: ClockSkew::default(),
+  };
+
+  if let Err(e) = session.refresh().await {
+    eprintln!("helltime: {}", e);
+    return 1;
+  }
+  print(&session.next_events(), opts.json);
+  if !opts.watch {
+    return 0;
+  }
+
+  loop {
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    let fetched = match session.refresh().await {
+      Ok(fetched) => fetched,
+      Err(e) => {
+        // Keep counting down from the stale schedule; try again next tick.
+        eprintln!("helltime: {}", e);
+        false
+      }
+    };
+    if opts.json {
+      if fetched {
+        print(&session.next_events(), true);
+      }
+    } else {
+      // Clear screen and redraw in place.
+      print!("\x1b[2J\x1b[H");
+      print(&session.next_events(), false);
+    }
+  }
+}
+
+/// Run the CLI if the command line asks for it. Returns the exit code, or `None`
+/// when the GUI should start.
+pub fn run_from_args() -> Option<i32> {
+  match parse_args(std::env::args().skip(1)) {
+    Parsed::Gui => None,
+    Parsed::Help => {
+      attach_parent_console();
+      println!("{}", USAGE);
+      Some(0)
+    }
+    Parsed::Invalid(arg) => {
+      attach_parent_console();
+      eprintln!("helltime: unknown option {}\n\n{}", arg, USAGE);
+      Some(2)
+    }
+    Parsed::Run(opts) => {
+      attach_parent_console();
+      let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+          eprintln!("helltime: failed to start runtime: {}", e);
+          return Some(1);
+        }
+      };
+      Some(runtime.block_on(run(opts)))
+    }
+  }
+}
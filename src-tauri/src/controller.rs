@@ -0,0 +1,85 @@
+//! Runs the [`Controller`]: one task owns it and takes [`Message`]s from the tray,
+//! commands and window events in order, then performs the resulting effects.
+//! Window events caused by an effect are queued behind it instead of racing it.
+
+use helltime_core::controller::{Controller, Effect, Message};
+use helltime_core::window_state::Visibility;
+use tauri::Manager;
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+pub struct AppController {
+  tx: UnboundedSender<Message>,
+}
+
+/// Start the controller task; `visibility` is how the main window starts out.
+pub fn spawn(app: &tauri::AppHandle, visibility: Visibility) {
+  let mut controller = Controller::new(visibility);
+  let (tx, mut rx) = mpsc::unbounded_channel();
+  app.manage(AppController { tx });
+
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    while let Some(message) = rx.recv().await {
+      let before = controller.visibility();
+      for effect in controller.handle(message) {
+        apply(&app, effect);
+      }
+      if controller.visibility() != before {
+        applog!("📍 Window state: {:?} → {:?} ({:?})", before, controller.visibility(), message);
+      }
+    }
+  });
+}
+
+/// Queue `message`; a no-op before [`spawn`] ran.
+pub fn send(app: &tauri::AppHandle, message: Message) {
+  if let Some(controller) = app.try_state::<AppController>() {
+    let _ = controller.tx.send(message);
+  }
+}
+
+fn apply(app: &tauri::AppHandle, effect: Effect) {
+  let main = || app.get_webview_window("main");
+  match effect {
+    Effect::ShowWindow => {
+      // Headless or unloaded so far: build the window (again).
+      let Some(window) = main().or_else(|| match crate::webview::create_main_window(app) {
+        Ok(window) => {
+          crate::window_geometry::restore(app, &window);
+          Some(window)
+        }
+        Err(e) => {
+          applog!("✗ {}", e);
+          None
+        }
+      }) else {
+        return;
+      };
+      // Order matters: show first, then configure
+      let _ = window.show();
+      let _ = window.unminimize();
+      let _ = window.set_skip_taskbar(false);
+      let _ = window.set_focus();
+      crate::foreground::ensure(window);
+    }
+    Effect::FocusWindow => {
+      let Some(window) = main() else { return };
+      let _ = window.set_focus();
+      crate::foreground::ensure(window);
+    }
+    Effect::HideWindow => {
+      let Some(window) = main() else { return };
+      let _ = window.hide();
+      let _ = window.set_skip_taskbar(true);
+      crate::webview::schedule_unload(app);
+    }
+    Effect::UnloadWindow => crate::webview::unload_main_window(app),
+    Effect::ShowInTaskbar => {
+      if let Some(window) = main() {
+        let _ = window.set_skip_taskbar(false);
+      }
+    }
+    Effect::ToggleOverlay => crate::tray::toggle_flag(app, |prefs| &mut prefs.overlay_enabled),
+    Effect::ToggleReminders => crate::tray::toggle_flag(app, |prefs| &mut prefs.reminders_enabled),
+  }
+}
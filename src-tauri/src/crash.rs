@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex as StdMutex, OnceLock};
+
+/// How many recent log lines are kept in memory for crash reports.
+const LOG_CAPACITY: usize = 200;
+
+/// Marker written next to the dump so the next launch can offer to open it.
+const PENDING_MARKER: &str = "pending.json";
+
+static LOG_RING: OnceLock<StdMutex<VecDeque<String>>> = OnceLock::new();
+
+/// `eprintln!` that also keeps the line for crash reports.
+macro_rules! applog {
+  ($($arg:tt)*) => {{
+    let line = format!($($arg)*);
+    eprintln!("{}", line);
+    $crate::crash::push_log(line);
+  }};
+}
+
+fn ring() -> &'static StdMutex<VecDeque<String>> {
+  LOG_RING.get_or_init(|| StdMutex::new(VecDeque::with_capacity(LOG_CAPACITY)))
+}
+
+pub fn push_log(line: String) {
+  // Never panic here: this also runs while a panic is being reported.
+  let mut ring = ring().lock().unwrap_or_else(|e| e.into_inner());
+  if ring.len() == LOG_CAPACITY {
+    ring.pop_front();
+  }
+  let stamp = chrono::Local::now().format("%H:%M:%S%.3f");
+  ring.push_back(format!("{} {}", stamp, line));
+}
+
+fn recent_logs() -> Vec<String> {
+  ring().lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashInfo {
+  pub path: String,
+  pub summary: String,
+  pub at: String,
+}
+
+fn write_dump(dir: &Path, info: &std::panic::PanicHookInfo<'_>) -> std::io::Result<CrashInfo> {
+  std::fs::create_dir_all(dir)?;
+  let now = chrono::Local::now();
+  let path = dir.join(format!("crash-{}.txt", now.format("%Y%m%d-%H%M%S")));
+
+  let thread = std::thread::current();
+  let thread_name = thread.name().unwrap_or("<unnamed>");
+  let message = info
+    .payload()
+    .downcast_ref::<&str>()
+    .map(|s| s.to_string())
+    .or_else(|| info.payload().downcast_ref::<String>().cloned())
+    .unwrap_or_else(|| "<non-string panic payload>".to_string());
+  let location = info
+    .location()
+    .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+    .unwrap_or_else(|| "<unknown>".to_string());
+  let summary = format!("panic in thread '{}' at {}: {}", thread_name, location, message);
+
+  let mut file = std::fs::File::create(&path)?;
+  writeln!(file, "helltime {} crash report", env!("CARGO_PKG_VERSION"))?;
+  writeln!(file, "time: {}", now.to_rfc3339())?;
+  writeln!(file, "os: {} {}", std::env::consts::OS, std::env::consts::ARCH)?;
+  writeln!(file, "{}", summary)?;
+  writeln!(file, "\nbacktrace:\n{}", std::backtrace::Backtrace::force_capture())?;
+  writeln!(file, "\nlast log lines:")?;
+  for line in recent_logs() {
+    writeln!(file, "{}", line)?;
+  }
+
+  let crash = CrashInfo {
+    path: path.to_string_lossy().into_owned(),
+    summary,
+    at: now.to_rfc3339(),
+  };
+  std::fs::write(dir.join(PENDING_MARKER), serde_json::to_vec(&crash).unwrap_or_default())?;
+  Ok(crash)
+}
+
+/// Write a crash dump for every panic (any thread), then run the default hook.
+pub fn install_panic_hook(dir: PathBuf) {
+  let default_hook = std::panic::take_hook();
+  std::panic::set_hook(Box::new(move |info| {
+    match write_dump(&dir, info) {
+      Ok(crash) => eprintln!("💥 Crash report written to {}", crash.path),
+      Err(e) => eprintln!("💥 Failed to write crash report: {}", e),
+    }
+    default_hook(info);
+  }));
+}
+
+/// Crash recorded by a previous session, if any. The marker is consumed so the
+/// notice is shown once.
+pub fn take_previous_crash(dir: &Path) -> Option<CrashInfo> {
+  let marker = dir.join(PENDING_MARKER);
+  let bytes = std::fs::read(&marker).ok()?;
+  let _ = std::fs::remove_file(&marker);
+  serde_json::from_slice(&bytes).ok()
+}
+
+/// Only paths of dumps inside the crash directory may be opened from the UI.
+pub fn is_crash_report(dir: &Path, path: &Path) -> bool {
+  match (dir.canonicalize(), path.canonicalize()) {
+    (Ok(dir), Ok(path)) => path.starts_with(dir) && path.extension().is_some_and(|e| e == "txt"),
+    _ => false,
+  }
+}
@@ -0,0 +1,133 @@
+use helltime_core::controller::Message;
+use helltime_core::deep_link::{self, DeepLink};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tauri::Emitter;
+
+const MAX_LINK_LEN: u64 = 2048;
+
+/// A forwarding launch that connects but never sends its line is dropped after this.
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Link the app was launched with, kept until the UI has loaded and asks for it.
+#[derive(Default)]
+pub struct PendingLink(pub StdMutex<Option<DeepLink>>);
+
+/// Per-user file with the loopback port the running instance listens on for links
+/// opened while it is already up (the OS starts a second process with the URL as
+/// argument). The OS picks the port, so no other process can claim it in advance.
+fn port_file() -> PathBuf {
+  let dir = std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from).unwrap_or_else(std::env::temp_dir);
+  let user = std::env::var("USER").or_else(|_| std::env::var("USERNAME")).unwrap_or_default();
+  dir.join(format!("helltime-{}.port", user))
+}
+
+/// Hand `url` to an already running instance. Returns `true` if one accepted it,
+/// in which case this process should exit.
+pub fn forward_to_running_instance(url: &str) -> bool {
+  let Some(port) = std::fs::read_to_string(port_file()).ok().and_then(|s| s.trim().parse::<u16>().ok()) else {
+    return false;
+  };
+  let addr = SocketAddr::from(([127, 0, 0, 1], port));
+  match TcpStream::connect_timeout(&addr, Duration::from_millis(300)) {
+    Ok(mut stream) => writeln!(stream, "{}", url).is_ok(),
+    Err(_) => false,
+  }
+}
+
+/// Accept links forwarded by later launches. Without a listener the app still
+/// works; links just open a second window instead of focusing this one.
+pub fn spawn_listener(app: tauri::AppHandle) {
+  let listener = match TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))) {
+    Ok(listener) => listener,
+    Err(e) => {
+      applog!("⚠ Deep link listener unavailable: {}", e);
+      return;
+    }
+  };
+  let published = listener.local_addr().and_then(|addr| std::fs::write(port_file(), addr.port().to_string()));
+  if let Err(e) = published {
+    applog!("⚠ Deep link listener unavailable ({}): {}", port_file().display(), e);
+    return;
+  }
+
+  std::thread::Builder::new()
+    .name("helltime-deep-link".into())
+    .spawn(move || {
+      for stream in listener.incoming().flatten() {
+        if stream.set_read_timeout(Some(READ_TIMEOUT)).is_err() {
+          continue;
+        }
+        let mut line = String::new();
+        if BufReader::new(stream.take(MAX_LINK_LEN)).read_line(&mut line).is_ok() {
+          handle(&app, line.trim());
+        }
+      }
+    })
+    .expect("failed to spawn deep link thread");
+}
+
+/// Bring the main window up and tell the UI where to navigate.
+pub fn handle(app: &tauri::AppHandle, url: &str) {
+  let Some(link) = deep_link::parse(url) else {
+    applog!("⚠ Ignoring unknown deep link: {}", url);
+    return;
+  };
+  applog!("🔗 Deep link: {:?}", link);
+
+  crate::controller::send(app, Message::ShowWindow);
+  let _ = app.emit("deeplink:navigate", link);
+}
+
+/// Register `helltime://` for the current user so browsers and the shell can open it.
+#[cfg(windows)]
+pub fn register_scheme() {
+  use std::os::windows::process::CommandExt;
+  const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+  let exe = match std::env::current_exe() {
+    Ok(exe) => exe,
+    Err(e) => {
+      applog!("⚠ Cannot register helltime:// (no exe path): {}", e);
+      return;
+    }
+  };
+  let key = format!(r"HKCU\Software\Classes\{}", deep_link::SCHEME);
+  let command = format!("\"{}\" \"%1\"", exe.display());
+  // Nothing to write when the open command already points at this exe.
+  let current = std::process::Command::new("reg")
+    .args(["query", &format!(r"{}\shell\open\command", key), "/ve"])
+    .creation_flags(CREATE_NO_WINDOW)
+    .output();
+  if matches!(current, Ok(ref out) if out.status.success() && String::from_utf8_lossy(&out.stdout).contains(&command)) {
+    return;
+  }
+
+  let entries: [(String, &str, String); 3] = [
+    (key.clone(), "/ve", "URL:helltime".to_string()),
+    (key.clone(), "URL Protocol", String::new()),
+    (format!(r"{}\shell\open\command", key), "/ve", command),
+  ];
+
+  for (path, value, data) in entries {
+    let mut cmd = std::process::Command::new("reg");
+    cmd.args(["add", &path]);
+    if value != "/ve" {
+      cmd.args(["/v", value]);
+    } else {
+      cmd.arg("/ve");
+    }
+    let status = cmd.args(["/d", &data, "/f"]).creation_flags(CREATE_NO_WINDOW).output();
+    if !matches!(status, Ok(ref out) if out.status.success()) {
+      applog!("⚠ Failed to register helltime:// ({})", path);
+      return;
+    }
+  }
+}
+
+/// Other platforms declare the scheme in the app bundle at install time.
+#[cfg(not(windows))]
+pub fn register_scheme() {}
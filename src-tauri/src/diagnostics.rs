@@ -0,0 +1,252 @@
+use crate::about::AboutInfo;
+use crate::{AppState, SCHEDULE_URL};
+use helltime_core::fetch::HttpFetcher;
+use helltime_core::schedule::EventKind;
+use serde::Serialize;
+use std::path::Path;
+use std::time::Instant;
+use tauri::Manager;
+use tauri_plugin_notification::NotificationExt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+  Ok,
+  Warn,
+  Fail,
+  Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticCheck {
+  pub name: &'static str,
+  pub status: CheckStatus,
+  pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticsReport {
+  pub generated_at: String,
+  pub version: &'static str,
+  pub about: AboutInfo,
+  pub checks: Vec<DiagnosticCheck>,
+}
+
+impl DiagnosticsReport {
+  /// Plain-text rendering for the clipboard / bug reports.
+  pub fn to_text(&self) -> String {
+    let mut out = format!("helltime {} diagnostics ({})\n", self.version, self.generated_at);
+    out.push_str(&self.about.to_text());
+    out.push('\n');
+    for check in &self.checks {
+      let mark = match check.status {
+        CheckStatus::Ok => "OK  ",
+        CheckStatus::Warn => "WARN",
+        CheckStatus::Fail => "FAIL",
+        CheckStatus::Skipped => "SKIP",
+      };
+      out.push_str(&format!("[{}] {}: {}\n", mark, check.name, check.detail));
+    }
+    out
+  }
+}
+
+fn check(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> DiagnosticCheck {
+  DiagnosticCheck { name, status, detail: detail.into() }
+}
+
+async fn check_dns() -> DiagnosticCheck {
+  let host = reqwest::Url::parse(SCHEDULE_URL)
+    .ok()
+    .and_then(|u| u.host_str().map(|h| format!("{}:443", h)))
+    .unwrap_or_default();
+
+  let lookup = tokio::task::spawn_blocking(move || {
+    use std::net::ToSocketAddrs;
+    host.to_socket_addrs().map(|addrs| addrs.count())
+  })
+  .await;
+
+  match lookup {
+    Ok(Ok(n)) if n > 0 => check("dns", CheckStatus::Ok, format!("{} address(es) resolved", n)),
+    Ok(Ok(_)) => check("dns", CheckStatus::Fail, "no addresses resolved"),
+    Ok(Err(e)) => check("dns", CheckStatus::Fail, format!("lookup failed: {e}")),
+    Err(e) => check("dns", CheckStatus::Fail, format!("lookup task failed: {e}")),
+  }
+}
+
+/// Network preferences, with token, header values and proxy credentials redacted.
+fn check_network(app: &tauri::AppHandle) -> DiagnosticCheck {
+  let network = crate::preferences(app).network;
+  let status = if network.insecure_tls { CheckStatus::Warn } else { CheckStatus::Ok };
+  check("network", status, network.summary())
+}
+
+async fn check_api(state: &AppState) -> DiagnosticCheck {
+  let started = Instant::now();
+  let resp = state.schedule_http().get(SCHEDULE_URL).await;
+  let ms = started.elapsed().as_millis();
+
+  match resp {
+    Ok(r) if (200..300).contains(&r.status) => check("api", CheckStatus::Ok, format!("{} in {}ms", r.status, ms)),
+    Ok(r) => check("api", CheckStatus::Fail, format!("{} in {}ms", r.status, ms)),
+    Err(e) => check("api", CheckStatus::Fail, format!("{} after {}ms", e, ms)),
+  }
+}
+
+fn check_rate_limit(state: &AppState) -> DiagnosticCheck {
+  let status = state.rate_limit.lock().unwrap_or_else(|e| e.into_inner()).status(Instant::now());
+  let level = if status.available == 0 { CheckStatus::Warn } else { CheckStatus::Ok };
+  check(
+    "rate_limit",
+    level,
+    format!(
+      "{}/min, {} available, next in {}ms, {} rejected",
+      status.per_minute, status.available, status.wait_ms, status.rejected
+    ),
+  )
+}
+
+async fn check_cache(state: &AppState) -> DiagnosticCheck {
+  let cache = state.cache.lock().await;
+  match cache.value() {
+    Some(value) => {
+      let counts: Vec<String> = EventKind::ALL
+        .iter()
+        .map(|kind| format!("{:?}={}", kind, value.raw(*kind).len()))
+        .collect();
+      let age = cache
+        .age()
+        .map(|age| format!("{}s old", age.as_secs()))
+        .unwrap_or_else(|| "stale".to_string());
+      check("cache", CheckStatus::Ok, format!("{}, {}", age, counts.join(" ")))
+    }
+    None => check("cache", CheckStatus::Warn, "empty (no successful fetch yet)"),
+  }
+}
+
+fn check_validation(state: &AppState) -> DiagnosticCheck {
+  let stats = *state.validation.lock().unwrap_or_else(|e| e.into_inner());
+  let level = if stats.last.total() > 0 { CheckStatus::Warn } else { CheckStatus::Ok };
+  let last = stats.last;
+  check(
+    "schedule_validation",
+    level,
+    format!(
+      "last fetch dropped {} ({} past, {} too far out, {} duplicate, {} bogus); {} dropped in {} fetch(es) since start",
+      last.total(),
+      last.past,
+      last.too_far,
+      last.duplicates,
+      last.bogus,
+      stats.since_start.total(),
+      stats.passes
+    ),
+  )
+}
+
+async fn check_clock(state: &AppState) -> DiagnosticCheck {
+  let status = state.clock.lock().await.status();
+  let level = if status.offset_ms.abs() >= 60_000 { CheckStatus::Warn } else { CheckStatus::Ok };
+  check(
+    "clock",
+    level,
+    format!("offset {}ms from server ({} samples)", status.offset_ms, status.samples),
+  )
+}
+
+fn check_overlay_window(app: &tauri::AppHandle) -> DiagnosticCheck {
+  match app.get_webview_window(crate::overlay::LABEL) {
+    Some(win) => {
+      let status = crate::overlay::OverlayFacade::new(app).status();
+      let pos = win
+        .outer_position()
+        .map(|p| format!("{},{}", p.x, p.y))
+        .unwrap_or_else(|_| "?".into());
+      let compat = match &status.wine_version {
+        Some(version) => format!(" compat={:?} (Wine {}, degraded {:?})", status.compat, version, status.degraded),
+        None if !status.degraded.is_empty() => format!(" compat={:?} (degraded {:?})", status.compat, status.degraded),
+        None => String::new(),
+      };
+      check(
+        "overlay_window",
+        CheckStatus::Ok,
+        format!(
+          "exists, engine={:?} (requested {:?}) visible={} click_through={} at {}{}",
+          status.engine, status.requested, status.visible, status.click_through, pos, compat
+        ),
+      )
+    }
+    None => check("overlay_window", CheckStatus::Warn, "not created (overlay disabled or UI not loaded)"),
+  }
+}
+
+fn check_notifications(app: &tauri::AppHandle) -> DiagnosticCheck {
+  match app.notification().permission_state() {
+    Ok(tauri::plugin::PermissionState::Granted) => check("notifications", CheckStatus::Ok, "granted"),
+    Ok(other) => check("notifications", CheckStatus::Warn, format!("{:?}", other)),
+    Err(e) => check("notifications", CheckStatus::Fail, format!("query failed: {e}")),
+  }
+}
+
+fn check_webview() -> DiagnosticCheck {
+  match crate::webview::runtime_version() {
+    Ok(version) if crate::webview::is_headless() => check("webview", CheckStatus::Ok, format!("{version}, headless")),
+    Ok(version) => check("webview", CheckStatus::Ok, version),
+    Err(e) => check("webview", CheckStatus::Fail, format!("runtime unavailable: {e}")),
+  }
+}
+
+fn check_autostart() -> DiagnosticCheck {
+  if !crate::autostart::SUPPORTED {
+    return check("autostart", CheckStatus::Skipped, "not supported on this platform");
+  }
+  match crate::autostart::is_enabled() {
+    Ok(true) => check("autostart", CheckStatus::Ok, "registered"),
+    Ok(false) => check("autostart", CheckStatus::Ok, "not registered"),
+    Err(e) => check("autostart", CheckStatus::Warn, e),
+  }
+}
+
+fn check_json_file(name: &'static str, path: &Path) -> DiagnosticCheck {
+  match std::fs::read(path) {
+    Ok(bytes) => match serde_json::from_slice::<serde_json::Value>(&bytes) {
+      Ok(_) => check(name, CheckStatus::Ok, format!("{} bytes, valid JSON", bytes.len())),
+      Err(e) => check(name, CheckStatus::Fail, format!("corrupt: {e}")),
+    },
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => check(name, CheckStatus::Ok, "not created yet"),
+    Err(e) => check(name, CheckStatus::Fail, format!("unreadable: {e}")),
+  }
+}
+
+pub async fn run(app: &tauri::AppHandle) -> DiagnosticsReport {
+  let state = app.state::<AppState>();
+  let state = state.inner();
+
+  let mut checks = vec![
+    check_dns().await,
+    check_network(app),
+    check_api(state).await,
+    check_rate_limit(state),
+    check_cache(state).await,
+    check_validation(state),
+    check_clock(state).await,
+    check_overlay_window(app),
+    check_notifications(app),
+    check_webview(),
+    check_autostart(),
+    check("metrics", CheckStatus::Ok, state.metrics.summary()),
+  ];
+
+  match app.path().app_data_dir() {
+    Ok(dir) => checks.push(check_json_file("event_history", &dir.join("event_history.json"))),
+    Err(e) => checks.push(check("data_dir", CheckStatus::Fail, format!("unavailable: {e}"))),
+  }
+
+  DiagnosticsReport {
+    generated_at: chrono::Utc::now().to_rfc3339(),
+    version: env!("CARGO_PKG_VERSION"),
+    about: crate::about::collect(app),
+    checks,
+  }
+}
@@ -0,0 +1,218 @@
+//! Discord Rich Presence ("Next Helltide in 12m") over Discord's local IPC socket.
+
+use crate::AppState;
+use helltime_core::countdown;
+use helltime_core::discord::{self, OP_CLOSE, OP_FRAME, OP_HANDSHAKE};
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::Manager;
+
+/// Discord application id; its art assets are keyed `helltide`, `legion`, `world_boss`.
+/// Builds without one simply report presence as unavailable.
+const CLIENT_ID: Option<&str> = option_env!("HELLTIME_DISCORD_CLIENT_ID");
+
+const UPDATE_INTERVAL: Duration = Duration::from_secs(discord::MIN_UPDATE_INTERVAL_SECS);
+
+/// A Discord client that accepts the connection but never answers is given up on after this.
+const READ_TIMEOUT: Duration = Duration::from_secs(3);
+
+enum Command {
+  Set(Option<Value>),
+  Shutdown,
+}
+
+trait IpcStream: Read + Write + Send {}
+impl<T: Read + Write + Send> IpcStream for T {}
+
+/// Named pipe opened for synchronous I/O, which has no read timeout of its own:
+/// reads wait for data with `PeekNamedPipe` until [`READ_TIMEOUT`] instead.
+#[cfg(windows)]
+struct Pipe(std::fs::File);
+
+#[cfg(windows)]
+impl Read for Pipe {
+  fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::System::Pipes::PeekNamedPipe;
+
+    let deadline = std::time::Instant::now() + READ_TIMEOUT;
+    loop {
+      let mut available = 0u32;
+      let ok = unsafe {
+        PeekNamedPipe(
+          self.0.as_raw_handle(),
+          std::ptr::null_mut(),
+          0,
+          std::ptr::null_mut(),
+          &mut available,
+          std::ptr::null_mut(),
+        )
+      };
+      if ok == 0 {
+        return Err(std::io::Error::last_os_error());
+      }
+      if available > 0 {
+        return self.0.read(buf);
+      }
+      if std::time::Instant::now() >= deadline {
+        return Err(std::io::ErrorKind::TimedOut.into());
+      }
+      std::thread::sleep(Duration::from_millis(20));
+    }
+  }
+}
+
+#[cfg(windows)]
+impl Write for Pipe {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    self.0.write(buf)
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    self.0.flush()
+  }
+}
+
+#[cfg(windows)]
+fn connect_socket(index: u8) -> std::io::Result<Box<dyn IpcStream>> {
+  let pipe = std::fs::OpenOptions::new()
+    .read(true)
+    .write(true)
+    .open(format!(r"\\?\pipe\discord-ipc-{}", index))?;
+  Ok(Box::new(Pipe(pipe)))
+}
+
+#[cfg(unix)]
+fn connect_socket(index: u8) -> std::io::Result<Box<dyn IpcStream>> {
+  let dir = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+    .iter()
+    .find_map(|key| std::env::var(key).ok())
+    .unwrap_or_else(|| "/tmp".to_string());
+  let socket = std::os::unix::net::UnixStream::connect(format!("{}/discord-ipc-{}", dir, index))?;
+  socket.set_read_timeout(Some(READ_TIMEOUT))?;
+  Ok(Box::new(socket))
+}
+
+fn read_frame(stream: &mut dyn IpcStream) -> std::io::Result<(u32, Value)> {
+  let mut header = [0u8; 8];
+  stream.read_exact(&mut header)?;
+  let (opcode, len) = discord::decode_header(header);
+  let mut body = vec![0u8; len as usize];
+  stream.read_exact(&mut body)?;
+  Ok((opcode, serde_json::from_slice(&body).unwrap_or(Value::Null)))
+}
+
+/// Discord listens on the first free of `discord-ipc-0..9`.
+fn connect(client_id: &str) -> Option<Box<dyn IpcStream>> {
+  for index in 0..10 {
+    let Ok(mut stream) = connect_socket(index) else { continue };
+    if stream.write_all(&discord::encode_frame(OP_HANDSHAKE, &discord::handshake(client_id))).is_err() {
+      continue;
+    }
+    match read_frame(stream.as_mut()) {
+      Ok((OP_FRAME, _)) => return Some(stream),
+      Ok((OP_CLOSE, reason)) => applog!("⚠ Discord rejected handshake: {}", reason),
+      _ => {}
+    }
+  }
+  None
+}
+
+/// Owns the IPC connection; reconnects lazily when Discord is (re)started.
+fn run_worker(client_id: &'static str, commands: Receiver<Command>) {
+  let pid = std::process::id();
+  let mut nonce = 0u64;
+  let mut conn: Option<Box<dyn IpcStream>> = None;
+
+  for command in commands {
+    let (activity, is_shutdown) = match command {
+      Command::Set(activity) => (activity, false),
+      Command::Shutdown => (None, true),
+    };
+
+    if conn.is_none() && !is_shutdown {
+      conn = connect(client_id);
+    }
+    if let Some(stream) = conn.as_mut() {
+      nonce += 1;
+      let frame = discord::encode_frame(OP_FRAME, &discord::set_activity(pid, activity, nonce));
+      // Discord answers every command; read it so the pipe never fills up.
+      if stream.write_all(&frame).is_err() || read_frame(stream.as_mut()).is_err() {
+        conn = None;
+      }
+    }
+    if is_shutdown {
+      break;
+    }
+  }
+}
+
+/// Handle to the presence worker, managed as Tauri state.
+pub struct DiscordPresence {
+  enabled: Arc<AtomicBool>,
+  commands: Option<Sender<Command>>,
+}
+
+impl DiscordPresence {
+  pub fn new() -> Self {
+    let commands = CLIENT_ID.map(|client_id| {
+      let (tx, rx) = mpsc::channel();
+      std::thread::Builder::new()
+        .name("helltime-discord".into())
+        .spawn(move || run_worker(client_id, rx))
+        .expect("failed to spawn discord thread");
+      tx
+    });
+    Self { enabled: Arc::new(AtomicBool::new(false)), commands }
+  }
+
+  pub fn is_available(&self) -> bool {
+    self.commands.is_some()
+  }
+
+  pub fn set_enabled(&self, enabled: bool) {
+    let was = self.enabled.swap(enabled, Ordering::SeqCst);
+    if was && !enabled {
+      self.send(Command::Set(None));
+    }
+  }
+
+  fn send(&self, command: Command) {
+    if let Some(tx) = &self.commands {
+      let _ = tx.send(command);
+    }
+  }
+
+  /// Clear the activity before the process exits so it doesn't linger in Discord.
+  pub fn shutdown(&self) {
+    if self.enabled.load(Ordering::SeqCst) {
+      self.send(Command::Shutdown);
+      // Give the worker a moment to flush; exit does not wait for threads.
+      std::thread::sleep(Duration::from_millis(150));
+    }
+  }
+}
+
+/// Refresh the presence from the cached schedule while enabled.
+pub fn spawn_updater(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    loop {
+      let presence = app.state::<DiscordPresence>();
+      if presence.is_available() && presence.enabled.load(Ordering::SeqCst) {
+        let state = app.state::<AppState>();
+        let cached = state.inner().cache.lock().await.value().cloned();
+        let now = state.inner().clock.lock().await.server_now();
+        if let Some(schedule) = cached {
+          let kinds = crate::preferences(&app).shown_kinds();
+          let next = countdown::next_events(&schedule, &kinds, now, None);
+          presence.send(Command::Set(discord::activity_for(crate::locale::current(&app), &next)));
+        }
+      }
+      tokio::time::sleep(UPDATE_INTERVAL).await;
+    }
+  });
+}
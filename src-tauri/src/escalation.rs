@@ -0,0 +1,99 @@
+//! Runs the reminder escalation (see `reminders::Escalation`). The steps are planned
+//! here; the UI shows the toasts and plays the sounds when it gets
+//! `reminder:escalation`. Without a UI (headless) only the notifications are sent.
+
+use crate::AppState;
+use chrono::Utc;
+use helltime_core::i18n::{self, Msg};
+use helltime_core::reminders::{EscalationAction, EscalationStep, EscalationTracker};
+use helltime_core::schedule::{EventKind, Titled};
+use helltime_core::telemetry::Feature;
+use serde::Serialize;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+/// Well inside `reminders::FIRE_WINDOW` and short enough for the repeat interval;
+/// not stretched by game mode.
+const TICK: Duration = Duration::from_secs(5);
+
+#[derive(Default)]
+pub struct EscalationState(StdMutex<EscalationTracker>);
+
+/// `reminder:acknowledged` payload.
+#[derive(Debug, Clone, Serialize)]
+pub struct Acknowledged {
+  pub kind: EventKind,
+  pub id: i64,
+}
+
+async fn tick(app: &tauri::AppHandle) {
+  let prefs = crate::preferences(app);
+  if !prefs.reminder_escalation.enabled || !prefs.reminders_enabled || crate::shutdown::in_progress() {
+    return;
+  }
+  let state = app.state::<AppState>();
+  if state.inner().reminder_pause.lock().unwrap_or_else(|e| e.into_inner()).is_paused(Utc::now()) {
+    return;
+  }
+  // The UI and the headless loop keep the cache fresh; no fetch from here.
+  let Some(schedule) = state.inner().cache.lock().await.value().cloned() else { return };
+  let now = state.inner().clock.lock().await.server_now();
+  let mut steps = app.state::<EscalationState>().0.lock().unwrap_or_else(|e| e.into_inner()).due(
+    &prefs.reminder_escalation,
+    &schedule,
+    &prefs.shown_kinds(),
+    now,
+  );
+  // Attendance is kept in the history, so it also holds across restarts.
+  let history = state.inner().history.lock().await;
+  steps.retain(|step| !history.attended(step.reminder.event.kind, step.reminder.event.id));
+  drop(history);
+  for step in steps {
+    run(app, &step);
+  }
+}
+
+fn run(app: &tauri::AppHandle, step: &EscalationStep) {
+  let event = &step.reminder.event;
+  if !step.repeat {
+    applog!("📣 Escalation step {} for {} {}: {:?}", step.reminder.timer + 1, event.kind.label(), event.id, step.actions);
+    // The UI renders toasts through `render_toast`, which counts them already.
+    if crate::webview::is_headless() || !step.actions.contains(&EscalationAction::OverlayToast) {
+      app.state::<AppState>().inner().metrics.reminder_fired();
+      crate::telemetry::record(app, Feature::Reminder);
+    }
+  }
+  if !crate::webview::is_headless() {
+    let _ = app.emit("reminder:escalation", step);
+    return;
+  }
+  if step.actions.contains(&EscalationAction::Notification) {
+    let locale = crate::locale::current(app);
+    let minutes = step.reminder.minutes_before.to_string();
+    crate::notify::queue(
+      app,
+      event.title_in(locale),
+      i18n::format(locale, Msg::ReminderStartsIn, &[("minutes", &minutes)]),
+      crate::notify::NotificationTarget { kind: event.kind, id: event.id },
+    );
+  }
+}
+
+/// Stop escalating the event: no more repeats and no further steps.
+pub fn acknowledge(app: &tauri::AppHandle, kind: EventKind, id: i64) {
+  let stopped = app.state::<EscalationState>().0.lock().unwrap_or_else(|e| e.into_inner()).acknowledge(kind, id, Utc::now());
+  if stopped {
+    applog!("📣 Escalation for {} {} acknowledged", kind.label(), id);
+  }
+  let _ = app.emit("reminder:acknowledged", Acknowledged { kind, id });
+}
+
+pub fn spawn(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    loop {
+      tick(&app).await;
+      tokio::time::sleep(TICK).await;
+    }
+  });
+}
@@ -0,0 +1,90 @@
+//! Installed font families for the overlay font picker (`list_fonts`).
+
+/// All font families GDI knows about.
+#[cfg(windows)]
+pub fn installed() -> Vec<String> {
+  use std::ffi::c_void;
+
+  const DEFAULT_CHARSET: u8 = 1;
+
+  #[repr(C)]
+  struct LogFontW {
+    height: i32,
+    width: i32,
+    escapement: i32,
+    orientation: i32,
+    weight: i32,
+    italic: u8,
+    underline: u8,
+    strike_out: u8,
+    char_set: u8,
+    out_precision: u8,
+    clip_precision: u8,
+    quality: u8,
+    pitch_and_family: u8,
+    face_name: [u16; 32],
+  }
+
+  type EnumProc = unsafe extern "system" fn(*const LogFontW, *const c_void, u32, isize) -> i32;
+
+  #[link(name = "user32")]
+  extern "system" {
+    fn GetDC(hwnd: isize) -> isize;
+    fn ReleaseDC(hwnd: isize, hdc: isize) -> i32;
+  }
+  #[link(name = "gdi32")]
+  extern "system" {
+    fn EnumFontFamiliesExW(hdc: isize, logfont: *const LogFontW, proc_: EnumProc, lparam: isize, flags: u32) -> i32;
+  }
+
+  unsafe extern "system" fn collect(font: *const LogFontW, _metric: *const c_void, _kind: u32, lparam: isize) -> i32 {
+    let names = &mut *(lparam as *mut Vec<String>);
+    let face = &(*font).face_name;
+    let len = face.iter().position(|&c| c == 0).unwrap_or(face.len());
+    names.push(String::from_utf16_lossy(&face[..len]));
+    1
+  }
+
+  // An empty face name with DEFAULT_CHARSET enumerates one entry per family and charset.
+  let query = LogFontW {
+    height: 0,
+    width: 0,
+    escapement: 0,
+    orientation: 0,
+    weight: 0,
+    italic: 0,
+    underline: 0,
+    strike_out: 0,
+    char_set: DEFAULT_CHARSET,
+    out_precision: 0,
+    clip_precision: 0,
+    quality: 0,
+    pitch_and_family: 0,
+    face_name: [0; 32],
+  };
+  let mut names: Vec<String> = Vec::new();
+  unsafe {
+    let hdc = GetDC(0);
+    if hdc == 0 {
+      return Vec::new();
+    }
+    EnumFontFamiliesExW(hdc, &query, collect, &mut names as *mut Vec<String> as isize, 0);
+    ReleaseDC(0, hdc);
+  }
+  helltime_core::fonts::normalize(names)
+}
+
+/// fontconfig's view of the installed families.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn installed() -> Vec<String> {
+  match std::process::Command::new("fc-list").args([":", "family"]).output() {
+    Ok(out) if out.status.success() => helltime_core::fonts::parse_fc_list(&String::from_utf8_lossy(&out.stdout)),
+    _ => Vec::new(),
+  }
+}
+
+/// No enumeration yet; the UI offers a free-text family name instead.
+#[cfg(target_os = "macos")]
+pub fn installed() -> Vec<String> {
+  Vec::new()
+}
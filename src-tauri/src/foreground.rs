@@ -0,0 +1,102 @@
+//! Getting a restored main window into the foreground.
+//!
+//! Windows' foreground-lock rules let `SetForegroundWindow` fail for a process that
+//! did not receive the last input (a notification click or a deep link often does
+//! not count), which leaves the window restored but behind the game. After a
+//! restore the window is checked and, if needed, pushed to the front again through
+//! `AllowSetForegroundWindow` and an attached input queue.
+
+use serde::Serialize;
+use std::time::Duration;
+use tauri::{Emitter, Manager, WebviewWindow};
+
+/// Fallback attempts before the restore is reported as failed.
+pub const ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// `window:restore-failed` payload.
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreFailed {
+  pub attempts: u32,
+}
+
+/// Verify in the background that `window` reached the foreground, retrying with
+/// the fallback path; emits `window:restore-failed` if it never gets there.
+pub fn ensure(window: WebviewWindow) {
+  tauri::async_runtime::spawn(async move {
+    for attempt in 0..=ATTEMPTS {
+      tokio::time::sleep(RETRY_DELAY).await;
+      if is_foreground(&window) {
+        if attempt > 0 {
+          applog!("🔼 Window reached the foreground after {attempt} retries");
+        }
+        return;
+      }
+      if attempt < ATTEMPTS {
+        force(&window);
+      }
+    }
+    applog!("⚠ Window restore did not reach the foreground after {ATTEMPTS} retries");
+    let _ = window.app_handle().emit("window:restore-failed", RestoreFailed { attempts: ATTEMPTS });
+  });
+}
+
+#[cfg(windows)]
+#[link(name = "user32")]
+extern "system" {
+  fn GetForegroundWindow() -> isize;
+  fn GetWindowThreadProcessId(hwnd: isize, process_id: *mut u32) -> u32;
+  fn AttachThreadInput(attach: u32, attach_to: u32, do_attach: i32) -> i32;
+  fn AllowSetForegroundWindow(process_id: u32) -> i32;
+  fn BringWindowToTop(hwnd: isize) -> i32;
+  fn SetForegroundWindow(hwnd: isize) -> i32;
+}
+
+#[cfg(windows)]
+#[link(name = "kernel32")]
+extern "system" {
+  fn GetCurrentThreadId() -> u32;
+}
+
+#[cfg(windows)]
+fn is_foreground(window: &WebviewWindow) -> bool {
+  match window.hwnd() {
+    Ok(hwnd) => unsafe { GetForegroundWindow() == hwnd.0 as isize },
+    // Nothing to compare against; do not report a failure we cannot see.
+    Err(_) => true,
+  }
+}
+
+/// Share the input queue of the current foreground thread for the duration of the
+/// call, which is what makes `SetForegroundWindow` succeed despite the lock.
+#[cfg(windows)]
+fn force(window: &WebviewWindow) {
+  const ASFW_ANY: u32 = u32::MAX;
+
+  let _ = window.unminimize();
+  let _ = window.set_focus();
+  let Ok(hwnd) = window.hwnd() else { return };
+  let hwnd = hwnd.0 as isize;
+  unsafe {
+    AllowSetForegroundWindow(ASFW_ANY);
+    let current = GetCurrentThreadId();
+    let foreground = GetWindowThreadProcessId(GetForegroundWindow(), std::ptr::null_mut());
+    let attached = foreground != 0 && foreground != current && AttachThreadInput(current, foreground, 1) != 0;
+    BringWindowToTop(hwnd);
+    SetForegroundWindow(hwnd);
+    if attached {
+      AttachThreadInput(current, foreground, 0);
+    }
+  }
+}
+
+#[cfg(not(windows))]
+fn is_foreground(window: &WebviewWindow) -> bool {
+  window.is_focused().unwrap_or(true)
+}
+
+#[cfg(not(windows))]
+fn force(window: &WebviewWindow) {
+  let _ = window.unminimize();
+  let _ = window.set_focus();
+}
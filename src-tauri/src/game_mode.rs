@@ -0,0 +1,90 @@
+//! Watches for Diablo IV and switches helltime into game mode while it runs:
+//! non-essential timers are throttled (see [`throttled`]), the UI is told to stop
+//! its animations (`game:running`) and the process drops to below-normal priority,
+//! which also covers the overlay's rendering.
+
+use helltime_core::game_mode::{self, CHECK_INTERVAL};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+#[derive(Default)]
+pub struct GameMode {
+  running: AtomicBool,
+}
+
+/// Whether game mode is active right now.
+pub fn is_running(app: &tauri::AppHandle) -> bool {
+  app.state::<GameMode>().running.load(Ordering::SeqCst)
+}
+
+/// `interval`, stretched while the game runs.
+pub fn throttled(app: &tauri::AppHandle, interval: Duration) -> Duration {
+  game_mode::throttled(interval, is_running(app))
+}
+
+pub fn spawn_watcher(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    loop {
+      let running = crate::preferences(&app).game_mode
+        && tauri::async_runtime::spawn_blocking(game_running).await.unwrap_or(false);
+      let previous = app.state::<GameMode>().running.swap(running, Ordering::SeqCst);
+      if previous != running {
+        applog!("🎮 Game mode {}", if running { "on: Diablo IV is running" } else { "off" });
+        set_low_priority(running);
+        let _ = app.emit("game:running", running);
+      }
+      tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+  });
+}
+
+#[cfg(windows)]
+fn game_running() -> bool {
+  use std::os::windows::process::CommandExt;
+  const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+  let filter = format!("IMAGENAME eq {}", game_mode::GAME_PROCESS);
+  match std::process::Command::new("tasklist")
+    .args(["/FI", filter.as_str(), "/FO", "CSV", "/NH"])
+    .creation_flags(CREATE_NO_WINDOW)
+    .output()
+  {
+    Ok(out) => game_mode::tasklist_has_game(&String::from_utf8_lossy(&out.stdout)),
+    Err(_) => false,
+  }
+}
+
+/// Under Wine/Proton the game shows up with its Windows executable name.
+#[cfg(target_os = "linux")]
+fn game_running() -> bool {
+  let Ok(entries) = std::fs::read_dir("/proc") else { return false };
+  entries.flatten().any(|entry| {
+    std::fs::read_to_string(entry.path().join("comm")).is_ok_and(|comm| game_mode::is_game_process(&comm))
+  })
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn game_running() -> bool {
+  false
+}
+
+#[cfg(windows)]
+fn set_low_priority(low: bool) {
+  const NORMAL_PRIORITY_CLASS: u32 = 0x0000_0020;
+  const BELOW_NORMAL_PRIORITY_CLASS: u32 = 0x0000_4000;
+
+  #[link(name = "kernel32")]
+  extern "system" {
+    fn GetCurrentProcess() -> isize;
+    fn SetPriorityClass(process: isize, priority_class: u32) -> i32;
+  }
+
+  let class = if low { BELOW_NORMAL_PRIORITY_CLASS } else { NORMAL_PRIORITY_CLASS };
+  if unsafe { SetPriorityClass(GetCurrentProcess(), class) } == 0 {
+    applog!("⚠ Failed to change process priority");
+  }
+}
+
+#[cfg(not(windows))]
+fn set_low_priority(_low: bool) {}
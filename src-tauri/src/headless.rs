@@ -0,0 +1,140 @@
+//! Reminders without the UI. Normally the UI polls the schedule and fires reminders
+//! with the user's lead times; while headless (see `webview`), this loop does both,
+//! from the lead times the UI mirrored into the preferences (`reminder_times`) and
+//! the backend's one-off reminders. It ends when the main window is opened after all.
+
+use crate::notify::NotificationTarget;
+use crate::AppState;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use helltime_core::helltide;
+use helltime_core::i18n::{self, Locale, Msg};
+use helltime_core::reminders::{self, FIRE_WINDOW};
+use helltime_core::schedule::{EventKind, Titled};
+use std::collections::HashMap;
+use std::time::Duration;
+use tauri::Manager;
+
+/// Well inside `reminders::FIRE_WINDOW`; not stretched by game mode, or reminders
+/// would be missed.
+const TICK: Duration = Duration::from_secs(15);
+
+/// Fired reminder keys are forgotten this long after they fired.
+const FIRED_RETENTION: chrono::Duration = chrono::Duration::hours(2);
+
+fn fire(app: &tauri::AppHandle, title: String, body: String, target: NotificationTarget) {
+  app.state::<AppState>().inner().metrics.reminder_fired();
+  crate::telemetry::record(app, helltime_core::telemetry::Feature::Reminder);
+  crate::notify::queue(app, title, body, target);
+}
+
+fn minutes_text(locale: Locale, msg: Msg, until: ChronoDuration) -> String {
+  // Rounded up, so "in 5 min" is not shown as 4 a few seconds after firing.
+  let minutes = ((until.num_seconds().max(0) + 59) / 60).to_string();
+  i18n::format(locale, msg, &[("minutes", &minutes)])
+}
+
+async fn tick(app: &tauri::AppHandle, fired: &mut HashMap<String, DateTime<Utc>>) -> Result<(), String> {
+  let state = app.state::<AppState>();
+  // Served from the cache while it is fresh, so this polls at the cache TTL.
+  let schedule = crate::load_schedule(app, &state, false).await?.schedule;
+  let now = state.inner().clock.lock().await.server_now();
+  fired.retain(|_, at| now - *at < FIRED_RETENTION);
+
+  let prefs = crate::preferences(app);
+  let paused = state.inner().reminder_pause.lock().unwrap_or_else(|e| e.into_inner()).is_paused(Utc::now());
+  if !prefs.reminders_enabled || paused || crate::shutdown::in_progress() {
+    return Ok(());
+  }
+  let locale = crate::locale::current(app);
+  let times = &prefs.reminder_times;
+
+  // The escalation (see `escalation`) replaces the per-category timers; this loop
+  // still keeps the schedule fresh for it.
+  if !prefs.reminder_escalation.enabled {
+    for kind in prefs.shown_kinds() {
+      for reminder in reminders::plan_for_next(&schedule, kind, times.minutes_before(kind), now) {
+        if !reminder.is_due(now) || fired.insert(reminder.key(), now).is_some() {
+          continue;
+        }
+        if state.inner().history.lock().await.attended(kind, reminder.event.id) {
+          continue;
+        }
+        let minutes = reminder.minutes_before.to_string();
+        fire(
+          app,
+          reminder.event.title_in(locale),
+          i18n::format(locale, Msg::ReminderStartsIn, &[("minutes", &minutes)]),
+          NotificationTarget { kind, id: reminder.event.id },
+        );
+      }
+    }
+  }
+
+  // One-off reminders were asked for explicitly, so they fire even for categories
+  // without regular reminders (as in the UI).
+  let custom = state.inner().custom_reminders.lock().unwrap_or_else(|e| e.into_inner()).list(now);
+  for reminder in custom {
+    if !reminder.is_due(now) || fired.insert(reminder.key(), now).is_some() {
+      continue;
+    }
+    let title = schedule
+      .events(reminder.kind)
+      .iter()
+      .find(|e| e.id == reminder.event_id)
+      .map(|e| e.title_in(locale))
+      .unwrap_or_else(|| i18n::event_label(locale, reminder.kind).to_string());
+    fire(
+      app,
+      title,
+      minutes_text(locale, Msg::ReminderStartsIn, reminder.event_start - now),
+      NotificationTarget { kind: reminder.kind, id: reminder.event_id },
+    );
+  }
+
+  if !prefs.shows(EventKind::Helltide) {
+    return Ok(());
+  }
+  let cadence = crate::cadence::current(app).helltide;
+  let helltide_title = i18n::event_label(locale, EventKind::Helltide);
+  if let Some(minutes_before) = times.helltide_end {
+    for reminder in reminders::plan_helltide_end(&schedule, &cadence, minutes_before, now) {
+      if !reminder.is_due(now) || fired.insert(reminder.key(), now).is_some() {
+        continue;
+      }
+      fire(
+        app,
+        helltide_title.to_string(),
+        minutes_text(locale, Msg::ReminderEndsIn, reminder.end - now),
+        NotificationTarget { kind: EventKind::Helltide, id: reminder.helltide_id },
+      );
+    }
+  }
+  if let Some(minutes_before) = times.chest {
+    for reset in helltide::upcoming_resets(&schedule, &cadence, now) {
+      let fire_at = reset.at - ChronoDuration::minutes(minutes_before);
+      if now < fire_at || now > fire_at + FIRE_WINDOW || fired.insert(reset.key(), now).is_some() {
+        continue;
+      }
+      fire(
+        app,
+        helltide_title.to_string(),
+        minutes_text(locale, Msg::ReminderChestIn, reset.at - now),
+        NotificationTarget { kind: EventKind::Helltide, id: reset.helltide_id },
+      );
+    }
+  }
+  Ok(())
+}
+
+pub fn spawn(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    applog!("🕶 Running headless: reminders come from the backend");
+    let mut fired = HashMap::new();
+    while crate::webview::is_headless() {
+      if let Err(e) = tick(&app, &mut fired).await {
+        applog!("⚠ Headless reminder check failed: {}", e);
+      }
+      tokio::time::sleep(TICK).await;
+    }
+  });
+}
@@ -0,0 +1,178 @@
+//! Global hotkeys for the tray actions (see `helltime_core::shortcuts`): by default
+//! Ctrl+Alt+N flashes the next event on the overlay, Ctrl+Alt+O makes the
+//! click-through overlay clickable for a few seconds, Ctrl+Alt+H shows or hides the
+//! window and Ctrl+Alt+P pauses reminders for an hour. Registered through the Win32
+//! API on a thread of its own, so they keep working while the main webview is hidden,
+//! loading or gone; [`reload`] re-registers them after the bindings changed.
+//! Other platforms have no global hotkeys yet; the tray menu offers the same actions.
+
+use crate::AppState;
+use helltime_core::controller::Message;
+use helltime_core::shortcuts::{self, ShortcutAction};
+use serde::Serialize;
+use std::sync::Mutex as StdMutex;
+use tauri::Manager;
+
+/// Actions whose hotkey was registered; the others are unbound or taken by another
+/// program.
+static REGISTERED: StdMutex<Vec<ShortcutAction>> = StdMutex::new(Vec::new());
+
+/// One row of `list_shortcuts`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutInfo {
+  pub action: ShortcutAction,
+  /// The binding in effect, e.g. `Ctrl+Alt+N`.
+  pub shortcut: Option<String>,
+  pub default_shortcut: Option<&'static str>,
+  /// The tray menu item doing the same.
+  pub tray_item: &'static str,
+  /// Whether the hotkey works right now (not unbound, not taken elsewhere).
+  pub registered: bool,
+}
+
+pub fn list(app: &tauri::AppHandle) -> Vec<ShortcutInfo> {
+  let registered = REGISTERED.lock().unwrap_or_else(|e| e.into_inner()).clone();
+  shortcuts::bindings(&crate::preferences(app).shortcuts)
+    .into_iter()
+    .map(|(action, shortcut)| ShortcutInfo {
+      action,
+      shortcut: shortcut.map(|s| s.to_string()),
+      default_shortcut: action.default_binding(),
+      tray_item: action.tray_item(),
+      registered: registered.contains(&action),
+    })
+    .collect()
+}
+
+/// Run `action`, from a hotkey or the tray menu.
+pub fn run(app: &tauri::AppHandle, action: ShortcutAction) {
+  match action {
+    ShortcutAction::ToggleWindow => crate::controller::send(app, Message::ToggleWindow),
+    ShortcutAction::ToggleOverlay => crate::controller::send(app, Message::ToggleOverlay),
+    ShortcutAction::ToggleReminders => crate::controller::send(app, Message::ToggleReminders),
+    ShortcutAction::PauseReminders => crate::update_reminder_pause(app, |p| {
+      p.pause_for(chrono::Utc::now(), chrono::Duration::hours(1))
+    }),
+    ShortcutAction::ResumeReminders => crate::update_reminder_pause(app, |p| p.resume()),
+    ShortcutAction::RevealOverlay => {
+      if let Err(e) = crate::overlay::OverlayFacade::new(app).reveal() {
+        applog!("✗ Overlay reveal failed: {}", e);
+      }
+    }
+    ShortcutAction::PeekNextEvent => {
+      let app = app.clone();
+      tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        if let Err(e) = crate::peek_next_event(app.clone(), state).await {
+          applog!("✗ Peek failed: {}", e);
+        }
+      });
+    }
+  }
+}
+
+#[cfg(windows)]
+mod win32 {
+  use std::sync::atomic::{AtomicU32, Ordering};
+
+  pub const MOD_NOREPEAT: u32 = 0x4000;
+  pub const WM_HOTKEY: u32 = 0x0312;
+  /// Posted by [`super::reload`].
+  pub const WM_RELOAD: u32 = 0x8000 + 1;
+
+  /// Id of the hotkey thread, 0 until it runs.
+  pub static THREAD: AtomicU32 = AtomicU32::new(0);
+
+  #[repr(C)]
+  #[derive(Default)]
+  pub struct Msg {
+    pub hwnd: isize,
+    pub message: u32,
+    pub wparam: usize,
+    pub lparam: isize,
+    pub time: u32,
+    pub pt_x: i32,
+    pub pt_y: i32,
+  }
+
+  #[link(name = "user32")]
+  extern "system" {
+    pub fn RegisterHotKey(hwnd: isize, id: i32, modifiers: u32, vk: u32) -> i32;
+    pub fn UnregisterHotKey(hwnd: isize, id: i32) -> i32;
+    pub fn GetMessageW(msg: *mut Msg, hwnd: isize, filter_min: u32, filter_max: u32) -> i32;
+    pub fn PostThreadMessageW(thread: u32, msg: u32, wparam: usize, lparam: isize) -> i32;
+  }
+
+  #[link(name = "kernel32")]
+  extern "system" {
+    pub fn GetCurrentThreadId() -> u32;
+  }
+
+  pub fn thread() -> u32 {
+    THREAD.load(Ordering::SeqCst)
+  }
+}
+
+/// Register the bindings from the preferences, replacing the previous ones.
+#[cfg(windows)]
+fn register(app: &tauri::AppHandle) {
+  use win32::*;
+
+  let mut registered = REGISTERED.lock().unwrap_or_else(|e| e.into_inner());
+  for action in registered.drain(..) {
+    unsafe { UnregisterHotKey(0, action.hotkey_id()) };
+  }
+  for (action, shortcut) in shortcuts::bindings(&crate::preferences(app).shortcuts) {
+    let Some(shortcut) = shortcut else { continue };
+    let modifiers = shortcut.win32_modifiers() | MOD_NOREPEAT;
+    if unsafe { RegisterHotKey(0, action.hotkey_id(), modifiers, shortcut.virtual_key()) } == 0 {
+      applog!("⚠ Hotkey {} is already taken by another program", shortcut);
+    } else {
+      applog!("⌨ Hotkey {}: {:?}", shortcut, action);
+      registered.push(action);
+    }
+  }
+}
+
+#[cfg(windows)]
+pub fn spawn(app: tauri::AppHandle) {
+  use std::sync::atomic::Ordering;
+  use win32::*;
+
+  std::thread::spawn(move || {
+    win32::THREAD.store(unsafe { GetCurrentThreadId() }, Ordering::SeqCst);
+    register(&app);
+
+    // Without a window, WM_HOTKEY is posted to this thread's message queue.
+    let mut msg = Msg::default();
+    while unsafe { GetMessageW(&mut msg, 0, 0, 0) } > 0 {
+      match msg.message {
+        WM_HOTKEY => {
+          if let Some(action) = ShortcutAction::from_hotkey_id(msg.wparam as i32) {
+            crate::telemetry::record(&app, helltime_core::telemetry::Feature::Hotkey);
+            run(&app, action);
+          }
+        }
+        WM_RELOAD => register(&app),
+        _ => {}
+      }
+    }
+  });
+}
+
+#[cfg(not(windows))]
+pub fn spawn(_app: tauri::AppHandle) {}
+
+/// Re-register after the bindings changed; hotkeys belong to the thread that
+/// registered them, so the hotkey thread is asked to do it.
+#[cfg(windows)]
+pub fn reload() {
+  let thread = win32::thread();
+  if thread != 0 {
+    unsafe { win32::PostThreadMessageW(thread, win32::WM_RELOAD, 0, 0) };
+  }
+}
+
+#[cfg(not(windows))]
+pub fn reload() {}
@@ -0,0 +1,208 @@
+use crate::SCHEDULE_URL;
+use helltime_core::fetch::{FetchError, HttpFetcher, HttpResponse, MAX_BODY_BYTES};
+use helltime_core::prefs::{NetworkSettings, ProxyMode};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use std::future::Future;
+use std::time::Duration;
+
+const USER_AGENT: &str = "helltime/0.1 (+https://github.com/)";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Until the response headers arrive, connecting included.
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+/// For reading the body once the headers are in, so a trickling upstream cannot
+/// hold a fetch open indefinitely.
+const BODY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `HttpFetcher` backed by reqwest; the only implementation that touches the network.
+#[derive(Clone)]
+pub struct ReqwestFetcher {
+  /// Carries the API token and extra headers; only used for the schedule API's host.
+  client: reqwest::Client,
+  /// Same proxy and TLS settings without the API token and extra headers, for
+  /// every other service (updates, cadence, telemetry, phone push).
+  plain: reqwest::Client,
+}
+
+impl Default for ReqwestFetcher {
+  fn default() -> Self {
+    let client = reqwest::Client::builder()
+      .user_agent(USER_AGENT)
+      .connect_timeout(CONNECT_TIMEOUT)
+      .build()
+      .unwrap_or_default();
+    Self { plain: client.clone(), client }
+  }
+}
+
+impl ReqwestFetcher {
+  /// Client honoring the user's proxy and TLS settings. Fails on an unreadable CA
+  /// bundle or an invalid proxy URL instead of silently ignoring them.
+  pub fn new(network: &NetworkSettings) -> Result<Self, String> {
+    network.validate()?;
+    if network.insecure_tls {
+      applog!("⚠ TLS certificate verification is disabled");
+    }
+    let plain = builder(network)?.build().map_err(|e| format!("HTTP client: {e}"))?;
+    let client = builder(network)?
+      .default_headers(default_headers(network)?)
+      .build()
+      .map_err(|e| format!("HTTP client: {e}"))?;
+    Ok(Self { client, plain })
+  }
+
+  /// Copy that never sends the API token or extra headers, for GETs to other hosts.
+  pub fn plain(&self) -> Self {
+    Self { client: self.plain.clone(), plain: self.plain.clone() }
+  }
+
+  /// The client for `url`: the one with the credentials only for the schedule API.
+  fn client_for(&self, url: &str) -> &reqwest::Client {
+    let host = |url: &str| reqwest::Url::parse(url).ok().and_then(|u| u.host_str().map(str::to_ascii_lowercase));
+    match host(url) {
+      Some(h) if Some(&h) == host(SCHEDULE_URL).as_ref() => &self.client,
+      _ => &self.plain,
+    }
+  }
+}
+
+/// Proxy, TLS and user agent from the settings.
+fn builder(network: &NetworkSettings) -> Result<reqwest::ClientBuilder, String> {
+  let mut builder = reqwest::Client::builder().connect_timeout(CONNECT_TIMEOUT);
+
+  match network.proxy_mode {
+    // reqwest picks up HTTP(S)_PROXY / ALL_PROXY on its own.
+    ProxyMode::System => {}
+    ProxyMode::Direct => builder = builder.no_proxy(),
+    ProxyMode::Manual => {
+      let url = network.proxy_url.as_deref().unwrap_or_default().trim();
+      let proxy = reqwest::Proxy::all(url).map_err(|e| format!("invalid proxy URL: {e}"))?;
+      builder = builder.proxy(proxy);
+    }
+  }
+
+  if let Some(path) = &network.ca_bundle {
+    let pem = std::fs::read(path).map_err(|e| format!("read CA bundle {}: {e}", path.display()))?;
+    let certs = reqwest::Certificate::from_pem_bundle(&pem).map_err(|e| format!("invalid CA bundle: {e}"))?;
+    for cert in certs {
+      builder = builder.add_root_certificate(cert);
+    }
+  }
+
+  if network.insecure_tls {
+    builder = builder.danger_accept_invalid_certs(true);
+  }
+
+  Ok(builder.user_agent(network.user_agent.as_deref().map(str::trim).unwrap_or(USER_AGENT)))
+}
+
+/// Token and extra headers, marked sensitive so reqwest never prints their values.
+fn default_headers(network: &NetworkSettings) -> Result<HeaderMap, String> {
+  let mut headers = HeaderMap::new();
+  if let Some(token) = &network.api_token {
+    let mut value =
+      HeaderValue::from_str(&format!("Bearer {}", token.trim())).map_err(|_| "invalid API token".to_string())?;
+    value.set_sensitive(true);
+    headers.insert(AUTHORIZATION, value);
+  }
+  for header in &network.extra_headers {
+    let name = HeaderName::from_bytes(header.name.as_bytes()).map_err(|_| format!("invalid header name: {}", header.name))?;
+    let mut value =
+      HeaderValue::from_str(header.value.trim()).map_err(|_| format!("invalid value for header {}", header.name))?;
+    value.set_sensitive(true);
+    headers.append(name, value);
+  }
+  Ok(headers)
+}
+
+fn map_err(e: reqwest::Error) -> FetchError {
+  if e.is_timeout() {
+    FetchError::Timeout
+  } else {
+    FetchError::Network(e.to_string())
+  }
+}
+
+/// Read the body in chunks, refusing it as soon as it passes [`MAX_BODY_BYTES`].
+async fn read_body(mut resp: reqwest::Response) -> Result<Vec<u8>, FetchError> {
+  if resp.content_length().is_some_and(|len| len > MAX_BODY_BYTES as u64) {
+    return Err(FetchError::TooLarge(MAX_BODY_BYTES));
+  }
+  let mut body = Vec::new();
+  while let Some(chunk) = resp.chunk().await.map_err(map_err)? {
+    if body.len() + chunk.len() > MAX_BODY_BYTES {
+      return Err(FetchError::TooLarge(MAX_BODY_BYTES));
+    }
+    body.extend_from_slice(&chunk);
+  }
+  Ok(body)
+}
+
+impl ReqwestFetcher {
+  /// POST `body` as JSON and return the status; the response body is not read. The
+  /// schedule API's token and extra headers are not sent along.
+  pub async fn post_json<T: serde::Serialize + ?Sized>(&self, url: &str, body: &T) -> Result<u16, FetchError> {
+    self.post_json_authorized(url, body, None).await
+  }
+
+  /// [`post_json`](Self::post_json) with `Authorization: Bearer <token>`.
+  pub async fn post_json_authorized<T: serde::Serialize + ?Sized>(
+    &self,
+    url: &str,
+    body: &T,
+    token: Option<&str>,
+  ) -> Result<u16, FetchError> {
+    let mut request = self.plain.post(url).json(body);
+    if let Some(token) = token {
+      request = request.bearer_auth(token);
+    }
+    let resp = tokio::time::timeout(RESPONSE_TIMEOUT, request.send())
+      .await
+      .map_err(|_| FetchError::Timeout)?
+      .map_err(map_err)?;
+    Ok(resp.status().as_u16())
+  }
+
+  /// POST an already serialized JSON `body` with extra `headers`, e.g. a signature
+  /// over exactly these bytes.
+  pub async fn post_json_text(&self, url: &str, body: String, headers: &[(&str, String)]) -> Result<u16, FetchError> {
+    let mut request = self.plain.post(url).header(reqwest::header::CONTENT_TYPE, "application/json").body(body);
+    for (name, value) in headers {
+      request = request.header(*name, value);
+    }
+    let resp = tokio::time::timeout(RESPONSE_TIMEOUT, request.send())
+      .await
+      .map_err(|_| FetchError::Timeout)?
+      .map_err(map_err)?;
+    Ok(resp.status().as_u16())
+  }
+}
+
+impl HttpFetcher for ReqwestFetcher {
+  fn get(&self, url: &str) -> impl Future<Output = Result<HttpResponse, FetchError>> + Send {
+    let request = self.client_for(url).get(url);
+
+    async move {
+      let resp = tokio::time::timeout(RESPONSE_TIMEOUT, request.send())
+        .await
+        .map_err(|_| FetchError::Timeout)?
+        .map_err(map_err)?;
+      let status = resp.status().as_u16();
+      let date = resp
+        .headers()
+        .get(reqwest::header::DATE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+      let headers = resp
+        .headers()
+        .iter()
+        .map(|(name, value)| (name.to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned()))
+        .collect();
+      let body = tokio::time::timeout(BODY_TIMEOUT, read_body(resp)).await.map_err(|_| FetchError::BodyTimeout)??;
+      Ok(HttpResponse { status, date, headers, body })
+    }
+  }
+
+  fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+    tokio::time::sleep(duration)
+  }
+}
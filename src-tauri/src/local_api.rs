@@ -0,0 +1,179 @@
+use crate::AppState;
+use helltime_core::countdown::{self, NextEvent};
+use helltime_core::ics;
+use helltime_core::local_api::{self, Reply, Route, MAX_REQUEST_HEAD, OBS_PAGE, SSE_INTERVAL_SECS};
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tauri::Manager;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A running localhost API server; dropping it does not stop it, call `stop`.
+pub struct LocalApiServer {
+  pub port: u16,
+  task: tauri::async_runtime::JoinHandle<()>,
+  /// Ends open `/events` streams, which outlive the accept loop otherwise.
+  shutdown: watch::Sender<bool>,
+}
+
+impl LocalApiServer {
+  pub fn stop(self) {
+    let _ = self.shutdown.send(true);
+    self.task.abort();
+    applog!("🔌 Local API stopped (port {})", self.port);
+  }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StatusBody {
+  version: &'static str,
+  has_schedule: bool,
+  cache_age_secs: Option<u64>,
+  clock_offset_ms: i64,
+  server_now: String,
+}
+
+async fn next_events(app: &tauri::AppHandle) -> Option<Vec<NextEvent>> {
+  let state = app.state::<AppState>();
+  let schedule = state.inner().cache.lock().await.value().cloned()?;
+  let now = state.inner().clock.lock().await.server_now();
+  let prefs = crate::preferences(app);
+  Some(countdown::next_events(&schedule, &prefs.shown_kinds(), now, prefs.secondary_zone.as_ref()))
+}
+
+/// Regenerated on every request, so subscribers always get the current schedule.
+async fn calendar_feed(app: &tauri::AppHandle) -> Option<String> {
+  let state = app.state::<AppState>();
+  let schedule = state.inner().cache.lock().await.value().cloned()?;
+  let now = state.inner().clock.lock().await.server_now();
+  let prefs = crate::preferences(app);
+  let events = ics::upcoming(&schedule, &prefs.shown_kinds(), now, ics::DEFAULT_HOURS_AHEAD);
+  Some(ics::feed(
+    &events,
+    crate::locale::current(app),
+    &crate::cadence::current(app).helltide,
+    chrono::Utc::now(),
+    prefs.secondary_zone.as_ref(),
+  ))
+}
+
+async fn reply_for(app: &tauri::AppHandle, route: Route) -> Reply {
+  let state = app.state::<AppState>();
+  let state = state.inner();
+  match route {
+    // Hidden categories stay out of here too, as everywhere else.
+    Route::Schedule => match state.cache.lock().await.value() {
+      Some(schedule) => Reply::json(200, &schedule.only(&crate::preferences(app).shown_kinds())),
+      None => Reply::error(503, "no schedule fetched yet"),
+    },
+    Route::Next => match next_events(app).await {
+      Some(events) => Reply::json(200, &events),
+      None => Reply::error(503, "no schedule fetched yet"),
+    },
+    Route::Status => {
+      let (has_schedule, cache_age_secs) = {
+        let cache = state.cache.lock().await;
+        (cache.value().is_some(), cache.age().map(|age| age.as_secs()))
+      };
+      let clock = state.clock.lock().await.status();
+      Reply::json(
+        200,
+        &StatusBody {
+          version: env!("CARGO_PKG_VERSION"),
+          has_schedule,
+          cache_age_secs,
+          clock_offset_ms: clock.offset_ms,
+          server_now: clock.server_now,
+        },
+      )
+    }
+    Route::ObsPage => Reply::html(OBS_PAGE),
+    Route::Calendar => match calendar_feed(app).await {
+      Some(feed) => Reply::calendar(feed),
+      None => Reply::error(503, "no schedule fetched yet"),
+    },
+    // Streams are handled by `stream_events`; never routed here.
+    Route::Events => Reply::error(500, "event stream not available"),
+    Route::NotFound => Reply::error(404, "unknown endpoint (try /schedule, /next, /status, /obs, /calendar.ics)"),
+    Route::MethodNotAllowed => Reply::error(405, "only GET is supported"),
+    Route::BadRequest => Reply::error(400, "malformed request"),
+  }
+}
+
+async fn read_head(stream: &mut TcpStream) -> Option<String> {
+  let mut buf = Vec::with_capacity(1024);
+  let mut chunk = [0u8; 1024];
+  loop {
+    let n = stream.read(&mut chunk).await.ok()?;
+    if n == 0 {
+      break;
+    }
+    buf.extend_from_slice(&chunk[..n]);
+    if buf.windows(4).any(|w| w == b"\r\n\r\n") || buf.len() >= MAX_REQUEST_HEAD {
+      break;
+    }
+  }
+  Some(String::from_utf8_lossy(&buf).into_owned())
+}
+
+/// Push `/next` payloads until the client (usually an OBS browser source) disconnects.
+async fn stream_events(app: tauri::AppHandle, mut stream: TcpStream, mut shutdown: watch::Receiver<bool>) {
+  if stream.write_all(local_api::sse_head()).await.is_err() {
+    return;
+  }
+  loop {
+    let events = next_events(&app).await.unwrap_or_default();
+    if stream.write_all(&local_api::sse_event(&events)).await.is_err() {
+      return;
+    }
+    tokio::select! {
+      _ = tokio::time::sleep(Duration::from_secs(SSE_INTERVAL_SECS)) => {}
+      _ = shutdown.changed() => return,
+    }
+  }
+}
+
+async fn serve_connection(app: tauri::AppHandle, mut stream: TcpStream, shutdown: watch::Receiver<bool>) {
+  let head = match tokio::time::timeout(READ_TIMEOUT, read_head(&mut stream)).await {
+    Ok(Some(head)) => head,
+    _ => return,
+  };
+  let route = local_api::route(&head);
+  if route == Route::Events {
+    stream_events(app, stream, shutdown).await;
+    return;
+  }
+  let reply = reply_for(&app, route).await;
+  let _ = stream.write_all(&reply.to_bytes()).await;
+  let _ = stream.shutdown().await;
+}
+
+/// Bind to loopback only; the API is for tools on this PC, never the network.
+pub async fn start(app: tauri::AppHandle, port: u16) -> Result<LocalApiServer, String> {
+  let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], port)))
+    .await
+    .map_err(|e| format!("cannot listen on 127.0.0.1:{port}: {e}"))?;
+  applog!("🔌 Local API listening on http://127.0.0.1:{}", port);
+
+  let (shutdown, shutdown_rx) = watch::channel(false);
+  let task = tauri::async_runtime::spawn(async move {
+    loop {
+      match listener.accept().await {
+        Ok((stream, _)) => {
+          tauri::async_runtime::spawn(serve_connection(app.clone(), stream, shutdown_rx.clone()));
+        }
+        Err(e) => {
+          applog!("⚠ Local API accept failed: {}", e);
+          tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+      }
+    }
+  });
+
+  Ok(LocalApiServer { port, task, shutdown })
+}
@@ -0,0 +1,43 @@
+//! Which language backend text (tray, tooltip, overlay title) uses.
+
+use helltime_core::i18n::Locale;
+
+/// The preference if set, otherwise the system language.
+pub fn current(app: &tauri::AppHandle) -> Locale {
+  crate::preferences(app).locale.unwrap_or_else(system)
+}
+
+/// POSIX locale variables first (also set by some Windows shells), then the user
+/// locale from Windows itself.
+pub fn system() -> Locale {
+  let mut tags: Vec<String> = ["LC_ALL", "LC_MESSAGES", "LANG"]
+    .iter()
+    .filter_map(|var| std::env::var(var).ok())
+    .filter(|value| !value.is_empty())
+    .collect();
+  tags.extend(windows_user_locale());
+  Locale::detect(tags.iter().map(String::as_str))
+}
+
+#[cfg(windows)]
+fn windows_user_locale() -> Option<String> {
+  const LOCALE_NAME_MAX_LENGTH: usize = 85;
+
+  #[link(name = "kernel32")]
+  extern "system" {
+    fn GetUserDefaultLocaleName(name: *mut u16, len: i32) -> i32;
+  }
+
+  let mut buf = [0u16; LOCALE_NAME_MAX_LENGTH];
+  // Returns the length in UTF-16 units including the terminating NUL, 0 on failure.
+  let len = unsafe { GetUserDefaultLocaleName(buf.as_mut_ptr(), buf.len() as i32) };
+  if len <= 1 {
+    return None;
+  }
+  String::from_utf16(&buf[..len as usize - 1]).ok()
+}
+
+#[cfg(not(windows))]
+fn windows_user_locale() -> Option<String> {
+  None
+}
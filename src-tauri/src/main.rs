@@ -1,288 +1,1022 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use serde::{Deserialize, Serialize};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::{Duration, Instant};
-use tauri::{Emitter, Listener, Manager, State};
-use tauri::menu::CheckMenuItem;
+use std::time::Instant;
+use tauri::{Emitter, Manager, State};
 use tokio::sync::Mutex;
 use std::sync::Mutex as StdMutex;
 
+#[macro_use]
+mod crash;
+
+mod about;
+mod autostart;
+mod cadence;
+mod cli;
+mod controller;
+mod deep_link;
+mod diagnostics;
+mod discord_rpc;
+mod escalation;
+mod fonts;
+mod foreground;
+mod game_mode;
+mod headless;
+mod hotkey;
+mod http;
+mod local_api;
+mod locale;
+mod motion;
+mod mqtt;
+mod network_watch;
+mod notify;
+mod overlay;
+mod power;
+mod power_saving;
+mod profiles;
+mod push;
+mod shutdown;
+mod sync;
+mod taskbar;
+mod telemetry;
+mod tray;
+mod tray_icon;
+mod updater;
+mod webhook;
+mod webview;
+mod window_geometry;
+
+use crash::CrashInfo;
+use deep_link::PendingLink;
+use diagnostics::DiagnosticsReport;
+use discord_rpc::DiscordPresence;
+use helltime_core::deep_link::DeepLink;
+use helltime_core::error::AppError;
+use helltime_core::boss_rotation;
+use helltime_core::cache::{self, ScheduleCache};
+use helltime_core::cadence::CadenceStore;
+use helltime_core::clock::{ClockSkew, ClockStatus};
+use helltime_core::connection::{ConnectionStatus, ConnectionTracker};
+use helltime_core::custom_reminders::{CustomReminder, CustomReminderStore};
+use helltime_core::countdown::{self, ActiveEvent, NextEvent};
+use helltime_core::fetch::{self as schedule_fetch, FetchError, HttpFetcher, RawResponse, RecordingFetcher, RetryPolicy};
+use helltime_core::helltide::{self, ChestReset};
+use helltime_core::i18n::{self, Locale, Msg};
+use helltime_core::ics;
+use helltime_core::json_store::JsonStore;
+use helltime_core::history::{EventHistory, EventStats, HistoryEntry, HistoryRange};
+use helltime_core::hud_layout::{HudLayout, Suggestion};
+use helltime_core::metrics::{Metrics, MetricsSnapshot};
+use helltime_core::onboarding::{OnboardingStatus, Permission};
+use helltime_core::overlay_style::OverlayStyle;
+use helltime_core::prefs::{CloseBehavior, Preferences, PrefsStore};
+use helltime_core::profiles::{Profile, ProfileList, ProfileStore};
+use helltime_core::rate_limit::{RateLimitedFetcher, TokenBucket};
+use helltime_core::reminders::{self, EndReminder, PlannedReminder, ReminderPause};
+use helltime_core::schedule::{EventKind, ScheduleResponse, Titled};
+use helltime_core::shortcuts::{self, ShortcutAction};
+use helltime_core::push::PushQueue;
+use helltime_core::telemetry::{Feature, Payload as TelemetryPayload, TelemetryQueue};
+use helltime_core::toast_template::{RenderedToast, ToastEvent, ToastTemplates};
+use helltime_core::update::UpdateInfo;
+use helltime_core::validation::{self, ValidationStats};
+use helltime_core::controller::Message;
+use helltime_core::window_state::Visibility;
+use http::ReqwestFetcher;
+use local_api::LocalApiServer;
+use overlay::OverlayFacade;
+use power::TimeJump;
+
+/// One schedule for every region: Diablo IV world events start at the same UTC
+/// instant on all realms, and the API takes no region or realm parameter. Should
+/// that change, the region belongs in this URL and in the cache key.
 const SCHEDULE_URL: &str = "https://helltides.com/api/schedule";
-const CACHE_TTL: Duration = Duration::from_secs(30);
-
-// ============================================================================
-// WINDOW STATE MANAGER - Provides deterministic, serialized window operations
-// ============================================================================
-
-/// Tracks the logical state of the main window for deterministic behavior
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum WindowVisibility {
-    Visible,    // Window is shown and in taskbar
-    Hidden,     // Window is hidden, only in tray (user closed/minimized)
-}
-
-/// Thread-safe window state manager with debouncing
-struct WindowStateManager {
-    /// Current logical visibility state
-    visibility: StdMutex<WindowVisibility>,
-    /// Last tray action timestamp for debouncing
-    last_tray_action: StdMutex<Instant>,
-    /// Lock to serialize window operations
-    operation_lock: StdMutex<()>,
-    /// Flag to prevent recursive event handling
-    in_transition: AtomicBool,
-}
-
-impl WindowStateManager {
-    fn new() -> Self {
-        Self {
-            visibility: StdMutex::new(WindowVisibility::Visible),
-            last_tray_action: StdMutex::new(Instant::now() - Duration::from_secs(10)),
-            operation_lock: StdMutex::new(()),
-            in_transition: AtomicBool::new(false),
-        }
-    }
+const SCHEDULE_PROVIDER: &str = "helltides.com";
 
-    /// Check if we should process a tray action (debounce rapid clicks)
-    fn should_process_tray_action(&self) -> bool {
-        let mut last = self.last_tray_action.lock().unwrap();
-        let now = Instant::now();
-        // Use 50ms debounce - filters double-events but allows normal clicks
-        if now.duration_since(*last) < Duration::from_millis(50) {
-            // Silent debounce for very rapid events (Tauri quirk)
-            return false;
-        }
-        *last = now;
-        true
-    }
+struct AppState {
+  cache: Mutex<ScheduleCache>,
+  /// What the sanity filter dropped from fetched schedules, for diagnostics.
+  validation: StdMutex<ValidationStats>,
+  clock: Mutex<ClockSkew>,
+  history: Mutex<EventHistory>,
+  /// Std mutex: swapped out when the network preferences change; cloning is cheap.
+  http: StdMutex<ReqwestFetcher>,
+  /// Courtesy limit for the schedule API; other hosts are not limited.
+  rate_limit: StdMutex<TokenBucket>,
+  /// Std mutex: also read by the synchronous tray tooltip code.
+  connection: StdMutex<ConnectionTracker>,
+  /// Last response from the schedule API, kept even when it failed to parse.
+  last_raw: Mutex<Option<RawResponse>>,
+  update: Mutex<Option<UpdateInfo>>,
+  local_api: Mutex<Option<LocalApiServer>>,
+  /// Std mutex: read synchronously from window event handlers.
+  prefs: StdMutex<PrefsStore>,
+  /// Pause is wall-clock based ("until tomorrow"), so it uses local time, not server time.
+  reminder_pause: StdMutex<JsonStore<ReminderPause>>,
+  /// One-off reminders for single events, see `add_custom_reminder`.
+  custom_reminders: StdMutex<CustomReminderStore>,
+  /// Std mutex: season timing, read by the predictor and the chest timers.
+  cadence: StdMutex<CadenceStore>,
+  /// Std mutex: also read while building the tray menu.
+  profiles: StdMutex<ProfileStore>,
+  /// Counters since startup (atomics, no lock), see `get_metrics`.
+  metrics: Metrics,
+  /// Std mutex: the opt-in usage counters, bumped from sync code.
+  telemetry: StdMutex<TelemetryQueue>,
+  /// Reminders waiting to be sent to phones.
+  push: StdMutex<PushQueue>,
+}
 
-    /// Get current visibility state
-    fn get_visibility(&self) -> WindowVisibility {
-        *self.visibility.lock().unwrap()
-    }
+impl AppState {
+  fn http(&self) -> ReqwestFetcher {
+    self.http.lock().unwrap_or_else(|e| e.into_inner()).clone()
+  }
 
-    /// Set visibility state
-    fn set_visibility(&self, state: WindowVisibility) {
-        let mut vis = self.visibility.lock().unwrap();
-        eprintln!("📍 Window state: {:?} → {:?}", *vis, state);
-        *vis = state;
-    }
+  /// Client for requests to the schedule API, which all go through the rate limiter.
+  fn schedule_http(&self) -> RateLimitedFetcher<'_, ReqwestFetcher> {
+    RateLimitedFetcher::new(self.http(), &self.rate_limit)
+  }
+}
+
+fn preferences(app: &tauri::AppHandle) -> Preferences {
+  let state = app.state::<AppState>();
+  let prefs = state.inner().prefs.lock().unwrap_or_else(|e| e.into_inner()).get().clone();
+  prefs
+}
+
+fn current_connection(app: &tauri::AppHandle) -> ConnectionStatus {
+  let state = app.state::<AppState>();
+  let tracker = state.inner().connection.lock().unwrap_or_else(|e| e.into_inner());
+  tracker.status(chrono::Utc::now())
+}
+
+/// Update the connection state after a fetch; on a change tell the UI and refresh the tray.
+fn record_connection(app: &tauri::AppHandle, change: impl FnOnce(&mut ConnectionTracker, chrono::DateTime<chrono::Utc>) -> bool) {
+  let changed = {
+    let state = app.state::<AppState>();
+    let mut tracker = state.inner().connection.lock().unwrap_or_else(|e| e.into_inner());
+    change(&mut tracker, chrono::Utc::now())
+  };
+  if changed {
+    notify_connection_changed(app);
+  }
+}
 
-    /// Acquire operation lock (serialize window operations)
-    fn acquire_lock(&self) -> std::sync::MutexGuard<'_, ()> {
-        self.operation_lock.lock().unwrap()
+fn notify_connection_changed(app: &tauri::AppHandle) {
+  let status = current_connection(app);
+  applog!("🌐 Connection: {:?}", status.state);
+  let _ = app.emit("connection:changed", &status);
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move { tray::refresh_tooltip(&app).await });
+}
+
+#[tauri::command]
+fn connection_status(app: tauri::AppHandle) -> ConnectionStatus {
+  current_connection(&app)
+}
+
+#[tauri::command]
+async fn fetch_schedule(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<ScheduleResponse, AppError> {
+  load_schedule(&app, &state, false).await.map(|loaded| loaded.schedule)
+}
+
+/// A schedule plus where it came from, for an explicit refresh button.
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScheduleRefresh {
+  schedule: ScheduleResponse,
+  served_from_cache: bool,
+  /// RFC 3339 time the schedule was fetched from the API.
+  fetched_at: Option<String>,
+  provider: &'static str,
+}
+
+/// Like `fetch_schedule`, but `force` skips the cache TTL. Forced refreshes still
+/// go through the rate limiter.
+#[tauri::command]
+async fn refresh_schedule(app: tauri::AppHandle, state: State<'_, AppState>, force: bool) -> Result<ScheduleRefresh, AppError> {
+  load_schedule(&app, &state, force).await
+}
+
+async fn load_schedule(app: &tauri::AppHandle, state: &State<'_, AppState>, force: bool) -> Result<ScheduleRefresh, AppError> {
+  if shutdown::in_progress() {
+    return Err(AppError::ShuttingDown);
+  }
+  if !force {
+    let cache = state.inner().cache.lock().await;
+    if let Some(value) = cache.fresh() {
+      state.inner().metrics.cache_hit();
+      return Ok(ScheduleRefresh {
+        schedule: value.clone(),
+        served_from_cache: true,
+        fetched_at: cache.fetched_at().map(|at| at.to_rfc3339()),
+        provider: SCHEDULE_PROVIDER,
+      });
     }
+  }
 
-    /// Check/set transition flag to prevent recursive handling
-    fn begin_transition(&self) -> bool {
-        !self.in_transition.swap(true, Ordering::SeqCst)
+  let http = state.inner().schedule_http();
+  let recording = RecordingFetcher::new(&http);
+  let started = Instant::now();
+  state.inner().metrics.fetch_started();
+  let fetched = schedule_fetch::fetch_schedule(&recording, SCHEDULE_URL, RetryPolicy::default()).await;
+  // Held back by the rate limiter: an attempt, but no request was made.
+  if !matches!(fetched, Err(FetchError::RateLimited(_))) {
+    state.inner().metrics.fetch_finished(started.elapsed(), fetched.is_ok());
+    if let Err(e) = &fetched {
+      let error = AppError::from(e.clone());
+      telemetry::record_error(app, &error);
+      webhook::app_error(app, &error);
     }
+  }
+  if let Some(raw) = recording.into_last() {
+    *state.inner().last_raw.lock().await = Some(raw);
+  }
+  let fetched = match fetched {
+    Ok(fetched) => {
+      record_connection(app, |tracker, now| tracker.record_success(now));
+      fetched
+    }
+    // Held back locally; says nothing about whether the API is reachable.
+    Err(e @ FetchError::RateLimited(_)) => return Err(e.into()),
+    Err(e) => {
+      record_connection(app, |tracker, now| tracker.record_failure(e.to_string(), now));
+      return Err(e.into());
+    }
+  };
+
+  if let Some(server_date) = fetched.server_date {
+    state.inner().clock.lock().await.record(server_date, fetched.sent_at, fetched.received_at);
+  }
 
-    fn end_transition(&self) {
-        self.in_transition.store(false, Ordering::SeqCst);
+  let mut json = fetched.schedule;
+
+  let dropped = validation::sanitize(&mut json, chrono::Utc::now());
+  if dropped.total() > 0 {
+    applog!(
+      "⚠ Dropped {} schedule entries: {} past, {} too far out, {} duplicate, {} bogus",
+      dropped.total(),
+      dropped.past,
+      dropped.too_far,
+      dropped.duplicates,
+      dropped.bogus
+    );
+  }
+  state.inner().validation.lock().unwrap_or_else(|e| e.into_inner()).record(dropped);
+
+  {
+    let observed: Vec<_> = EventKind::ALL.iter().flat_map(|kind| json.events(*kind)).collect();
+    let mut history = state.inner().history.lock().await;
+    history.record(&observed, chrono::Utc::now());
+    boss_rotation::annotate(&mut json, &history.boss_sequence());
+  }
+  // After recording: predictions are not observations.
+  helltide::extrapolate(&mut json, &cadence::current(app).helltide, chrono::Utc::now());
+
+  let changes = {
+    let mut cache = state.inner().cache.lock().await;
+    let changes = cache.value().map(|old| cache::diff(old, &json, chrono::Utc::now())).unwrap_or_default();
+    cache.store(json.clone());
+    changes
+  };
+  if !changes.is_empty() {
+    applog!("🔀 Schedule changed: {} shifted, {} cancelled", changes.shifted.len(), changes.cancelled.len());
+    let _ = app.emit("schedule:changed", &changes);
+    webhook::schedule_changed(app, &changes);
+    if state.inner().custom_reminders.lock().unwrap_or_else(|e| e.into_inner()).reschedule(&changes) {
+      let _ = app.emit("reminders:changed", ());
     }
+  }
+
+  Ok(ScheduleRefresh {
+    schedule: json,
+    served_from_cache: false,
+    fetched_at: Some(fetched.received_at.to_rfc3339()),
+    provider: SCHEDULE_PROVIDER,
+  })
 }
 
-// Global window state manager
-static WINDOW_STATE: std::sync::OnceLock<WindowStateManager> = std::sync::OnceLock::new();
+/// What the schedule API last returned, unparsed. `refetch` requests a new copy
+/// (e.g. when the schedule was served from cache since startup).
+#[tauri::command]
+async fn fetch_schedule_raw(state: State<'_, AppState>, refetch: Option<bool>) -> Result<Option<RawResponse>, AppError> {
+  let mut last = state.inner().last_raw.lock().await;
+  if refetch.unwrap_or(false) || last.is_none() {
+    let resp = state.inner().schedule_http().get(SCHEDULE_URL).await?;
+    *last = Some(RawResponse::new(SCHEDULE_URL, &resp, chrono::Utc::now()));
+  }
+  Ok(last.clone())
+}
 
-fn get_window_state() -> &'static WindowStateManager {
-    WINDOW_STATE.get_or_init(WindowStateManager::new)
+/// Write the last raw response (headers and body) to the data dir for a bug report.
+#[tauri::command]
+async fn save_raw_payload(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<String, AppError> {
+  let raw = state.inner().last_raw.lock().await.clone().ok_or_else(|| AppError::Other("no API response recorded yet".into()))?;
+  let dir = app.path().app_data_dir().map_err(|e| AppError::Storage(format!("no data dir: {e}")))?.join("raw");
+  std::fs::create_dir_all(&dir).map_err(|e| AppError::Storage(format!("create {}: {e}", dir.display())))?;
+
+  let path = dir.join(format!("schedule-{}.json", raw.received_at.format("%Y%m%d-%H%M%S")));
+  let json = serde_json::to_vec_pretty(&raw).map_err(|e| AppError::Other(e.to_string()))?;
+  std::fs::write(&path, json).map_err(|e| AppError::Storage(format!("write {}: {e}", path.display())))?;
+  applog!("💾 Saved raw API payload to {}", path.display());
+  Ok(path.display().to_string())
 }
 
-/// Restore window to visible state (show + taskbar + focus)
-fn restore_window(window: &tauri::WebviewWindow) {
-    let state = get_window_state();
+/// Milliseconds until the UI should poll the schedule again, per the poll preferences.
+#[tauri::command]
+async fn next_poll_delay(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<u64, AppError> {
+  let settings = preferences(&app).poll;
+  let cached = state.inner().cache.lock().await.value().cloned();
+  let now = state.inner().clock.lock().await.server_now();
+  Ok(power_saving::poll_delay(&app, settings.next_delay(cached.as_ref(), now)).as_millis() as u64)
+}
 
-    // Skip if already visible (optimization)
-    if state.get_visibility() == WindowVisibility::Visible {
-        // Still focus the window even if already visible
-        let _ = window.set_focus();
-        return;
-    }
+#[tauri::command]
+async fn clock_status(state: State<'_, AppState>) -> Result<ClockStatus, AppError> {
+  Ok(state.inner().clock.lock().await.status())
+}
 
-    let _lock = state.acquire_lock();
+#[tauri::command]
+async fn event_history(state: State<'_, AppState>, range: Option<HistoryRange>) -> Result<Vec<HistoryEntry>, AppError> {
+  Ok(state.inner().history.lock().await.query(&range.unwrap_or_default()))
+}
 
-    if !state.begin_transition() {
-        eprintln!("⚠ Restore skipped - already in transition");
-        return;
-    }
+#[tauri::command]
+async fn event_stats(state: State<'_, AppState>) -> Result<EventStats, AppError> {
+  Ok(state.inner().history.lock().await.stats(chrono::Utc::now()))
+}
+
+#[tauri::command]
+async fn run_diagnostics(app: tauri::AppHandle) -> Result<DiagnosticsReport, AppError> {
+  Ok(diagnostics::run(&app).await)
+}
+
+fn crash_dir(app: &tauri::AppHandle) -> Result<std::path::PathBuf, AppError> {
+  app
+    .path()
+    .app_data_dir()
+    .map(|dir| dir.join("crashes"))
+    .map_err(|e| AppError::Storage(format!("no data dir: {e}")))
+}
+
+/// Crash left behind by the previous session (reported once).
+#[tauri::command]
+fn previous_crash(app: tauri::AppHandle) -> Result<Option<CrashInfo>, AppError> {
+  Ok(crash::take_previous_crash(&crash_dir(&app)?))
+}
 
-    eprintln!("🔼 Restoring window...");
+#[tauri::command]
+fn open_crash_report(app: tauri::AppHandle, path: String) -> Result<(), AppError> {
+  use tauri_plugin_opener::OpenerExt;
+  let dir = crash_dir(&app)?;
+  if !crash::is_crash_report(&dir, std::path::Path::new(&path)) {
+    return Err(AppError::Other("not a crash report".to_string()));
+  }
+  app
+    .opener()
+    .open_path(path, None::<&str>)
+    .map_err(|e| AppError::Storage(format!("open failed: {e}")))
+}
 
-    // Order matters: show first, then configure
-    let _ = window.show();
-    let _ = window.unminimize();
-    let _ = window.set_skip_taskbar(false);
-    let _ = window.set_focus();
+/// Next upcoming event per category, with countdowns corrected for local clock skew.
+#[tauri::command]
+async fn next_events(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<Vec<NextEvent>, AppError> {
+  let schedule = fetch_schedule(app.clone(), state.clone()).await?;
+  let now = state.inner().clock.lock().await.server_now();
+  let prefs = preferences(&app);
+  Ok(countdown::next_events(&schedule, &prefs.shown_kinds(), now, prefs.secondary_zone.as_ref()))
+}
 
-    state.set_visibility(WindowVisibility::Visible);
-    state.end_transition();
+/// The tray's "Copy next event times": running and next events in local time.
+async fn next_events_text(app: &tauri::AppHandle) -> Result<String, AppError> {
+  let state = app.state::<AppState>();
+  let schedule = load_schedule(app, &state, false).await?.schedule;
+  let now = state.inner().clock.lock().await.server_now();
+  let kinds = preferences(app).shown_kinds();
+  let cadence = cadence::current(app).helltide;
+  Ok(countdown::share_text(locale::current(app), &schedule, &kinds, &cadence, now, &chrono::Local))
+}
 
-    eprintln!("✅ Window restored");
+/// Desktop notification for a reminder; clicking it restores the window and emits
+/// `notification:activated` with the event.
+#[tauri::command]
+fn notify_event(app: tauri::AppHandle, title: String, body: String, kind: EventKind, id: i64) -> Result<(), AppError> {
+  if shutdown::in_progress() {
+    return Ok(());
+  }
+  notify::queue(&app, title, body, notify::NotificationTarget { kind, id });
+  Ok(())
 }
 
-/// Hide window to tray using Window type (from on_window_event)
-fn hide_window_to_tray_v2(window: &tauri::Window, _app_handle: &tauri::AppHandle) {
-    let state = get_window_state();
-    let _lock = state.acquire_lock();
+/// Send a test message to the enabled phone services right away.
+#[tauri::command]
+async fn send_test_push(app: tauri::AppHandle) -> Result<(), AppError> {
+  let body = i18n::text(locale::current(&app), Msg::PushTest);
+  push::test(&app, "helltime", body).await
+}
 
-    if !state.begin_transition() {
-        eprintln!("⚠ Hide skipped - already in transition");
-        return;
-    }
+/// Post a sample "event starting" to the webhook once.
+#[tauri::command]
+async fn test_webhook(app: tauri::AppHandle) -> Result<(), AppError> {
+  webhook::test(&app).await
+}
 
-    eprintln!("🔽 Hiding window to tray...");
+/// Exactly what the next telemetry send would post, for transparency in the settings.
+#[tauri::command]
+fn preview_telemetry_payload(app: tauri::AppHandle) -> TelemetryPayload {
+  telemetry::preview(&app)
+}
 
-    // Hide main window
-    let _ = window.hide();
-    let _ = window.set_skip_taskbar(true);
+/// Counters since startup for the debug panel and bug reports.
+#[tauri::command]
+fn get_metrics(state: State<'_, AppState>) -> MetricsSnapshot {
+  state.inner().metrics.snapshot()
+}
 
-    // Note: Overlay visibility is managed independently by the frontend
-    // based on user settings, not coupled to main window state
+/// Fire a reminder for a synthetic event one minute out. The reminder is planned by
+/// the same engine as real ones and handed to the UI via `reminder:test`, which runs
+/// the usual overlay, notification and sound path (ignoring pause and fired state).
+#[tauri::command]
+async fn test_reminder(app: tauri::AppHandle, state: State<'_, AppState>, event_type: EventKind) -> Result<PlannedReminder, AppError> {
+  let now = state.inner().clock.lock().await.server_now();
+  let reminder =
+    reminders::test_reminder(event_type, now).ok_or_else(|| AppError::Other("reminder engine planned nothing".into()))?;
+  app.emit("reminder:test", &reminder).map_err(|e| AppError::Other(format!("emit failed: {e}")))?;
+  applog!("🔔 Test reminder for {}", event_type.label());
+  Ok(reminder)
+}
 
-    state.set_visibility(WindowVisibility::Hidden);
-    state.end_transition();
+/// "I'm at the event": no further reminders for this occurrence, and the event
+/// history counts it as attended.
+async fn attend(app: &tauri::AppHandle, kind: Option<EventKind>, id: i64) -> Result<HistoryEntry, AppError> {
+  let entry = app.state::<AppState>().inner().history.lock().await.mark_attended(kind, id)?;
+  applog!("🙋 Attending {} {}", entry.kind.label(), entry.id);
+  escalation::acknowledge(app, entry.kind, entry.id);
+  let _ = app.emit("reminder:attending", notify::NotificationTarget { kind: entry.kind, id: entry.id });
+  Ok(entry)
+}
 
-    eprintln!("✅ Window hidden to tray");
+/// Mute the remaining reminders of an event and record the attendance. `kind` is
+/// only needed if the id is not unique across the categories.
+#[tauri::command]
+async fn mark_attending(app: tauri::AppHandle, event_id: i64, kind: Option<EventKind>) -> Result<HistoryEntry, AppError> {
+  attend(&app, kind, event_id).await
 }
 
-/// Hide window to tray using WebviewWindow type (from tray click)
-fn hide_window_to_tray(window: &tauri::WebviewWindow, _app_handle: &tauri::AppHandle) {
-    let state = get_window_state();
+/// Stop a reminder escalation for the event: its repeated sound and any later steps.
+#[tauri::command]
+fn acknowledge_reminder(app: tauri::AppHandle, kind: EventKind, id: i64) {
+  escalation::acknowledge(&app, kind, id);
+}
 
-    // Skip if already hidden
-    if state.get_visibility() == WindowVisibility::Hidden {
-        return;
-    }
+/// Overlay toast text for `event`, from the toast templates in the preferences.
+#[tauri::command]
+async fn render_toast(app: tauri::AppHandle, state: State<'_, AppState>, event: ToastEvent) -> Result<RenderedToast, AppError> {
+  // Called once per reminder the UI shows.
+  state.inner().metrics.reminder_fired();
+  telemetry::record(&app, Feature::Reminder);
+  let now = state.inner().clock.lock().await.server_now();
+  Ok(preferences(&app).toast_templates.render(&event, locale::current(&app), now, &chrono::Local))
+}
 
-    let _lock = state.acquire_lock();
+/// Live preview for the template settings: unsaved `templates` rendered for the next
+/// cached event of `event_type`, or the test reminder's synthetic event.
+#[tauri::command]
+async fn preview_toast(
+  app: tauri::AppHandle,
+  state: State<'_, AppState>,
+  templates: ToastTemplates,
+  event_type: EventKind,
+) -> Result<RenderedToast, AppError> {
+  templates.validate().map_err(AppError::InvalidSettings)?;
+  let now = state.inner().clock.lock().await.server_now();
+  let cached = state.inner().cache.lock().await.value().and_then(|s| s.next_event(event_type, now));
+  let event = match cached {
+    Some(event) => event,
+    None => reminders::test_reminder(event_type, now).ok_or("reminder engine planned nothing".to_string())?.event,
+  };
+  Ok(templates.render(&ToastEvent::from(&event), locale::current(&app), now, &chrono::Local))
+}
 
-    if !state.begin_transition() {
-        eprintln!("⚠ Hide skipped - already in transition");
-        return;
-    }
+/// Events running right now, with the time left until they end.
+#[tauri::command]
+async fn active_events(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<Vec<ActiveEvent>, AppError> {
+  let schedule = fetch_schedule(app.clone(), state.clone()).await?;
+  let now = state.inner().clock.lock().await.server_now();
+  let cadence = cadence::current(&app).helltide;
+  Ok(countdown::active_events(&schedule, &preferences(&app).shown_kinds(), &cadence, now))
+}
 
-    eprintln!("🔽 Hiding window to tray...");
+/// Save the events of the next `hours_ahead` hours (default 48) as an .ics file in
+/// the downloads folder and return its path.
+#[tauri::command]
+async fn export_ics(app: tauri::AppHandle, state: State<'_, AppState>, hours_ahead: Option<u32>) -> Result<String, AppError> {
+  let schedule = fetch_schedule(app.clone(), state.clone()).await?;
+  let now = state.inner().clock.lock().await.server_now();
+  let hours_ahead = hours_ahead.unwrap_or(ics::DEFAULT_HOURS_AHEAD);
+  let prefs = preferences(&app);
+  let events = ics::upcoming(&schedule, &prefs.shown_kinds(), now, hours_ahead);
+  let calendar = ics::calendar(
+    &events,
+    locale::current(&app),
+    &cadence::current(&app).helltide,
+    chrono::Utc::now(),
+    prefs.secondary_zone.as_ref(),
+  );
+
+  let dir = match app.path().download_dir() {
+    Ok(dir) => dir,
+    Err(_) => app.path().app_data_dir().map_err(|e| AppError::Storage(format!("no data dir: {e}")))?,
+  };
+  std::fs::create_dir_all(&dir).map_err(|e| AppError::Storage(format!("create {}: {e}", dir.display())))?;
+  let path = dir.join(format!("helltime-{}.ics", chrono::Local::now().format("%Y%m%d-%H%M")));
+  std::fs::write(&path, calendar).map_err(|e| AppError::Storage(format!("write {}: {e}", path.display())))?;
+  applog!("📅 Exported {} events to {}", events.len(), path.display());
+  telemetry::record(&app, Feature::CalendarExport);
+  Ok(path.display().to_string())
+}
 
-    // Hide main window
-    let _ = window.hide();
-    let _ = window.set_skip_taskbar(true);
+/// Upcoming mystery chest resets for the chest reminder; empty while Helltides are filtered out.
+#[tauri::command]
+async fn chest_resets(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<Vec<ChestReset>, AppError> {
+  if !preferences(&app).shows(EventKind::Helltide) {
+    return Ok(Vec::new());
+  }
+  let schedule = fetch_schedule(app.clone(), state.clone()).await?;
+  let now = state.inner().clock.lock().await.server_now();
+  Ok(helltide::upcoming_resets(&schedule, &cadence::current(&app).helltide, now))
+}
 
-    // Note: Overlay visibility is managed independently by the frontend
-    // based on user settings, not coupled to main window state
+/// One-off reminders for single events, soonest first.
+#[tauri::command]
+async fn list_reminders(state: State<'_, AppState>) -> Result<Vec<CustomReminder>, AppError> {
+  let now = state.inner().clock.lock().await.server_now();
+  Ok(state.inner().custom_reminders.lock().unwrap_or_else(|e| e.into_inner()).list(now))
+}
 
-    state.set_visibility(WindowVisibility::Hidden);
-    state.end_transition();
+/// Remind `minutes_before` the start of one particular upcoming event.
+#[tauri::command]
+async fn add_custom_reminder(
+  app: tauri::AppHandle,
+  state: State<'_, AppState>,
+  event_type: EventKind,
+  event_id: i64,
+  minutes_before: i64,
+) -> Result<CustomReminder, AppError> {
+  let schedule = fetch_schedule(app.clone(), state.clone()).await?;
+  let event = schedule
+    .events(event_type)
+    .into_iter()
+    .find(|e| e.id == event_id)
+    .ok_or_else(|| AppError::Other(format!("no {} with id {event_id} in the schedule", event_type.key())))?;
+  let now = state.inner().clock.lock().await.server_now();
+  let reminder = state.inner().custom_reminders.lock().unwrap_or_else(|e| e.into_inner()).add(&event, minutes_before, now)?;
+  applog!("🔔 Custom reminder {} min before {} at {}", minutes_before, event.title(), event.start);
+  let _ = app.emit("reminders:changed", ());
+  Ok(reminder)
+}
 
-    eprintln!("✅ Window hidden to tray");
+#[tauri::command]
+fn remove_custom_reminder(app: tauri::AppHandle, state: State<'_, AppState>, id: u64) -> bool {
+  let removed = state.inner().custom_reminders.lock().unwrap_or_else(|e| e.into_inner()).remove(id);
+  if removed {
+    let _ = app.emit("reminders:changed", ());
+  }
+  removed
 }
 
-/// Toggle window visibility (for tray click)
-fn toggle_window(window: &tauri::WebviewWindow, app_handle: &tauri::AppHandle) {
-    let state = get_window_state();
-    let current = state.get_visibility();
+/// Upcoming "Helltide ends soon" reminders, `minutes_before` each end; empty while
+/// Helltides are filtered out.
+#[tauri::command]
+async fn helltide_end_reminders(
+  app: tauri::AppHandle,
+  state: State<'_, AppState>,
+  minutes_before: i64,
+) -> Result<Vec<EndReminder>, AppError> {
+  if !preferences(&app).shows(EventKind::Helltide) {
+    return Ok(Vec::new());
+  }
+  let schedule = fetch_schedule(app.clone(), state.clone()).await?;
+  let now = state.inner().clock.lock().await.server_now();
+  Ok(reminders::plan_helltide_end(&schedule, &cadence::current(&app).helltide, minutes_before, now))
+}
 
-    eprintln!("🔄 Toggle requested, current state: {:?}", current);
+#[tauri::command]
+async fn check_for_update(app: tauri::AppHandle) -> Result<Option<UpdateInfo>, AppError> {
+  updater::check(&app).await
+}
 
-    match current {
-        WindowVisibility::Visible => hide_window_to_tray(window, app_handle),
-        WindowVisibility::Hidden => restore_window(window),
+#[tauri::command]
+async fn open_update_page(app: tauri::AppHandle) -> Result<(), AppError> {
+  use tauri_plugin_opener::OpenerExt;
+  let url = app.state::<AppState>().inner().update.lock().await.as_ref().map(|u| u.url.clone());
+  match url {
+    Some(url) => {
+      app.opener().open_url(url, None::<&str>).map_err(|e| AppError::Other(format!("failed to open release page: {e}")))
     }
+    None => Err(AppError::Other("no update available".to_string())),
+  }
 }
 
-// ============================================================================
+/// Start, restart (port change) or stop the localhost API. Returns the bound port.
+#[tauri::command]
+async fn set_local_api(app: tauri::AppHandle, enabled: bool, port: Option<u16>) -> Result<Option<u16>, AppError> {
+  let port = port.unwrap_or(helltime_core::local_api::DEFAULT_PORT);
+  let state = app.state::<AppState>();
+  let mut server = state.inner().local_api.lock().await;
+
+  if let Some(running) = server.take() {
+    if enabled && running.port == port {
+      *server = Some(running);
+      return Ok(Some(port));
+    }
+    running.stop();
+  }
+  if !enabled {
+    return Ok(None);
+  }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub struct ScheduleResponse {
-  #[serde(default)]
-  pub world_boss: Vec<serde_json::Value>,
-  #[serde(default)]
-  pub legion: Vec<serde_json::Value>,
-  #[serde(default)]
-  pub helltide: Vec<serde_json::Value>,
+  *server = Some(local_api::start(app.clone(), port).await.map_err(AppError::InvalidSettings)?);
+  telemetry::record(&app, Feature::LocalApi);
+  Ok(Some(port))
 }
 
-#[derive(Default)]
-struct Cache {
-  last_fetch: Option<Instant>,
-  value: Option<ScheduleResponse>,
+/// Turn Discord Rich Presence on/off. Returns `false` if this build has no Discord app id.
+#[tauri::command]
+fn set_discord_presence(app: tauri::AppHandle, presence: State<'_, DiscordPresence>, enabled: bool) -> bool {
+  presence.set_enabled(enabled);
+  if enabled {
+    telemetry::record(&app, Feature::DiscordPresence);
+  }
+  presence.is_available()
 }
 
-struct AppState {
-  cache: Mutex<Cache>,
-  http: reqwest::Client,
+#[tauri::command]
+fn overlay_create(app: tauri::AppHandle) -> Result<(), AppError> {
+  OverlayFacade::new(&app).create().map_err(AppError::OverlayUnavailable)
 }
 
 #[tauri::command]
-async fn fetch_schedule(state: State<'_, AppState>) -> Result<ScheduleResponse, String> {
-  {
-    let cache = state.inner().cache.lock().await;
-    if let (Some(at), Some(value)) = (cache.last_fetch, cache.value.clone()) {
-      if at.elapsed() < CACHE_TTL {
-        return Ok(value);
-      }
+fn overlay_show(app: tauri::AppHandle) -> Result<(), AppError> {
+  OverlayFacade::new(&app).show().map_err(AppError::OverlayUnavailable)
+}
+
+#[tauri::command]
+fn overlay_hide(app: tauri::AppHandle) -> Result<(), AppError> {
+  OverlayFacade::new(&app).hide().map_err(AppError::OverlayUnavailable)
+}
+
+#[tauri::command]
+fn overlay_set_position(app: tauri::AppHandle, x: i32, y: i32) -> Result<(), AppError> {
+  OverlayFacade::new(&app).set_position(x, y).map_err(AppError::OverlayUnavailable)
+}
+
+#[tauri::command]
+fn overlay_set_click_through(app: tauri::AppHandle, enabled: bool) -> Result<(), AppError> {
+  OverlayFacade::new(&app).set_click_through(enabled).map_err(AppError::OverlayUnavailable)
+}
+
+/// Overlay spots that keep clear of the game's HUD, for the position presets.
+#[tauri::command]
+fn overlay_hud_placement(app: tauri::AppHandle, layout: HudLayout) -> Result<overlay::HudPlacement, AppError> {
+  OverlayFacade::new(&app).hud_placement(layout).map_err(AppError::OverlayUnavailable)
+}
+
+/// Move the overlay into a named safe zone of the game's HUD.
+#[tauri::command]
+fn overlay_set_anchor(app: tauri::AppHandle, layout: HudLayout, zone: String) -> Result<Suggestion, AppError> {
+  OverlayFacade::new(&app).set_anchor(layout, &zone).map_err(AppError::OverlayUnavailable)
+}
+
+/// Apply the overlay style from the settings UI on whichever engine draws it.
+#[tauri::command]
+fn overlay_apply_style(app: tauri::AppHandle, style: OverlayStyle) -> Result<(), AppError> {
+  style.validate().map_err(AppError::InvalidSettings)?;
+  OverlayFacade::new(&app).apply_style(&style).map_err(AppError::OverlayUnavailable)
+}
+
+/// Make the overlay clickable for a few seconds (also bound to a global hotkey).
+#[tauri::command]
+fn overlay_reveal(app: tauri::AppHandle) -> Result<(), AppError> {
+  OverlayFacade::new(&app).reveal().map_err(AppError::OverlayUnavailable)
+}
+
+/// The onboarding's positioning step: the overlay stays clickable and shows its
+/// handles for a minute.
+#[tauri::command]
+fn enter_config(app: tauri::AppHandle) -> Result<(), AppError> {
+  OverlayFacade::new(&app).enter_config().map_err(AppError::OverlayUnavailable)
+}
+
+/// Flash the soonest upcoming event on the overlay for a few seconds (also bound to
+/// the global hotkey). Uses the cached schedule when there is one.
+#[tauri::command]
+async fn peek_next_event(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<Option<NextEvent>, AppError> {
+  let cached = state.inner().cache.lock().await.value().cloned();
+  let schedule = match cached {
+    Some(schedule) => schedule,
+    None => fetch_schedule(app.clone(), state.clone()).await?,
+  };
+  let now = state.inner().clock.lock().await.server_now();
+  let prefs = preferences(&app);
+  let next = countdown::next_events(&schedule, &prefs.shown_kinds(), now, prefs.secondary_zone.as_ref()).into_iter().next();
+  if let Some(next) = &next {
+    OverlayFacade::new(&app).peek(next).map_err(AppError::OverlayUnavailable)?;
+  }
+  Ok(next)
+}
+
+/// Installed font families for the overlay font picker, sorted.
+#[tauri::command]
+async fn list_fonts() -> Result<Vec<String>, AppError> {
+  tauri::async_runtime::spawn_blocking(fonts::installed).await.map_err(|e| AppError::Other(e.to_string()))
+}
+
+/// Every keyboard shortcut with its binding and tray menu item.
+#[tauri::command]
+fn list_shortcuts(app: tauri::AppHandle) -> Vec<hotkey::ShortcutInfo> {
+  hotkey::list(&app)
+}
+
+/// Whether Diablo IV is running and game mode is active.
+#[tauri::command]
+fn game_running(app: tauri::AppHandle) -> bool {
+  game_mode::is_running(&app)
+}
+
+/// Whether animations and flashing cues are off (preference or system setting).
+#[tauri::command]
+fn reduced_motion(app: tauri::AppHandle) -> bool {
+  motion::is_reduced(&app)
+}
+
+/// Whether power saving is on, and why (battery, metered connection).
+#[tauri::command]
+fn power_status(app: tauri::AppHandle) -> power_saving::PowerStatus {
+  power_saving::status(&app)
+}
+
+#[tauri::command]
+fn overlay_status(app: tauri::AppHandle) -> overlay::OverlayStatus {
+  OverlayFacade::new(&app).status()
+}
+
+/// Text the overlay window shows on its own, in the backend's language.
+#[tauri::command]
+fn overlay_texts(app: tauri::AppHandle) -> i18n::OverlayTexts {
+  i18n::OverlayTexts::new(locale::current(&app))
+}
+
+#[tauri::command]
+fn get_preferences(app: tauri::AppHandle) -> Preferences {
+  preferences(&app)
+}
+
+#[tauri::command]
+fn set_preferences(app: tauri::AppHandle, prefs: Preferences) -> Result<(), AppError> {
+  // The UI does not know about window placement or onboarding, and sets the sync
+  // folder through `set_sync_dir`; keep what the backend saved.
+  store_preferences(&app, |current| Preferences {
+    window: current.window,
+    onboarding_completed: current.onboarding_completed,
+    sync_dir: current.sync_dir.clone(),
+    ..prefs
+  })?;
+  push_preferences(&app);
+  Ok(())
+}
+
+/// Hand saved preferences on to the sync folder; a failure is only logged.
+fn push_preferences(app: &tauri::AppHandle) {
+  if let Err(e) = sync::push(app, None) {
+    applog!("⚠ Settings sync failed: {}", e);
+  }
+}
+
+/// Save and apply new preferences, from the UI, the sync folder or the backend's own
+/// settings. `change` turns the current preferences into the new ones.
+fn store_preferences(app: &tauri::AppHandle, change: impl FnOnce(&Preferences) -> Preferences) -> Result<(), AppError> {
+  let state = app.state::<AppState>();
+  let (saved, network_changed, locale_changed, shortcuts_changed) = {
+    let mut store = state.inner().prefs.lock().unwrap_or_else(|e| e.into_inner());
+    let prefs = change(store.get());
+    prefs.overlay_visibility.validate().map_err(AppError::InvalidSettings)?;
+    prefs.toast_templates.validate().map_err(AppError::InvalidSettings)?;
+    shortcuts::validate(&prefs.shortcuts).map_err(AppError::InvalidSettings)?;
+    prefs.telemetry.validate().map_err(AppError::InvalidSettings)?;
+    prefs.reminder_times.validate().map_err(AppError::InvalidSettings)?;
+    prefs.reminder_escalation.validate().map_err(AppError::InvalidSettings)?;
+    prefs.push.validate().map_err(AppError::InvalidSettings)?;
+    prefs.webhook.validate().map_err(AppError::InvalidSettings)?;
+    prefs.mqtt.validate().map_err(AppError::InvalidSettings)?;
+    prefs.poll.validate().map_err(AppError::InvalidSettings)?;
+    if let Some(zone) = &prefs.secondary_zone {
+      zone.validate().map_err(AppError::InvalidSettings)?;
     }
+    let network_changed = store.get().network != prefs.network;
+    let locale_changed = store.get().locale != prefs.locale;
+    let shortcuts_changed = store.get().shortcuts != prefs.shortcuts;
+    // Built first so invalid proxy/CA settings are rejected rather than saved, but
+    // only put to use once the settings they come from are on disk.
+    let http = if network_changed {
+      Some(ReqwestFetcher::new(&prefs.network).map_err(AppError::InvalidSettings)?)
+    } else {
+      None
+    };
+    store.set(prefs).map_err(AppError::Storage)?;
+    if let Some(http) = http {
+      *state.inner().http.lock().unwrap_or_else(|e| e.into_inner()) = http;
+    }
+    state.inner().rate_limit.lock().unwrap_or_else(|e| e.into_inner()).set_rate(store.get().poll.max_requests_per_minute);
+    (store.get().clone(), network_changed, locale_changed, shortcuts_changed)
+  };
+  if network_changed {
+    applog!("🌐 HTTP client rebuilt for new network settings: {}", saved.network.summary());
+  }
+  if shortcuts_changed {
+    hotkey::reload();
   }
+  // The menu shows the shortcuts next to the items.
+  if locale_changed || shortcuts_changed {
+    tray::rebuild_menu(app);
+  } else {
+    tray::sync_checks(app, &saved);
+  }
+  overlay::apply_schedule(app);
+  overlay::apply_capture_exclusion(app);
+  overlay::apply_hover_fade(app);
+  tray_icon::apply(app);
+  telemetry::update(app);
+  // The event filter also applies to the overlay window and the tray tooltip.
+  let _ = app.emit("preferences:changed", &saved);
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    app.state::<AppState>().inner().cache.lock().await.set_ttl(saved.poll.cache_ttl());
+    motion::update(&app).await;
+    power_saving::update(&app).await;
+    tray::refresh_tooltip(&app).await
+  });
+  Ok(())
+}
+
+/// Version, build and environment facts for the about dialog.
+#[tauri::command]
+fn about_info(app: tauri::AppHandle) -> about::AboutInfo {
+  about::collect(&app)
+}
 
-  let resp = state
-    .inner()
-    .http
-    .get(SCHEDULE_URL)
-    .header(
-      reqwest::header::USER_AGENT,
-      "helltime/0.1 (+https://github.com/)",
-    )
-    .timeout(Duration::from_secs(10))
-    .send()
-    .await
-    .map_err(|e| format!("request failed: {e}"))?;
+#[tauri::command]
+fn list_profiles(app: tauri::AppHandle) -> ProfileList {
+  profiles::list(&app)
+}
+
+/// Save the current configuration as profile `name`, replacing one of the same name.
+/// `settings` are the UI's settings (lead times, overlay style).
+#[tauri::command]
+fn save_profile(app: tauri::AppHandle, state: State<'_, AppState>, name: String, settings: serde_json::Value) -> Result<ProfileList, AppError> {
+  let profile = Profile::capture(&name, &preferences(&app), Some(settings)).map_err(AppError::InvalidSettings)?;
+  applog!("🎛 Profile \"{}\" saved", profile.name);
+  state.inner().profiles.lock().unwrap_or_else(|e| e.into_inner()).put(profile);
+  Ok(profiles::changed(&app))
+}
+
+#[tauri::command]
+fn delete_profile(app: tauri::AppHandle, state: State<'_, AppState>, name: String) -> ProfileList {
+  if state.inner().profiles.lock().unwrap_or_else(|e| e.into_inner()).remove(&name) {
+    applog!("🎛 Profile \"{}\" deleted", name);
+  }
+  profiles::changed(&app)
+}
+
+#[tauri::command]
+fn activate_profile(app: tauri::AppHandle, name: String) -> Result<(), AppError> {
+  profiles::activate(&app, &name)
+}
 
-  if !resp.status().is_success() {
-    return Err(format!("bad status: {}", resp.status()));
+/// Sync settings through `dir` from now on (`None` turns sync off). `settings` are the
+/// UI's settings, written there if the folder has no sync file yet.
+#[tauri::command]
+fn set_sync_dir(app: tauri::AppHandle, dir: Option<std::path::PathBuf>, settings: serde_json::Value) -> Result<(), AppError> {
+  if dir.is_some() {
+    telemetry::record(&app, Feature::Sync);
   }
+  sync::set_dir(&app, dir, settings)
+}
 
-  let json = resp
-    .json::<ScheduleResponse>()
-    .await
-    .map_err(|e| format!("invalid json: {e}"))?;
+/// Push the UI's settings to the sync folder; a no-op while sync is off.
+#[tauri::command]
+fn sync_push_settings(app: tauri::AppHandle, settings: serde_json::Value) -> Result<(), AppError> {
+  sync::push(&app, Some(settings))
+}
 
-  let mut cache = state.inner().cache.lock().await;
-  cache.last_fetch = Some(Instant::now());
-  cache.value = Some(json.clone());
+/// What the first-run flow still has to cover.
+#[tauri::command]
+fn onboarding_status(app: tauri::AppHandle) -> OnboardingStatus {
+  use tauri_plugin_notification::NotificationExt;
+
+  let permission = match app.notification().permission_state() {
+    Ok(tauri::plugin::PermissionState::Granted) => Permission::Granted,
+    Ok(tauri::plugin::PermissionState::Denied) => Permission::Denied,
+    Ok(_) => Permission::Prompt,
+    Err(_) => Permission::Unknown,
+  };
+  let autostart = autostart::SUPPORTED.then(|| autostart::is_enabled().unwrap_or(false));
+  let prefs = preferences(&app);
+  OnboardingStatus::new(prefs.onboarding_completed, permission, autostart, prefs.overlay_enabled)
+}
 
-  Ok(json)
+/// Record that the first-run flow was finished (or skipped).
+#[tauri::command]
+fn complete_onboarding(app: tauri::AppHandle) -> Result<(), AppError> {
+  store_preferences(&app, |current| Preferences { onboarding_completed: true, ..current.clone() })?;
+  push_preferences(&app);
+  applog!("👋 Onboarding completed");
+  Ok(())
 }
 
-fn try_load_tray_icon(icon_path: &std::path::Path) -> Option<tauri::image::Image<'static>> {
-  use tauri::image::Image;
+#[tauri::command]
+fn set_autostart(enabled: bool) -> Result<(), AppError> {
+  autostart::set(enabled).map_err(AppError::Storage)?;
+  applog!("🚀 Autostart {}", if enabled { "enabled" } else { "disabled" });
+  Ok(())
+}
 
-  match std::fs::read(icon_path) {
-    Ok(png_data) => {
-      match image::load_from_memory_with_format(&png_data, image::ImageFormat::Png) {
-        Ok(img) => {
-          let rgba_img = img.to_rgba8();
-          let (width, height) = rgba_img.dimensions();
-          eprintln!("✓ Loaded icon: {:?} ({}x{})", icon_path, width, height);
-          return Some(Image::new_owned(rgba_img.into_raw(), width, height));
-        }
-        Err(e) => {
-          eprintln!("✗ Failed to decode PNG at {:?}: {}", icon_path, e);
-        }
-      }
+/// Set the language of backend text (`None` follows the system); returns the one now in use.
+#[tauri::command]
+fn set_locale(app: tauri::AppHandle, locale: Option<Locale>) -> Result<Locale, AppError> {
+  // Also rebuilds the tray menu and tooltip in the new language.
+  store_preferences(&app, |current| Preferences { locale, ..current.clone() })?;
+  push_preferences(&app);
+  let current = locale::current(&app);
+  applog!("🌍 Locale: {}", current.tag());
+  let _ = app.emit("locale:changed", current);
+  Ok(current)
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PauseStatus {
+  /// RFC 3339 end of the pause, `None` when reminders are active.
+  paused_until: Option<String>,
+}
+
+fn pause_status(app: &tauri::AppHandle) -> PauseStatus {
+  let pause = **app.state::<AppState>().inner().reminder_pause.lock().unwrap_or_else(|e| e.into_inner());
+  PauseStatus { paused_until: pause.paused_until(chrono::Utc::now()).map(|t| t.to_rfc3339()) }
+}
+
+/// Apply a tray pause action, tell the UI and refresh the tooltip right away.
+fn update_reminder_pause(app: &tauri::AppHandle, change: impl FnOnce(&mut ReminderPause)) {
+  {
+    let state = app.state::<AppState>();
+    let mut pause = state.inner().reminder_pause.lock().unwrap_or_else(|e| e.into_inner());
+    change(&mut pause);
+    pause.save();
+  }
+  let status = pause_status(app);
+  applog!("⏸ Reminder pause: {:?}", status.paused_until);
+  let _ = app.emit("reminders:pause-changed", status);
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move { tray::refresh_tooltip(&app).await });
+}
+
+#[tauri::command]
+fn reminder_pause_status(app: tauri::AppHandle) -> PauseStatus {
+  pause_status(&app)
+}
+
+#[tauri::command]
+fn resume_reminders(app: tauri::AppHandle) {
+  update_reminder_pause(&app, |pause| pause.resume());
+}
+
+/// Link the app was started with; the UI asks once after it has loaded.
+#[tauri::command]
+fn take_pending_deep_link(pending: State<'_, PendingLink>) -> Option<DeepLink> {
+  pending.0.lock().unwrap_or_else(|e| e.into_inner()).take()
+}
+
+/// After sleep or a clock change, Instant-based TTLs and frontend timers are unreliable:
+/// drop the cache freshness, forget stale skew samples and tell the UI to reschedule.
+fn handle_time_jump(app: &tauri::AppHandle, jump: TimeJump) {
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    let state = app.state::<AppState>();
+    // A zone change leaves UTC alone: the schedule and clock skew are still valid.
+    if !matches!(jump, TimeJump::ZoneChanged { .. }) {
+      state.inner().cache.lock().await.invalidate();
     }
-    Err(e) => {
-      eprintln!("✗ Icon file not found at {:?}: {}", icon_path, e);
+    if matches!(jump, TimeJump::ClockChanged { .. }) {
+      state.inner().clock.lock().await.reset();
     }
-  }
-  None
+    let _ = app.emit("system:time-jump", jump);
+    // The tooltip shows local times.
+    tray::refresh_tooltip(&app).await;
+  });
 }
 
 fn create_fallback_icon() -> tauri::image::Image<'static> {
   use tauri::image::Image;
-  eprintln!("⚠ Using fallback hourglass icon");
+  applog!("⚠ Using fallback hourglass icon");
 
   let mut pixels = vec![0u8; 64 * 64 * 4]; // 64x64 RGBA
 
@@ -298,116 +1032,234 @@ fn create_fallback_icon() -> tauri::image::Image<'static> {
 }
 
 fn main() {
+  if let Some(code) = cli::run_from_args() {
+    std::process::exit(code);
+  }
+  // Core's lines (fetch retries, corrupt files) belong in the app log and crash reports.
+  helltime_core::log::set_hook(|line| applog!("{}", line));
+
+  let startup_link = helltime_core::deep_link::find_in_args(std::env::args());
+  if let Some(url) = &startup_link {
+    if deep_link::forward_to_running_instance(url) {
+      return;
+    }
+  }
+
+  webview::check_runtime();
+  let mut context = tauri::generate_context!();
+  webview::take_window_config(&mut context);
+
   tauri::Builder::default()
-    .manage(AppState {
-      cache: Mutex::new(Cache::default()),
-      http: reqwest::Client::new(),
-    })
     .plugin(tauri_plugin_notification::init())
     .plugin(tauri_plugin_shell::init())
-    .setup(|app| {
+    .plugin(tauri_plugin_clipboard_manager::init())
+    .plugin(tauri_plugin_opener::init())
+    .setup(move |app| {
+      let data_dir = app.path().app_data_dir()?;
+      crash::install_panic_hook(data_dir.join("crashes"));
+      let prefs = PrefsStore::load(data_dir.join("preferences.json"));
+      let http = ReqwestFetcher::new(&prefs.get().network).unwrap_or_else(|e| {
+        applog!("⚠ Network settings ignored: {}", e);
+        ReqwestFetcher::default()
+      });
+      app.manage(AppState {
+        cache: Mutex::new(ScheduleCache::new(prefs.get().poll.cache_ttl())),
+        validation: StdMutex::new(ValidationStats::default()),
+        clock: Mutex::new(ClockSkew::default()),
+        history: Mutex::new(EventHistory::load(data_dir.join("event_history.json"))),
+        http: StdMutex::new(http),
+        rate_limit: StdMutex::new(TokenBucket::new(prefs.get().poll.max_requests_per_minute)),
+        connection: StdMutex::new(ConnectionTracker::default()),
+        last_raw: Mutex::new(None),
+        update: Mutex::new(None),
+        local_api: Mutex::new(None),
+        prefs: StdMutex::new(prefs),
+        reminder_pause: StdMutex::new(JsonStore::load(data_dir.join("reminder_pause.json"), "reminder pause")),
+        custom_reminders: StdMutex::new(CustomReminderStore::load(data_dir.join("custom_reminders.json"))),
+        cadence: StdMutex::new(CadenceStore::load(data_dir.join("cadence.json"))),
+        profiles: StdMutex::new(ProfileStore::load(data_dir.join("profiles.json"))),
+        metrics: Metrics::default(),
+        telemetry: StdMutex::new(TelemetryQueue::load(data_dir.join("telemetry.json"))),
+        push: StdMutex::new(PushQueue::load(data_dir.join("push-queue.json"))),
+      });
+      app.manage(DiscordPresence::new());
+      app.manage(notify::NotifyQueue::default());
+      app.manage(escalation::EscalationState::default());
+      app.manage(webhook::WebhookState::default());
+      app.manage(sync::SyncState::default());
+      app.manage(overlay::OverlayState::default());
+      app.manage(game_mode::GameMode::default());
+      app.manage(motion::Motion::default());
+      app.manage(power_saving::PowerSaver::default());
+      app.manage(tray::TrayMenu::default());
+      app.manage(PendingLink(StdMutex::new(
+        startup_link.as_deref().and_then(helltime_core::deep_link::parse),
+      )));
+
+      // The main window is created hidden (tauri.conf.json) so "start minimized"
+      // never flashes it on screen. Headless runs never create it.
+      let mut visibility = Visibility::Visible;
+      if webview::is_headless() || cli::headless_requested() || preferences(app.handle()).headless {
+        webview::set_headless();
+        visibility = Visibility::Hidden;
+        headless::spawn(app.handle().clone());
+      } else {
+        match webview::create_main_window(app.handle()) {
+          Ok(window) => {
+            window_geometry::restore(app.handle(), &window);
+            if preferences(app.handle()).start_minimized {
+              applog!("🔽 Starting minimized to tray");
+              let _ = window.set_skip_taskbar(true);
+              visibility = Visibility::Hidden;
+              webview::schedule_unload(app.handle());
+            } else {
+              let _ = window.show();
+            }
+          }
+          Err(e) => applog!("✗ {}", e),
+        }
+      }
+      controller::spawn(app.handle(), visibility);
+
       // Debug: print current working directory
       if let Ok(cwd) = std::env::current_dir() {
-        eprintln!("Current working directory: {:?}", cwd);
+        applog!("Current working directory: {:?}", cwd);
       }
 
-      // Try to load the actual PNG icon from various paths
-      let icon = {
-        let paths = [
-          std::path::PathBuf::from("icons/icon.png"),
-          std::path::PathBuf::from("src-tauri/icons/icon.png"),
-          std::path::PathBuf::from("../icons/icon.png"),
-          std::path::PathBuf::from("../../icons/icon.png"),
-          std::path::PathBuf::from("./icons/icon.png"),
-        ];
-
-        eprintln!("Attempting to load tray icon...");
-        let mut loaded_icon = None;
-        for path in &paths {
-          if let Some(icon) = try_load_tray_icon(path) {
-            loaded_icon = Some(icon);
-            break;
-          }
-        }
+      // Embedded, so it does not depend on the working directory; the variant follows
+      // the taskbar theme (see `tray_icon::spawn_updater`).
+      let icon = tray_icon::image(tray_icon::current(app.handle()), false).unwrap_or_else(create_fallback_icon);
 
-        loaded_icon.unwrap_or_else(create_fallback_icon)
-      };
-
-      let _tray_icon = tauri::tray::TrayIconBuilder::new()
+      let _tray_icon = tauri::tray::TrayIconBuilder::with_id(tray::TRAY_ID)
         .icon(icon)
         .tooltip("Helltime")
         .on_tray_icon_event(|tray, event| {
           use tauri::tray::{TrayIconEvent, MouseButton};
-          match event {
-            // Left click: toggle window visibility
-            TrayIconEvent::Click { button: MouseButton::Left, .. } => {
-              let state = get_window_state();
-
-              // Debounce rapid clicks
-              if !state.should_process_tray_action() {
-                return;
-              }
-
-              if let Some(window) = tray.app_handle().get_webview_window("main") {
-                toggle_window(&window, tray.app_handle());
-              }
-            }
-            _ => {}
+          // Left click: toggle window visibility
+          if let TrayIconEvent::Click { button: MouseButton::Left, .. } = event {
+            controller::send(tray.app_handle(), Message::TrayClick(Instant::now()));
           }
         })
         .on_menu_event(|app, event| {
           match event.id.as_ref() {
-            "restore" => {
-              if let Some(window) = app.get_webview_window("main") {
-                restore_window(&window);
-              }
+            "restore" => controller::send(app, Message::ShowWindow),
+            "toggle-overlay" => controller::send(app, Message::ToggleOverlay),
+            "toggle-reminder" => controller::send(app, Message::ToggleReminders),
+            "peek-next" => hotkey::run(app, ShortcutAction::PeekNextEvent),
+            "reveal-overlay" => hotkey::run(app, ShortcutAction::RevealOverlay),
+            "pause-30m" => {
+              update_reminder_pause(app, |p| p.pause_for(chrono::Utc::now(), chrono::Duration::minutes(30)));
+            }
+            "pause-1h" => {
+              update_reminder_pause(app, |p| p.pause_for(chrono::Utc::now(), chrono::Duration::hours(1)));
+            }
+            "pause-tomorrow" => {
+              update_reminder_pause(app, |p| p.pause_until_tomorrow(chrono::Local::now()));
+            }
+            "resume-reminders" => {
+              update_reminder_pause(app, |p| p.resume());
             }
-            "toggle-overlay" => {
-              let _ = app.emit("menu:toggle-overlay", ());
+            "export-calendar" => {
+              let app = app.clone();
+              tauri::async_runtime::spawn(async move {
+                use tauri_plugin_opener::OpenerExt;
+                let state = app.state::<AppState>();
+                match export_ics(app.clone(), state, None).await {
+                  // Opening the file hands it to the default calendar app for import.
+                  Ok(path) => {
+                    if let Err(e) = app.opener().open_path(path, None::<&str>) {
+                      applog!("⚠ Failed to open calendar export: {}", e);
+                    }
+                  }
+                  Err(e) => applog!("✗ Calendar export failed: {}", e),
+                }
+              });
             }
-            "toggle-reminder" => {
-              let _ = app.emit("menu:toggle-reminder", ());
+            "copy-next-events" => {
+              let app = app.clone();
+              tauri::async_runtime::spawn(async move {
+                use tauri_plugin_clipboard_manager::ClipboardExt;
+                match next_events_text(&app).await {
+                  Ok(text) => {
+                    if let Err(e) = app.clipboard().write_text(text) {
+                      applog!("✗ Failed to copy event times: {}", e);
+                    }
+                  }
+                  Err(e) => applog!("✗ No event times to copy: {}", e),
+                }
+              });
             }
-            "quit" => {
-              app.exit(0);
+            "copy-diagnostics" => {
+              let app = app.clone();
+              tauri::async_runtime::spawn(async move {
+                use tauri_plugin_clipboard_manager::ClipboardExt;
+                let report = diagnostics::run(&app).await;
+                if let Err(e) = app.clipboard().write_text(report.to_text()) {
+                  applog!("✗ Failed to copy diagnostics: {}", e);
+                }
+              });
+            }
+            "update" => {
+              let app = app.clone();
+              tauri::async_runtime::spawn(async move {
+                let known = app.state::<AppState>().inner().update.lock().await.is_some();
+                if !known {
+                  match updater::check(&app).await {
+                    Ok(Some(_)) => {}
+                    Ok(None) => {
+                      applog!("✓ helltime is up to date");
+                      return;
+                    }
+                    Err(e) => {
+                      applog!("⚠ Update check failed: {}", e);
+                      return;
+                    }
+                  }
+                }
+                if let Err(e) = open_update_page(app.clone()).await {
+                  applog!("✗ {}", e);
+                }
+              });
+            }
+            "quit" => shutdown::quit(app),
+            id => {
+              if let Some(name) = id.strip_prefix(profiles::MENU_PREFIX) {
+                if let Err(e) = profiles::activate(app, name) {
+                  applog!("✗ {}", e);
+                }
+              }
             }
-            _ => {}
           }
         })
-        .menu({
-          let overlay_item = CheckMenuItem::with_id(app, "toggle-overlay", "Overlay", true, true, None::<&str>)?;
-          let reminder_item = CheckMenuItem::with_id(app, "toggle-reminder", "Reminder", true, true, None::<&str>)?;
-
-          let overlay_item_clone = overlay_item.clone();
-          let reminder_item_clone = reminder_item.clone();
-
-          app.listen("menu:update-overlay-state", move |event| {
-            let payload_str = event.payload();
-            let checked = payload_str == "true";
-            let _ = overlay_item_clone.set_checked(checked);
-          });
-
-          app.listen("menu:update-reminder-state", move |event| {
-            let payload_str = event.payload();
-            let checked = payload_str == "true";
-            let _ = reminder_item_clone.set_checked(checked);
-          });
-
-          &tauri::menu::Menu::with_items(
-            app,
-            &[
-              &tauri::menu::MenuItem::with_id(app, "restore", "Restore", true, None::<&str>)?,
-              &tauri::menu::PredefinedMenuItem::separator(app)?,
-              &overlay_item,
-              &reminder_item,
-              &tauri::menu::PredefinedMenuItem::separator(app)?,
-              &tauri::menu::MenuItem::with_id(app, "quit", "Exit", true, None::<&str>)?,
-            ],
-          )?
-        })
+        .menu(&tray::build_menu(app.handle())?)
         .show_menu_on_left_click(false)
         .build(app);
 
+      let handle = app.handle().clone();
+      power::spawn_watcher(move |jump| handle_time_jump(&handle, jump));
+      tray::listen_menu_state(app.handle());
+      tray::spawn_tooltip_refresher(app.handle().clone());
+      tray_icon::spawn_updater(app.handle().clone());
+      hotkey::spawn(app.handle().clone());
+      overlay::spawn_schedule_checker(app.handle().clone());
+      overlay::spawn_hover_watcher(app.handle().clone());
+      game_mode::spawn_watcher(app.handle().clone());
+      motion::spawn_watcher(app.handle().clone());
+      power_saving::spawn_watcher(app.handle().clone());
+      network_watch::spawn_watcher(app.handle().clone());
+      updater::spawn_checker(app.handle().clone());
+      cadence::spawn_checker(app.handle().clone());
+      sync::spawn_watcher(app.handle().clone());
+      discord_rpc::spawn_updater(app.handle().clone());
+      taskbar::spawn_updater(app.handle().clone());
+      telemetry::spawn(app.handle().clone());
+      push::spawn(app.handle().clone());
+      webhook::spawn(app.handle().clone());
+      mqtt::spawn(app.handle().clone());
+      escalation::spawn(app.handle().clone());
+      deep_link::register_scheme();
+      deep_link::spawn_listener(app.handle().clone());
+
       Ok(())
     })
     .on_window_event(|window, event| {
@@ -417,31 +1269,31 @@ fn main() {
       }
 
       match event {
-        // Close button → hide to tray (don't actually close)
-        tauri::WindowEvent::CloseRequested { api, .. } => {
-          api.prevent_close();
-          hide_window_to_tray_v2(window, window.app_handle());
-        }
+        // Close button → hide to tray or quit, per preference
+        tauri::WindowEvent::CloseRequested { api, .. } => match preferences(window.app_handle()).close_behavior {
+          CloseBehavior::HideToTray => {
+            api.prevent_close();
+            controller::send(window.app_handle(), Message::CloseToTray);
+          }
+          CloseBehavior::Exit => {
+            api.prevent_close();
+            applog!("👋 Close requested - exiting");
+            shutdown::quit(window.app_handle());
+          }
+        },
 
         // Window focused → ensure visible state is correct
-        tauri::WindowEvent::Focused(focused) => {
-          if *focused {
-            let state = get_window_state();
-            // If we're focused, we should be visible
-            if state.get_visibility() == WindowVisibility::Hidden {
-              eprintln!("📍 Focus received while hidden - updating state");
-              state.set_visibility(WindowVisibility::Visible);
-              let _ = window.set_skip_taskbar(false);
-            }
-          }
-        }
+        tauri::WindowEvent::Focused(true) => controller::send(window.app_handle(), Message::Focused),
+
+        tauri::WindowEvent::Moved(_) => window_geometry::schedule_save(window),
 
-        // Resized with zero size often indicates minimize on Windows
-        tauri::WindowEvent::Resized(size) => {
-          // When minimized, Windows reports size as 0,0 or very small
-          if size.width == 0 && size.height == 0 {
-            eprintln!("📥 Window minimized (size 0x0 detected)");
-            hide_window_to_tray_v2(window, window.app_handle());
+        // Ask the OS whether this resize is a minimize instead of guessing from the size
+        tauri::WindowEvent::Resized(_) => {
+          let minimized = window.is_minimized().unwrap_or(false);
+          let to_tray = preferences(window.app_handle()).minimize_to_tray;
+          controller::send(window.app_handle(), Message::Resized { minimized, to_tray });
+          if !minimized {
+            window_geometry::schedule_save(window);
           }
         }
 
@@ -450,10 +1302,81 @@ fn main() {
     })
     .invoke_handler(tauri::generate_handler![
       fetch_schedule,
+      refresh_schedule,
+      fetch_schedule_raw,
+      connection_status,
+      next_poll_delay,
+      save_raw_payload,
+      clock_status,
+      next_events,
+      active_events,
+      export_ics,
+      notify_event,
+      test_reminder,
+      render_toast,
+      preview_toast,
+      chest_resets,
+      helltide_end_reminders,
+      list_reminders,
+      add_custom_reminder,
+      remove_custom_reminder,
+      event_history,
+      event_stats,
+      run_diagnostics,
+      previous_crash,
+      open_crash_report,
+      check_for_update,
+      open_update_page,
+      take_pending_deep_link,
+      set_local_api,
+      set_discord_presence,
+      overlay_create,
+      overlay_show,
+      overlay_hide,
+      overlay_set_position,
+      overlay_set_click_through,
+      overlay_hud_placement,
+      overlay_set_anchor,
+      overlay_apply_style,
+      overlay_reveal,
+      enter_config,
+      overlay_status,
+      overlay_texts,
+      peek_next_event,
+      game_running,
+      reduced_motion,
+      power_status,
+      get_metrics,
+      acknowledge_reminder,
+      mark_attending,
+      preview_telemetry_payload,
+      send_test_push,
+      test_webhook,
+      list_shortcuts,
+      list_fonts,
+      get_preferences,
+      onboarding_status,
+      complete_onboarding,
+      set_autostart,
+      set_preferences,
+      about_info,
+      list_profiles,
+      save_profile,
+      delete_profile,
+      activate_profile,
+      set_sync_dir,
+      sync_push_settings,
+      set_locale,
+      reminder_pause_status,
+      resume_reminders,
     ])
-    .build(tauri::generate_context!())
+    .build(context)
     .expect("error while building tauri application")
-    .run(|_app_handle, _event| {
-      // Tauri handles exit gracefully by default
+    .run(|app_handle, event| match event {
+      // Destroying the last window (unloading the main one without an overlay) is
+      // not a reason to quit; quitting goes through `shutdown::quit` with a code.
+      tauri::RunEvent::ExitRequested { code: None, api, .. } if webview::is_headless() => api.prevent_exit(),
+      tauri::RunEvent::Exit => app_handle.state::<DiscordPresence>().shutdown(),
+      _ => {}
     });
 }
@@ -0,0 +1,74 @@
+//! Reduced motion (see `helltime_core::motion`): the tray pulse stops here, and the
+//! windows get `motion:reduced` to drop their CSS animations and transitions.
+
+use helltime_core::motion;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+/// The system setting has no change notification outside a window procedure.
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Default)]
+pub struct Motion {
+  reduced: AtomicBool,
+}
+
+/// Whether motion is reduced right now.
+pub fn is_reduced(app: &tauri::AppHandle) -> bool {
+  app.state::<Motion>().reduced.load(Ordering::SeqCst)
+}
+
+/// Windows' "Show animations in Windows" (`SPI_GETCLIENTAREAANIMATION`).
+#[cfg(windows)]
+fn system_animations() -> bool {
+  const SPI_GETCLIENTAREAANIMATION: u32 = 0x1042;
+
+  #[link(name = "user32")]
+  extern "system" {
+    fn SystemParametersInfoW(action: u32, param: u32, value: *mut std::ffi::c_void, win_ini: u32) -> i32;
+  }
+
+  let mut enabled = 1i32;
+  let ok = unsafe {
+    SystemParametersInfoW(SPI_GETCLIENTAREAANIMATION, 0, &mut enabled as *mut i32 as *mut std::ffi::c_void, 0)
+  };
+  ok == 0 || enabled != 0
+}
+
+/// GNOME's `enable-animations`; other desktops have no common setting.
+#[cfg(target_os = "linux")]
+fn system_animations() -> bool {
+  std::process::Command::new("gsettings")
+    .args(["get", "org.gnome.desktop.interface", "enable-animations"])
+    .output()
+    .ok()
+    .and_then(|out| motion::parse_gsettings_bool(&String::from_utf8_lossy(&out.stdout)))
+    .unwrap_or(true)
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn system_animations() -> bool {
+  true
+}
+
+/// Re-evaluate the preference and the system setting, and tell the windows about a
+/// change.
+pub async fn update(app: &tauri::AppHandle) {
+  let setting = crate::preferences(app).reduced_motion;
+  let system = tauri::async_runtime::spawn_blocking(system_animations).await.unwrap_or(true);
+  let reduced = motion::reduced(setting, system);
+  if app.state::<Motion>().reduced.swap(reduced, Ordering::SeqCst) != reduced {
+    applog!("🐢 Reduced motion {}", if reduced { "on" } else { "off" });
+    let _ = app.emit("motion:reduced", reduced);
+  }
+}
+
+pub fn spawn_watcher(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    loop {
+      update(&app).await;
+      tokio::time::sleep(crate::game_mode::throttled(&app, CHECK_INTERVAL)).await;
+    }
+  });
+}
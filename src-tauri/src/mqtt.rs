@@ -0,0 +1,108 @@
+//! Publishes countdowns and "event active" states to an MQTT broker (see
+//! `helltime_core::mqtt`), reconnecting on its own.
+
+use crate::AppState;
+use helltime_core::countdown;
+use helltime_core::mqtt::{self, Message, MqttSettings, ONLINE, OFFLINE, PUBLISH_INTERVAL};
+use std::time::Duration;
+use tauri::Manager;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often a disabled client looks at the preferences again.
+const IDLE_CHECK: Duration = Duration::from_secs(5);
+
+fn client_id() -> String {
+  format!("helltime-{}", std::process::id())
+}
+
+async fn open(settings: &MqttSettings) -> Result<TcpStream, String> {
+  let address = (settings.host.trim().to_string(), settings.port);
+  let mut stream = tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect(address))
+    .await
+    .map_err(|_| "connect timed out".to_string())?
+    .map_err(|e| e.to_string())?;
+  stream.write_all(&mqtt::connect(settings, &client_id())).await.map_err(|e| e.to_string())?;
+  let mut connack = [0u8; 4];
+  tokio::time::timeout(CONNECT_TIMEOUT, stream.read_exact(&mut connack))
+    .await
+    .map_err(|_| "no CONNACK".to_string())?
+    .map_err(|e| e.to_string())?;
+  mqtt::check_connack(connack)?;
+  Ok(stream)
+}
+
+async fn publish(stream: &mut TcpStream, messages: &[Message]) -> Result<(), String> {
+  let mut bytes = Vec::new();
+  for message in messages {
+    bytes.extend_from_slice(&mqtt::publish(message));
+  }
+  stream.write_all(&bytes).await.map_err(|e| e.to_string())
+}
+
+/// State messages from the cached schedule; nothing before the first fetch.
+async fn states(app: &tauri::AppHandle, settings: &MqttSettings) -> Vec<Message> {
+  let state = app.state::<AppState>();
+  let Some(schedule) = state.inner().cache.lock().await.value().cloned() else { return Vec::new() };
+  let now = state.inner().clock.lock().await.server_now();
+  let kinds = crate::preferences(app).shown_kinds();
+  let next = countdown::next_events(&schedule, &kinds, now, None);
+  let active = countdown::active_events(&schedule, &kinds, &crate::cadence::current(app).helltide, now);
+  mqtt::states(settings, &kinds, &next, &active)
+}
+
+/// One connection: announce, then publish until the settings change, the app quits
+/// or the broker goes away. `failures` is reset once connected.
+async fn session(app: &tauri::AppHandle, settings: &MqttSettings, failures: &mut u32) -> Result<(), String> {
+  let mut stream = open(settings).await?;
+  *failures = 0;
+  applog!("📡 Connected to MQTT broker {}:{}", settings.host.trim(), settings.port);
+  publish(&mut stream, &[Message { topic: settings.status_topic(), payload: ONLINE.into() }]).await?;
+  let mut shown = None;
+  loop {
+    let kinds = crate::preferences(app).shown_kinds();
+    // Again whenever categories are hidden or shown, so their entities follow.
+    if settings.discovery && shown.as_ref() != Some(&kinds) {
+      publish(&mut stream, &mqtt::discovery(settings, &kinds)).await?;
+      shown = Some(kinds);
+    }
+    publish(&mut stream, &states(app, settings).await).await?;
+    tokio::time::sleep(PUBLISH_INTERVAL).await;
+    if crate::shutdown::in_progress() || crate::preferences(app).mqtt != *settings {
+      // A clean DISCONNECT drops the will, so say "offline" first.
+      let offline = Message { topic: settings.status_topic(), payload: OFFLINE.into() };
+      publish(&mut stream, &[offline]).await?;
+      let _ = stream.write_all(&mqtt::disconnect()).await;
+      return Ok(());
+    }
+  }
+}
+
+/// Keep a connection to the broker from the preferences while enabled.
+pub fn spawn(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    let mut failures = 0;
+    loop {
+      if crate::shutdown::in_progress() {
+        return;
+      }
+      let settings = crate::preferences(&app).mqtt;
+      if !settings.enabled {
+        tokio::time::sleep(IDLE_CHECK).await;
+        continue;
+      }
+      if let Err(e) = session(&app, &settings, &mut failures).await {
+        let delay = mqtt::reconnect_delay(failures);
+        applog!("⚠ MQTT broker {}:{}: {}; reconnecting in {}s", settings.host.trim(), settings.port, e, delay.as_secs());
+        failures += 1;
+        // Cut short when the settings change, e.g. to fix a wrong host.
+        let until = tokio::time::Instant::now() + delay;
+        while tokio::time::Instant::now() < until && crate::preferences(&app).mqtt == settings {
+          tokio::time::sleep(IDLE_CHECK.min(until - tokio::time::Instant::now())).await;
+        }
+      }
+    }
+  });
+}
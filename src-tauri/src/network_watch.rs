@@ -0,0 +1,69 @@
+//! Refresh the schedule as soon as the network comes back (see
+//! `helltime_core::network_change`). Windows reports address changes through
+//! `NotifyAddrChange`, on a thread of its own since the call blocks; Linux samples
+//! the routing table. The UI gets `system:network-changed` and re-reads the
+//! schedule, which the backend has fetched by then.
+
+use crate::AppState;
+use tauri::{Emitter, Manager};
+
+/// Fetch the schedule past the cache TTL (still rate limited) and tell the UI.
+async fn refresh(app: &tauri::AppHandle) {
+  applog!("🌐 Network changed, refreshing the schedule");
+  let state = app.state::<AppState>();
+  if let Err(e) = crate::load_schedule(app, &state, true).await {
+    applog!("⚠ Refresh after network change failed: {}", e);
+  }
+  let _ = app.emit("system:network-changed", ());
+  crate::tray::refresh_tooltip(app).await;
+}
+
+#[cfg(windows)]
+pub fn spawn_watcher(app: tauri::AppHandle) {
+  #[link(name = "iphlpapi")]
+  extern "system" {
+    fn NotifyAddrChange(handle: *mut isize, overlapped: *const std::ffi::c_void) -> u32;
+  }
+
+  #[link(name = "wininet")]
+  extern "system" {
+    fn InternetGetConnectedState(flags: *mut u32, reserved: u32) -> i32;
+  }
+
+  const NO_ERROR: u32 = 0;
+
+  std::thread::spawn(move || loop {
+    // Without a handle and overlapped structure the call blocks until an IPv4
+    // address changes.
+    if unsafe { NotifyAddrChange(std::ptr::null_mut(), std::ptr::null()) } != NO_ERROR {
+      applog!("⚠ Network change notifications unavailable");
+      return;
+    }
+    std::thread::sleep(helltime_core::network_change::SETTLE);
+    let mut flags = 0u32;
+    if unsafe { InternetGetConnectedState(&mut flags, 0) } != 0 {
+      let app = app.clone();
+      tauri::async_runtime::spawn(async move { refresh(&app).await });
+    }
+  });
+}
+
+#[cfg(target_os = "linux")]
+pub fn spawn_watcher(app: tauri::AppHandle) {
+  use helltime_core::network_change::{self, NetworkWatch};
+
+  tauri::async_runtime::spawn(async move {
+    let mut watch = NetworkWatch::default();
+    loop {
+      let table = std::fs::read_to_string("/proc/net/route").unwrap_or_default();
+      if watch.observe(network_change::default_routes(&table)) {
+        tokio::time::sleep(network_change::SETTLE).await;
+        refresh(&app).await;
+      }
+      tokio::time::sleep(crate::game_mode::throttled(&app, network_change::CHECK_INTERVAL)).await;
+    }
+  });
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+pub fn spawn_watcher(_app: tauri::AppHandle) {}
@@ -0,0 +1,152 @@
+//! Desktop notifications sent by the backend so a click on one can be routed
+//! back into the app. The notification plugin only fires and forgets, so the
+//! platform notifiers are used directly where they report activation. Reminders
+//! go through [`queue`], which merges bursts and caps the rate.
+
+use helltime_core::coalesce::{self, Batch, NotificationCoalescer};
+use helltime_core::controller::Message;
+use helltime_core::i18n::{self, Msg};
+use helltime_core::schedule::EventKind;
+use serde::Serialize;
+use std::sync::Mutex as StdMutex;
+use std::time::Instant;
+use tauri::{Emitter, Manager};
+
+/// What a notification is about; sent back with `notification:activated`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationTarget {
+  pub kind: EventKind,
+  pub id: i64,
+}
+
+struct Pending {
+  title: String,
+  body: String,
+  target: NotificationTarget,
+}
+
+#[derive(Default)]
+pub struct NotifyQueue(StdMutex<NotificationCoalescer<Pending>>);
+
+/// Show a reminder notification, merged with others arriving within
+/// [`coalesce::WINDOW`] and dropped beyond [`coalesce::MAX_PER_MINUTE`].
+pub fn queue(app: &tauri::AppHandle, title: String, body: String, target: NotificationTarget) {
+  let started = app.state::<NotifyQueue>().0.lock().unwrap_or_else(|e| e.into_inner()).push(Pending { title, body, target });
+  if !started {
+    return;
+  }
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    tokio::time::sleep(coalesce::WINDOW).await;
+    let batch = app.state::<NotifyQueue>().0.lock().unwrap_or_else(|e| e.into_inner()).flush(Instant::now());
+    let (title, body, target, attendable) = match batch {
+      None => {
+        applog!("🔕 Notification dropped: more than {} per minute", coalesce::MAX_PER_MINUTE);
+        return;
+      }
+      Some(Batch::One(p)) => (p.title, p.body, p.target, true),
+      Some(Batch::Summary(items)) => {
+        let count = items.len().to_string();
+        // A click leads to the first of them.
+        let target = items[0].target.clone();
+        let title = i18n::format(crate::locale::current(&app), Msg::NotificationSummary, &[("count", &count)]);
+        let body = items.iter().map(|p| format!("{} · {}", p.title, p.body)).collect::<Vec<_>>().join("\n");
+        // "I'm there" would only apply to the first of them.
+        (title, body, target, false)
+      }
+    };
+    crate::push::queue(&app, &title, &body);
+    if let Err(e) = show(&app, &title, &body, target, attendable) {
+      applog!("✗ Notification failed: {}", e);
+    }
+  });
+}
+
+/// Action id of the "I'm there" notification button.
+const ATTEND_ACTION: &str = "attend";
+
+/// The "I'm there" button: mute the event's reminders without opening the window.
+fn attend(app: &tauri::AppHandle, target: NotificationTarget) {
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    if let Err(e) = crate::attend(&app, Some(target.kind), target.id).await {
+      applog!("✗ Failed to mark event as attended: {}", e);
+    }
+  });
+}
+
+/// Restore the main window and let the UI focus the event's card.
+fn activated(app: &tauri::AppHandle, target: NotificationTarget) {
+  applog!("🔔 Notification clicked: {:?}", target);
+  crate::escalation::acknowledge(app, target.kind, target.id);
+  crate::controller::send(app, Message::ShowWindow);
+  let _ = app.emit("notification:activated", target);
+}
+
+#[cfg(windows)]
+pub fn show(app: &tauri::AppHandle, title: &str, body: &str, target: NotificationTarget, attendable: bool) -> Result<(), String> {
+  use tauri_winrt_notification::Toast;
+
+  // Toasts need the installed app's AppUserModelID; dev builds borrow PowerShell's.
+  let exe = std::env::current_exe().map_err(|e| format!("current exe: {e}"))?;
+  let dev = exe.parent().is_some_and(|dir| dir.ends_with("target\\debug") || dir.ends_with("target\\release"));
+  let app_id = if dev { Toast::POWERSHELL_APP_ID.to_string() } else { app.config().identifier.clone() };
+
+  let handle = app.clone();
+  let (title, body) = (title.to_string(), body.to_string());
+  let attend_label = i18n::text(crate::locale::current(app), Msg::NotificationAttend);
+  // Toast::show blocks on COM; keep it off the caller's thread.
+  std::thread::spawn(move || {
+    let mut toast = Toast::new(&app_id).title(&title).text1(&body);
+    if attendable {
+      toast = toast.add_button(attend_label, ATTEND_ACTION);
+    }
+    let result = toast
+      .on_activated(move |action| {
+        match action.as_deref() {
+          Some(ATTEND_ACTION) => attend(&handle, target.clone()),
+          _ => activated(&handle, target.clone()),
+        }
+        Ok(())
+      })
+      .show();
+    if let Err(e) = result {
+      applog!("✗ Notification failed: {}", e);
+    }
+  });
+  Ok(())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn show(app: &tauri::AppHandle, title: &str, body: &str, target: NotificationTarget, attendable: bool) -> Result<(), String> {
+  let locale = crate::locale::current(app);
+  let mut notification = notify_rust::Notification::new();
+  notification
+    .appname("helltime")
+    .summary(title)
+    .body(body)
+    // The "default" action is what notification daemons invoke on a click.
+    .action("default", i18n::text(locale, Msg::NotificationOpen));
+  if attendable {
+    notification.action(ATTEND_ACTION, i18n::text(locale, Msg::NotificationAttend));
+  }
+  let handle = notification.show().map_err(|e| format!("notification failed: {e}"))?;
+
+  let app = app.clone();
+  std::thread::spawn(move || {
+    handle.wait_for_action(|action| match action {
+      "default" => activated(&app, target),
+      ATTEND_ACTION => attend(&app, target),
+      _ => {}
+    });
+  });
+  Ok(())
+}
+
+/// macOS: no activation callback available; the notification only informs.
+#[cfg(target_os = "macos")]
+pub fn show(app: &tauri::AppHandle, title: &str, body: &str, _target: NotificationTarget, _attendable: bool) -> Result<(), String> {
+  use tauri_plugin_notification::NotificationExt;
+  app.notification().builder().title(title).body(body).show().map_err(|e| e.to_string())
+}
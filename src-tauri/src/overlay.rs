@@ -0,0 +1,726 @@
+//! The always-on-top webview overlay (label `overlay`, rendered by `OverlayWindow.tsx`).
+//!
+//! The backend owns its lifecycle so the window is created with the same options no
+//! matter which webview asks for it, and so its click-through state is known here
+//! (diagnostics) instead of only inside the frontend.
+//!
+//! Commands go through [`OverlayFacade`], which routes to the engine picked in the
+//! preferences. Only the webview engine exists so far; picking the native one falls
+//! back to the webview and the status reports both. Everything an engine is asked to
+//! do (style, anchors, click-through) goes through the facade, so the engines stay
+//! interchangeable; the overlay webview does not change its own window.
+//!
+//! Rendering: the transparent webview is composited by WebView2 through
+//! DirectComposition, so it already has per-pixel alpha (translucent rounded
+//! background, opaque text, soft CSS shadows) without GDI layered-window painting.
+//! `overlay_engine` is the renderer switch; a GDI/`UpdateLayeredWindow` path would
+//! only make sense as part of the native engine.
+//!
+//! Under Wine/Proton (`helltime_core::overlay_compat`) the window is opaque and
+//! click-through skips the layered styles; the status lists what that gives up.
+//!
+//! There is no overlay thread or Win32 message loop yet: the webview overlay runs on
+//! Tauri's event loop, so a harness driving `WM_OVERLAY_*` messages belongs with the
+//! native engine, next to its thread.
+
+use helltime_core::countdown::NextEvent;
+use helltime_core::hover_fade::{self, HoverFade};
+use helltime_core::hud_layout::{self, HudLayout, Rect, Suggestion};
+use helltime_core::i18n::{self, Msg};
+use helltime_core::overlay_compat::{self, DegradedFeature, OverlayCompat};
+use helltime_core::overlay_style::OverlayStyle;
+use helltime_core::prefs::OverlayEngine;
+use helltime_core::schedule::EventKind;
+use helltime_core::toast_template::ToastEvent;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex as StdMutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::window::Color;
+use tauri::{Emitter, Manager, PhysicalPosition, WebviewUrl, WebviewWindow, WebviewWindowBuilder, WindowEvent};
+
+pub const LABEL: &str = "overlay";
+
+const DEFAULT_POSITION: (f64, f64) = (40.0, 40.0);
+const DEFAULT_SIZE: (f64, f64) = (260.0, 130.0);
+
+/// How long [`OverlayFacade::peek`] shows the next event.
+pub const PEEK_DURATION: Duration = Duration::from_secs(3);
+
+/// How long [`OverlayFacade::reveal`] lifts click-through.
+pub const REVEAL_DURATION: Duration = Duration::from_secs(5);
+
+/// How long [`OverlayFacade::enter_config`] keeps the overlay in positioning mode.
+pub const CONFIG_DURATION: Duration = Duration::from_secs(60);
+
+/// At most one `overlay:position-changed` per this interval while the overlay moves.
+const MOVE_THROTTLE: Duration = Duration::from_millis(100);
+
+/// Background of the opaque overlay in Wine mode, the standard theme's tint.
+const WINE_BACKGROUND: Color = Color(12, 12, 14, 255);
+/// Cleared explicitly: some WebView2 versions paint white behind a transparent window.
+const TRANSPARENT_BACKGROUND: Color = Color(0, 0, 0, 0);
+
+/// How often the visibility schedule is re-evaluated.
+const SCHEDULE_CHECK: Duration = Duration::from_secs(30);
+
+/// Cursor sampling interval while hover fading is off or the overlay is hidden.
+const HOVER_IDLE: Duration = Duration::from_secs(1);
+
+/// Click-through setting, remembered so a recreated window gets it again.
+pub struct OverlayState {
+  click_through: AtomicBool,
+  /// Bumped per peek so only the latest one hides the overlay again.
+  peeks: AtomicU64,
+  /// Set while the overlay is only on screen because of a peek that will hide it.
+  peek_shown: AtomicBool,
+  /// Bumped per reveal so only the latest one restores click-through.
+  reveals: AtomicU64,
+  /// Set while a reveal has lifted click-through and will restore it.
+  revealing: AtomicBool,
+  /// Whether the visibility schedule currently allows the overlay.
+  scheduled_visible: AtomicBool,
+  /// Whether the window is currently excluded from screen capture.
+  capture_hidden: AtomicBool,
+  /// The `overlay_hover_fade` preference, read on every cursor sample.
+  hover_fade: AtomicBool,
+  /// Set while a throttled position broadcast is waiting to go out.
+  move_pending: AtomicBool,
+  /// Most recent failed overlay operation.
+  last_error: StdMutex<Option<String>>,
+  /// What the last `overlay:status` event carried, so unchanged states are not re-sent.
+  last_status: StdMutex<Option<OverlayStatus>>,
+}
+
+impl Default for OverlayState {
+  fn default() -> Self {
+    Self {
+      click_through: AtomicBool::new(true),
+      peeks: AtomicU64::new(0),
+      peek_shown: AtomicBool::new(false),
+      reveals: AtomicU64::new(0),
+      revealing: AtomicBool::new(false),
+      scheduled_visible: AtomicBool::new(true),
+      capture_hidden: AtomicBool::new(false),
+      hover_fade: AtomicBool::new(false),
+      move_pending: AtomicBool::new(false),
+      last_error: StdMutex::new(None),
+      last_status: StdMutex::new(None),
+    }
+  }
+}
+
+/// `helltime:toast` payload understood by `OverlayWindow.tsx`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Toast {
+  title: String,
+  body: String,
+  #[serde(rename = "type")]
+  event_type: EventKind,
+  kind: &'static str,
+  duration_ms: u64,
+}
+
+/// `overlay:position-changed` payload: the top-left corner in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct OverlayPosition {
+  pub x: i32,
+  pub y: i32,
+}
+
+/// Also sent as the `overlay:status` event whenever it changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayStatus {
+  /// Engine chosen in the preferences.
+  pub requested: OverlayEngine,
+  /// Engine actually drawing the overlay.
+  pub engine: OverlayEngine,
+  /// Whether the native engine can be chosen at all.
+  pub native_available: bool,
+  pub exists: bool,
+  pub visible: bool,
+  pub click_through: bool,
+  /// False outside the time windows of the visibility schedule.
+  pub scheduled_visible: bool,
+  /// Hidden from screen capture (the `overlay_capture_hidden` preference is in effect).
+  pub capture_hidden: bool,
+  pub position: Option<OverlayPosition>,
+  pub last_error: Option<String>,
+  pub compat: OverlayCompat,
+  /// Reported by Wine's `wine_get_version`.
+  pub wine_version: Option<String>,
+  /// Features the compatibility mode turns off.
+  pub degraded: Vec<DegradedFeature>,
+}
+
+/// The version of the Wine/Proton this runs under, if any.
+#[cfg(windows)]
+pub fn wine_version() -> Option<&'static str> {
+  static VERSION: OnceLock<Option<String>> = OnceLock::new();
+  VERSION
+    .get_or_init(|| {
+      #[link(name = "kernel32")]
+      extern "system" {
+        fn GetModuleHandleW(name: *const u16) -> isize;
+        fn GetProcAddress(module: isize, name: *const u8) -> *const std::ffi::c_void;
+      }
+
+      let ntdll: Vec<u16> = "ntdll.dll".encode_utf16().chain([0]).collect();
+      unsafe {
+        let module = GetModuleHandleW(ntdll.as_ptr());
+        if module == 0 {
+          return None;
+        }
+        let proc = GetProcAddress(module, c"wine_get_version".as_ptr().cast());
+        if proc.is_null() {
+          return None;
+        }
+        let wine_get_version: extern "C" fn() -> *const std::ffi::c_char = std::mem::transmute(proc);
+        let version = wine_get_version();
+        (!version.is_null()).then(|| std::ffi::CStr::from_ptr(version).to_string_lossy().into_owned())
+      }
+    })
+    .as_deref()
+}
+
+#[cfg(not(windows))]
+pub fn wine_version() -> Option<&'static str> {
+  None
+}
+
+/// The compatibility mode, detected once and logged.
+pub fn compat() -> OverlayCompat {
+  static COMPAT: OnceLock<OverlayCompat> = OnceLock::new();
+  *COMPAT.get_or_init(|| {
+    let env = std::env::var(overlay_compat::ENV_OVERRIDE).ok();
+    let compat = overlay_compat::detect(wine_version(), env.as_deref());
+    if compat == OverlayCompat::Wine {
+      applog!("🍷 Wine {} detected, overlay in compatibility mode", wine_version().unwrap_or("(forced)"));
+    }
+    compat
+  })
+}
+
+/// Result of [`OverlayFacade::hud_placement`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HudPlacement {
+  /// Safe zones the overlay fits in on its monitor.
+  pub suggestions: Vec<Suggestion>,
+  /// HUD elements the overlay covers where it is now.
+  pub covered: Vec<&'static str>,
+}
+
+/// The native engine is not part of this build yet.
+pub fn native_available() -> bool {
+  false
+}
+
+/// One entry point for overlay operations, whichever engine draws it.
+pub struct OverlayFacade<'a> {
+  app: &'a tauri::AppHandle,
+  requested: OverlayEngine,
+  engine: OverlayEngine,
+}
+
+impl<'a> OverlayFacade<'a> {
+  pub fn new(app: &'a tauri::AppHandle) -> Self {
+    let requested = crate::preferences(app).overlay_engine;
+    let engine = match requested {
+      OverlayEngine::Native if native_available() => OverlayEngine::Native,
+      _ => OverlayEngine::Webview,
+    };
+    Self { app, requested, engine }
+  }
+
+  pub fn create(&self) -> Result<(), String> {
+    self.track(match self.engine {
+      OverlayEngine::Webview => ensure(self.app).map(|_| ()),
+      OverlayEngine::Native => Err(NATIVE_MISSING.into()),
+    })
+  }
+
+  /// Does nothing outside the visibility schedule's windows.
+  pub fn show(&self) -> Result<(), String> {
+    if !self.app.state::<OverlayState>().scheduled_visible.load(Ordering::SeqCst) {
+      return Ok(());
+    }
+    self.track(match self.engine {
+      OverlayEngine::Webview => show(self.app),
+      OverlayEngine::Native => Err(NATIVE_MISSING.into()),
+    })
+  }
+
+  pub fn hide(&self) -> Result<(), String> {
+    self.track(match self.engine {
+      OverlayEngine::Webview => hide(self.app),
+      OverlayEngine::Native => Err(NATIVE_MISSING.into()),
+    })
+  }
+
+  /// Move the overlay's top-left corner (physical pixels).
+  pub fn set_position(&self, x: i32, y: i32) -> Result<(), String> {
+    self.track(match self.engine {
+      OverlayEngine::Webview => ensure(self.app).and_then(|window| {
+        window.set_position(PhysicalPosition::new(x, y)).map_err(|e| format!("move overlay: {e}"))
+      }),
+      OverlayEngine::Native => Err(NATIVE_MISSING.into()),
+    })
+  }
+
+  /// Move the overlay into the safe zone `zone` of the HUD `layout`.
+  pub fn set_anchor(&self, layout: HudLayout, zone: &str) -> Result<Suggestion, String> {
+    let placement = self.hud_placement(layout)?;
+    let suggestion = placement
+      .suggestions
+      .into_iter()
+      .find(|s| s.zone == zone)
+      .ok_or_else(|| format!("overlay does not fit in {zone} for {layout:?}"))?;
+    self.set_position(suggestion.x, suggestion.y)?;
+    Ok(suggestion)
+  }
+
+  /// Draw the overlay in `style`. The webview reads the UI's settings itself, so it
+  /// is sent the style and told to reload them.
+  pub fn apply_style(&self, style: &OverlayStyle) -> Result<(), String> {
+    self.track(match self.engine {
+      OverlayEngine::Webview => match self.app.get_webview_window(LABEL) {
+        Some(_) => self.app.emit_to(LABEL, "helltime:overlay-settings", style).map_err(|e| format!("overlay style: {e}")),
+        None => Ok(()),
+      },
+      OverlayEngine::Native => Err(NATIVE_MISSING.into()),
+    })
+  }
+
+  /// Safe-zone suggestions for the game's HUD `layout` on the overlay's monitor.
+  pub fn hud_placement(&self, layout: HudLayout) -> Result<HudPlacement, String> {
+    let window = match self.engine {
+      OverlayEngine::Webview => ensure(self.app)?,
+      OverlayEngine::Native => return Err(NATIVE_MISSING.into()),
+    };
+    let monitor = window
+      .current_monitor()
+      .ok()
+      .flatten()
+      .or_else(|| window.primary_monitor().ok().flatten())
+      .ok_or("no monitor for the overlay")?;
+    let monitor = Rect {
+      x: monitor.position().x,
+      y: monitor.position().y,
+      width: monitor.size().width,
+      height: monitor.size().height,
+    };
+    let size = window.outer_size().map_err(|e| format!("overlay size: {e}"))?;
+    let position = window.outer_position().map_err(|e| format!("overlay position: {e}"))?;
+    let overlay = Rect { x: position.x, y: position.y, width: size.width, height: size.height };
+    Ok(HudPlacement {
+      suggestions: hud_layout::suggest(layout, monitor, (size.width, size.height)),
+      covered: hud_layout::covered(layout, monitor, overlay),
+    })
+  }
+
+  pub fn set_click_through(&self, enabled: bool) -> Result<(), String> {
+    self.track(match self.engine {
+      OverlayEngine::Webview => set_click_through(self.app, enabled),
+      OverlayEngine::Native => Err(NATIVE_MISSING.into()),
+    })
+  }
+
+  /// Show `next` for [`PEEK_DURATION`], then hide the overlay again unless it was
+  /// already visible. Driven from here, so it works without the main window.
+  pub fn peek(&self, next: &NextEvent) -> Result<(), String> {
+    self.track(self.peek_with_engine(next))
+  }
+
+  fn peek_with_engine(&self, next: &NextEvent) -> Result<(), String> {
+    match self.engine {
+      OverlayEngine::Webview => {
+        let state = self.app.state::<OverlayState>();
+        // Every peek cancels the pending hide of the one before; a peek during another
+        // peek takes over hiding, since the overlay was not up on its own.
+        let generation = state.peeks.fetch_add(1, Ordering::SeqCst) + 1;
+        let was_visible = self.status().visible && !state.peek_shown.load(Ordering::SeqCst);
+        let now = chrono::Utc::now();
+        let text = crate::preferences(self.app).toast_templates.render(
+          &ToastEvent::from_next(next, now),
+          crate::locale::current(self.app),
+          now,
+          &chrono::Local,
+        );
+        let toast = Toast {
+          title: text.title,
+          body: text.body,
+          event_type: next.kind,
+          kind: "event",
+          duration_ms: PEEK_DURATION.as_millis() as u64,
+        };
+        show(self.app)?;
+        self
+          .app
+          .emit_to(LABEL, "helltime:toast", &toast)
+          .map_err(|e| format!("peek overlay: {e}"))?;
+        if was_visible {
+          return Ok(());
+        }
+        state.peek_shown.store(true, Ordering::SeqCst);
+        let app = self.app.clone();
+        tauri::async_runtime::spawn(async move {
+          tokio::time::sleep(PEEK_DURATION).await;
+          let state = app.state::<OverlayState>();
+          if state.peeks.load(Ordering::SeqCst) == generation {
+            state.peek_shown.store(false, Ordering::SeqCst);
+            let _ = hide(&app);
+            publish_status(&app);
+          }
+        });
+        Ok(())
+      }
+      OverlayEngine::Native => Err(NATIVE_MISSING.into()),
+    }
+  }
+
+  /// Make a click-through overlay clickable for [`REVEAL_DURATION`] so it can be
+  /// dragged without opening the settings, then make it click-through again. The
+  /// overlay shows its positioning handles meanwhile (`overlay:reveal`).
+  pub fn reveal(&self) -> Result<(), String> {
+    self.track(self.reveal_with_engine(REVEAL_DURATION))
+  }
+
+  /// Positioning mode for the onboarding: like [`Self::reveal`], but for
+  /// [`CONFIG_DURATION`] so there is time to find a spot next to the game.
+  pub fn enter_config(&self) -> Result<(), String> {
+    self.track(self.reveal_with_engine(CONFIG_DURATION))
+  }
+
+  fn reveal_with_engine(&self, duration: Duration) -> Result<(), String> {
+    match self.engine {
+      OverlayEngine::Webview => {
+        let state = self.app.state::<OverlayState>();
+        // A second press during a reveal extends it rather than keeping it clickable.
+        let restore = state.click_through.load(Ordering::SeqCst) || state.revealing.load(Ordering::SeqCst);
+        show(self.app)?;
+        set_click_through(self.app, false)?;
+        self
+          .app
+          .emit_to(LABEL, "overlay:reveal", duration.as_millis() as u64)
+          .map_err(|e| format!("reveal overlay: {e}"))?;
+        let generation = state.reveals.fetch_add(1, Ordering::SeqCst) + 1;
+        if !restore {
+          return Ok(());
+        }
+        state.revealing.store(true, Ordering::SeqCst);
+        let app = self.app.clone();
+        tauri::async_runtime::spawn(async move {
+          tokio::time::sleep(duration).await;
+          let state = app.state::<OverlayState>();
+          if state.reveals.load(Ordering::SeqCst) == generation {
+            state.revealing.store(false, Ordering::SeqCst);
+            if let Err(e) = set_click_through(&app, true) {
+              applog!("✗ Overlay click-through not restored: {}", e);
+            }
+            publish_status(&app);
+          }
+        });
+        Ok(())
+      }
+      OverlayEngine::Native => Err(NATIVE_MISSING.into()),
+    }
+  }
+
+  pub fn status(&self) -> OverlayStatus {
+    let state = self.app.state::<OverlayState>();
+    let window = self.app.get_webview_window(LABEL);
+    let last_error = state.last_error.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    OverlayStatus {
+      requested: self.requested,
+      engine: self.engine,
+      native_available: native_available(),
+      exists: window.is_some(),
+      visible: window.as_ref().and_then(|w| w.is_visible().ok()).unwrap_or(false),
+      click_through: state.click_through.load(Ordering::SeqCst),
+      scheduled_visible: state.scheduled_visible.load(Ordering::SeqCst),
+      capture_hidden: state.capture_hidden.load(Ordering::SeqCst),
+      position: window
+        .as_ref()
+        .and_then(|w| w.outer_position().ok())
+        .map(|p| OverlayPosition { x: p.x, y: p.y }),
+      last_error,
+      compat: compat(),
+      wine_version: wine_version().map(str::to_string),
+      degraded: compat().degraded(),
+    }
+  }
+
+  /// Remember the outcome for the status (a success clears an earlier failure)
+  /// and publish whatever changed.
+  fn track(&self, result: Result<(), String>) -> Result<(), String> {
+    *self.app.state::<OverlayState>().last_error.lock().unwrap_or_else(|e| e.into_inner()) = result.as_ref().err().cloned();
+    publish_status(self.app);
+    result
+  }
+}
+
+/// Emit `overlay:status` if the status differs from the last one sent, so the UI
+/// does not have to poll `overlay_status`.
+pub fn publish_status(app: &tauri::AppHandle) {
+  let status = OverlayFacade::new(app).status();
+  let state = app.state::<OverlayState>();
+  let mut last = state.last_status.lock().unwrap_or_else(|e| e.into_inner());
+  if last.as_ref() == Some(&status) {
+    return;
+  }
+  *last = Some(status.clone());
+  drop(last);
+  let _ = app.emit("overlay:status", status);
+}
+
+/// Re-evaluate the visibility schedule. On a change the overlay is hidden (or
+/// allowed again) and the UI is told via `overlay:schedule` so it can show it.
+pub fn apply_schedule(app: &tauri::AppHandle) {
+  let allowed = crate::preferences(app).overlay_visibility.allows(&chrono::Local::now());
+  let previous = app.state::<OverlayState>().scheduled_visible.swap(allowed, Ordering::SeqCst);
+  if previous == allowed {
+    return;
+  }
+  if !allowed {
+    let _ = OverlayFacade::new(app).hide();
+  }
+  applog!("🕒 Overlay {} by its visibility schedule", if allowed { "allowed" } else { "hidden" });
+  let _ = app.emit("overlay:schedule", allowed);
+  publish_status(app);
+}
+
+/// Apply the `overlay_capture_hidden` preference to an existing overlay window.
+pub fn apply_capture_exclusion(app: &tauri::AppHandle) {
+  let Some(window) = app.get_webview_window(LABEL) else { return };
+  let _ = OverlayFacade::new(app).track(capture_exclusion(app, &window));
+}
+
+fn capture_exclusion(app: &tauri::AppHandle, window: &WebviewWindow) -> Result<(), String> {
+  let hidden = crate::preferences(app).overlay_capture_hidden;
+  let state = app.state::<OverlayState>();
+  if state.capture_hidden.load(Ordering::SeqCst) == hidden {
+    return Ok(());
+  }
+  set_window_capture_hidden(window, hidden)?;
+  state.capture_hidden.store(hidden, Ordering::SeqCst);
+  applog!("🎥 Overlay {} screen capture", if hidden { "hidden from" } else { "visible in" });
+  Ok(())
+}
+
+/// Take over the `overlay_hover_fade` preference.
+pub fn apply_hover_fade(app: &tauri::AppHandle) {
+  let enabled = crate::preferences(app).overlay_hover_fade;
+  app.state::<OverlayState>().hover_fade.store(enabled, Ordering::SeqCst);
+}
+
+/// Fade the overlay while the cursor rests on it. The click-through window gets no
+/// mouse events, so the cursor position is sampled instead.
+pub fn spawn_hover_watcher(app: tauri::AppHandle) {
+  apply_hover_fade(&app);
+  tauri::async_runtime::spawn(async move {
+    let mut fade = HoverFade::default();
+    loop {
+      let hovered = hovered(&app);
+      let changed = match hovered {
+        Some(inside) => fade.observe(inside, Instant::now()),
+        None => fade.reset(),
+      };
+      if let Some(faded) = changed {
+        let opacity = if faded { hover_fade::FADED_OPACITY } else { 1.0 };
+        let _ = app.emit_to(LABEL, "overlay:hover-opacity", opacity);
+      }
+      tokio::time::sleep(if hovered.is_some() { hover_fade::POLL_INTERVAL } else { HOVER_IDLE }).await;
+    }
+  });
+}
+
+/// Whether the cursor is over the overlay; `None` while fading does not apply: the
+/// option is off, the overlay hidden, or click-through lifted to move it.
+fn hovered(app: &tauri::AppHandle) -> Option<bool> {
+  let state = app.state::<OverlayState>();
+  if !state.hover_fade.load(Ordering::SeqCst) || !state.click_through.load(Ordering::SeqCst) {
+    return None;
+  }
+  let window = app.get_webview_window(LABEL)?;
+  if !window.is_visible().unwrap_or(false) {
+    return None;
+  }
+  let (position, size) = (window.outer_position().ok()?, window.outer_size().ok()?);
+  let cursor = app.cursor_position().ok()?;
+  let rect = Rect { x: position.x, y: position.y, width: size.width, height: size.height };
+  Some(rect.contains(cursor.x, cursor.y))
+}
+
+pub fn spawn_schedule_checker(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    loop {
+      apply_schedule(&app);
+      tokio::time::sleep(SCHEDULE_CHECK).await;
+    }
+  });
+}
+
+const NATIVE_MISSING: &str = "native overlay is not available in this build";
+
+/// The overlay window, created hidden and click-through on first use.
+fn ensure(app: &tauri::AppHandle) -> Result<WebviewWindow, String> {
+  if let Some(window) = app.get_webview_window(LABEL) {
+    return Ok(window);
+  }
+  if !crate::webview::overlay_allowed() {
+    return Err("running headless, overlay window unavailable".into());
+  }
+
+  let builder = WebviewWindowBuilder::new(app, LABEL, WebviewUrl::App("/?view=overlay".into()))
+    .title(i18n::text(crate::locale::current(app), Msg::OverlayTitle))
+    .position(DEFAULT_POSITION.0, DEFAULT_POSITION.1)
+    .inner_size(DEFAULT_SIZE.0, DEFAULT_SIZE.1)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .shadow(false)
+    .resizable(false)
+    .focused(false)
+    // Never takes focus from the game, even while click-through is off for dragging.
+    .focusable(false)
+    .visible_on_all_workspaces(false)
+    .visible(false);
+  #[cfg(not(target_os = "macos"))]
+  let builder = match compat().transparent() {
+    true => builder.transparent(true).background_color(TRANSPARENT_BACKGROUND),
+    false => builder.background_color(WINE_BACKGROUND),
+  };
+
+  let window = builder.build().map_err(|e| format!("create overlay: {e}"))?;
+  let handle = app.clone();
+  window.on_window_event(move |event| {
+    if let WindowEvent::Moved(_) = event {
+      broadcast_position(&handle);
+    }
+  });
+  let click_through = app.state::<OverlayState>().click_through.load(Ordering::SeqCst);
+  set_window_click_through(&window, click_through)?;
+  // A new window is always capturable; the preference is applied afresh.
+  app.state::<OverlayState>().capture_hidden.store(false, Ordering::SeqCst);
+  if let Err(e) = capture_exclusion(app, &window) {
+    applog!("⚠ Failed to hide the overlay from screen capture: {}", e);
+  }
+  applog!("🪟 Overlay window created");
+  Ok(window)
+}
+
+/// Tell the settings UI where the overlay is while it is dragged, throttled to
+/// [`MOVE_THROTTLE`]. The position is read when the broadcast goes out, so the
+/// last one of a drag always carries the final position.
+fn broadcast_position(app: &tauri::AppHandle) {
+  if app.state::<OverlayState>().move_pending.swap(true, Ordering::SeqCst) {
+    return;
+  }
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    tokio::time::sleep(MOVE_THROTTLE).await;
+    app.state::<OverlayState>().move_pending.store(false, Ordering::SeqCst);
+    let Some(position) = app.get_webview_window(LABEL).and_then(|w| w.outer_position().ok()) else {
+      return;
+    };
+    let _ = app.emit("overlay:position-changed", OverlayPosition { x: position.x, y: position.y });
+    publish_status(&app);
+  });
+}
+
+/// Close the overlay window for good (quitting).
+pub fn destroy(app: &tauri::AppHandle) {
+  if let Some(window) = app.get_webview_window(LABEL) {
+    if let Err(e) = window.destroy() {
+      applog!("⚠ Failed to close the overlay window: {}", e);
+    }
+  }
+}
+
+fn show(app: &tauri::AppHandle) -> Result<(), String> {
+  let window = ensure(app)?;
+  if !window.is_visible().unwrap_or(false) {
+    app.state::<crate::AppState>().inner().metrics.overlay_shown();
+    crate::telemetry::record(app, helltime_core::telemetry::Feature::Overlay);
+  }
+  window.show().map_err(|e| format!("show overlay: {e}"))?;
+  // Some shells drop topmost when a window is hidden; re-assert it.
+  let _ = window.set_always_on_top(true);
+  Ok(())
+}
+
+fn hide(app: &tauri::AppHandle) -> Result<(), String> {
+  match app.get_webview_window(LABEL) {
+    Some(window) => window.hide().map_err(|e| format!("hide overlay: {e}")),
+    None => Ok(()),
+  }
+}
+
+/// Click-through lets mouse input fall through to the game underneath.
+fn set_click_through(app: &tauri::AppHandle, enabled: bool) -> Result<(), String> {
+  app.state::<OverlayState>().click_through.store(enabled, Ordering::SeqCst);
+  match app.get_webview_window(LABEL) {
+    Some(window) => set_window_click_through(&window, enabled),
+    None => Ok(()),
+  }
+}
+
+/// Toggle `WS_EX_TRANSPARENT | WS_EX_LAYERED` directly: unlike the cursor-events
+/// flag alone this also keeps the window out of hit-testing for fullscreen games.
+/// Not under Wine, where the styles changed after creation hide the window.
+#[cfg(windows)]
+fn set_window_click_through(window: &WebviewWindow, enabled: bool) -> Result<(), String> {
+  const GWL_EXSTYLE: i32 = -20;
+  const WS_EX_TRANSPARENT: isize = 0x0000_0020;
+  const WS_EX_LAYERED: isize = 0x0008_0000;
+
+  #[link(name = "user32")]
+  extern "system" {
+    fn GetWindowLongPtrW(hwnd: isize, index: i32) -> isize;
+    fn SetWindowLongPtrW(hwnd: isize, index: i32, value: isize) -> isize;
+  }
+
+  window.set_ignore_cursor_events(enabled).map_err(|e| format!("click-through: {e}"))?;
+  if !compat().layered_click_through() {
+    return Ok(());
+  }
+  let hwnd = window.hwnd().map_err(|e| format!("overlay hwnd: {e}"))?.0 as isize;
+  unsafe {
+    let style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+    let style = if enabled { style | WS_EX_TRANSPARENT | WS_EX_LAYERED } else { style & !WS_EX_TRANSPARENT };
+    SetWindowLongPtrW(hwnd, GWL_EXSTYLE, style);
+  }
+  Ok(())
+}
+
+#[cfg(not(windows))]
+fn set_window_click_through(window: &WebviewWindow, enabled: bool) -> Result<(), String> {
+  window.set_ignore_cursor_events(enabled).map_err(|e| format!("click-through: {e}"))
+}
+
+/// `WDA_EXCLUDEFROMCAPTURE` (Windows 10 2004 and later): the window is drawn on the
+/// screen but left out of captures, which show what is underneath instead.
+#[cfg(windows)]
+fn set_window_capture_hidden(window: &WebviewWindow, hidden: bool) -> Result<(), String> {
+  const WDA_NONE: u32 = 0x0000_0000;
+  const WDA_EXCLUDEFROMCAPTURE: u32 = 0x0000_0011;
+
+  #[link(name = "user32")]
+  extern "system" {
+    fn SetWindowDisplayAffinity(hwnd: isize, affinity: u32) -> i32;
+  }
+
+  let hwnd = window.hwnd().map_err(|e| format!("overlay hwnd: {e}"))?.0 as isize;
+  let affinity = if hidden { WDA_EXCLUDEFROMCAPTURE } else { WDA_NONE };
+  if unsafe { SetWindowDisplayAffinity(hwnd, affinity) } == 0 {
+    return Err(format!("capture exclusion: {}", std::io::Error::last_os_error()));
+  }
+  Ok(())
+}
+
+#[cfg(not(windows))]
+fn set_window_capture_hidden(window: &WebviewWindow, hidden: bool) -> Result<(), String> {
+  window.set_content_protected(hidden).map_err(|e| format!("capture exclusion: {e}"))
+}
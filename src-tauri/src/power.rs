@@ -0,0 +1,95 @@
+use serde::Serialize;
+use std::time::{Duration, Instant, SystemTime};
+
+const TICK: Duration = Duration::from_secs(5);
+
+/// Jumps smaller than this are scheduler jitter, not sleep or a clock change.
+const JUMP_THRESHOLD: Duration = Duration::from_secs(20);
+
+/// A discontinuity in time observed by the watcher.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TimeJump {
+  /// The process was suspended (PC slept/hibernated) for roughly `gap_secs`.
+  Resumed { gap_secs: i64 },
+  /// The wall clock moved by `delta_secs` relative to the monotonic clock
+  /// (manual change, NTP correction, timezone/DST switch on some systems).
+  ClockChanged { delta_secs: i64 },
+  /// The local UTC offset changed (DST switch or a new time zone). UTC time is
+  /// unaffected; only local display strings need to be derived again.
+  ZoneChanged { from_offset_secs: i32, to_offset_secs: i32 },
+}
+
+fn local_offset_secs() -> i32 {
+  chrono::Local::now().offset().local_minus_utc()
+}
+
+fn signed_secs(later: SystemTime, earlier: SystemTime) -> i64 {
+  match later.duration_since(earlier) {
+    Ok(d) => d.as_secs() as i64,
+    Err(e) => -(e.duration().as_secs() as i64),
+  }
+}
+
+/// Classify one watcher tick. `mono` is the monotonic elapsed time, `wall_secs`
+/// the wall-clock elapsed time (may be negative if the clock was set back).
+fn classify(mono: Duration, wall_secs: i64) -> Option<TimeJump> {
+  let threshold = JUMP_THRESHOLD.as_secs() as i64;
+  let mono_secs = mono.as_secs() as i64;
+
+  // Windows keeps counting the monotonic clock across sleep, so both clocks jump together.
+  if mono > TICK + JUMP_THRESHOLD && (wall_secs - mono_secs).abs() < threshold {
+    return Some(TimeJump::Resumed { gap_secs: mono_secs });
+  }
+
+  let delta = wall_secs - mono_secs;
+  if delta.abs() >= threshold {
+    // Linux/macOS pause the monotonic clock during suspend: a large forward-only
+    // wall delta after a short tick is indistinguishable from a resume there.
+    if delta > 0 && mono < TICK + JUMP_THRESHOLD && cfg!(not(windows)) {
+      return Some(TimeJump::Resumed { gap_secs: wall_secs });
+    }
+    return Some(TimeJump::ClockChanged { delta_secs: delta });
+  }
+
+  None
+}
+
+/// Spawn a background thread that reports sleep/resume, wall-clock and time zone changes.
+///
+/// A watcher is used instead of `WM_POWERBROADCAST`/`WM_TIMECHANGE`/`WM_SETTINGCHANGE` because the app
+/// has no top-level Win32 window of its own to receive those broadcasts.
+pub fn spawn_watcher<F>(on_jump: F)
+where
+  F: Fn(TimeJump) + Send + 'static,
+{
+  std::thread::Builder::new()
+    .name("helltime-time-watcher".into())
+    .spawn(move || {
+      let mut last_mono = Instant::now();
+      let mut last_wall = SystemTime::now();
+      let mut last_offset = local_offset_secs();
+      loop {
+        std::thread::sleep(TICK);
+        let mono_now = Instant::now();
+        let wall_now = SystemTime::now();
+
+        if let Some(jump) = classify(mono_now - last_mono, signed_secs(wall_now, last_wall)) {
+          applog!("⏰ Time jump detected: {:?}", jump);
+          on_jump(jump);
+        }
+
+        let offset = local_offset_secs();
+        if offset != last_offset {
+          let jump = TimeJump::ZoneChanged { from_offset_secs: last_offset, to_offset_secs: offset };
+          applog!("⏰ Local time zone changed: {:?}", jump);
+          on_jump(jump);
+          last_offset = offset;
+        }
+
+        last_mono = mono_now;
+        last_wall = wall_now;
+      }
+    })
+    .expect("failed to spawn time watcher thread");
+}
@@ -0,0 +1,141 @@
+//! PowerSaver saving (see `helltime_core::power_saving`): polls are stretched in
+//! `next_poll_delay`, and the windows get `power:saving` to repaint the overlay
+//! countdown less often.
+
+use helltime_core::power_saving::{self, PowerState, CHECK_INTERVAL};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+#[derive(Default)]
+pub struct PowerSaver {
+  saving: AtomicBool,
+  state: StdMutex<PowerState>,
+}
+
+/// `power_status` result.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerStatus {
+  pub saving: bool,
+  pub on_battery: bool,
+  pub metered: bool,
+}
+
+/// Whether power saving is on right now.
+pub fn is_saving(app: &tauri::AppHandle) -> bool {
+  app.state::<PowerSaver>().saving.load(Ordering::SeqCst)
+}
+
+/// The poll `delay`, stretched while saving power.
+pub fn poll_delay(app: &tauri::AppHandle, delay: Duration) -> Duration {
+  power_saving::poll_delay(delay, is_saving(app))
+}
+
+pub fn status(app: &tauri::AppHandle) -> PowerStatus {
+  let power = app.state::<PowerSaver>();
+  let state = *power.state.lock().unwrap_or_else(|e| e.into_inner());
+  PowerStatus { saving: power.saving.load(Ordering::SeqCst), on_battery: state.on_battery, metered: state.metered }
+}
+
+#[cfg(windows)]
+fn read_state() -> PowerState {
+  use std::os::windows::process::CommandExt;
+  const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+  const AC_OFFLINE: u8 = 0;
+
+  #[repr(C)]
+  #[derive(Default)]
+  struct SystemPowerStatus {
+    pub ac_line_status: u8,
+    pub battery_flag: u8,
+    pub battery_life_percent: u8,
+    pub system_status_flag: u8,
+    pub battery_life_time: u32,
+    pub battery_full_life_time: u32,
+  }
+
+  #[link(name = "kernel32")]
+  extern "system" {
+    fn GetSystemPowerStatus(status: *mut SystemPowerStatus) -> i32;
+  }
+
+  let mut status = SystemPowerStatus::default();
+  let on_battery = unsafe { GetSystemPowerStatus(&mut status) } != 0 && status.ac_line_status == AC_OFFLINE;
+
+  // The connection cost is only exposed through WinRT; PowerShell projects it.
+  const COST: &str = "[Windows.Networking.Connectivity.NetworkInformation,Windows.Networking.Connectivity,\
+    ContentType=WindowsRuntime]::GetInternetConnectionProfile().GetConnectionCost().NetworkCostType";
+  let metered = std::process::Command::new("powershell")
+    .args(["-NoProfile", "-NonInteractive", "-Command", COST])
+    .creation_flags(CREATE_NO_WINDOW)
+    .output()
+    .ok()
+    .and_then(|out| power_saving::parse_network_cost(&String::from_utf8_lossy(&out.stdout)))
+    .unwrap_or(false);
+  PowerState { on_battery, metered }
+}
+
+/// PowerSaver supplies from sysfs; the connection cost from NetworkManager.
+#[cfg(target_os = "linux")]
+fn read_state() -> PowerState {
+  let supplies: Vec<(String, Option<String>)> = std::fs::read_dir("/sys/class/power_supply")
+    .map(|entries| {
+      entries
+        .flatten()
+        .filter_map(|entry| {
+          let kind = std::fs::read_to_string(entry.path().join("type")).ok()?;
+          Some((kind, std::fs::read_to_string(entry.path().join("online")).ok()))
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+  let on_battery = power_saving::sysfs_on_battery(supplies.iter().map(|(kind, online)| (kind.as_str(), online.as_deref())));
+  let metered = std::process::Command::new("busctl")
+    .args([
+      "get-property",
+      "org.freedesktop.NetworkManager",
+      "/org/freedesktop/NetworkManager",
+      "org.freedesktop.NetworkManager",
+      "Metered",
+    ])
+    .output()
+    .ok()
+    .and_then(|out| power_saving::parse_nm_metered(&String::from_utf8_lossy(&out.stdout)))
+    .unwrap_or(false);
+  PowerState { on_battery, metered }
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn read_state() -> PowerState {
+  PowerState::default()
+}
+
+/// Re-read the power source and connection cost, apply the preference, and tell the
+/// windows about a change.
+pub async fn update(app: &tauri::AppHandle) {
+  let state = tauri::async_runtime::spawn_blocking(read_state).await.unwrap_or_default();
+  let saving = power_saving::saving(crate::preferences(app).power_saving, state);
+  let power = app.state::<PowerSaver>();
+  *power.state.lock().unwrap_or_else(|e| e.into_inner()) = state;
+  if power.saving.swap(saving, Ordering::SeqCst) != saving {
+    applog!(
+      "🔋 PowerSaver saving {} (battery: {}, metered: {})",
+      if saving { "on" } else { "off" },
+      state.on_battery,
+      state.metered
+    );
+    let _ = app.emit("power:saving", saving);
+  }
+}
+
+pub fn spawn_watcher(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    loop {
+      update(&app).await;
+      tokio::time::sleep(crate::game_mode::throttled(&app, CHECK_INTERVAL)).await;
+    }
+  });
+}
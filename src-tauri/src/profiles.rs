@@ -0,0 +1,40 @@
+//! Switching between named profiles, from the UI or the tray's "Profile" submenu.
+//! See `helltime_core::profiles`.
+
+use crate::AppState;
+use helltime_core::error::AppError;
+use helltime_core::profiles::ProfileList;
+use tauri::{Emitter, Manager};
+
+/// Menu id prefix of the tray's profile entries; the rest is the profile name.
+pub const MENU_PREFIX: &str = "profile:";
+
+pub fn list(app: &tauri::AppHandle) -> ProfileList {
+  let state = app.state::<AppState>();
+  let list = state.inner().profiles.lock().unwrap_or_else(|e| e.into_inner()).list();
+  list
+}
+
+/// Tell the UI and the tray that profiles were added, removed or switched.
+pub fn changed(app: &tauri::AppHandle) -> ProfileList {
+  let list = list(app);
+  crate::tray::rebuild_menu(app);
+  let _ = app.emit("profiles:changed", &list);
+  list
+}
+
+/// Apply profile `name` to the preferences and hand its UI settings to the UI via
+/// `profile:activated`.
+pub fn activate(app: &tauri::AppHandle, name: &str) -> Result<(), AppError> {
+  let profile = {
+    let state = app.state::<AppState>();
+    let mut store = state.inner().profiles.lock().unwrap_or_else(|e| e.into_inner());
+    store.activate(name).ok_or_else(|| AppError::Other(format!("no profile named {name:?}")))?
+  };
+  crate::store_preferences(app, |current| profile.apply(current))?;
+  applog!("🎛 Profile \"{}\" activated", profile.name);
+  crate::telemetry::record(app, helltime_core::telemetry::Feature::ProfileSwitch);
+  let _ = app.emit("profile:activated", &profile);
+  changed(app);
+  Ok(())
+}
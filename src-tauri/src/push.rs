@@ -0,0 +1,107 @@
+//! Forwards reminder notifications to phones through ntfy and Pushover (see
+//! `helltime_core::push`), retrying while offline.
+
+use crate::AppState;
+use helltime_core::error::AppError;
+use helltime_core::push::{self, Delivery, PushMessage, RETRY_INTERVAL};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::Manager;
+
+/// Keeps two sends from delivering the same message twice.
+static SENDING: AtomicBool = AtomicBool::new(false);
+
+/// Queue a reminder for every enabled service and send right away.
+pub fn queue(app: &tauri::AppHandle, title: &str, body: &str) {
+  let sinks = crate::preferences(app).push.sinks();
+  if sinks.is_empty() {
+    return;
+  }
+  {
+    let state = app.state::<AppState>();
+    let mut queue = state.inner().push.lock().unwrap_or_else(|e| e.into_inner());
+    queue.push(&sinks, title, body, chrono::Utc::now());
+    queue.save();
+  }
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move { send(&app).await });
+}
+
+/// Write the queue to disk, e.g. before quitting.
+pub fn flush(app: &tauri::AppHandle) {
+  app.state::<AppState>().inner().push.lock().unwrap_or_else(|e| e.into_inner()).save();
+}
+
+/// Post one message and return the response status.
+async fn deliver(app: &tauri::AppHandle, message: &PushMessage) -> Result<u16, String> {
+  let request = push::request(&crate::preferences(app).push, message);
+  let http = app.state::<AppState>().inner().http();
+  http.post_json_authorized(&request.url, &request.body, request.bearer.as_deref()).await.map_err(|e| e.to_string())
+}
+
+async fn send(app: &tauri::AppHandle) {
+  if SENDING.swap(true, Ordering::SeqCst) {
+    return;
+  }
+  let sinks = crate::preferences(app).push.sinks();
+  let state = app.state::<AppState>();
+  let (due, expired) = state.inner().push.lock().unwrap_or_else(|e| e.into_inner()).due(&sinks, chrono::Utc::now());
+  if expired > 0 {
+    applog!("📱 Dropped {} phone reminders that were too old or for a disabled service", expired);
+  }
+  for message in &due {
+    let sink = message.sink.label();
+    match deliver(app, message).await.map(|status| (status, push::delivery(status))) {
+      Ok((_, Delivery::Sent)) => {}
+      Ok((status, Delivery::Rejected)) => applog!("✗ {} rejected a reminder with HTTP {}; check the settings", sink, status),
+      Ok((status, Delivery::Retry)) => {
+        applog!("⚠ {} answered HTTP {}, retrying the reminder later", sink, status);
+        continue;
+      }
+      Err(e) => {
+        applog!("⚠ {} not reachable, retrying the reminder later: {}", sink, e);
+        continue;
+      }
+    }
+    state.inner().push.lock().unwrap_or_else(|e| e.into_inner()).remove(message.id);
+  }
+  if expired > 0 || !due.is_empty() {
+    state.inner().push.lock().unwrap_or_else(|e| e.into_inner()).save();
+  }
+  SENDING.store(false, Ordering::SeqCst);
+}
+
+/// A message to every enabled service right away, bypassing the queue, for the
+/// settings' test button. Fails with the services that did not take it.
+pub async fn test(app: &tauri::AppHandle, title: &str, body: &str) -> Result<(), AppError> {
+  let sinks = crate::preferences(app).push.sinks();
+  if sinks.is_empty() {
+    return Err(AppError::InvalidSettings("no phone service enabled".to_string()));
+  }
+  let mut failed = Vec::new();
+  for sink in sinks {
+    let message = PushMessage { id: 0, sink, title: title.to_string(), body: body.to_string(), queued_at: chrono::Utc::now() };
+    match deliver(app, &message).await {
+      Ok(status) if push::delivery(status) == Delivery::Sent => {}
+      Ok(status) => failed.push(format!("{}: HTTP {status}", sink.label())),
+      Err(e) => failed.push(format!("{}: {e}", sink.label())),
+    }
+  }
+  if failed.is_empty() {
+    Ok(())
+  } else {
+    Err(AppError::Network(failed.join(", ")))
+  }
+}
+
+/// Retry the queue every [`RETRY_INTERVAL`] while it holds anything.
+pub fn spawn(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    loop {
+      tokio::time::sleep(RETRY_INTERVAL).await;
+      let empty = app.state::<AppState>().inner().push.lock().unwrap_or_else(|e| e.into_inner()).is_empty();
+      if !empty {
+        send(&app).await;
+      }
+    }
+  });
+}
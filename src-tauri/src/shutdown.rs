@@ -0,0 +1,43 @@
+//! Orderly quit. Tray "Quit" and "close exits" go through [`quit`] instead of calling
+//! `app.exit(0)` directly, so pending writes land and the overlay is torn down first.
+
+use crate::AppState;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+/// Time the UI gets to stop its timers after `app:shutdown` before the process exits.
+const UI_GRACE: Duration = Duration::from_millis(300);
+
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Whether [`quit`] is running; schedule fetches and notifications are refused then.
+pub fn in_progress() -> bool {
+  SHUTTING_DOWN.load(Ordering::SeqCst)
+}
+
+/// Stop polling and reminders, flush what is still pending, close the overlay, then
+/// exit. Repeated calls while it runs are ignored.
+pub fn quit(app: &tauri::AppHandle) {
+  if SHUTTING_DOWN.swap(true, Ordering::SeqCst) {
+    return;
+  }
+  applog!("👋 Shutting down...");
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    // The UI owns the poll and reminder timers.
+    let _ = app.emit("app:shutdown", ());
+
+    if let Some(server) = app.state::<AppState>().inner().local_api.lock().await.take() {
+      server.stop();
+    }
+    crate::window_geometry::flush(&app);
+    crate::telemetry::flush(&app);
+    crate::push::flush(&app);
+    crate::overlay::destroy(&app);
+
+    tokio::time::sleep(UI_GRACE).await;
+    applog!("👋 Shutdown complete");
+    app.exit(0);
+  });
+}
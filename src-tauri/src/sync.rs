@@ -0,0 +1,146 @@
+//! Settings sync through the user's sync folder: pushes local changes to the
+//! sync file and polls it for changes made on other PCs. See `helltime_core::sync`.
+
+use crate::AppState;
+use chrono::{DateTime, Utc};
+use helltime_core::error::AppError;
+use helltime_core::prefs::Preferences;
+use helltime_core::sync::{self, SyncDocument, CHECK_INTERVAL};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex as StdMutex;
+use tauri::{Emitter, Manager};
+
+#[derive(Default)]
+pub struct SyncState(StdMutex<Inner>);
+
+#[derive(Default)]
+struct Inner {
+  /// `updatedAt` of the last document this PC wrote or applied.
+  last_synced: Option<DateTime<Utc>>,
+  /// The UI's settings as last pushed or received.
+  ui_settings: Option<serde_json::Value>,
+}
+
+/// Payload of `sync:changed`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SyncChanged {
+  device: String,
+  updated_at: DateTime<Utc>,
+  ui_settings: Option<serde_json::Value>,
+}
+
+fn device_name() -> String {
+  std::env::var("COMPUTERNAME")
+    .or_else(|_| std::env::var("HOSTNAME"))
+    .ok()
+    .or_else(|| std::fs::read_to_string("/etc/hostname").ok().map(|name| name.trim().to_string()))
+    .filter(|name| !name.is_empty())
+    .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Write the current preferences (and UI settings, if given) to the sync folder.
+/// Skipped when sync is off or the file already has the same content, so applying
+/// a remote change does not bounce it back.
+pub fn push(app: &tauri::AppHandle, ui_settings: Option<serde_json::Value>) -> Result<(), AppError> {
+  let prefs = crate::preferences(app);
+  let Some(dir) = prefs.sync_dir.as_deref() else {
+    return Ok(());
+  };
+  let sync_state = app.state::<SyncState>();
+  let mut inner = sync_state.0.lock().unwrap_or_else(|e| e.into_inner());
+  if ui_settings.is_some() {
+    inner.ui_settings = ui_settings;
+  }
+  let preferences = sync::shared(&prefs);
+  let existing = sync::read(dir)?;
+  if existing.as_ref().is_some_and(|doc| sync::is_newer(doc, inner.last_synced)) {
+    // Another PC's change we have not applied yet; the watcher picks it up first.
+    return Ok(());
+  }
+  if existing.as_ref().is_some_and(|doc| {
+    doc.preferences == preferences && (inner.ui_settings.is_none() || doc.ui_settings == inner.ui_settings)
+  }) {
+    return Ok(());
+  }
+  let doc = SyncDocument {
+    updated_at: Utc::now(),
+    device: device_name(),
+    preferences,
+    ui_settings: inner.ui_settings.clone().or_else(|| existing.and_then(|doc| doc.ui_settings)),
+  };
+  sync::write(dir, &doc)?;
+  inner.last_synced = Some(doc.updated_at);
+  Ok(())
+}
+
+/// Adopt the sync file if another PC wrote it since our last push or pull. Emits
+/// `sync:changed` with the UI settings for the frontend to take over.
+pub fn pull(app: &tauri::AppHandle, dir: &Path) -> Result<bool, AppError> {
+  let Some(doc) = sync::read(dir)? else {
+    return Ok(false);
+  };
+  {
+    let sync_state = app.state::<SyncState>();
+    let mut inner = sync_state.0.lock().unwrap_or_else(|e| e.into_inner());
+    if !sync::is_newer(&doc, inner.last_synced) {
+      return Ok(false);
+    }
+    inner.last_synced = Some(doc.updated_at);
+    if doc.ui_settings.is_some() {
+      inner.ui_settings = doc.ui_settings.clone();
+    }
+  }
+  let local = crate::preferences(app);
+  if sync::shared(&local) != doc.preferences {
+    crate::store_preferences(app, |current| sync::apply(current, &doc.preferences))?;
+  }
+  applog!("🔄 Settings synced from {} ({})", doc.device, doc.updated_at.to_rfc3339());
+  let _ = app.emit(
+    "sync:changed",
+    SyncChanged { device: doc.device, updated_at: doc.updated_at, ui_settings: doc.ui_settings },
+  );
+  Ok(true)
+}
+
+/// Start syncing through `dir` (or stop, with `None`). An existing sync file is
+/// adopted; otherwise this PC's settings seed it.
+pub fn set_dir(app: &tauri::AppHandle, dir: Option<PathBuf>, ui_settings: serde_json::Value) -> Result<(), AppError> {
+  if let Some(dir) = dir.as_deref() {
+    if !dir.is_dir() {
+      return Err(AppError::InvalidSettings(format!("sync folder {:?} does not exist", dir)));
+    }
+  }
+  let saved = {
+    let state = app.state::<AppState>();
+    let mut store = state.inner().prefs.lock().unwrap_or_else(|e| e.into_inner());
+    let prefs = Preferences { sync_dir: dir.clone(), ..store.get().clone() };
+    store.set(prefs).map_err(AppError::Storage)?;
+    store.get().clone()
+  };
+  let _ = app.emit("preferences:changed", &saved);
+  *app.state::<SyncState>().0.lock().unwrap_or_else(|e| e.into_inner()) = Inner::default();
+  let Some(dir) = dir else {
+    applog!("🔄 Settings sync disabled");
+    return Ok(());
+  };
+  applog!("🔄 Settings sync via {}", dir.display());
+  if !pull(app, &dir)? {
+    push(app, Some(ui_settings))?;
+  }
+  Ok(())
+}
+
+pub fn spawn_watcher(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    loop {
+      if let Some(dir) = crate::preferences(&app).sync_dir {
+        if let Err(e) = pull(&app, &dir) {
+          applog!("⚠ Settings sync failed: {}", e);
+        }
+      }
+      tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+  });
+}
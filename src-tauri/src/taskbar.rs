@@ -0,0 +1,69 @@
+//! Taskbar progress towards the next Helltide (ITaskbarList3 on Windows, libunity
+//! on Linux) plus an overlay badge while one has just started.
+
+use crate::AppState;
+use helltime_core::schedule::EventKind;
+use helltime_core::taskbar;
+use std::time::Duration;
+use tauri::window::{ProgressBarState, ProgressBarStatus};
+use tauri::Manager;
+
+const REFRESH: Duration = Duration::from_secs(10);
+
+/// Small orange dot shown over the taskbar icon while a Helltide is active.
+#[cfg(windows)]
+fn active_badge() -> tauri::image::Image<'static> {
+  const SIZE: u32 = 16;
+  let mut pixels = vec![0u8; (SIZE * SIZE * 4) as usize];
+  let center = (SIZE as f32 - 1.0) / 2.0;
+  for y in 0..SIZE {
+    for x in 0..SIZE {
+      let (dx, dy) = (x as f32 - center, y as f32 - center);
+      if dx * dx + dy * dy <= center * center {
+        let i = ((y * SIZE + x) * 4) as usize;
+        pixels[i..i + 4].copy_from_slice(&[235, 90, 30, 255]);
+      }
+    }
+  }
+  tauri::image::Image::new_owned(pixels, SIZE, SIZE)
+}
+
+#[cfg(windows)]
+fn set_badge(window: &tauri::WebviewWindow, active: bool) {
+  let _ = window.set_overlay_icon(active.then(active_badge));
+}
+
+/// Windows is the only platform with per-window overlay icons; elsewhere the
+/// progress bar alone carries the information.
+#[cfg(not(windows))]
+fn set_badge(_window: &tauri::WebviewWindow, _active: bool) {}
+
+/// Keep the taskbar entry of the main window in sync with the cached schedule.
+/// Hidden-to-tray windows have no taskbar entry, updating them is harmless.
+pub fn spawn_updater(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    let mut last: Option<taskbar::TaskbarProgress> = None;
+    loop {
+      let current = {
+        let state = app.state::<AppState>();
+        let cached = state.inner().cache.lock().await.value().cloned();
+        let now = state.inner().clock.lock().await.server_now();
+        cached.and_then(|schedule| taskbar::progress(&schedule, EventKind::Helltide, now))
+      };
+
+      if current != last {
+        if let Some(window) = app.get_webview_window("main") {
+          let bar = match current {
+            Some(p) => ProgressBarState { status: Some(ProgressBarStatus::Normal), progress: Some(p.percent) },
+            None => ProgressBarState { status: Some(ProgressBarStatus::None), progress: None },
+          };
+          let _ = window.set_progress_bar(bar);
+          set_badge(&window, current.is_some_and(|p| p.active));
+        }
+        last = current;
+      }
+
+      tokio::time::sleep(crate::game_mode::throttled(&app, REFRESH)).await;
+    }
+  });
+}
@@ -0,0 +1,93 @@
+//! Sends the opt-in usage counters (see `helltime_core::telemetry`) to the
+//! configured endpoint. Nothing is counted unless the user opted in.
+
+use crate::AppState;
+use helltime_core::error::AppError;
+use helltime_core::telemetry::{self, Feature, Payload, ENV_KILL_SWITCH, SEND_INTERVAL, STATUS_GONE};
+use tauri::Manager;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+fn allowed(app: &tauri::AppHandle) -> bool {
+  let retired = app.state::<AppState>().inner().telemetry.lock().unwrap_or_else(|e| e.into_inner()).retired();
+  let kill_switch = std::env::var(ENV_KILL_SWITCH).ok();
+  telemetry::allowed(&crate::preferences(app).telemetry, kill_switch.as_deref(), retired)
+}
+
+pub fn record(app: &tauri::AppHandle, feature: Feature) {
+  if allowed(app) {
+    app.state::<AppState>().inner().telemetry.lock().unwrap_or_else(|e| e.into_inner()).record_feature(feature);
+  }
+}
+
+/// Count the category of `error`; its message is never recorded.
+pub fn record_error(app: &tauri::AppHandle, error: &AppError) {
+  if allowed(app) {
+    app.state::<AppState>().inner().telemetry.lock().unwrap_or_else(|e| e.into_inner()).record_error(error.kind());
+  }
+}
+
+/// After the preferences were saved: opting out drops everything counted so far.
+pub fn update(app: &tauri::AppHandle) {
+  if crate::preferences(app).telemetry.enabled {
+    return;
+  }
+  let state = app.state::<AppState>();
+  let mut queue = state.inner().telemetry.lock().unwrap_or_else(|e| e.into_inner());
+  if !queue.preview(VERSION).batches.is_empty() {
+    queue.clear();
+    queue.save();
+    applog!("📊 Telemetry turned off, queued counters dropped");
+  }
+}
+
+/// What the next send would contain, for `preview_telemetry_payload`.
+pub fn preview(app: &tauri::AppHandle) -> Payload {
+  app.state::<AppState>().inner().telemetry.lock().unwrap_or_else(|e| e.into_inner()).preview(VERSION)
+}
+
+/// Write the counters to disk, e.g. before quitting.
+pub fn flush(app: &tauri::AppHandle) {
+  app.state::<AppState>().inner().telemetry.lock().unwrap_or_else(|e| e.into_inner()).save();
+}
+
+async fn send(app: &tauri::AppHandle) {
+  if !allowed(app) {
+    return;
+  }
+  let state = app.state::<AppState>();
+  let payload = {
+    let mut queue = state.inner().telemetry.lock().unwrap_or_else(|e| e.into_inner());
+    queue.cut();
+    queue.save();
+    queue.payload(VERSION)
+  };
+  let (Some(payload), Some(endpoint)) = (payload, crate::preferences(app).telemetry.endpoint) else { return };
+
+  let count = payload.batches.len();
+  let sent = state.inner().http().post_json(endpoint.trim(), &payload).await;
+  let mut queue = state.inner().telemetry.lock().unwrap_or_else(|e| e.into_inner());
+  match sent {
+    Ok(status) if (200..300).contains(&status) => {
+      queue.sent(count);
+      applog!("📊 Sent {} telemetry batches", count);
+    }
+    Ok(STATUS_GONE) => {
+      queue.retire();
+      applog!("📊 Telemetry endpoint is retired; telemetry stays off");
+    }
+    Ok(status) => applog!("⚠ Telemetry endpoint answered HTTP {}, keeping {} batches queued", status, count),
+    Err(e) => applog!("⚠ Telemetry not sent, keeping {} batches queued: {}", count, e),
+  }
+  queue.save();
+}
+
+/// Cut a batch and send the queue every [`SEND_INTERVAL`].
+pub fn spawn(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    loop {
+      tokio::time::sleep(SEND_INTERVAL).await;
+      send(&app).await;
+    }
+  });
+}
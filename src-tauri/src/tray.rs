@@ -0,0 +1,243 @@
+use crate::AppState;
+use chrono::{DateTime, Utc};
+use helltime_core::clock::format_local_time;
+use helltime_core::connection::{ConnectionState, ConnectionStatus};
+use helltime_core::cadence::HelltideCadence;
+use helltime_core::countdown::{active_events, format_countdown};
+use helltime_core::i18n::{self, Locale, Msg, TRAY_TOOLTIP_MAX_UTF16};
+use helltime_core::prefs::Preferences;
+use helltime_core::schedule::{EventKind, ScheduleResponse, Titled};
+use helltime_core::shortcuts::{self, ShortcutAction};
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tauri::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::{Listener, Manager, Wry};
+
+pub const TRAY_ID: &str = "main";
+
+const TOOLTIP_REFRESH: Duration = Duration::from_secs(30);
+
+/// Menu items whose state changes at runtime. Replaced whenever the menu is
+/// rebuilt (language switch); the listeners always go through the current ones.
+#[derive(Default)]
+pub struct TrayMenu {
+  items: StdMutex<Option<MenuItems>>,
+}
+
+struct MenuItems {
+  overlay: CheckMenuItem<Wry>,
+  reminder: CheckMenuItem<Wry>,
+  update: MenuItem<Wry>,
+  update_available: bool,
+}
+
+/// Build the tray menu in the current locale. Check marks come from the
+/// preferences; the update hint is kept from the menu it replaces.
+pub fn build_menu(app: &tauri::AppHandle) -> tauri::Result<Menu<Wry>> {
+  let locale = crate::locale::current(app);
+  let t = |msg| i18n::text(locale, msg);
+  let prefs = crate::preferences(app);
+  // Windows menus show the text after a tab right-aligned, like an accelerator.
+  let keyed = |msg, action: ShortcutAction| match shortcuts::binding(&prefs.shortcuts, action) {
+    Some(shortcut) if cfg!(windows) => format!("{}\t{}", t(msg), shortcut),
+    _ => t(msg).to_string(),
+  };
+  let state = app.state::<TrayMenu>();
+  let mut items = state.items.lock().unwrap_or_else(|e| e.into_inner());
+  let update_available = items.as_ref().is_some_and(|old| old.update_available);
+
+  let overlay = CheckMenuItem::with_id(
+    app,
+    "toggle-overlay",
+    keyed(Msg::MenuOverlay, ShortcutAction::ToggleOverlay),
+    true,
+    prefs.overlay_enabled,
+    None::<&str>,
+  )?;
+  let reminder = CheckMenuItem::with_id(
+    app,
+    "toggle-reminder",
+    keyed(Msg::MenuReminder, ShortcutAction::ToggleReminders),
+    true,
+    prefs.reminders_enabled,
+    None::<&str>,
+  )?;
+  let update_text = if update_available { Msg::MenuUpdateAvailable } else { Msg::MenuCheckUpdates };
+  let update = MenuItem::with_id(app, "update", t(update_text), true, None::<&str>)?;
+
+  let pause_menu = Submenu::with_items(
+    app,
+    t(Msg::MenuPauseReminders),
+    true,
+    &[
+      &MenuItem::with_id(app, "pause-30m", t(Msg::MenuPause30m), true, None::<&str>)?,
+      &MenuItem::with_id(app, "pause-1h", keyed(Msg::MenuPause1h, ShortcutAction::PauseReminders), true, None::<&str>)?,
+      &MenuItem::with_id(app, "pause-tomorrow", t(Msg::MenuPauseTomorrow), true, None::<&str>)?,
+      &PredefinedMenuItem::separator(app)?,
+      &MenuItem::with_id(app, "resume-reminders", keyed(Msg::MenuResume, ShortcutAction::ResumeReminders), true, None::<&str>)?,
+    ],
+  )?;
+
+  let profiles = crate::profiles::list(app);
+  let profile_menu = Submenu::new(app, t(Msg::MenuProfiles), true)?;
+  for profile in &profiles.profiles {
+    let id = format!("{}{}", crate::profiles::MENU_PREFIX, profile.name);
+    let active = profiles.active.as_ref() == Some(&profile.name);
+    profile_menu.append(&CheckMenuItem::with_id(app, id, &profile.name, true, active, None::<&str>)?)?;
+  }
+  if profiles.profiles.is_empty() {
+    profile_menu.append(&MenuItem::with_id(app, "no-profiles", t(Msg::MenuNoProfiles), false, None::<&str>)?)?;
+  }
+
+  let menu = Menu::with_items(
+    app,
+    &[
+      &MenuItem::with_id(app, "restore", keyed(Msg::MenuRestore, ShortcutAction::ToggleWindow), true, None::<&str>)?,
+      &PredefinedMenuItem::separator(app)?,
+      &overlay,
+      &MenuItem::with_id(app, "peek-next", keyed(Msg::MenuPeekNext, ShortcutAction::PeekNextEvent), true, None::<&str>)?,
+      &MenuItem::with_id(app, "reveal-overlay", keyed(Msg::MenuRevealOverlay, ShortcutAction::RevealOverlay), true, None::<&str>)?,
+      &reminder,
+      &pause_menu,
+      &profile_menu,
+      &PredefinedMenuItem::separator(app)?,
+      &MenuItem::with_id(app, "copy-next-events", t(Msg::MenuCopyNextEvents), true, None::<&str>)?,
+      &MenuItem::with_id(app, "export-calendar", t(Msg::MenuExportCalendar), true, None::<&str>)?,
+      &MenuItem::with_id(app, "copy-diagnostics", t(Msg::MenuCopyDiagnostics), true, None::<&str>)?,
+      &update,
+      &MenuItem::with_id(app, "quit", t(Msg::MenuExit), true, None::<&str>)?,
+    ],
+  )?;
+  *items = Some(MenuItems { overlay, reminder, update, update_available });
+  Ok(menu)
+}
+
+/// Swap in a freshly built menu, e.g. after the language changed.
+pub fn rebuild_menu(app: &tauri::AppHandle) {
+  let Some(tray) = app.tray_by_id(TRAY_ID) else { return };
+  match build_menu(app) {
+    Ok(menu) => {
+      let _ = tray.set_menu(Some(menu));
+    }
+    Err(e) => applog!("✗ Failed to rebuild tray menu: {}", e),
+  }
+}
+
+/// Set the check marks from the preferences, after they were changed by the UI.
+pub fn sync_checks(app: &tauri::AppHandle, prefs: &Preferences) {
+  with_items(app, |items| {
+    let _ = items.overlay.set_checked(prefs.overlay_enabled);
+    let _ = items.reminder.set_checked(prefs.reminders_enabled);
+  });
+}
+
+/// Flip a check item's preference from the menu or a hotkey (via the app
+/// controller), without waiting for the UI (which may not be loaded). Goes through
+/// `set_preferences` like a change in the settings, so it is applied, synced and
+/// announced with `preferences:changed` the same way.
+pub fn toggle_flag(app: &tauri::AppHandle, flag: fn(&mut Preferences) -> &mut bool) {
+  let mut prefs = crate::preferences(app);
+  let value = flag(&mut prefs);
+  *value = !*value;
+  if let Err(e) = crate::set_preferences(app.clone(), prefs) {
+    applog!("✗ Failed to save preferences: {}", e);
+  }
+}
+
+/// Keep the update entry in sync with the updater.
+pub fn listen_menu_state(app: &tauri::AppHandle) {
+  let handle = app.clone();
+  app.listen("update:available", move |_| {
+    let locale = crate::locale::current(&handle);
+    with_items(&handle, |items| {
+      items.update_available = true;
+      let _ = items.update.set_text(i18n::text(locale, Msg::MenuUpdateAvailable));
+    });
+  });
+}
+
+fn with_items(app: &tauri::AppHandle, f: impl FnOnce(&mut MenuItems)) {
+  let state = app.state::<TrayMenu>();
+  let mut items = state.items.lock().unwrap_or_else(|e| e.into_inner());
+  if let Some(items) = items.as_mut() {
+    f(items);
+  }
+}
+
+/// One line per category, soonest first, titled as in notifications, after a line
+/// for each event that is running right now. Only `kinds` are listed. `paused_until`
+/// adds a line with the remaining reminder pause, and a failing connection one
+/// saying how old the shown times are. Cut to what a Windows tray tooltip can hold.
+pub fn tooltip_text(
+  locale: Locale,
+  schedule: &ScheduleResponse,
+  kinds: &[EventKind],
+  cadence: &HelltideCadence,
+  now: DateTime<Utc>,
+  paused_until: Option<DateTime<Utc>>,
+  connection: &ConnectionStatus,
+) -> String {
+  let mut next: Vec<_> = kinds.iter().filter_map(|kind| schedule.next_event(*kind, now)).collect();
+  next.sort_by_key(|e| e.start);
+
+  let mut lines = vec!["Helltime".to_string()];
+  let since = connection
+    .last_success
+    .map(|at| format!(" ({})", i18n::format(locale, Msg::TooltipDataFrom, &[("time", &format_local_time(at))])))
+    .unwrap_or_default();
+  match connection.state {
+    ConnectionState::Online => {}
+    ConnectionState::Degraded => lines.push(format!("{}{since}", i18n::text(locale, Msg::TooltipConnectionProblems))),
+    ConnectionState::Offline => lines.push(format!("{}{since}", i18n::text(locale, Msg::TooltipOffline))),
+  }
+  if let Some(until) = paused_until {
+    let remaining = format_countdown((until - now).num_seconds());
+    lines.push(i18n::format(locale, Msg::TooltipRemindersPaused, &[("remaining", &remaining)]));
+  }
+  for event in active_events(schedule, kinds, cadence, now) {
+    let remaining = format_countdown(event.seconds_remaining);
+    lines.push(i18n::format(locale, Msg::TooltipActive, &[("event", &event.title_in(locale)), ("remaining", &remaining)]));
+  }
+  for event in next {
+    lines.push(format!("{}: {}", event.title_in(locale), format_countdown((event.start - now).num_seconds())));
+  }
+  i18n::truncate_utf16(&lines.join("\n"), TRAY_TOOLTIP_MAX_UTF16)
+}
+
+/// Rebuild the tooltip from the cached schedule (never triggers a fetch).
+pub async fn refresh_tooltip(app: &tauri::AppHandle) {
+  let text = {
+    let state = app.state::<AppState>();
+    let cached = state.inner().cache.lock().await.value().cloned();
+    let now = state.inner().clock.lock().await.server_now();
+    let paused_until = state.inner().reminder_pause.lock().unwrap_or_else(|e| e.into_inner()).paused_until(Utc::now());
+    let kinds = crate::preferences(app).shown_kinds();
+    let connection = crate::current_connection(app);
+    let locale = crate::locale::current(app);
+    let cadence = crate::cadence::current(app).helltide;
+    cached.map(|schedule| tooltip_text(locale, &schedule, &kinds, &cadence, now, paused_until, &connection))
+  };
+
+  if let (Some(text), Some(tray)) = (text, app.tray_by_id(TRAY_ID)) {
+    let _ = tray.set_tooltip(Some(text));
+  }
+}
+
+/// Keep the tray tooltip in sync with the cached schedule. Also notices the
+/// connection ageing from degraded to offline, which no fetch reports.
+pub fn spawn_tooltip_refresher(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    let mut last_state = ConnectionState::Online;
+    loop {
+      let state = crate::current_connection(&app).state;
+      if state != last_state {
+        last_state = state;
+        if state == ConnectionState::Offline {
+          crate::notify_connection_changed(&app);
+        }
+      }
+      refresh_tooltip(&app).await;
+      tokio::time::sleep(crate::game_mode::throttled(&app, TOOLTIP_REFRESH)).await;
+    }
+  });
+}
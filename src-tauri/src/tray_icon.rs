@@ -0,0 +1,157 @@
+//! The tray icon, swapped between variants as the taskbar theme or the preference
+//! changes, and pulsing when an event is imminent unless motion is reduced. See
+//! `helltime_core::tray_icon`.
+
+use crate::AppState;
+use helltime_core::tray_icon::{self, TaskbarTheme, TrayIcon};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
+use tauri::image::Image;
+use tauri::Manager;
+
+/// Windows has no theme-change event short of a window procedure; polling the
+/// registry value is cheap.
+const THEME_CHECK: Duration = Duration::from_secs(10);
+
+/// Time per frame while pulsing.
+const PULSE_FRAME: Duration = Duration::from_millis(700);
+
+/// The variant and frame currently shown, so unchanged checks do not touch the tray.
+static SHOWN: StdMutex<Option<(TrayIcon, bool)>> = StdMutex::new(None);
+
+/// Whether the lit pulse frame is due.
+static LIT: AtomicBool = AtomicBool::new(false);
+
+fn png(icon: TrayIcon) -> &'static [u8] {
+  match icon {
+    TrayIcon::Light => include_bytes!("../icons/tray-light.png"),
+    TrayIcon::Dark => include_bytes!("../icons/tray-dark.png"),
+    TrayIcon::MonochromeLight => include_bytes!("../icons/tray-mono-light.png"),
+    TrayIcon::MonochromeDark => include_bytes!("../icons/tray-mono-dark.png"),
+  }
+}
+
+/// `icon`, or its lit pulse frame.
+pub fn image(icon: TrayIcon, lit: bool) -> Option<Image<'static>> {
+  match image::load_from_memory_with_format(png(icon), image::ImageFormat::Png) {
+    Ok(img) => {
+      let mut rgba = img.to_rgba8();
+      if lit {
+        tray_icon::light_up(icon, &mut rgba);
+      }
+      let (width, height) = rgba.dimensions();
+      Some(Image::new_owned(rgba.into_raw(), width, height))
+    }
+    Err(e) => {
+      applog!("✗ Failed to decode tray icon {:?}: {}", icon, e);
+      None
+    }
+  }
+}
+
+/// `HKCU\...\Themes\Personalize\SystemUsesLightTheme`; dark when it cannot be read
+/// (older Windows versions only had a dark taskbar).
+#[cfg(windows)]
+pub fn taskbar_theme() -> TaskbarTheme {
+  const HKEY_CURRENT_USER: isize = 0x8000_0001u32 as i32 as isize;
+  const RRF_RT_REG_DWORD: u32 = 0x10;
+
+  #[link(name = "advapi32")]
+  extern "system" {
+    fn RegGetValueW(
+      key: isize,
+      sub_key: *const u16,
+      value: *const u16,
+      flags: u32,
+      kind: *mut u32,
+      data: *mut std::ffi::c_void,
+      size: *mut u32,
+    ) -> i32;
+  }
+
+  let wide = |s: &str| s.encode_utf16().chain(std::iter::once(0)).collect::<Vec<u16>>();
+  let sub_key = wide(r"Software\Microsoft\Windows\CurrentVersion\Themes\Personalize");
+  let value = wide("SystemUsesLightTheme");
+  let mut data = 0u32;
+  let mut size = std::mem::size_of::<u32>() as u32;
+  let status = unsafe {
+    RegGetValueW(
+      HKEY_CURRENT_USER,
+      sub_key.as_ptr(),
+      value.as_ptr(),
+      RRF_RT_REG_DWORD,
+      std::ptr::null_mut(),
+      &mut data as *mut u32 as *mut std::ffi::c_void,
+      &mut size,
+    )
+  };
+  if status == 0 && data != 0 {
+    TaskbarTheme::Light
+  } else {
+    TaskbarTheme::Dark
+  }
+}
+
+/// Most Linux panels are dark; there is no portable way to ask.
+#[cfg(not(windows))]
+pub fn taskbar_theme() -> TaskbarTheme {
+  TaskbarTheme::Dark
+}
+
+/// The variant for the current preference and taskbar theme.
+pub fn current(app: &tauri::AppHandle) -> TrayIcon {
+  tray_icon::pick(crate::preferences(app).tray_icon_style, taskbar_theme())
+}
+
+/// Show the right variant and frame, if not already shown.
+pub fn apply(app: &tauri::AppHandle) {
+  let icon = current(app);
+  let lit = LIT.load(Ordering::SeqCst);
+  let mut shown = SHOWN.lock().unwrap_or_else(|e| e.into_inner());
+  if *shown == Some((icon, lit)) {
+    return;
+  }
+  let Some(tray) = app.tray_by_id(crate::tray::TRAY_ID) else { return };
+  if let Some(image) = image(icon, lit) {
+    if tray.set_icon(Some(image)).is_ok() {
+      if shown.is_none_or(|(old, _)| old != icon) {
+        applog!("🎨 Tray icon: {:?}", icon);
+      }
+      *shown = Some((icon, lit));
+    }
+  }
+}
+
+/// Whether the icon should pulse right now, from the cached schedule.
+async fn should_pulse(app: &tauri::AppHandle) -> bool {
+  let prefs = crate::preferences(app);
+  if !prefs.tray_pulse || crate::motion::is_reduced(app) {
+    return false;
+  }
+  let state = app.state::<AppState>();
+  let Some(schedule) = state.inner().cache.lock().await.value().cloned() else { return false };
+  let now = state.inner().clock.lock().await.server_now();
+  tray_icon::should_pulse(&schedule, &prefs.shown_kinds(), &crate::cadence::current(app).helltide, now)
+}
+
+/// Follow taskbar theme switches while the app runs, and alternate the frames while
+/// an event is imminent or a Helltide runs.
+pub fn spawn_updater(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    loop {
+      if !should_pulse(&app).await {
+        LIT.store(false, Ordering::SeqCst);
+        apply(&app);
+        tokio::time::sleep(crate::game_mode::throttled(&app, THEME_CHECK)).await;
+        continue;
+      }
+      let until = Instant::now() + THEME_CHECK;
+      while Instant::now() < until {
+        LIT.fetch_xor(true, Ordering::SeqCst);
+        apply(&app);
+        tokio::time::sleep(PULSE_FRAME).await;
+      }
+    }
+  });
+}
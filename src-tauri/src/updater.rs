@@ -0,0 +1,48 @@
+use crate::AppState;
+use helltime_core::error::AppError;
+use helltime_core::fetch::HttpFetcher;
+use helltime_core::update::{self, GithubRelease, UpdateInfo};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+const RELEASES_URL: &str = "https://api.github.com/repos/Trissilein/helltime/releases";
+
+/// First check shortly after start (don't compete with the initial schedule fetch),
+/// then a few times a day for long-running tray sessions.
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(30);
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Query GitHub releases and remember/announce a newer version.
+/// Emits `update:available` only when the offered version changes.
+pub async fn check(app: &tauri::AppHandle) -> Result<Option<UpdateInfo>, AppError> {
+  let state = app.state::<AppState>();
+  let resp = state.inner().http().plain().get(RELEASES_URL).await.map_err(|e| AppError::Network(e.to_string()))?;
+  if !(200..300).contains(&resp.status) {
+    return Err(AppError::Network(format!("bad status: {}", resp.status)));
+  }
+  let releases: Vec<GithubRelease> =
+    serde_json::from_slice(&resp.body).map_err(|e| AppError::Parse(format!("invalid json: {e}")))?;
+
+  let found = update::newest_update(env!("CARGO_PKG_VERSION"), &releases, false);
+  let mut current = state.inner().update.lock().await;
+  if let Some(info) = &found {
+    if current.as_ref() != Some(info) {
+      applog!("⬆ Update available: {} -> {}", info.current, info.latest);
+      let _ = app.emit("update:available", info.clone());
+    }
+  }
+  *current = found.clone();
+  Ok(found)
+}
+
+pub fn spawn_checker(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    tokio::time::sleep(FIRST_CHECK_DELAY).await;
+    loop {
+      if let Err(e) = check(&app).await {
+        applog!("⚠ Update check failed: {}", e);
+      }
+      tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+  });
+}
@@ -0,0 +1,119 @@
+//! Posts the user's webhook (see `helltime_core::webhook`) when events start, the
+//! schedule changes or fetching fails, retrying with backoff.
+
+use crate::AppState;
+use chrono::Utc;
+use helltime_core::cache::ScheduleDiff;
+use helltime_core::error::AppError;
+use helltime_core::fetch::FetchError;
+use helltime_core::schedule::Titled;
+use helltime_core::webhook::{self, ErrorThrottle, WebhookEvent, WebhookSettings, WebhookTrigger, SIGNATURE_HEADER};
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tauri::Manager;
+
+/// How often the schedule is checked for events that just started.
+const TICK: Duration = Duration::from_secs(5);
+
+/// Starts missed by more than this (sleep, a clock change) are not posted late.
+const MAX_LATE: chrono::Duration = chrono::Duration::minutes(1);
+
+#[derive(Default)]
+pub struct WebhookState {
+  errors: StdMutex<ErrorThrottle>,
+}
+
+/// Post once; the response status, or why there was none.
+async fn post(app: &tauri::AppHandle, settings: &WebhookSettings, event: &WebhookEvent) -> Result<u16, FetchError> {
+  let body = webhook::body(settings, event);
+  let headers: Vec<(&str, String)> = match settings.secret.as_deref() {
+    Some(secret) => vec![(SIGNATURE_HEADER, webhook::signature(secret.trim(), &body))],
+    None => Vec::new(),
+  };
+  let http = app.state::<AppState>().inner().http();
+  http.post_json_text(settings.url.trim(), body, &headers).await
+}
+
+/// Deliver `event`, retrying server errors and unreachable URLs per `webhook::BACKOFF`.
+fn send(app: &tauri::AppHandle, event: WebhookEvent) {
+  let settings = crate::preferences(app).webhook;
+  if !settings.fires_on(event.trigger) {
+    return;
+  }
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    let mut retry = 0;
+    loop {
+      let error = match post(&app, &settings, &event).await {
+        Ok(status) if (200..300).contains(&status) => return,
+        Ok(status) => FetchError::Status(status),
+        Err(e) => e,
+      };
+      match webhook::backoff(retry).filter(|_| error.is_retryable() && !crate::shutdown::in_progress()) {
+        Some(pause) => {
+          applog!("⚠ Webhook ({:?}) failed, retrying in {}s: {}", event.trigger, pause.as_secs(), error);
+          tokio::time::sleep(pause).await;
+          retry += 1;
+        }
+        None => {
+          applog!("✗ Webhook ({:?}) failed, giving up: {}", event.trigger, error);
+          return;
+        }
+      }
+    }
+  });
+}
+
+/// After a refresh shifted or cancelled events.
+pub fn schedule_changed(app: &tauri::AppHandle, changes: &ScheduleDiff) {
+  send(app, WebhookEvent::schedule_changed(changes, Utc::now()));
+}
+
+/// A failed fetch; each category at most every `webhook::ERROR_REPEAT_AFTER`.
+pub fn app_error(app: &tauri::AppHandle, error: &AppError) {
+  if !crate::preferences(app).webhook.fires_on(WebhookTrigger::AppError) {
+    return;
+  }
+  let now = Utc::now();
+  if app.state::<WebhookState>().errors.lock().unwrap_or_else(|e| e.into_inner()).allow(error.kind(), now) {
+    send(app, WebhookEvent::app_error(error.kind(), &error.to_string(), now));
+  }
+}
+
+/// Post a sample "event starting" once, without retries, for the settings' test button.
+pub async fn test(app: &tauri::AppHandle) -> Result<(), AppError> {
+  let settings = crate::preferences(app).webhook;
+  if !settings.enabled {
+    return Err(AppError::InvalidSettings("webhook is disabled".to_string()));
+  }
+  match post(app, &settings, &WebhookEvent::sample(Utc::now())).await {
+    Ok(status) if (200..300).contains(&status) => Ok(()),
+    Ok(status) => Err(AppError::Network(format!("HTTP {status}"))),
+    Err(e) => Err(AppError::Network(e.to_string())),
+  }
+}
+
+/// Watch the cached schedule for events of shown categories starting.
+pub fn spawn(app: tauri::AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    let mut last = app.state::<AppState>().inner().clock.lock().await.server_now();
+    loop {
+      tokio::time::sleep(TICK).await;
+      let state = app.state::<AppState>();
+      let now = state.inner().clock.lock().await.server_now();
+      let prefs = crate::preferences(&app);
+      if prefs.webhook.fires_on(WebhookTrigger::EventStarting) {
+        // The UI and the headless loop keep the cache fresh; no fetch from here.
+        let cached = state.inner().cache.lock().await.value().cloned();
+        if let Some(schedule) = cached {
+          let locale = crate::locale::current(&app);
+          let after = last.max(now - MAX_LATE);
+          for event in webhook::starting_between(&schedule, &prefs.shown_kinds(), after, now) {
+            send(&app, WebhookEvent::event_starting(&event, &event.title_in(locale), now));
+          }
+        }
+      }
+      last = now;
+    }
+  });
+}
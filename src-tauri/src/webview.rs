@@ -0,0 +1,142 @@
+//! WebView runtime health and headless mode. Without a working WebView2 (Windows) or
+//! WebKitGTK the windows would stay blank, so startup checks the runtime first; if it
+//! is missing, the user gets a native message with guidance and helltime runs
+//! headless: tray, tooltip and backend reminders (see `headless`), no windows. Users
+//! can also choose headless mode (`--headless` or the preference) to save memory.
+//!
+//! The main window is therefore not created from tauri.conf.json by Tauri itself but
+//! by [`create_main_window`], once setup knows whether to.
+
+use helltime_core::controller::Message;
+use helltime_core::i18n::{self, Locale, Msg};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::Manager;
+use std::sync::OnceLock;
+use tauri::utils::config::WindowConfig;
+use tauri::WebviewWindowBuilder;
+
+pub const WEBVIEW2_DOWNLOAD_URL: &str = "https://developer.microsoft.com/microsoft-edge/webview2/";
+
+static HEADLESS: AtomicBool = AtomicBool::new(false);
+static RUNTIME_MISSING: AtomicBool = AtomicBool::new(false);
+/// Headless only because the main window was unloaded, not by choice.
+static UNLOADED: AtomicBool = AtomicBool::new(false);
+static MAIN_WINDOW: OnceLock<WindowConfig> = OnceLock::new();
+/// Bumped whenever the main window is hidden; only the latest unload timer acts.
+static UNLOAD_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Version of the installed runtime, or why there is none.
+pub fn runtime_version() -> Result<String, String> {
+  tauri::webview_version().map_err(|e| e.to_string())
+}
+
+/// No webview windows are created in this run.
+pub fn is_headless() -> bool {
+  HEADLESS.load(Ordering::SeqCst)
+}
+
+pub fn set_headless() {
+  HEADLESS.store(true, Ordering::SeqCst);
+}
+
+/// Whether the webview overlay may be created: not in headless mode, except while
+/// the main window is merely unloaded.
+pub fn overlay_allowed() -> bool {
+  !is_headless() || UNLOADED.load(Ordering::SeqCst)
+}
+
+/// Check the runtime before anything else; without one, tell the user and stay headless.
+pub fn check_runtime() {
+  if let Err(e) = runtime_version() {
+    applog!("✗ WebView runtime unavailable: {}", e);
+    report_missing_runtime(crate::locale::system(), &e);
+    RUNTIME_MISSING.store(true, Ordering::SeqCst);
+    set_headless();
+  }
+}
+
+/// Take the main window out of `context` so Tauri does not create it on its own.
+pub fn take_window_config(context: &mut tauri::Context<tauri::Wry>) {
+  let windows = std::mem::take(&mut context.config_mut().app.windows);
+  if let Some(main) = windows.into_iter().find(|w| w.label == "main") {
+    let _ = MAIN_WINDOW.set(main);
+  }
+}
+
+/// Create the main window from its tauri.conf.json entry (hidden), leaving headless
+/// mode; the backend reminders stop once the UI runs.
+pub fn create_main_window(app: &tauri::AppHandle) -> Result<tauri::WebviewWindow, String> {
+  if RUNTIME_MISSING.load(Ordering::SeqCst) {
+    return Err("no webview runtime, main window unavailable".into());
+  }
+  let config = MAIN_WINDOW.get().ok_or("main window missing from tauri.conf.json")?;
+  let window = WebviewWindowBuilder::from_config(app, config)
+    .and_then(|builder| builder.build())
+    .map_err(|e| format!("create main window: {e}"))?;
+  let was_unloaded = UNLOADED.swap(false, Ordering::SeqCst);
+  if HEADLESS.swap(false, Ordering::SeqCst) && !was_unloaded {
+    applog!("🪟 Leaving headless mode");
+  }
+  Ok(window)
+}
+
+/// The main window was just hidden: unload it if it stays hidden for the time set in
+/// the preferences (`unload_after_minutes`).
+pub fn schedule_unload(app: &tauri::AppHandle) {
+  let generation = UNLOAD_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+  let Some(minutes) = crate::preferences(app).unload_after_minutes.filter(|m| *m > 0) else { return };
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    tokio::time::sleep(Duration::from_secs(u64::from(minutes) * 60)).await;
+    if UNLOAD_GENERATION.load(Ordering::SeqCst) == generation {
+      // The controller ignores this if the window was shown in the meantime.
+      crate::controller::send(&app, Message::UnloadIdle);
+    }
+  });
+}
+
+/// Destroy the hidden main window and its webview. Until it is rebuilt (tray
+/// "Restore", notification click), reminders come from the backend as in headless mode.
+pub fn unload_main_window(app: &tauri::AppHandle) {
+  let Some(window) = app.get_webview_window("main") else { return };
+  crate::window_geometry::flush(app);
+  if let Err(e) = window.destroy() {
+    applog!("✗ Failed to unload main window: {}", e);
+    return;
+  }
+  applog!("💤 Main window unloaded to free memory");
+  UNLOADED.store(true, Ordering::SeqCst);
+  set_headless();
+  crate::headless::spawn(app.clone());
+}
+
+/// Tell the user the UI cannot start and what to do about it. Blocks until the
+/// message is dismissed.
+pub fn report_missing_runtime(locale: Locale, error: &str) {
+  let title = i18n::text(locale, Msg::WebviewMissingTitle);
+  let body = i18n::format(locale, Msg::WebviewMissingBody, &[("url", WEBVIEW2_DOWNLOAD_URL), ("error", error)]);
+  message_box(title, &body);
+}
+
+#[cfg(windows)]
+fn message_box(title: &str, body: &str) {
+  const MB_OK: u32 = 0x0;
+  const MB_ICONWARNING: u32 = 0x30;
+
+  #[link(name = "user32")]
+  extern "system" {
+    fn MessageBoxW(hwnd: isize, text: *const u16, caption: *const u16, kind: u32) -> i32;
+  }
+
+  let wide = |s: &str| s.encode_utf16().chain(std::iter::once(0)).collect::<Vec<u16>>();
+  let (title, body) = (wide(title), wide(body));
+  unsafe {
+    MessageBoxW(0, body.as_ptr(), title.as_ptr(), MB_OK | MB_ICONWARNING);
+  }
+}
+
+#[cfg(not(windows))]
+fn message_box(title: &str, body: &str) {
+  eprintln!("✗ {title}\n{body}");
+}
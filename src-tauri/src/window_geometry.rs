@@ -0,0 +1,96 @@
+//! Persist the main window's placement (debounced) and restore it on startup.
+
+use crate::AppState;
+use helltime_core::prefs::{MonitorRect, WindowGeometry};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{Manager, PhysicalPosition, PhysicalSize};
+
+/// Moves and resizes arrive in bursts while dragging; save once things settle.
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(600);
+
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+/// A debounced save is waiting; [`flush`] does it right away on quit.
+static PENDING: AtomicBool = AtomicBool::new(false);
+
+fn monitors(window: &tauri::WebviewWindow) -> Vec<MonitorRect> {
+  let mut monitors = Vec::new();
+  // Primary first: it is the fallback when the saved monitor is gone.
+  if let Ok(Some(primary)) = window.primary_monitor() {
+    monitors.push(primary);
+  }
+  monitors.extend(window.available_monitors().unwrap_or_default());
+  monitors
+    .iter()
+    .map(|m| {
+      let area = m.work_area();
+      MonitorRect { x: area.position.x, y: area.position.y, width: area.size.width, height: area.size.height }
+    })
+    .collect()
+}
+
+/// Apply the saved placement before the window is first shown.
+pub fn restore(app: &tauri::AppHandle, window: &tauri::WebviewWindow) {
+  let Some(saved) = crate::preferences(app).window else { return };
+  let geometry = saved.clamp_to(&monitors(window));
+  if geometry != saved {
+    applog!("📐 Saved window placement adjusted to current monitors: {:?}", geometry);
+  }
+
+  let _ = window.set_size(PhysicalSize::new(geometry.width, geometry.height));
+  let _ = window.set_position(PhysicalPosition::new(geometry.x, geometry.y));
+  if geometry.maximized {
+    let _ = window.maximize();
+  }
+}
+
+fn current(window: &tauri::Window) -> Option<WindowGeometry> {
+  if !window.is_visible().unwrap_or(false) || window.is_minimized().unwrap_or(false) {
+    return None;
+  }
+  let maximized = window.is_maximized().unwrap_or(false);
+  let position = window.outer_position().ok()?;
+  let size = window.inner_size().ok()?;
+  Some(WindowGeometry { x: position.x, y: position.y, width: size.width, height: size.height, maximized })
+}
+
+/// Call on every Moved/Resized event of the main window.
+pub fn schedule_save(window: &tauri::Window) {
+  let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+  PENDING.store(true, Ordering::SeqCst);
+  let window = window.clone();
+  tauri::async_runtime::spawn(async move {
+    tokio::time::sleep(SAVE_DEBOUNCE).await;
+    if GENERATION.load(Ordering::SeqCst) != generation || !PENDING.swap(false, Ordering::SeqCst) {
+      return;
+    }
+    save(&window);
+  });
+}
+
+/// Save a placement still waiting for its debounce (called while quitting).
+pub fn flush(app: &tauri::AppHandle) {
+  if !PENDING.swap(false, Ordering::SeqCst) {
+    return;
+  }
+  if let Some(window) = app.get_webview_window("main") {
+    save(&window.as_ref().window());
+  }
+}
+
+fn save(window: &tauri::Window) {
+  let Some(geometry) = current(window) else { return };
+
+  let state = window.app_handle().state::<AppState>();
+  let mut prefs = state.inner().prefs.lock().unwrap_or_else(|e| e.into_inner());
+  // While maximized keep the last normal placement so un-maximizing after a restart works.
+  let geometry = match (geometry.maximized, prefs.get().window) {
+    (true, Some(previous)) => WindowGeometry { maximized: true, ..previous },
+    _ => geometry,
+  };
+  if prefs.get().window != Some(geometry) {
+    if let Err(e) = prefs.update(|p| p.window = Some(geometry)) {
+      applog!("⚠ Failed to save window placement: {}", e);
+    }
+  }
+}
@@ -1,24 +1,102 @@
 import React, { useEffect, useMemo, useRef, useState } from "react";
 import { isTauri } from "@tauri-apps/api/core";
-import { emit, listen } from "@tauri-apps/api/event";
-import { fetchSchedule } from "./lib/helltides";
-import { formatCountdown, formatLocalTime } from "./lib/time";
-import { loadSettings, saveSettings, type BeepPattern, type Settings, type TimerSettings } from "./lib/settings";
+import { listen } from "@tauri-apps/api/event";
+import { refreshSchedule, type ScheduleDiff, type ScheduleRefresh } from "./lib/helltides";
+import { formatCountdown, formatLocalTime, formatUtcOffset, formatZoneTime, serverNow } from "./lib/time";
+import { loadSettings, reminderTimes, saveSettings, type BeepPattern, type Settings, type TimerSettings } from "./lib/settings";
 import { playBeep } from "./lib/sound";
 import { formatRemainingSpeech, speak } from "./lib/speech";
-import type { ScheduleResponse, ScheduleType, WorldBossScheduleItem } from "./lib/types";
+import {
+  displayBossName,
+  displayZone,
+  type ScheduleItemBase,
+  type ScheduleResponse,
+  type ScheduleType,
+  type WorldBossScheduleItem
+} from "./lib/types";
 import { disablePanicStop, isPanicStopEnabled } from "./lib/safety";
 import {
   broadcastOverlayWindowSettings,
   ensureOverlayWindow,
+  getHudPlacement,
   getOverlayWindowDebugStatus,
+  HUD_ELEMENT_LABELS,
+  HUD_ZONE_LABELS,
   OVERLAY_WINDOW_LABEL,
   resetOverlayWindowBounds,
+  setOverlayAnchor,
+  type HudPlacement,
+  type OverlayPosition,
+  type OverlayResized,
+  type OverlayStatus,
   setOverlayWindowInteractive,
   setOverlayWindowVisible
 } from "./lib/overlay_window";
 import { clearOverlayDiag, readOverlayDiag } from "./lib/overlay_diag";
 import { findNext } from "./lib/helpers";
+import { openCrashReport, takePreviousCrash, type CrashInfo } from "./lib/crash";
+import { openUpdatePage, type UpdateInfo } from "./lib/update";
+import { takePendingDeepLink, type DeepLink } from "./lib/deep_link";
+import { setDiscordPresence, setLocalApi } from "./lib/local_api";
+import {
+  formatHeaderLines,
+  getPreferences,
+  hiddenEvents,
+  parseHeaderLines,
+  setLocale,
+  setPreferences,
+  type NetworkSettings,
+  type Preferences,
+  type PowerSaving,
+  type ReducedMotion,
+  type TrayIconStyle,
+  type VisibilityWindow,
+  WEEKDAYS,
+  minutesToTime,
+  timeToMinutes
+} from "./lib/preferences";
+import { getReminderPause, resumeReminders, type PauseStatus } from "./lib/reminder_pause";
+import { notifyEvent, type NotificationTarget } from "./lib/notify";
+import {
+  acknowledgeReminder,
+  ESCALATION_ACTION_LABELS,
+  FIRE_WINDOW_MS,
+  testReminder,
+  MAX_ESCALATION_RULES,
+  type EscalationAction,
+  type EscalationRule,
+  type EscalationStep,
+  type PlannedReminder
+} from "./lib/reminders";
+import { getChestResets, type ChestReset } from "./lib/chests";
+import { getHelltideEndReminders, type EndReminder } from "./lib/helltide_end";
+import { markAttending } from "./lib/history";
+import {
+  completeOnboarding,
+  enterOverlayConfig,
+  getOnboardingStatus,
+  requestNotificationPermission,
+  setAutostart,
+  type OnboardingStatus
+} from "./lib/onboarding";
+import { addCustomReminder, listReminders, removeCustomReminder, type CustomReminder } from "./lib/custom_reminders";
+import { formatMetrics, getMetrics } from "./lib/metrics";
+import { previewTelemetryPayload } from "./lib/telemetry";
+import { sendTestPush } from "./lib/push";
+import { testWebhook, WEBHOOK_PLACEHOLDERS, WEBHOOK_TRIGGER_LABELS, type WebhookTrigger } from "./lib/webhook";
+import { fetchScheduleRaw, formatRawResponse, saveRawPayload } from "./lib/raw_api";
+import { getConnectionStatus, type ConnectionStatus } from "./lib/connection";
+import { nextPollDelay } from "./lib/polling";
+import { followGameMode } from "./lib/game_mode";
+import { followReducedMotion } from "./lib/motion";
+import { followPowerSaving, getPowerStatus, type PowerStatus } from "./lib/power_saving";
+import { listShortcuts, SHORTCUT_LABELS, type ShortcutAction, type ShortcutInfo } from "./lib/shortcuts";
+import { listFonts } from "./lib/fonts";
+import { formatAbout, getAboutInfo, type AboutInfo } from "./lib/about";
+import { pushSyncedSettings, setSyncDir, type SyncChanged } from "./lib/sync";
+import { errorText, isAppError } from "./lib/errors";
+import { previewToast, renderToast, TOAST_PLACEHOLDERS, type RenderedToast, type ToastTemplates } from "./lib/toast";
+import { activateProfile, deleteProfile, listProfiles, saveProfile, type Profile, type ProfileList } from "./lib/profiles";
 
 type FiredMap = Record<string, number>;
 
@@ -72,20 +150,26 @@ function spokenTypeLabel(type: ScheduleType): string {
 
 function getEventName(type: ScheduleType, item: { startTime: string } | null): string {
   if (!item) return typeLabel(type);
+  const zone = displayZone(item as ScheduleItemBase);
+  const suffix = zone ? ` in ${zone}` : "";
   if (type === "world_boss") {
-    const boss = (item as WorldBossScheduleItem).boss;
-    return boss ? `World Boss ${boss}` : "World Boss";
+    const boss = displayBossName(item as WorldBossScheduleItem);
+    return boss ? `World Boss ${boss}${suffix}` : `World Boss${suffix}`;
   }
-  return typeLabel(type);
+  return `${typeLabel(type)}${suffix}`;
 }
 
 function getEventTitleParts(type: ScheduleType, item: { startTime: string } | null): { title: string; subtitle?: string } {
   if (!item) return { title: typeLabel(type) };
+  const zone = displayZone(item as ScheduleItemBase) ?? undefined;
   if (type === "world_boss") {
-    const boss = (item as WorldBossScheduleItem).boss;
-    return boss ? { title: "World Boss", subtitle: boss } : { title: "World Boss" };
+    const boss = displayBossName(item as WorldBossScheduleItem);
+    const subtitle = [boss, zone].filter(Boolean).join(" · ");
+    return subtitle ? { title: "World Boss", subtitle } : { title: "World Boss" };
   }
-  return { title: typeLabel(type) };
+  const predicted = (item as ScheduleItemBase).predicted ? "vorhergesagt" : undefined;
+  const subtitle = [zone, predicted].filter(Boolean).join(" · ");
+  return subtitle ? { title: typeLabel(type), subtitle } : { title: typeLabel(type) };
 }
 
 function getSpokenEventNameWithTemplate(
@@ -95,7 +179,7 @@ function getSpokenEventNameWithTemplate(
 ): string {
   const base = (template ?? "").trim() || spokenTypeLabel(type);
   if (type !== "world_boss") return base;
-  const boss = item ? (item as WorldBossScheduleItem).boss : null;
+  const boss = item ? (item as WorldBossScheduleItem).boss || (item as WorldBossScheduleItem).predictedBoss : null;
   if (!boss) return base.replaceAll("{boss}", "").replaceAll("  ", " ").trim();
   if (base.includes("{boss}")) return base.replaceAll("{boss}", boss).replaceAll("  ", " ").trim();
   return `${base} ${boss}`.trim();
@@ -117,24 +201,72 @@ function sliderToOpacity(slider: number): number {
 }
 
 const types: ScheduleType[] = ["helltide", "legion", "world_boss"];
+const WEEKDAY_LABELS = ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"];
 
 export default function App() {
   const [schedule, setSchedule] = useState<ScheduleResponse | null>(null);
   const [error, setError] = useState<string | null>(null);
-  const [now, setNow] = useState(() => Date.now());
+  // Text of the screen-reader live region; cleared first so a repeated reminder is read again.
+  const [announcement, setAnnouncement] = useState("");
+  const announce = (text: string) => {
+    setAnnouncement("");
+    window.setTimeout(() => setAnnouncement(text), 100);
+  };
+  const [now, setNow] = useState(() => serverNow());
   const [lastRefreshAt, setLastRefreshAt] = useState<number | null>(null);
+  const [scheduleSource, setScheduleSource] = useState<Omit<ScheduleRefresh, "schedule"> | null>(null);
   const [nextAutoRefreshAt, setNextAutoRefreshAt] = useState<number | null>(null);
   const autoRefreshTimeoutRef = useRef<number | null>(null);
+  const [shuttingDown, setShuttingDown] = useState(false);
   const refreshInFlightRef = useRef(false);
+  // A refresh asked for while one runs; it may carry newer data, so it runs afterwards.
+  const refreshQueuedRef = useRef<{ force: boolean } | null>(null);
   const [settingsOpen, setSettingsOpen] = useState(false);
   const [debugOpen, setDebugOpen] = useState(false);
   const [overlayDebug, setOverlayDebug] = useState<string | null>(null);
+  const [overlayPosition, setOverlayPosition] = useState<OverlayPosition | null>(null);
+  const [overlayStatus, setOverlayStatus] = useState<OverlayStatus | null>(null);
+  const [hudPlacement, setHudPlacement] = useState<HudPlacement | null>(null);
+  const overlayToastGenerationRef = useRef(0);
+  const [rawApi, setRawApi] = useState<string | null>(null);
+  const [metrics, setMetrics] = useState<string | null>(null);
+  const [telemetryPreview, setTelemetryPreview] = useState<string | null>(null);
+  const [pushTestResult, setPushTestResult] = useState<string | null>(null);
+  const [webhookTestResult, setWebhookTestResult] = useState<string | null>(null);
   const [openCategory, setOpenCategory] = useState<ScheduleType | null>(null);
   const [panicStopEnabled, setPanicStopEnabled] = useState(() => isPanicStopEnabled());
+  const [previousCrash, setPreviousCrash] = useState<CrashInfo | null>(null);
+  const [update, setUpdate] = useState<UpdateInfo | null>(null);
+  const [localApiError, setLocalApiError] = useState<string | null>(null);
+  const [discordAvailable, setDiscordAvailable] = useState(true);
+  const [prefs, setPrefs] = useState<Preferences | null>(null);
+  const [pausedUntilMs, setPausedUntilMs] = useState<number | null>(null);
+  // Occurrences marked "I'm there" (`${type}:${id}`); their reminders stay silent.
+  const [attending, setAttending] = useState<Set<string>>(() => new Set());
+  // An escalation step repeating its sound until acknowledged.
+  const [escalating, setEscalating] = useState<{ kind: ScheduleType; id: number; title: string; startMs: number } | null>(null);
+  // Toggled by the backend's overlay visibility schedule; only used to re-run the overlay effect.
+  const [overlayScheduled, setOverlayScheduled] = useState(true);
+  const [fonts, setFonts] = useState<string[]>([]);
+  const [shortcutList, setShortcutList] = useState<ShortcutInfo[]>([]);
+  const [chestResets, setChestResets] = useState<ChestReset[]>([]);
+  const [helltideEnds, setHelltideEnds] = useState<EndReminder[]>([]);
+  const [customReminders, setCustomReminders] = useState<CustomReminder[]>([]);
+  const [onboarding, setOnboarding] = useState<OnboardingStatus | null>(null);
+  const [customMinutes, setCustomMinutes] = useState(25);
+  const [customReminderError, setCustomReminderError] = useState<string | null>(null);
+  const [connection, setConnection] = useState<ConnectionStatus | null>(null);
+  const [syncNote, setSyncNote] = useState<string | null>(null);
+  const [about, setAbout] = useState<AboutInfo | null>(null);
+  const [profiles, setProfiles] = useState<ProfileList>({ active: null, profiles: [] });
+  const [profileName, setProfileName] = useState("");
+  const [profileError, setProfileError] = useState<string | null>(null);
 
   const [settings, setSettings] = useState<Settings>(() => loadSettings());
   const firedRef = useRef<FiredMap>(loadFired());
   const lastSettingsRef = useRef<Settings>(settings);
+  // Settings as last exchanged with the sync folder; only real changes are pushed.
+  const syncedSettingsRef = useRef(JSON.stringify(settings));
 
   function updateSettings(updater: (prev: Settings) => Settings): void {
     setSettings((prev) => {
@@ -147,7 +279,7 @@ export default function App() {
   }
 
   useEffect(() => {
-    const id = window.setInterval(() => setNow(Date.now()), 1000);
+    const id = window.setInterval(() => setNow(serverNow()), 1000);
     return () => window.clearInterval(id);
   }, []);
 
@@ -205,6 +337,90 @@ export default function App() {
     return () => window.clearTimeout(timer);
   }, [openCategory, categoryLayoutKey, settingsOpen, debugOpen, overlayDebug, panicStopEnabled]);
 
+  useEffect(() => followGameMode(), []);
+  useEffect(() => followReducedMotion(), []);
+
+  const [powerStatus, setPowerStatus] = useState<PowerStatus | null>(null);
+  useEffect(() => followPowerSaving(() => void getPowerStatus().then(setPowerStatus)), []);
+
+  useEffect(() => {
+    if (!settingsOpen || about) return;
+    void getAboutInfo().then(setAbout);
+  }, [settingsOpen, about]);
+
+  // Installed fonts for the overlay font picker; enumerated once, when settings first open.
+  useEffect(() => {
+    if (!settingsOpen || fonts.length) return;
+    void listFonts().then(setFonts);
+  }, [settingsOpen, fonts.length]);
+
+  useEffect(() => {
+    void takePreviousCrash().then(setPreviousCrash);
+    void getPreferences().then(setPrefs);
+  }, []);
+
+  const updatePrefs = (patch: Partial<Preferences>) => {
+    if (!prefs) return;
+    const next = { ...prefs, ...patch };
+    const previous = prefs;
+    setPrefs(next);
+    void setPreferences(next).catch((e) => {
+      // Rejected (e.g. invalid proxy URL): show the error and keep what the backend has.
+      setPrefs(previous);
+      setError(errorText(e));
+    });
+  };
+
+  // The backend fires reminders itself while headless; keep its copy of the lead times current.
+  useEffect(() => {
+    if (!prefs) return;
+    const times = reminderTimes(settings);
+    if (JSON.stringify(times) === JSON.stringify(prefs.reminderTimes)) return;
+    updatePrefs({ reminderTimes: times });
+  }, [settings, prefs]);
+
+  const setOverlayEnabled = (enabled: boolean) => {
+    if (prefs) updatePrefs({ overlayEnabled: enabled });
+    else updateSettings((s) => ({ ...s, overlayWindowEnabled: enabled }));
+  };
+
+  // Toast templates are edited as a draft with a live preview and saved on blur.
+  const [toastDraft, setToastDraft] = useState<ToastTemplates | null>(null);
+  const [toastPreview, setToastPreview] = useState<{ toast?: RenderedToast; error?: string }>({});
+
+  useEffect(() => {
+    if (prefs) setToastDraft(prefs.toastTemplates);
+  }, [prefs?.toastTemplates.title, prefs?.toastTemplates.body]);
+
+  useEffect(() => {
+    if (!toastDraft || !isTauri()) return;
+    const timer = window.setTimeout(() => {
+      previewToast(toastDraft, "world_boss")
+        .then((toast) => setToastPreview({ toast }))
+        .catch((e) => setToastPreview({ error: errorText(e) }));
+    }, 250);
+    return () => window.clearTimeout(timer);
+  }, [toastDraft?.title, toastDraft?.body]);
+
+  // Re-registration happens on the hotkey thread; give it a moment before asking.
+  useEffect(() => {
+    const timer = window.setTimeout(() => void listShortcuts().then(setShortcutList), 300);
+    return () => window.clearTimeout(timer);
+  }, [JSON.stringify(prefs?.shortcuts)]);
+
+  const setShortcut = (action: ShortcutAction, value: string | undefined) => {
+    if (!prefs) return;
+    const shortcuts = { ...prefs.shortcuts };
+    if (value === undefined) delete shortcuts[action];
+    else shortcuts[action] = value;
+    updatePrefs({ shortcuts });
+  };
+
+  const updateNetwork = (patch: Partial<NetworkSettings>) => {
+    if (!prefs) return;
+    updatePrefs({ network: { ...prefs.network, ...patch } });
+  };
+
   useEffect(() => {
     if (!settingsOpen) return;
     const onKeyDown = (e: KeyboardEvent) => {
@@ -223,97 +439,230 @@ export default function App() {
     return () => window.removeEventListener("helltime:panic-stop", onPanic);
   }, []);
 
+  // Tray check items live in the backend preferences; follow changes made there.
   useEffect(() => {
     if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      unlisten = await listen<Preferences>("preferences:changed", (event) => setPrefs(event.payload));
+    })();
+    return () => {
+      unlisten?.();
+    };
+  }, []);
 
-    let unlistenOverlay: (() => void) | null = null;
-    let unlistenReminder: (() => void) | null = null;
+  // Quit from the tray: stop polling and reminders while the backend winds down.
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      unlisten = await listen("app:shutdown", () => setShuttingDown(true));
+    })();
+    return () => {
+      unlisten?.();
+    };
+  }, []);
 
+  // Reported by the overlay while it is being positioned.
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
     void (async () => {
-      unlistenOverlay = await listen("menu:toggle-overlay", () => {
-        updateSettings((prev) => ({
-          ...prev,
-          overlayWindowEnabled: !prev.overlayWindowEnabled,
-        }));
-      });
+      unlisten = await listen<OverlayPosition>("overlay:position-changed", (event) => setOverlayPosition(event.payload));
+    })();
+    return () => {
+      unlisten?.();
+    };
+  }, []);
 
-      unlistenReminder = await listen("menu:toggle-reminder", () => {
-        updateSettings((prev) => {
-          const ARCHIVE_KEY = "helltime:reminder_archive";
-          const anyEnabled =
-            prev.categories.helltide.enabled ||
-            prev.categories.legion.enabled ||
-            prev.categories.world_boss.enabled;
-
-          if (anyEnabled) {
-            // Archive current state and disable all
-            const archive = {
-              helltide: prev.categories.helltide.enabled,
-              legion: prev.categories.legion.enabled,
-              world_boss: prev.categories.world_boss.enabled,
-            };
-            localStorage.setItem(ARCHIVE_KEY, JSON.stringify(archive));
-
-            return {
-              ...prev,
-              categories: {
-                ...prev.categories,
-                helltide: { ...prev.categories.helltide, enabled: false },
-                legion: { ...prev.categories.legion, enabled: false },
-                world_boss: { ...prev.categories.world_boss, enabled: false },
-              },
-            };
-          } else {
-            // Restore from archive (or enable all if no archive)
-            let archive = { helltide: true, legion: true, world_boss: true };
-            try {
-              const stored = localStorage.getItem(ARCHIVE_KEY);
-              if (stored) {
-                const parsed = JSON.parse(stored);
-                archive = {
-                  helltide: typeof parsed.helltide === "boolean" ? parsed.helltide : true,
-                  legion: typeof parsed.legion === "boolean" ? parsed.legion : true,
-                  world_boss: typeof parsed.world_boss === "boolean" ? parsed.world_boss : true,
-                };
-              }
-            } catch {
-              // ignore parse errors
-            }
+  // Position presets for the game's HUD layout; re-checked whenever the overlay moves.
+  useEffect(() => {
+    if (!settings.overlayWindowEnabled) {
+      setHudPlacement(null);
+      return;
+    }
+    let cancelled = false;
+    void getHudPlacement(settings.overlayHudLayout).then((placement) => {
+      if (!cancelled) setHudPlacement(placement);
+    });
+    return () => {
+      cancelled = true;
+    };
+  }, [settings.overlayWindowEnabled, settings.overlayHudLayout, overlayPosition]);
 
-            return {
-              ...prev,
-              categories: {
-                ...prev.categories,
-                helltide: { ...prev.categories.helltide, enabled: archive.helltide },
-                legion: { ...prev.categories.legion, enabled: archive.legion },
-                world_boss: { ...prev.categories.world_boss, enabled: archive.world_boss },
-              },
-            };
-          }
-        });
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      unlisten = await listen<OverlayStatus>("overlay:status", (event) => {
+        setOverlayStatus(event.payload);
+        if (event.payload.position) setOverlayPosition(event.payload.position);
       });
     })();
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  // The overlay was resized by dragging its edges in positioning mode.
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      unlisten = await listen<OverlayResized>("overlay:resized", (event) =>
+        updateSettings((s) => ({ ...s, overlayWidth: event.payload.width, overlayHeight: event.payload.height }))
+      );
+    })();
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      unlisten = await listen<boolean>("overlay:schedule", (event) => setOverlayScheduled(event.payload));
+    })();
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  // The backend checks GitHub releases periodically and announces newer versions.
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      unlisten = await listen<UpdateInfo>("update:available", (event) => setUpdate(event.payload));
+    })();
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    setLocalApi(settings.localApiEnabled, settings.localApiPort)
+      .then(() => setLocalApiError(null))
+      .catch((e) => setLocalApiError(errorText(e)));
+  }, [settings.localApiEnabled, settings.localApiPort]);
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    setDiscordPresence(settings.discordPresenceEnabled)
+      .then(setDiscordAvailable)
+      .catch(() => setDiscordAvailable(false));
+  }, [settings.discordPresenceEnabled]);
+
+  // Reminder pause is set from the tray menu and enforced here while firing.
+  useEffect(() => {
+    if (!isTauri()) return;
+    const apply = (status: PauseStatus) =>
+      setPausedUntilMs(status.pausedUntil ? new Date(status.pausedUntil).getTime() : null);
+
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      apply(await getReminderPause());
+      unlisten = await listen<PauseStatus>("reminders:pause-changed", (event) => apply(event.payload));
+    })();
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  // Connection state is tracked by the backend from every fetch, including the overlay's.
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      setConnection(await getConnectionStatus());
+      unlisten = await listen<ConnectionStatus>("connection:changed", (event) => setConnection(event.payload));
+    })();
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  // helltime:// links: the launch link is fetched once, later ones arrive as events.
+  useEffect(() => {
+    if (!isTauri()) return;
+    const navigate = (link: DeepLink) => {
+      if (link.route === "show_next") {
+        setSettingsOpen(false);
+        setOpenCategory(link.kind);
+      } else if (link.route === "settings") {
+        setSettingsOpen(true);
+        if (link.section) {
+          window.setTimeout(() => {
+            document.getElementById(`settings-${link.section}`)?.scrollIntoView({ behavior: "smooth" });
+          }, 50);
+        }
+      }
+    };
+
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      const pending = await takePendingDeepLink();
+      if (pending) navigate(pending);
+      unlisten = await listen<DeepLink>("deeplink:navigate", (event) => navigate(event.payload));
+    })();
+    return () => {
+      unlisten?.();
+    };
+  }, []);
 
+  // Clicking a reminder notification restores the window (backend); open the event's card.
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      unlisten = await listen<NotificationTarget>("notification:activated", (event) => {
+        setSettingsOpen(false);
+        setOpenCategory(event.payload.kind);
+      });
+    })();
     return () => {
-      unlistenOverlay?.();
-      unlistenReminder?.();
+      unlisten?.();
     };
   }, []);
 
-  // Sync menu checkbox states with Rust backend
+  // After sleep/resume or a clock change the backend drops its cache; refetch and
+  // re-derive all countdowns so pending reminders are rescheduled from fresh data.
   useEffect(() => {
     if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      unlisten = await listen("system:time-jump", () => {
+        setNow(serverNow());
+        void refresh();
+      });
+    })();
+    return () => {
+      unlisten?.();
+    };
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, []);
 
-    // Update overlay checkbox in tray menu
-    void emit("menu:update-overlay-state", settings.overlayWindowEnabled);
+  // Back online: the backend has just fetched the schedule, pick it up.
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      unlisten = await listen("system:network-changed", () => void refresh());
+    })();
+    return () => {
+      unlisten?.();
+    };
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, []);
 
-    // Update reminder checkbox - checked if ANY category is enabled
-    const anyReminderEnabled =
-      settings.categories.helltide.enabled ||
-      settings.categories.legion.enabled ||
-      settings.categories.world_boss.enabled;
-    void emit("menu:update-reminder-state", anyReminderEnabled);
-  }, [settings.overlayWindowEnabled, settings.categories]);
+  // The overlay switch is owned by the backend (tray); mirror it into the UI settings.
+  useEffect(() => {
+    if (!prefs || prefs.overlayEnabled === settings.overlayWindowEnabled) return;
+    updateSettings((s) => ({ ...s, overlayWindowEnabled: prefs.overlayEnabled }));
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [prefs?.overlayEnabled]);
 
   useEffect(() => {
     lastSettingsRef.current = settings;
@@ -336,35 +685,45 @@ export default function App() {
 
     void (async () => {
       await ensureOverlayWindow();
-      if (settings.overlayWindowMode === "overview") {
+      if (settings.overlayWindowMode !== "toast") {
         await setOverlayWindowVisible(true);
       } else {
         // toast mode: keep it hidden until a toast arrives
         await setOverlayWindowVisible(false);
       }
     })();
-  }, [settings.overlayWindowEnabled, settings.overlayWindowMode, panicStopEnabled]);
+  }, [settings.overlayWindowEnabled, settings.overlayWindowMode, panicStopEnabled, overlayScheduled]);
 
   useEffect(() => {
     firedRef.current = pruneFired(firedRef.current, now);
     saveFired(firedRef.current);
   }, [now]);
 
-  async function refresh() {
-    if (refreshInFlightRef.current) return;
+  async function refresh(force = false) {
+    if (refreshInFlightRef.current) {
+      refreshQueuedRef.current = { force: force || Boolean(refreshQueuedRef.current?.force) };
+      return;
+    }
     refreshInFlightRef.current = true;
     setError(null);
     try {
-      const data = await fetchSchedule();
+      const { schedule: data, ...source } = await refreshSchedule(force);
+      setScheduleSource(source);
       data.helltide.sort((a, b) => a.timestamp - b.timestamp);
       data.legion.sort((a, b) => a.timestamp - b.timestamp);
       data.world_boss.sort((a, b) => a.timestamp - b.timestamp);
       setSchedule(data);
       setLastRefreshAt(Date.now());
     } catch (e) {
-      setError(String(e));
+      // In the app the connection banner reports fetch failures and keeps the old times visible;
+      // being held back by the rate limiter is not a connection problem, so say so here.
+      if (isAppError(e, "rateLimited")) setError(`Zu viele Aktualisierungen – nächster Versuch in ${e.retryAfterSecs ?? 1}s möglich.`);
+      else if (!isTauri()) setError(errorText(e));
     } finally {
       refreshInFlightRef.current = false;
+      const queued = refreshQueuedRef.current;
+      refreshQueuedRef.current = null;
+      if (queued) void refresh(queued.force);
     }
   }
 
@@ -375,12 +734,15 @@ export default function App() {
   }
 
   function scheduleNextAutoRefresh(baseNow: number): void {
-    const nextAt = baseNow + randomAutoRefreshMs();
-    setNextAutoRefreshAt(nextAt);
-    if (autoRefreshTimeoutRef.current) window.clearTimeout(autoRefreshTimeoutRef.current);
-    autoRefreshTimeoutRef.current = window.setTimeout(() => {
-      void refresh();
-    }, Math.max(1000, nextAt - Date.now()));
+    // The backend applies the poll preferences; the random interval is the browser fallback.
+    void nextPollDelay().then((delayMs) => {
+      const nextAt = baseNow + (delayMs ?? randomAutoRefreshMs());
+      setNextAutoRefreshAt(nextAt);
+      if (autoRefreshTimeoutRef.current) window.clearTimeout(autoRefreshTimeoutRef.current);
+      autoRefreshTimeoutRef.current = window.setTimeout(() => {
+        void refresh();
+      }, Math.max(1000, nextAt - Date.now()));
+    });
   }
 
   useEffect(() => {
@@ -388,7 +750,7 @@ export default function App() {
   }, []);
 
   useEffect(() => {
-    if (panicStopEnabled) {
+    if (panicStopEnabled || shuttingDown) {
       if (autoRefreshTimeoutRef.current) window.clearTimeout(autoRefreshTimeoutRef.current);
       autoRefreshTimeoutRef.current = null;
       setNextAutoRefreshAt(null);
@@ -401,14 +763,14 @@ export default function App() {
       autoRefreshTimeoutRef.current = null;
     };
     // eslint-disable-next-line react-hooks/exhaustive-deps
-  }, [panicStopEnabled]);
+  }, [panicStopEnabled, shuttingDown]);
 
   useEffect(() => {
-    if (panicStopEnabled) return;
+    if (panicStopEnabled || shuttingDown) return;
     if (!lastRefreshAt) return;
     scheduleNextAutoRefresh(lastRefreshAt);
     // eslint-disable-next-line react-hooks/exhaustive-deps
-  }, [lastRefreshAt, panicStopEnabled]);
+  }, [lastRefreshAt, panicStopEnabled, shuttingDown, prefs?.poll.mode, prefs?.poll.intervalMinutes]);
 
   const nextByType = useMemo(() => {
     if (!schedule) return null;
@@ -419,9 +781,18 @@ export default function App() {
     };
   }, [schedule, now]);
 
+  const hiddenTypes = hiddenEvents(prefs);
+
+  function setEventHidden(type: ScheduleType, hidden: boolean) {
+    const rest = hiddenTypes.filter((t) => t !== type);
+    if (hidden && openCategory === type) setOpenCategory(null);
+    updatePrefs({ hiddenEvents: hidden ? [...rest, type] : rest });
+  }
+
   const orderedTypes = useMemo<ScheduleType[]>(() => {
-    const enabled = types.filter((t) => settings.categories[t].enabled);
-    const disabled = types.filter((t) => !settings.categories[t].enabled);
+    const shown = types.filter((t) => !hiddenTypes.includes(t));
+    const enabled = shown.filter((t) => settings.categories[t].enabled);
+    const disabled = shown.filter((t) => !settings.categories[t].enabled);
     if (!nextByType) return [...enabled, ...disabled];
 
     const enabledSorted = [...enabled]
@@ -435,14 +806,14 @@ export default function App() {
 
     // Disabled categories are always at the bottom in stable order.
     return [...enabledSorted, ...disabled];
-  }, [nextByType, settings.categories]);
+  }, [nextByType, settings.categories, prefs]);
 
   const nextEnabledOverall = useMemo(() => {
     if (!nextByType) return null;
     const candidates: Array<{ type: ScheduleType; startMs: number; startTime: string; name: string }> = [];
 
     for (const type of types) {
-      if (!settings.categories[type].enabled) continue;
+      if (!settings.categories[type].enabled || hiddenTypes.includes(type)) continue;
       const next = nextByType[type];
       if (!next) continue;
       const startMs = new Date(next.startTime).getTime();
@@ -451,32 +822,32 @@ export default function App() {
 
     candidates.sort((a, b) => a.startMs - b.startMs);
     return candidates[0] ?? null;
-  }, [nextByType, settings]);
+  }, [nextByType, settings, prefs]);
 
   useEffect(() => {
     if (!schedule) return;
-    if (panicStopEnabled) return;
+    if (panicStopEnabled || shuttingDown) return;
+    // The pause is wall-clock based, so compare against the local clock.
+    if (pausedUntilMs !== null && Date.now() < pausedUntilMs) return;
+    if (prefs && !prefs.remindersEnabled) return;
+    // The backend's escalation takes the place of the timers.
+    if (prefs?.reminderEscalation.enabled) return;
 
-    const fireWindowMs = 30_000;
-    const ttsPauseMs = 500;
 
     for (const type of types) {
       const category = settings.categories[type];
-      if (!category.enabled) continue;
+      if (!category.enabled || hiddenTypes.includes(type)) continue;
 
       const next = findNext(schedule[type] as Array<{ id: number; startTime: string }>, now);
       if (!next) continue;
 
       const startMs = new Date(next.startTime).getTime();
       const remainingMs = startMs - now;
-      const title = getEventName(type, next);
-      const spokenTitle = getSpokenEventNameWithTemplate(type, next, category.ttsName);
-      const timeLabel = formatLocalTime(next.startTime);
 
       for (let i = 0; i < category.timerCount; i++) {
         const timer = category.timers[i];
         const triggerMs = startMs - timer.minutesBefore * 60_000;
-        if (now < triggerMs || now > triggerMs + fireWindowMs) continue;
+        if (now < triggerMs || now > triggerMs + FIRE_WINDOW_MS) continue;
 
         const key = `${type}:${next.id}:${i}`;
         if (firedRef.current[key]) continue;
@@ -484,89 +855,345 @@ export default function App() {
         firedRef.current[key] = now;
         saveFired(firedRef.current);
 
-        const body = formatCountdown(Math.max(0, remainingMs));
-        void showOverlayToast({ title, body, type, kind: "event" });
-
-        if (!settings.soundEnabled) continue;
-
-        const beepMs = playBeep(timer.beepPattern, timer.pitchHz, settings.volume);
-
-        if (timer.ttsEnabled) {
-          window.setTimeout(() => {
-            void speak(`${spokenTitle} in ${formatRemainingSpeech(Math.max(0, remainingMs))}`, settings.volume);
-          }, beepMs + ttsPauseMs);
-        }
+        announceEvent(type, next, timer, remainingMs);
       }
     }
-  }, [schedule, now, settings, panicStopEnabled]);
+  }, [schedule, now, settings, panicStopEnabled, shuttingDown, pausedUntilMs, prefs]);
 
-  function setCategoryEnabled(type: ScheduleType, enabled: boolean) {
-    if (enabled) {
-      const nowMs = Date.now();
-      const next = nextByType ? nextByType[type] : null;
-      if (schedule && next) {
-        const startMs = new Date(next.startTime).getTime();
-        const remainingMs = startMs - nowMs;
-        if (Number.isFinite(remainingMs) && remainingMs > 0) {
-          const category = settings.categories[type];
-          const candidates = Array.from({ length: category.timerCount })
-            .map((_, i) => ({ i, timer: category.timers[i] }))
-            .filter(({ timer }) => timer.minutesBefore * 60_000 >= remainingMs);
+  // A refresh moved or dropped upcoming events: forget their fired reminders so they
+  // fire again for the new time, and pick up the new schedule if it came from elsewhere.
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      unlisten = await listen<ScheduleDiff>("schedule:changed", (event) => {
+        const changed = [...event.payload.shifted, ...event.payload.cancelled].map((e) => `${e.kind}:${e.id}:`);
+        const fired = firedRef.current;
+        for (const key of Object.keys(fired)) {
+          if (changed.some((prefix) => key.startsWith(prefix))) delete fired[key];
+        }
+        saveFired(fired);
+        void refresh();
+      });
+    })();
+    return () => {
+      unlisten?.();
+    };
+  }, []);
 
-          candidates.sort((a, b) => a.timer.minutesBefore - b.timer.minutesBefore);
-          const chosen = candidates[0];
+  useEffect(() => {
+    void getOnboardingStatus().then(setOnboarding);
+  }, []);
 
-          if (chosen) {
-            const key = `${type}:${(next as any).id ?? next.startTime}:${chosen.i}`;
-            if (!firedRef.current[key]) {
-              firedRef.current[key] = nowMs;
-              saveFired(firedRef.current);
+  // Another PC changed the synced settings (last write wins): take over its UI settings.
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      unlisten = await listen<SyncChanged>("sync:changed", (event) => {
+        const { device, updatedAt, uiSettings } = event.payload;
+        setSyncNote(`Einstellungen von ${device} übernommen (${formatClock(Date.parse(updatedAt))})`);
+        if (uiSettings) adoptSettings(uiSettings, true);
+      });
+    })();
+    return () => {
+      unlisten?.();
+    };
+  }, []);
 
-              const title = getEventName(type, next);
-              const spokenTitle = getSpokenEventNameWithTemplate(type, next, category.ttsName);
-              const timeLabel = formatLocalTime(next.startTime);
-              const body = formatCountdown(Math.max(0, remainingMs));
-              void showOverlayToast({ title, body, type, kind: "event" });
+  useEffect(() => {
+    if (!prefs?.syncDir) return;
+    const json = JSON.stringify(settings);
+    if (json === syncedSettingsRef.current) return;
+    const timer = window.setTimeout(() => {
+      syncedSettingsRef.current = json;
+      void pushSyncedSettings(settings).catch((e) => setSyncNote(`Sync fehlgeschlagen: ${errorText(e)}`));
+    }, 2000);
+    return () => window.clearTimeout(timer);
+  }, [settings, prefs?.syncDir]);
+
+  /** Take over settings from elsewhere (sync folder, profile); `synced` if they came from the sync folder. */
+  function adoptSettings(blob: unknown, synced: boolean): void {
+    // Stored first so loadSettings() fills in and clamps whatever this version expects.
+    saveSettings(blob as Settings);
+    const next = loadSettings();
+    if (synced) syncedSettingsRef.current = JSON.stringify(next);
+    updateSettings(() => next);
+  }
 
-              if (settings.soundEnabled) {
-                const beepMs = playBeep(chosen.timer.beepPattern, chosen.timer.pitchHz, settings.volume);
-                if (chosen.timer.ttsEnabled) {
-                  window.setTimeout(() => {
-                    void speak(`${spokenTitle} in ${formatRemainingSpeech(Math.max(0, remainingMs))}`, settings.volume);
-                  }, beepMs + 500);
-                }
-              }
-            }
-          }
-        }
-      }
-    }
+  useEffect(() => {
+    if (!isTauri()) return;
+    void listProfiles().then(setProfiles);
+    const unlisteners: (() => void)[] = [];
+    void (async () => {
+      unlisteners.push(await listen<ProfileList>("profiles:changed", (event) => setProfiles(event.payload)));
+      unlisteners.push(
+        await listen<Profile>("profile:activated", (event) => {
+          if (event.payload.uiSettings) adoptSettings(event.payload.uiSettings, false);
+        })
+      );
+    })();
+    return () => {
+      for (const unlisten of unlisteners) unlisten();
+    };
+  }, []);
 
-    if (!enabled && openCategory === type) setOpenCategory(null);
-    updateSettings((s) => ({
-      ...s,
-      categories: {
-        ...s.categories,
-        [type]: { ...s.categories[type], enabled }
-      }
-    }));
+  function storeProfile(): void {
+    setProfileError(null);
+    void saveProfile(profileName, settings)
+      .then((list) => {
+        setProfiles(list);
+        setProfileName("");
+      })
+      .catch((e) => setProfileError(errorText(e)));
   }
 
-  function setTimerCount(type: ScheduleType, timerCount: 1 | 2 | 3) {
-    updateSettings((s) => ({
-      ...s,
-      categories: {
-        ...s.categories,
-        [type]: { ...s.categories[type], timerCount }
-      }
-    }));
+  function changeSyncDir(value: string): void {
+    const dir = value.trim() || null;
+    if (!prefs || dir === (prefs.syncDir ?? null)) return;
+    setSyncNote(null);
+    syncedSettingsRef.current = JSON.stringify(settings);
+    void setSyncDir(dir, settings)
+      .then(() => setPrefs({ ...prefs, syncDir: dir ?? undefined }))
+      .catch((e) => setSyncNote(errorText(e)));
   }
 
-  function updateTimer(type: ScheduleType, index: number, patch: Partial<TimerSettings>) {
-    updateSettings((s) => {
-      const category = s.categories[type];
-      const timers: Settings["categories"][ScheduleType]["timers"] = [category.timers[0], category.timers[1], category.timers[2]];
-      timers[index] = { ...timers[index], ...patch };
+  async function finishOnboarding(): Promise<void> {
+    try {
+      await completeOnboarding();
+    } catch (e) {
+      console.warn("complete_onboarding failed", e);
+    }
+    setOnboarding(null);
+  }
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    void listReminders().then(setCustomReminders);
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      unlisten = await listen("reminders:changed", () => {
+        void listReminders().then(setCustomReminders);
+      });
+    })();
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  // One-off reminders were asked for explicitly, so they fire even for categories
+  // without regular reminders.
+  useEffect(() => {
+    if (panicStopEnabled || shuttingDown) return;
+    if (pausedUntilMs !== null && Date.now() < pausedUntilMs) return;
+    if (prefs && !prefs.remindersEnabled) return;
+
+    for (const reminder of customReminders) {
+      const triggerMs = new Date(reminder.fireAt).getTime();
+      if (now < triggerMs || now > triggerMs + FIRE_WINDOW_MS) continue;
+
+      const key = `custom:${reminder.id}`;
+      if (firedRef.current[key]) continue;
+      firedRef.current[key] = now;
+      saveFired(firedRef.current);
+
+      const listed = (schedule?.[reminder.kind] as Array<{ id: number; startTime: string }> | undefined)?.find(
+        (e) => e.id === reminder.eventId
+      );
+      const item = listed ?? { id: reminder.eventId, startTime: reminder.eventStart };
+      const category = settings.categories[reminder.kind];
+      announceEvent(reminder.kind, item, category.timers[0], new Date(reminder.eventStart).getTime() - now);
+    }
+  }, [customReminders, schedule, now, settings, panicStopEnabled, shuttingDown, pausedUntilMs, prefs]);
+
+  // Test reminders from the settings come back from the backend's reminder engine
+  // and take the same path as real ones.
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      unlisten = await listen<PlannedReminder>("reminder:test", (event) => {
+        const { event: planned, timer } = event.payload;
+        const item = { id: planned.id, startTime: planned.start, boss: planned.boss ?? undefined, zone: planned.zone ?? undefined };
+        const category = settings.categories[planned.kind];
+        const remainingMs = new Date(planned.start).getTime() - serverNow();
+        announceEvent(planned.kind, item, category.timers[timer] ?? category.timers[0], remainingMs);
+      });
+    })();
+    return () => {
+      unlisten?.();
+    };
+  }, [settings, panicStopEnabled]);
+
+  // Escalation steps are planned by the backend; the UI shows and plays them.
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlistenStep: (() => void) | null = null;
+    let unlistenAck: (() => void) | null = null;
+    void (async () => {
+      unlistenStep = await listen<EscalationStep>("reminder:escalation", (event) => runEscalationStep(event.payload));
+      unlistenAck = await listen<NotificationTarget>("reminder:acknowledged", (event) => {
+        setEscalating((current) =>
+          current && current.kind === event.payload.kind && current.id === event.payload.id ? null : current
+        );
+      });
+    })();
+    return () => {
+      unlistenStep?.();
+      unlistenAck?.();
+    };
+  }, [settings, panicStopEnabled]);
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      unlisten = await listen<NotificationTarget>("reminder:attending", (event) => {
+        const { kind, id } = event.payload;
+        setAttending((prev) => new Set(prev).add(`${kind}:${id}`));
+      });
+    })();
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  // The backend stops repeating shortly after the start on its own.
+  useEffect(() => {
+    if (escalating && now > escalating.startMs + 120_000) setEscalating(null);
+  }, [escalating, now]);
+
+  // Mystery chest resets are derived by the backend; reload them whenever the schedule
+  // or the event filter changes.
+  useEffect(() => {
+    if (!schedule || !settings.chestReminderEnabled) {
+      setChestResets([]);
+      return;
+    }
+    void getChestResets().then(setChestResets);
+  }, [schedule, settings.chestReminderEnabled, prefs]);
+
+  useEffect(() => {
+    if (!settings.chestReminderEnabled || panicStopEnabled || shuttingDown) return;
+    if (pausedUntilMs !== null && Date.now() < pausedUntilMs) return;
+    if (prefs && !prefs.remindersEnabled) return;
+
+    for (const reset of chestResets) {
+      const atMs = new Date(reset.at).getTime();
+      const triggerMs = atMs - settings.chestReminderMinutesBefore * 60_000;
+      if (now < triggerMs || now > triggerMs + FIRE_WINDOW_MS) continue;
+
+      const key = `chest:${reset.helltideId}:${Math.floor(atMs / 1000)}`;
+      if (firedRef.current[key]) continue;
+      firedRef.current[key] = now;
+      saveFired(firedRef.current);
+
+      const body = formatCountdown(Math.max(0, atMs - now));
+      void showOverlayToast({ title: "Mystery-Truhe", body, type: "helltide", kind: "chest" });
+      if (settings.soundEnabled) {
+        playBeep("double", 660, settings.volume);
+      }
+    }
+  }, [chestResets, now, settings, panicStopEnabled, shuttingDown, pausedUntilMs, prefs]);
+
+  useEffect(() => {
+    if (!schedule || !settings.helltideEndReminderEnabled) {
+      setHelltideEnds([]);
+      return;
+    }
+    void getHelltideEndReminders(settings.helltideEndReminderMinutesBefore).then(setHelltideEnds);
+  }, [schedule, settings.helltideEndReminderEnabled, settings.helltideEndReminderMinutesBefore, prefs]);
+
+  useEffect(() => {
+    if (!settings.helltideEndReminderEnabled || panicStopEnabled || shuttingDown) return;
+    if (pausedUntilMs !== null && Date.now() < pausedUntilMs) return;
+    if (prefs && !prefs.remindersEnabled) return;
+
+    for (const reminder of helltideEnds) {
+      const triggerMs = new Date(reminder.fireAt).getTime();
+      if (now < triggerMs || now > triggerMs + FIRE_WINDOW_MS) continue;
+
+      const endMs = new Date(reminder.end).getTime();
+      const key = `helltide_end:${reminder.helltideId}:${Math.floor(endMs / 1000)}`;
+      if (firedRef.current[key]) continue;
+      firedRef.current[key] = now;
+      saveFired(firedRef.current);
+
+      const body = `endet in ${formatCountdown(Math.max(0, endMs - now))}`;
+      void showOverlayToast({ title: "Helltide", body, type: "helltide", kind: "event" });
+      if (settings.soundEnabled) {
+        playBeep("double", 520, settings.volume);
+      }
+    }
+  }, [helltideEnds, now, settings, panicStopEnabled, shuttingDown, pausedUntilMs, prefs]);
+
+  function setCategoryEnabled(type: ScheduleType, enabled: boolean) {
+    if (enabled) {
+      const nowMs = serverNow();
+      const next = nextByType ? nextByType[type] : null;
+      if (schedule && next) {
+        const startMs = new Date(next.startTime).getTime();
+        const remainingMs = startMs - nowMs;
+        if (Number.isFinite(remainingMs) && remainingMs > 0) {
+          const category = settings.categories[type];
+          const candidates = Array.from({ length: category.timerCount })
+            .map((_, i) => ({ i, timer: category.timers[i] }))
+            .filter(({ timer }) => timer.minutesBefore * 60_000 >= remainingMs);
+
+          candidates.sort((a, b) => a.timer.minutesBefore - b.timer.minutesBefore);
+          const chosen = candidates[0];
+
+          if (chosen) {
+            const key = `${type}:${(next as any).id ?? next.startTime}:${chosen.i}`;
+            if (!firedRef.current[key]) {
+              firedRef.current[key] = nowMs;
+              saveFired(firedRef.current);
+
+              const title = getEventName(type, next);
+              const spokenTitle = getSpokenEventNameWithTemplate(type, next, category.ttsName);
+              const timeLabel = formatLocalTime(next.startTime);
+              const body = formatCountdown(Math.max(0, remainingMs));
+              void showOverlayToast({ title, body, type, kind: "event" });
+
+              if (settings.soundEnabled) {
+                const beepMs = playBeep(chosen.timer.beepPattern, chosen.timer.pitchHz, settings.volume);
+                if (chosen.timer.ttsEnabled) {
+                  window.setTimeout(() => {
+                    void speak(`${spokenTitle} in ${formatRemainingSpeech(Math.max(0, remainingMs))}`, settings.volume);
+                  }, beepMs + 500);
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+
+    if (!enabled && openCategory === type) setOpenCategory(null);
+    updateSettings((s) => ({
+      ...s,
+      categories: {
+        ...s.categories,
+        [type]: { ...s.categories[type], enabled }
+      }
+    }));
+  }
+
+  function setTimerCount(type: ScheduleType, timerCount: 1 | 2 | 3) {
+    updateSettings((s) => ({
+      ...s,
+      categories: {
+        ...s.categories,
+        [type]: { ...s.categories[type], timerCount }
+      }
+    }));
+  }
+
+  function updateTimer(type: ScheduleType, index: number, patch: Partial<TimerSettings>) {
+    updateSettings((s) => {
+      const category = s.categories[type];
+      const timers: Settings["categories"][ScheduleType]["timers"] = [category.timers[0], category.timers[1], category.timers[2]];
+      timers[index] = { ...timers[index], ...patch };
 
       return {
         ...s,
@@ -586,7 +1213,76 @@ export default function App() {
     }
   }
 
-  async function showOverlayToast(payload: { title: string; body: string; type?: ScheduleType; kind?: "event" | "debug" }) {
+  /** Overlay toast, desktop notification, beep and speech for one event reminder. */
+  function announceEvent(type: ScheduleType, next: { id: number; startTime: string }, timer: TimerSettings, remainingMs: number): void {
+    if (attending.has(`${type}:${next.id}`)) return;
+    const category = settings.categories[type];
+    const title = getEventName(type, next);
+    const spokenTitle = getSpokenEventNameWithTemplate(type, next, category.ttsName);
+    const timeLabel = formatLocalTime(next.startTime);
+    const body = formatCountdown(Math.max(0, remainingMs));
+
+    // The overlay uses the toast templates; the built-in text is the fallback.
+    const item = next as Partial<WorldBossScheduleItem>;
+    void renderToast({
+      kind: type,
+      start: next.startTime,
+      boss: item.boss || item.predictedBoss || null,
+      bossPredicted: !item.boss && !!item.predictedBoss,
+      zone: displayZone(item)
+    }).then((toast) => showOverlayToast({ title: toast?.title ?? title, body: toast?.body ?? body, type, kind: "event" }));
+    if (settings.screenReaderAnnouncements) announce(`${title}: ${timeLabel}, in ${body}`);
+    // Narrator reads system notifications even while helltime is in the background.
+    if (settings.desktopNotificationEnabled || settings.screenReaderAnnouncements) {
+      void notifyEvent(title, `${timeLabel} · in ${body}`, { kind: type, id: next.id });
+    }
+
+    if (!settings.soundEnabled) return;
+    const beepMs = playBeep(timer.beepPattern, timer.pitchHz, settings.volume);
+    if (timer.ttsEnabled) {
+      window.setTimeout(() => {
+        void speak(`${spokenTitle} in ${formatRemainingSpeech(Math.max(0, remainingMs))}`, settings.volume);
+      }, beepMs + 500);
+    }
+  }
+
+  /** One step of the backend's escalation. The steps were set up explicitly, so the
+   *  notification and sound toggles do not apply to them. */
+  function runEscalationStep(step: EscalationStep): void {
+    if (panicStopEnabled) return;
+    const planned = step.reminder.event;
+    const item = { id: planned.id, startTime: planned.start, boss: planned.boss ?? undefined, zone: planned.zone ?? undefined };
+    const title = getEventName(planned.kind, item);
+    const remainingMs = new Date(planned.start).getTime() - serverNow();
+    const body = formatCountdown(Math.max(0, remainingMs));
+
+    if (step.actions.includes("overlay_toast")) {
+      void renderToast({
+        kind: planned.kind,
+        start: planned.start,
+        boss: planned.boss,
+        bossPredicted: planned.boss_predicted,
+        zone: planned.zone
+      }).then((toast) => showOverlayToast({ title: toast?.title ?? title, body: toast?.body ?? body, type: planned.kind, kind: "event" }));
+    }
+    if (step.actions.includes("notification")) {
+      void notifyEvent(title, `${formatLocalTime(planned.start)} · in ${body}`, { kind: planned.kind, id: planned.id });
+    }
+    if (step.actions.includes("sound") || step.actions.includes("repeat_sound")) {
+      const timer = settings.categories[planned.kind].timers[0];
+      playBeep(timer.beepPattern, timer.pitchHz, settings.volume);
+    }
+    if (step.actions.includes("repeat_sound")) {
+      setEscalating({ kind: planned.kind, id: planned.id, title, startMs: new Date(planned.start).getTime() });
+    }
+  }
+
+  async function showOverlayToast(payload: {
+    title: string;
+    body: string;
+    type?: ScheduleType;
+    kind?: "event" | "chest" | "debug";
+  }) {
     if (panicStopEnabled) return;
 
     if (settings.overlayWindowEnabled) {
@@ -601,11 +1297,14 @@ export default function App() {
           title: payload.title,
           body: payload.body,
           type: payload.type,
+          kind: payload.kind,
           durationMs
         });
         if (settings.overlayWindowMode === "toast") {
+          // Only the latest toast hides the window; an older timer must not cut a newer toast short.
+          const generation = ++overlayToastGenerationRef.current;
           window.setTimeout(() => {
-            void setOverlayWindowVisible(false);
+            if (overlayToastGenerationRef.current === generation) void setOverlayWindowVisible(false);
           }, durationMs + 50);
         }
       } catch (e) {
@@ -660,14 +1359,19 @@ export default function App() {
         }
         void setOverlayWindowInteractive(false);
       }, 15_000);
-      setOverlayDebug("Overlay ist 15s anklickbar (Positionieren). Danach wird es wieder klick-durch.");
+      setOverlayDebug(
+        "Overlay ist 15s anklickbar (Positionieren): ziehen oder Pfeiltasten (Shift = 10px), Enter übernimmt, Esc bricht ab. Danach wird es wieder klick-durch."
+      );
     } catch (e) {
-      setOverlayDebug(`Overlay nach vorne fehlgeschlagen: ${String(e)}`);
+      setOverlayDebug(`Overlay nach vorne fehlgeschlagen: ${errorText(e)}`);
     }
   }
 
   return (
     <div className="container">
+      <div className="srOnly" role="status" aria-live="assertive">
+        {announcement}
+      </div>
       <div className="header">
         <div className="title">
           <h1 className="brand">
@@ -679,6 +1383,20 @@ export default function App() {
         <div className="actions">
           <div className="actionStack">
             <div className="actionRow">
+              <button
+                className="iconBtn"
+                type="button"
+                aria-label="Jetzt aktualisieren"
+                title="Jetzt aktualisieren (ignoriert den Cache)"
+                onClick={() => void refresh(true)}
+              >
+                <svg viewBox="0 0 24 24" width="16" height="16" aria-hidden="true">
+                  <path
+                    fill="currentColor"
+                    d="M17.65 6.35A7.96 7.96 0 0 0 12 4a8 8 0 1 0 7.75 10h-2.08A6 6 0 1 1 12 6c1.66 0 3.14.69 4.22 1.78L13 11h7V4l-2.35 2.35Z"
+                  />
+                </svg>
+              </button>
               <button
                 className="iconBtn"
                 type="button"
@@ -696,11 +1414,85 @@ export default function App() {
             </div>
             <div className="subNote">
               Letztes Update: {lastRefreshAt ? formatClock(lastRefreshAt) : "—"}
+              {scheduleSource
+                ? ` · ${scheduleSource.servedFromCache ? "aus Cache" : "neu geladen"}${
+                    scheduleSource.fetchedAt ? ` (Stand ${formatClock(Date.parse(scheduleSource.fetchedAt))})` : ""
+                  } · ${scheduleSource.provider}`
+                : ""}
             </div>
           </div>
         </div>
       </div>
 
+      {onboarding && !onboarding.completed ? (
+        <div className="warning onboarding" style={{ marginTop: 10 }}>
+          <div>
+            <div className="warningTitle">Willkommen bei helltime</div>
+            <div className="warningBody">Ein paar Dinge, damit Erinnerungen zuverlässig ankommen:</div>
+            {onboarding.steps.includes("notifications") ? (
+              <div className="inline" style={{ justifyContent: "space-between" }}>
+                <div className="hint">
+                  {onboarding.notificationPermission === "denied"
+                    ? "Benachrichtigungen sind im System blockiert."
+                    : "Desktop-Benachrichtigungen erlauben"}
+                </div>
+                <button
+                  className="btn"
+                  type="button"
+                  disabled={onboarding.notificationPermission === "denied"}
+                  onClick={() => {
+                    void requestNotificationPermission()
+                      .catch(() => false)
+                      .then(() => getOnboardingStatus())
+                      .then(setOnboarding);
+                  }}
+                >
+                  Erlauben
+                </button>
+              </div>
+            ) : null}
+            {onboarding.steps.includes("autostart") ? (
+              <div className="inline" style={{ justifyContent: "space-between" }}>
+                <div className="hint">Mit dem System starten</div>
+                <button
+                  className="btn"
+                  type="button"
+                  onClick={() => {
+                    void setAutostart(true)
+                      .catch((e) => console.warn("set_autostart failed", e))
+                      .then(() => getOnboardingStatus())
+                      .then(setOnboarding);
+                  }}
+                >
+                  Aktivieren
+                </button>
+              </div>
+            ) : null}
+            {onboarding.steps.includes("overlay_position") ? (
+              <div className="inline" style={{ justifyContent: "space-between" }}>
+                <div className="hint">Overlay an die richtige Stelle ziehen</div>
+                <button
+                  className="btn"
+                  type="button"
+                  onClick={() =>
+                    void enterOverlayConfig().catch((e) =>
+                      setOverlayDebug(`Overlay positionieren fehlgeschlagen: ${errorText(e)}`)
+                    )
+                  }
+                >
+                  Positionieren
+                </button>
+              </div>
+            ) : null}
+          </div>
+          <div className="actions">
+            <button className="btn" type="button" onClick={() => void finishOnboarding()}>
+              Fertig
+            </button>
+          </div>
+        </div>
+      ) : null}
+
       {panicStopEnabled ? (
         <div className="warning" style={{ marginTop: 10 }}>
           <div>
@@ -722,6 +1514,122 @@ export default function App() {
         </div>
       ) : null}
 
+      {previousCrash ? (
+        <div className="warning" style={{ marginTop: 10 }}>
+          <div>
+            <div className="warningTitle">Letzte Sitzung ist abgestürzt</div>
+            <div className="warningBody">{previousCrash.summary}</div>
+          </div>
+          <div className="actions">
+            <button
+              className="btn"
+              type="button"
+              onClick={() => void openCrashReport(previousCrash.path).catch((e) => setError(errorText(e)))}
+            >
+              Bericht öffnen
+            </button>
+            <button className="btn" type="button" onClick={() => setPreviousCrash(null)}>
+              OK
+            </button>
+          </div>
+        </div>
+      ) : null}
+
+      {prefs && !prefs.remindersEnabled ? (
+        <div className="warning" style={{ marginTop: 10 }}>
+          <div>
+            <div className="warningTitle">Erinnerungen aus</div>
+            <div className="warningBody">Über das Tray-Menü abgeschaltet</div>
+          </div>
+          <div className="actions">
+            <button className="btn" type="button" onClick={() => updatePrefs({ remindersEnabled: true })}>
+              Einschalten
+            </button>
+          </div>
+        </div>
+      ) : null}
+
+      {escalating ? (
+        <div className="warning" style={{ marginTop: 10 }} role="alert">
+          <div>
+            <div className="warningTitle">{escalating.title}</div>
+            <div className="warningBody">beginnt in {formatCountdown(Math.max(0, escalating.startMs - now))}</div>
+          </div>
+          <div className="actions">
+            <button
+              className="btn"
+              type="button"
+              onClick={() => void acknowledgeReminder(escalating.kind, escalating.id).catch((e) => setError(errorText(e)))}
+            >
+              Bestätigen
+            </button>
+            <button
+              className="btn"
+              type="button"
+              onClick={() => void markAttending(escalating.kind, escalating.id).catch((e) => setError(errorText(e)))}
+            >
+              Bin dabei
+            </button>
+          </div>
+        </div>
+      ) : null}
+
+      {pausedUntilMs !== null && now < pausedUntilMs ? (
+        <div className="warning" style={{ marginTop: 10 }}>
+          <div>
+            <div className="warningTitle">Erinnerungen pausiert</div>
+            <div className="warningBody">bis {new Date(pausedUntilMs).toLocaleTimeString([], { hour: "2-digit", minute: "2-digit" })}</div>
+          </div>
+          <div className="actions">
+            <button className="btn" type="button" onClick={() => void resumeReminders().catch((e) => setError(errorText(e)))}>
+              Fortsetzen
+            </button>
+          </div>
+        </div>
+      ) : null}
+
+      {update ? (
+        <div className="warning" style={{ marginTop: 10 }}>
+          <div>
+            <div className="warningTitle">Update verfügbar: v{update.latest}</div>
+            <div className="warningBody">Installiert ist v{update.current}.</div>
+          </div>
+          <div className="actions">
+            <button
+              className="btn"
+              type="button"
+              onClick={() => void openUpdatePage().catch((e) => setError(errorText(e)))}
+            >
+              Herunterladen
+            </button>
+            <button className="btn" type="button" onClick={() => setUpdate(null)}>
+              Später
+            </button>
+          </div>
+        </div>
+      ) : null}
+
+      {connection && connection.state !== "online" ? (
+        <div className="warning" style={{ marginTop: 10 }}>
+          <div>
+            <div className="warningTitle">
+              {connection.state === "degraded" ? "Verbindung gestört" : "Offline"}
+            </div>
+            <div className="warningBody">
+              {connection.lastSuccess
+                ? `Zeiten vom ${new Date(connection.lastSuccess).toLocaleTimeString([], { hour: "2-digit", minute: "2-digit" })}`
+                : "Noch keine Daten geladen"}
+              {connection.lastError ? ` · ${connection.lastError}` : ""}
+            </div>
+          </div>
+          <div className="actions">
+            <button className="btn" type="button" onClick={() => void refresh(true)}>
+              Erneut versuchen
+            </button>
+          </div>
+        </div>
+      ) : null}
+
       {error ? (
         <div className="errorBanner" style={{ marginTop: 10 }}>
           Fehler: {error}
@@ -751,7 +1659,7 @@ export default function App() {
 
             <div className="modalBody">
               <div className="form">
-	                <div className="settingsBlock">
+	                <div className="settingsBlock" id="settings-overlay">
 	                  <div className="sectionTitle">Overlay</div>
 	
 	                  <div className="inline">
@@ -759,13 +1667,224 @@ export default function App() {
 	                    <div className="pill small">{settings.overlayWindowEnabled ? "an" : "aus"}</div>
 	                  </div>
 	                  <div className="hint">An/Aus und Position unten rechts im Hauptfenster.</div>
+                  <div className="hint">Das nächste Event lässt sich per Tastenkürzel oder Tray-Menü kurz einblenden (siehe Tastenkürzel).</div>
+                  {overlayStatus?.compat === "wine" ? (
+                    <div className="hint">
+                      Wine{overlayStatus.wineVersion ? ` ${overlayStatus.wineVersion}` : ""} erkannt: Das Overlay läuft im
+                      Kompatibilitätsmodus mit undurchsichtigem Hintergrund; Klicks gehen nur eingeschränkt an das Spiel durch.
+                    </div>
+                  ) : null}
+                  {overlayStatus?.lastError ? (
+                    <div className="hint">Letzter Overlay-Fehler: {overlayStatus.lastError}</div>
+                  ) : null}
 	
-	                  <div className="field">
-	                    <label className="hint">
-	                      Inhalt <span className="pill small">{settings.overlayWindowMode}</span>
-	                    </label>
-                    <div className="toggleRow" style={{ marginBottom: 8 }}>
-                      <label className="toggle">
+                  {prefs && (overlayStatus?.nativeAvailable || prefs.overlayEngine === "native") ? (
+                    <div className="field">
+                      <label className="hint">Darstellung</label>
+                      <div className="toggleRow">
+                        {(
+                          [
+                            ["webview", "Webview"],
+                            ["native", "Nativ"]
+                          ] as const
+                        ).map(([engine, label]) => (
+                          <label className="toggle" key={engine}>
+                            <input
+                              type="radio"
+                              name="overlayEngine"
+                              checked={prefs.overlayEngine === engine}
+                              onChange={() => updatePrefs({ overlayEngine: engine })}
+                            />
+                            <span className="toggleLabel">{label}</span>
+                          </label>
+                        ))}
+                      </div>
+                      {prefs.overlayEngine === "native" ? (
+                        <div className="hint">Natives Overlay ist in dieser Version noch nicht verfügbar – es wird das Webview-Overlay genutzt.</div>
+                      ) : null}
+                    </div>
+                  ) : null}
+
+                  {prefs ? (
+                    <div className="field">
+                      <div className="inline">
+                        <label className="hint">Bei Bildschirmaufnahmen ausblenden</label>
+                        <label className="toggle">
+                          <input
+                            type="checkbox"
+                            checked={prefs.overlayCaptureHidden}
+                            onChange={(e) => updatePrefs({ overlayCaptureHidden: e.target.checked })}
+                          />
+                          <span className="toggleLabel">{prefs.overlayCaptureHidden ? "an" : "aus"}</span>
+                        </label>
+                      </div>
+                      <div className="hint">
+                        Du siehst das Overlay weiter, in OBS, Discord-Streams und Screenshots fehlt es (ab Windows 10 2004).
+                      </div>
+                    </div>
+                  ) : null}
+
+                  {prefs ? (
+                    <div className="field">
+                      <div className="inline">
+                        <label className="hint">Unter dem Mauszeiger ausblenden</label>
+                        <label className="toggle">
+                          <input
+                            type="checkbox"
+                            checked={prefs.overlayHoverFade}
+                            onChange={(e) => updatePrefs({ overlayHoverFade: e.target.checked })}
+                          />
+                          <span className="toggleLabel">{prefs.overlayHoverFade ? "an" : "aus"}</span>
+                        </label>
+                      </div>
+                      <div className="hint">
+                        Bleibt der Mauszeiger kurz auf dem Overlay, wird es durchsichtig, damit nichts darunter verdeckt ist.
+                      </div>
+                    </div>
+                  ) : null}
+
+                  {prefs ? (
+                    <div className="field">
+                      <div className="inline">
+                        <label className="hint">Nur zu bestimmten Zeiten</label>
+                        <label className="toggle">
+                          <input
+                            type="checkbox"
+                            checked={prefs.overlayVisibility.enabled}
+                            onChange={(e) => {
+                              const windows = prefs.overlayVisibility.windows.length
+                                ? prefs.overlayVisibility.windows
+                                : [{ days: WEEKDAYS.slice(0, 5), startMinute: 18 * 60, endMinute: 24 * 60 }];
+                              updatePrefs({ overlayVisibility: { enabled: e.target.checked, windows } });
+                            }}
+                          />
+                          <span className="toggleLabel">{prefs.overlayVisibility.enabled ? "an" : "aus"}</span>
+                        </label>
+                      </div>
+                      {prefs.overlayVisibility.enabled ? (
+                        <>
+                          {prefs.overlayVisibility.windows.map((w, i) => {
+                            const setWindow = (patch: Partial<VisibilityWindow>) =>
+                              updatePrefs({
+                                overlayVisibility: {
+                                  ...prefs.overlayVisibility,
+                                  windows: prefs.overlayVisibility.windows.map((old, j) => (j === i ? { ...old, ...patch } : old))
+                                }
+                              });
+                            return (
+                              <div className="level" key={i}>
+                                <div className="toggleRow">
+                                  {WEEKDAYS.map((day, d) => (
+                                    <label className="toggle" key={day}>
+                                      <input
+                                        type="checkbox"
+                                        checked={w.days.length === 0 || w.days.includes(day)}
+                                        onChange={(e) => {
+                                          const current = w.days.length === 0 ? WEEKDAYS : w.days;
+                                          const days = e.target.checked ? [...current, day] : current.filter((x) => x !== day);
+                                          setWindow({ days: WEEKDAYS.filter((x) => days.includes(x)) });
+                                        }}
+                                      />
+                                      <span className="toggleLabel">{WEEKDAY_LABELS[d]}</span>
+                                    </label>
+                                  ))}
+                                </div>
+                                <div className="inline">
+                                  <input
+                                    className="input"
+                                    type="time"
+                                    value={minutesToTime(w.startMinute)}
+                                    onChange={(e) => {
+                                      const minutes = timeToMinutes(e.target.value);
+                                      if (minutes !== null) setWindow({ startMinute: minutes });
+                                    }}
+                                  />
+                                  <span className="hint">bis</span>
+                                  <input
+                                    className="input"
+                                    type="time"
+                                    value={minutesToTime(w.endMinute)}
+                                    onChange={(e) => {
+                                      const minutes = timeToMinutes(e.target.value);
+                                      if (minutes !== null) setWindow({ endMinute: minutes });
+                                    }}
+                                  />
+                                  <button
+                                    className="btn"
+                                    type="button"
+                                    onClick={() =>
+                                      updatePrefs({
+                                        overlayVisibility: {
+                                          ...prefs.overlayVisibility,
+                                          windows: prefs.overlayVisibility.windows.filter((_, j) => j !== i)
+                                        }
+                                      })
+                                    }
+                                  >
+                                    Entfernen
+                                  </button>
+                                </div>
+                              </div>
+                            );
+                          })}
+                          <button
+                            className="btn"
+                            type="button"
+                            onClick={() =>
+                              updatePrefs({
+                                overlayVisibility: {
+                                  ...prefs.overlayVisibility,
+                                  windows: [...prefs.overlayVisibility.windows, { days: [], startMinute: 18 * 60, endMinute: 0 }]
+                                }
+                              })
+                            }
+                          >
+                            Zeitfenster hinzufügen
+                          </button>
+                          <div className="hint">
+                            Außerhalb der Zeitfenster bleibt das Overlay ausgeblendet. Ein Ende vor dem Start reicht über Mitternacht.
+                          </div>
+                        </>
+                      ) : null}
+                    </div>
+                  ) : null}
+
+                  {prefs && toastDraft ? (
+                    <div className="field">
+                      <label className="hint">Toast-Text</label>
+                      <input
+                        className="input"
+                        value={toastDraft.title}
+                        placeholder="{title}"
+                        onChange={(e) => setToastDraft({ ...toastDraft, title: e.target.value })}
+                        onBlur={() => updatePrefs({ toastTemplates: toastDraft })}
+                      />
+                      <input
+                        className="input"
+                        value={toastDraft.body}
+                        placeholder="{countdown}"
+                        onChange={(e) => setToastDraft({ ...toastDraft, body: e.target.value })}
+                        onBlur={() => updatePrefs({ toastTemplates: toastDraft })}
+                      />
+                      {toastPreview.error ? (
+                        <div className="hint">Ungültig: {toastPreview.error}</div>
+                      ) : toastPreview.toast ? (
+                        <div className="hint">
+                          Vorschau: <b>{toastPreview.toast.title}</b> · {toastPreview.toast.body}
+                        </div>
+                      ) : null}
+                      <div className="hint">
+                        Platzhalter: {TOAST_PLACEHOLDERS.map((p) => `{${p}}`).join(" ")} – {"{{"} und {"}}"} für Klammern.
+                      </div>
+                    </div>
+                  ) : null}
+
+	                  <div className="field">
+	                    <label className="hint">
+	                      Inhalt <span className="pill small">{settings.overlayWindowMode}</span>
+	                    </label>
+                    <div className="toggleRow" style={{ marginBottom: 8 }}>
+                      <label className="toggle">
                         <input
                           type="radio"
                           name="overlayMode"
@@ -785,7 +1904,22 @@ export default function App() {
                         />
                         <span className="toggleLabel">Toast</span>
                       </label>
+                      <label className="toggle">
+                        <input
+                          type="radio"
+                          name="overlayMode"
+                          disabled={panicStopEnabled || !settings.overlayWindowEnabled}
+                          checked={settings.overlayWindowMode === "minibar"}
+                          onChange={() => updateSettings((s) => ({ ...s, overlayWindowMode: "minibar" }))}
+                        />
+                        <span className="toggleLabel">Mini-Bar</span>
+                      </label>
                     </div>
+                    {settings.overlayWindowMode === "minibar" ? (
+                      <div className="hint" style={{ marginBottom: 8 }}>
+                        Drei kompakte Zeilen mit Countdown, immer sichtbar. Erinnerungen erscheinen weiterhin als Toast darüber.
+                      </div>
+                    ) : null}
                     <div className="toggleRow">
                       <label className="toggle">
                         <input
@@ -899,6 +2033,192 @@ export default function App() {
 	                    />
 	                  </div>
 	
+                  <div className="field">
+                    <label className="hint">Größe</label>
+                    <div className="toggleRow">
+                      {(
+                        [
+                          ["small", "Klein"],
+                          ["medium", "Mittel"],
+                          ["large", "Groß"]
+                        ] as const
+                      ).map(([size, label]) => (
+                        <label className="toggle" key={size}>
+                          <input
+                            type="radio"
+                            name="overlaySizePreset"
+                            checked={settings.overlaySizePreset === size}
+                            onChange={() =>
+                              updateSettings((s) => ({ ...s, overlaySizePreset: size, overlayWidth: 0, overlayHeight: 0 }))
+                            }
+                          />
+                          <span className="toggleLabel">{label}</span>
+                        </label>
+                      ))}
+                    </div>
+                    {settings.overlayWidth || settings.overlayHeight ? (
+                      <div className="inline">
+                        <span className="hint">
+                          Eigene Größe {settings.overlayWidth || "auto"} × {settings.overlayHeight || "auto"} px
+                        </span>
+                        <button
+                          className="btn"
+                          type="button"
+                          onClick={() => updateSettings((s) => ({ ...s, overlayWidth: 0, overlayHeight: 0 }))}
+                        >
+                          Zurücksetzen
+                        </button>
+                      </div>
+                    ) : (
+                      <span className="hint">Beim Positionieren lässt sich das Overlay an den Rändern größer ziehen.</span>
+                    )}
+                    <span className="hint">
+                      Position {overlayPosition ? `${overlayPosition.x}, ${overlayPosition.y}` : "—"} · beim Positionieren mit
+                      Pfeiltasten verschieben (Shift = 10px), Enter übernimmt, Esc bricht ab
+                    </span>
+                    <span className="hint">Per Tastenkürzel (Standard Strg+Alt+O) wird das Overlay 5 Sekunden lang anklickbar.</span>
+                  </div>
+
+                  <div className="field">
+                    <label className="hint">Spiel-HUD</label>
+                    <div className="toggleRow">
+                      {(
+                        [
+                          ["default", "Standard"],
+                          ["left_skill_bar", "Fertigkeitsleiste links"]
+                        ] as const
+                      ).map(([layout, label]) => (
+                        <label className="toggle" key={layout}>
+                          <input
+                            type="radio"
+                            name="overlayHudLayout"
+                            checked={settings.overlayHudLayout === layout}
+                            onChange={() => updateSettings((s) => ({ ...s, overlayHudLayout: layout }))}
+                          />
+                          <span className="toggleLabel">{label}</span>
+                        </label>
+                      ))}
+                    </div>
+                    {hudPlacement?.suggestions.length ? (
+                      <div className="inline">
+                        {hudPlacement.suggestions.map((s) => (
+                          <button
+                            className="btn"
+                            type="button"
+                            key={s.zone}
+                            disabled={panicStopEnabled}
+                            onClick={() => void setOverlayAnchor(settings.overlayHudLayout, s.zone)}
+                          >
+                            {HUD_ZONE_LABELS[s.zone] ?? s.zone}
+                          </button>
+                        ))}
+                      </div>
+                    ) : null}
+                    {hudPlacement?.covered.length ? (
+                      <span className="hint">
+                        Das Overlay verdeckt: {hudPlacement.covered.map((id) => HUD_ELEMENT_LABELS[id] ?? id).join(", ")}
+                      </span>
+                    ) : (
+                      <span className="hint">Die Vorschläge lassen Lebens- und Ressourcenkugel, Fertigkeitsleiste und Minikarte frei.</span>
+                    )}
+                  </div>
+
+                  <div className="field">
+                    <div className="inline">
+                      <label className="hint">Lange Namen umbrechen</label>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={settings.overlayWrapText}
+                          onChange={(e) => updateSettings((s) => ({ ...s, overlayWrapText: e.target.checked }))}
+                        />
+                        <span className="toggleLabel">{settings.overlayWrapText ? "an" : "aus"}</span>
+                      </label>
+                    </div>
+                  </div>
+
+                  <div className="field">
+                    <label className="hint">Farben</label>
+                    <div className="toggleRow">
+                      {(
+                        [
+                          ["standard", "Standard"],
+                          ["highContrast", "Hoher Kontrast"]
+                        ] as const
+                      ).map(([theme, label]) => (
+                        <label className="toggle" key={theme}>
+                          <input
+                            type="radio"
+                            name="overlayTheme"
+                            checked={settings.overlayTheme === theme}
+                            onChange={() => updateSettings((s) => ({ ...s, overlayTheme: theme }))}
+                          />
+                          <span className="toggleLabel">{label}</span>
+                        </label>
+                      ))}
+                    </div>
+                    {settings.overlayTheme === "highContrast" ? (
+                      <div className="hint">Schwarzer Hintergrund, weiße und gelbe Schrift; Hintergrundfarbe und Deckkraft werden ignoriert.</div>
+                    ) : null}
+                  </div>
+
+                  <div className="field">
+                    <label className="hint">Textkontur</label>
+                    <div className="toggleRow">
+                      {(
+                        [
+                          ["soft", "Weich"],
+                          ["outline8", "Kontur"],
+                          ["blur", "Schatten"],
+                          ["none", "Keine"]
+                        ] as const
+                      ).map(([shadow, label]) => (
+                        <label className="toggle" key={shadow}>
+                          <input
+                            type="radio"
+                            name="overlayTextShadow"
+                            checked={settings.overlayTextShadow === shadow}
+                            onChange={() => updateSettings((s) => ({ ...s, overlayTextShadow: shadow }))}
+                          />
+                          <span className="toggleLabel">{label}</span>
+                        </label>
+                      ))}
+                    </div>
+                  </div>
+
+                  <div className="field">
+                    <label className="hint">Schrift</label>
+                    <div className="inline">
+                      <select
+                        className="select"
+                        value={settings.overlayFontFamily}
+                        onChange={(e) => updateSettings((s) => ({ ...s, overlayFontFamily: e.target.value }))}
+                      >
+                        <option value="">Standard</option>
+                        {settings.overlayFontFamily && !fonts.includes(settings.overlayFontFamily) ? (
+                          <option value={settings.overlayFontFamily}>{settings.overlayFontFamily}</option>
+                        ) : null}
+                        {fonts.map((font) => (
+                          <option key={font} value={font}>
+                            {font}
+                          </option>
+                        ))}
+                      </select>
+                      <select
+                        className="select"
+                        value={settings.overlayFontWeight}
+                        onChange={(e) => updateSettings((s) => ({ ...s, overlayFontWeight: Number(e.target.value) }))}
+                      >
+                        <option value={0}>Standard</option>
+                        {[300, 400, 500, 600, 700, 800, 900].map((weight) => (
+                          <option key={weight} value={weight}>
+                            {weight}
+                          </option>
+                        ))}
+                      </select>
+                    </div>
+                  </div>
+
 	                  <div className="field">
 	                    <label>
 	                      Hintergrund-Transparenz <span className="pill">{Math.round(settings.overlayBgOpacity * 100)}%</span>
@@ -933,7 +2253,7 @@ export default function App() {
 	                  </div>
 	                </div>
 
-                <div className="settingsBlock">
+                <div className="settingsBlock" id="settings-sound">
                   <div className="sectionTitle">Ton</div>
 
                   <div className="inline">
@@ -972,103 +2292,1460 @@ export default function App() {
                   </div>
                 </div>
 
-                <div className="modalFooter">
-                  <button className="btn small" type="button" onClick={() => setDebugOpen((v) => !v)} aria-expanded={debugOpen}>
-                    Debug
-                  </button>
-                </div>
+                {prefs ? (
+                  <div className="settingsBlock" id="settings-window">
+                    <div className="sectionTitle">Fenster</div>
 
-	                {debugOpen ? (
-	                  <div className="settingsBlock">
-	                    <div className="sectionTitle">Debug</div>
-	                    <div className="actions">
-	                      <button className="btn" type="button" disabled={panicStopEnabled} onClick={() => void refreshOverlayDebug()}>
-	                        Overlay Status
-	                      </button>
-	                      <button
-	                        className="btn"
-	                        type="button"
-	                        disabled={panicStopEnabled}
-	                        onClick={() => {
-	                          clearOverlayDiag();
-	                          setOverlayDebug(null);
-	                        }}
-	                      >
-	                        Logs leeren
-	                      </button>
-	                    </div>
-	                    {overlayDebug ? <pre className="overlayDebugBox">{overlayDebug}</pre> : null}
-	                  </div>
-	                ) : null}
-              </div>
-            </div>
-          </div>
-        </div>
-      ) : null}
+                    <div className="field">
+                      <label className="hint">Schließen-Button</label>
+                      <div className="toggleRow">
+                        <label className="toggle">
+                          <input
+                            type="radio"
+                            name="closeBehavior"
+                            checked={prefs.closeBehavior === "hide_to_tray"}
+                            onChange={() => updatePrefs({ closeBehavior: "hide_to_tray" })}
+                          />
+                          <span className="toggleLabel">In den Tray</span>
+                        </label>
+                        <label className="toggle">
+                          <input
+                            type="radio"
+                            name="closeBehavior"
+                            checked={prefs.closeBehavior === "exit"}
+                            onChange={() => updatePrefs({ closeBehavior: "exit" })}
+                          />
+                          <span className="toggleLabel">Beenden</span>
+                        </label>
+                      </div>
+                    </div>
 
-      <div className="grid">
-        {orderedTypes.map((type) => {
-          const category = settings.categories[type];
-          const next = nextByType ? nextByType[type] : null;
-          const nextStartMs = next ? new Date(next.startTime).getTime() : null;
-          const countdown = nextStartMs ? formatCountdown(nextStartMs - now) : "—";
-          const timeLabel = next ? formatLocalTime(next.startTime) : "—";
-          const name = getEventName(type, next);
-          const titleParts = getEventTitleParts(type, next);
-          const spokenName = getSpokenEventNameWithTemplate(type, next, category.ttsName);
+                    <div className="inline">
+                      <div className="hint">Minimieren in den Tray</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.minimizeToTray}
+                          onChange={(e) => updatePrefs({ minimizeToTray: e.target.checked })}
+                        />
+                        <span className="toggleLabel">{prefs.minimizeToTray ? "an" : "aus"}</span>
+                      </label>
+                    </div>
 
-          const isOpen = category.enabled && openCategory === type;
+                    <div className="inline">
+                      <div className="hint">Minimiert starten</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.startMinimized}
+                          onChange={(e) => updatePrefs({ startMinimized: e.target.checked })}
+                        />
+                        <span className="toggleLabel">{prefs.startMinimized ? "an" : "aus"}</span>
+                      </label>
+                    </div>
 
-          return (
-            <div
-              className={`card span12 categoryCard ${type} categoryDetails ${isOpen ? "open" : ""} ${category.enabled ? "" : "disabled"}`}
-              key={type}
-              aria-disabled={!category.enabled}
-            >
-              <div className="panelHeaderRow categoryHeader">
-                <button
-                  className="panelHeaderBtn categoryExpandBtn"
-                  type="button"
-                  disabled={!category.enabled}
-                  aria-expanded={isOpen}
-                  onClick={() => {
-                    if (!category.enabled) return;
-                    setOpenCategory((prev) => (prev === type ? null : type));
-                  }}
-                >
-                  <span className="panelHeaderTitle categoryTitle" title={name}>
-                    <span className="categoryTitleMain">{titleParts.title}</span>
-                    <span className={`categoryTitleSub ${titleParts.subtitle ? "" : "placeholder"}`}>{titleParts.subtitle ?? "—"}</span>
-                  </span>
-                  <span className="panelHeaderMeta">{countdown}</span>
-                </button>
-                <div className="panelHeaderRight">
-                  <div className="pill">{timeLabel}</div>
-                  <label className="toggle">
-                    <input
-                      type="checkbox"
-                      checked={category.enabled}
-                      onChange={(e) => setCategoryEnabled(type, e.target.checked)}
-                    />
-                    <span className="toggleLabel">Erinnern</span>
-                  </label>
-                </div>
-              </div>
+                    <div className="field">
+                      <label>Tray-Symbol</label>
+                      <select
+                        className="select"
+                        value={prefs.trayIconStyle}
+                        onChange={(e) => updatePrefs({ trayIconStyle: e.target.value as TrayIconStyle })}
+                      >
+                        <option value="auto">Automatisch (nach Taskleiste)</option>
+                        <option value="light">Für helle Taskleiste</option>
+                        <option value="dark">Für dunkle Taskleiste</option>
+                        <option value="monochrome">Einfarbig</option>
+                      </select>
+                    </div>
 
-              <div className="form">
-                {category.enabled && isOpen ? (
-                  <>
-                    <div className="inline" style={{ justifyContent: "space-between" }}>
-                      <div className="hint">Timer Anzahl</div>
+                    <div className="inline">
+                      <div className="hint">Tray-Symbol blinkt kurz vor Events und während Helltides</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.trayPulse}
+                          onChange={(e) => updatePrefs({ trayPulse: e.target.checked })}
+                        />
+                        <span className="toggleLabel">{prefs.trayPulse ? "an" : "aus"}</span>
+                      </label>
+                    </div>
+
+                    <div className="field">
+                      <label>Bewegung reduzieren</label>
                       <select
                         className="select"
-                        value={category.timerCount}
-                        onChange={(e) => setTimerCount(type, clampInt(Number(e.target.value), 1, 3) as 1 | 2 | 3)}
+                        value={prefs.reducedMotion}
+                        onChange={(e) => updatePrefs({ reducedMotion: e.target.value as ReducedMotion })}
                       >
-                        <option value={1}>1</option>
-                        <option value={2}>2</option>
-                        <option value={3}>3</option>
+                        <option value="auto">Automatisch (Windows-Animationen)</option>
+                        <option value="on">An – keine Animationen, kein Blinken</option>
+                        <option value="off">Aus</option>
                       </select>
+                      <div className="hint">Gilt für Overlay, Hauptfenster und das blinkende Tray-Symbol.</div>
+                    </div>
+
+                    <div className="inline">
+                      <div className="hint">Im Tray Speicher freigeben</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.unloadAfterMinutes !== null}
+                          onChange={(e) => updatePrefs({ unloadAfterMinutes: e.target.checked ? 15 : null })}
+                        />
+                        <span className="toggleLabel">{prefs.unloadAfterMinutes !== null ? "an" : "aus"}</span>
+                      </label>
+                    </div>
+                    {prefs.unloadAfterMinutes !== null ? (
+                      <div className="field">
+                        <label>Fenster entladen nach (Minuten im Tray)</label>
+                        <input
+                          type="number"
+                          min={1}
+                          max={1440}
+                          value={prefs.unloadAfterMinutes}
+                          onChange={(e) =>
+                            updatePrefs({ unloadAfterMinutes: clampInt(Number(e.target.value) || 15, 1, 1440) })
+                          }
+                        />
+                        <div className="hint">
+                          Das Fenster wird danach ganz geschlossen und beim Öffnen neu geladen. Solange erinnert
+                          helltime mit denselben Vorlaufzeiten als Desktop-Benachrichtigung.
+                        </div>
+                      </div>
+                    ) : null}
+
+                    <div className="inline">
+                      <div className="hint">Ohne Fenster starten (nur Tray)</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.headless}
+                          onChange={(e) => updatePrefs({ headless: e.target.checked })}
+                        />
+                        <span className="toggleLabel">{prefs.headless ? "an" : "aus"}</span>
+                      </label>
+                    </div>
+                    {prefs.headless ? (
+                      <div className="hint">
+                        Ab dem nächsten Start: Erinnerungen kommen mit deinen Vorlaufzeiten als Desktop-Benachrichtigung,
+                        ohne Overlay. „Anzeigen“ im Tray öffnet das Fenster trotzdem.
+                      </div>
+                    ) : null}
+                  </div>
+                ) : null}
+
+                {prefs ? (
+                  <div className="settingsBlock" id="settings-polling">
+                    <div className="sectionTitle">Aktualisierung</div>
+
+                    <div className="field">
+                      <label className="hint">Modus</label>
+                      <div className="toggleRow">
+                        <label className="toggle">
+                          <input
+                            type="radio"
+                            name="pollMode"
+                            checked={prefs.poll.mode === "adaptive"}
+                            onChange={() => updatePrefs({ poll: { ...prefs.poll, mode: "adaptive" } })}
+                          />
+                          <span className="toggleLabel">Adaptiv</span>
+                        </label>
+                        <label className="toggle">
+                          <input
+                            type="radio"
+                            name="pollMode"
+                            checked={prefs.poll.mode === "fixed"}
+                            onChange={() => updatePrefs({ poll: { ...prefs.poll, mode: "fixed" } })}
+                          />
+                          <span className="toggleLabel">Fest</span>
+                        </label>
+                      </div>
+                    </div>
+                    <div className="hint">
+                      Adaptiv: kurz vor einem Event jede Minute, sonst seltener als das Intervall.
+                    </div>
+
+                    <div className="field">
+                      <label>Intervall (Minuten)</label>
+                      <input
+                        type="number"
+                        min={1}
+                        max={60}
+                        key={prefs.poll.intervalMinutes}
+                        defaultValue={prefs.poll.intervalMinutes}
+                        onBlur={(e) =>
+                          updatePrefs({ poll: { ...prefs.poll, intervalMinutes: clampInt(Number(e.target.value), 1, 60) } })
+                        }
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                    </div>
+
+                    <div className="field">
+                      <label>Max. Anfragen pro Minute</label>
+                      <input
+                        type="number"
+                        min={1}
+                        max={60}
+                        key={prefs.poll.maxRequestsPerMinute}
+                        defaultValue={prefs.poll.maxRequestsPerMinute}
+                        onBlur={(e) =>
+                          updatePrefs({
+                            poll: { ...prefs.poll, maxRequestsPerMinute: clampInt(Number(e.target.value), 1, 60) }
+                          })
+                        }
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                    </div>
+                    <div className="hint">Schont die helltides.com-API; Wiederholungsversuche zählen mit.</div>
+
+                    <div className="field">
+                      <label>Zwischenspeicher (Sekunden)</label>
+                      <input
+                        type="number"
+                        min={5}
+                        max={600}
+                        key={prefs.poll.cacheTtlSeconds}
+                        defaultValue={prefs.poll.cacheTtlSeconds}
+                        onBlur={(e) =>
+                          updatePrefs({ poll: { ...prefs.poll, cacheTtlSeconds: clampInt(Number(e.target.value), 5, 600) } })
+                        }
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                    </div>
+                    <div className="hint">So lange wird ein abgerufener Zeitplan wiederverwendet, ohne die API erneut zu fragen.</div>
+
+                    <div className="inline">
+                      <div className="hint">Saison-Rhythmus online aktualisieren</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.cadenceUpdates}
+                          onChange={(e) => updatePrefs({ cadenceUpdates: e.target.checked })}
+                        />
+                        <span className="toggleLabel">{prefs.cadenceUpdates ? "an" : "aus"}</span>
+                      </label>
+                    </div>
+                    <div className="hint">
+                      Fragt alle 12 Stunden GitHub nach einem neuen Höllenhochwasser-Takt; aus behält den zuletzt bekannten.
+                    </div>
+                  </div>
+                ) : null}
+
+                {prefs ? (
+                  <div className="settingsBlock" id="settings-language">
+                    <div className="sectionTitle">Sprache</div>
+                    <div className="hint">Für Tray-Menü, Tooltip und Overlay-Fenstertitel.</div>
+                    <div className="toggleRow">
+                      {(
+                        [
+                          [null, "System"],
+                          ["de", "Deutsch"],
+                          ["en", "English"]
+                        ] as const
+                      ).map(([locale, label]) => (
+                        <label className="toggle" key={label}>
+                          <input
+                            type="radio"
+                            name="locale"
+                            checked={prefs.locale === locale}
+                            onChange={() => {
+                              void setLocale(locale)
+                                .then(() => setPrefs({ ...prefs, locale }))
+                                .catch((e) => setError(errorText(e)));
+                            }}
+                          />
+                          <span className="toggleLabel">{label}</span>
+                        </label>
+                      ))}
+                    </div>
+                  </div>
+                ) : null}
+
+                {prefs ? (
+                  <div className="settingsBlock" id="settings-timezone">
+                    <div className="sectionTitle">Zweite Zeitzone</div>
+                    <div className="inline">
+                      <div className="hint">Zeiten zusätzlich in einer gemeinsamen Zeitzone zeigen</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.secondaryZone !== null}
+                          onChange={(e) => updatePrefs({ secondaryZone: e.target.checked ? { label: "UTC", offsetMinutes: 0 } : null })}
+                        />
+                        <span className="toggleLabel">{prefs.secondaryZone ? "an" : "aus"}</span>
+                      </label>
+                    </div>
+                    {prefs.secondaryZone ? (
+                      <>
+                        <div className="field">
+                          <label>Bezeichnung</label>
+                          <input
+                            type="text"
+                            maxLength={12}
+                            key={`zone-${prefs.secondaryZone.label}`}
+                            defaultValue={prefs.secondaryZone.label}
+                            onBlur={(e) => {
+                              const label = e.target.value.trim();
+                              if (label && prefs.secondaryZone) updatePrefs({ secondaryZone: { ...prefs.secondaryZone, label } });
+                            }}
+                            onKeyDown={(e) => {
+                              if (e.key === "Enter") e.currentTarget.blur();
+                            }}
+                          />
+                        </div>
+                        <div className="field">
+                          <label>Abstand zu UTC</label>
+                          <select
+                            value={prefs.secondaryZone.offsetMinutes}
+                            onChange={(e) =>
+                              prefs.secondaryZone &&
+                              updatePrefs({ secondaryZone: { ...prefs.secondaryZone, offsetMinutes: Number(e.target.value) } })
+                            }
+                          >
+                            {Array.from({ length: (14 * 60 + 12 * 60) / 15 + 1 }, (_, i) => -12 * 60 + i * 15)
+                              .filter((minutes) => minutes % 30 === 0 || minutes === prefs.secondaryZone?.offsetMinutes || [345, 525, 765].includes(minutes))
+                              .map((minutes) => (
+                                <option key={minutes} value={minutes}>
+                                  {formatUtcOffset(minutes)}
+                                </option>
+                              ))}
+                          </select>
+                        </div>
+                        <div className="hint">
+                          Fester Abstand ohne Sommerzeit: bei der Umstellung der gemeinsamen Zeitzone hier anpassen. Gilt
+                          auch für den Kalender-Export.
+                        </div>
+                      </>
+                    ) : null}
+                  </div>
+                ) : null}
+
+                {prefs ? (
+                  <div className="settingsBlock" id="settings-network">
+                    <div className="sectionTitle">Netzwerk</div>
+
+                    <div className="field">
+                      <label className="hint">Proxy</label>
+                      <div className="toggleRow">
+                        {(
+                          [
+                            ["system", "System"],
+                            ["direct", "Keiner"],
+                            ["manual", "Manuell"]
+                          ] as const
+                        ).map(([mode, label]) => (
+                          <label className="toggle" key={mode}>
+                            <input
+                              type="radio"
+                              name="proxyMode"
+                              checked={prefs.network.proxyMode === mode}
+                              onChange={() => updateNetwork({ proxyMode: mode })}
+                            />
+                            <span className="toggleLabel">{label}</span>
+                          </label>
+                        ))}
+                      </div>
+                    </div>
+
+                    <div className="field">
+                      <label>Proxy-URL</label>
+                      <input
+                        type="text"
+                        placeholder="http://proxy:3128"
+                        disabled={prefs.network.proxyMode !== "manual"}
+                        key={`proxy-${prefs.network.proxyUrl ?? ""}`}
+                        defaultValue={prefs.network.proxyUrl ?? ""}
+                        onBlur={(e) => updateNetwork({ proxyUrl: e.target.value.trim() || undefined })}
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                    </div>
+
+                    <div className="field">
+                      <label>Eigenes CA-Zertifikat (PEM-Pfad)</label>
+                      <input
+                        type="text"
+                        placeholder={"C:\\Pfad\\zu\\firma-ca.pem"}
+                        key={`ca-${prefs.network.caBundle ?? ""}`}
+                        defaultValue={prefs.network.caBundle ?? ""}
+                        onBlur={(e) => updateNetwork({ caBundle: e.target.value.trim() || undefined })}
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                    </div>
+
+                    <div className="inline">
+                      <div className="hint">Zertifikate nicht prüfen (unsicher)</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.network.insecureTls}
+                          onChange={(e) => updateNetwork({ insecureTls: e.target.checked })}
+                        />
+                        <span className="toggleLabel">{prefs.network.insecureTls ? "an" : "aus"}</span>
+                      </label>
+                    </div>
+
+                    <div className="field">
+                      <label>User-Agent</label>
+                      <input
+                        type="text"
+                        placeholder="Standard"
+                        key={`ua-${prefs.network.userAgent ?? ""}`}
+                        defaultValue={prefs.network.userAgent ?? ""}
+                        onBlur={(e) => updateNetwork({ userAgent: e.target.value.trim() || undefined })}
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                    </div>
+
+                    <div className="field">
+                      <label>API-Token</label>
+                      <input
+                        type="password"
+                        autoComplete="off"
+                        placeholder="keins"
+                        key={`token-${prefs.network.apiToken ?? ""}`}
+                        defaultValue={prefs.network.apiToken ?? ""}
+                        onBlur={(e) => updateNetwork({ apiToken: e.target.value.trim() || undefined })}
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                    </div>
+
+                    <div className="field">
+                      <label>Zusätzliche Header (eine Zeile je „Name: Wert“)</label>
+                      <textarea
+                        rows={3}
+                        spellCheck={false}
+                        placeholder="X-Api-Key: …"
+                        key={`headers-${formatHeaderLines(prefs.network.extraHeaders)}`}
+                        defaultValue={formatHeaderLines(prefs.network.extraHeaders)}
+                        onBlur={(e) => updateNetwork({ extraHeaders: parseHeaderLines(e.target.value) })}
+                      />
+                      <div className="hint">Token und Header-Werte erscheinen nicht in Logs oder der Diagnose.</div>
+                    </div>
+                  </div>
+                ) : null}
+
+                {prefs ? (
+                  <div className="settingsBlock" id="settings-profiles">
+                    <div className="sectionTitle">Profile</div>
+                    <div className="hint">
+                      Speichert Vorlaufzeiten, sichtbare Events und Overlay-Stil unter einem Namen; umschalten geht auch
+                      über das Tray-Menü.
+                    </div>
+                    {profiles.profiles.map((profile) => (
+                      <div className="inline" key={profile.name} style={{ justifyContent: "space-between" }}>
+                        <div className="hint">
+                          {profile.name}
+                          {profiles.active === profile.name ? " · aktiv" : ""}
+                        </div>
+                        <div className="inline">
+                          <button
+                            className="btn"
+                            type="button"
+                            disabled={profiles.active === profile.name}
+                            onClick={() =>
+                              void activateProfile(profile.name).catch((e) => setProfileError(errorText(e)))
+                            }
+                          >
+                            Aktivieren
+                          </button>
+                          <button
+                            className="btn"
+                            type="button"
+                            onClick={() => void deleteProfile(profile.name).then(setProfiles)}
+                          >
+                            Löschen
+                          </button>
+                        </div>
+                      </div>
+                    ))}
+                    <div className="inline">
+                      <input
+                        type="text"
+                        placeholder="z. B. Hardcore-Abende"
+                        maxLength={40}
+                        value={profileName}
+                        onChange={(e) => setProfileName(e.target.value)}
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") storeProfile();
+                        }}
+                      />
+                      <button className="btn" type="button" disabled={!profileName.trim()} onClick={storeProfile}>
+                        Aktuelles speichern
+                      </button>
+                    </div>
+                    {profileError ? <div className="hint">{profileError}</div> : null}
+                  </div>
+                ) : null}
+
+                {prefs ? (
+                  <div className="settingsBlock" id="settings-sync">
+                    <div className="sectionTitle">Sync</div>
+                    <div className="hint">
+                      Ordner, den deine PCs teilen (z. B. Dropbox oder OneDrive). Einstellungen und Erinnerungen werden dort
+                      abgelegt; die zuletzt gespeicherte Fassung gilt. Fensterposition und Netzwerk bleiben pro PC.
+                    </div>
+                    <div className="field">
+                      <label>Sync-Ordner</label>
+                      <input
+                        type="text"
+                        placeholder={"C:\\Users\\du\\Dropbox\\helltime"}
+                        key={`sync-${prefs.syncDir ?? ""}`}
+                        defaultValue={prefs.syncDir ?? ""}
+                        onBlur={(e) => changeSyncDir(e.target.value)}
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                    </div>
+                    {syncNote ? <div className="hint">{syncNote}</div> : null}
+                  </div>
+                ) : null}
+
+                {prefs ? (
+                  <div className="settingsBlock" id="settings-events">
+                    <div className="sectionTitle">Events</div>
+                    <div className="hint">
+                      Ausgeblendete Events erscheinen nirgends: keine Erinnerungen, nicht im Tray, Overlay oder in den
+                      Integrationen.
+                    </div>
+
+                    {types.map((type) => (
+                      <div className="inline" key={type}>
+                        <div className="hint">{typeLabel(type)}</div>
+                        <label className="toggle">
+                          <input
+                            type="checkbox"
+                            checked={!hiddenTypes.includes(type)}
+                            onChange={(e) => setEventHidden(type, !e.target.checked)}
+                          />
+                          <span className="toggleLabel">{hiddenTypes.includes(type) ? "ausgeblendet" : "sichtbar"}</span>
+                        </label>
+                      </div>
+                    ))}
+                  </div>
+                ) : null}
+
+                <div className="settingsBlock" id="settings-notifications">
+                  <div className="sectionTitle">Desktop-Benachrichtigungen</div>
+                  <div className="inline">
+                    <div className="hint">Erinnerungen zusätzlich als Systembenachrichtigung</div>
+                    <label className="toggle">
+                      <input
+                        type="checkbox"
+                        checked={settings.desktopNotificationEnabled}
+                        onChange={(e) => updateSettings((s) => ({ ...s, desktopNotificationEnabled: e.target.checked }))}
+                      />
+                      <span className="toggleLabel">{settings.desktopNotificationEnabled ? "an" : "aus"}</span>
+                    </label>
+                  </div>
+                  <div className="hint">Ein Klick auf die Benachrichtigung öffnet helltime beim jeweiligen Event.</div>
+                  <div className="inline">
+                    <div className="hint">Erinnerungen für Screenreader ansagen</div>
+                    <label className="toggle">
+                      <input
+                        type="checkbox"
+                        checked={settings.screenReaderAnnouncements}
+                        onChange={(e) => updateSettings((s) => ({ ...s, screenReaderAnnouncements: e.target.checked }))}
+                      />
+                      <span className="toggleLabel">{settings.screenReaderAnnouncements ? "an" : "aus"}</span>
+                    </label>
+                  </div>
+                  <div className="hint">
+                    Sendet jede Erinnerung auch als Systembenachrichtigung (Sprachausgabe liest sie vor, auch wenn helltime im
+                    Hintergrund ist) und sagt sie im Hauptfenster an.
+                  </div>
+                </div>
+
+                {prefs ? (
+                  <div className="settingsBlock" id="settings-escalation">
+                    <div className="sectionTitle">Eskalation</div>
+                    <div className="inline">
+                      <div className="hint">Erinnerung wird lauter, je näher das Event rückt</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.reminderEscalation.enabled}
+                          onChange={(e) =>
+                            updatePrefs({ reminderEscalation: { ...prefs.reminderEscalation, enabled: e.target.checked } })
+                          }
+                        />
+                        <span className="toggleLabel">{prefs.reminderEscalation.enabled ? "an" : "aus"}</span>
+                      </label>
+                    </div>
+                    <div className="hint">
+                      Ersetzt die Timer der Kategorien. Ein wiederholter Ton läuft, bis du ihn bestätigst (oder die
+                      Benachrichtigung anklickst).
+                    </div>
+                    {prefs.reminderEscalation.rules.map((rule, index) => {
+                      const setRules = (rules: EscalationRule[]) =>
+                        updatePrefs({
+                          reminderEscalation: {
+                            ...prefs.reminderEscalation,
+                            rules: [...rules].sort((a, b) => b.minutesBefore - a.minutesBefore)
+                          }
+                        });
+                      const replace = (next: EscalationRule) =>
+                        setRules(prefs.reminderEscalation.rules.map((r, i) => (i === index ? next : r)));
+                      const toggleAction = (action: EscalationAction, on: boolean) =>
+                        replace({
+                          ...rule,
+                          actions: on ? [...rule.actions, action] : rule.actions.filter((a) => a !== action)
+                        });
+                      return (
+                        <div className="inline" key={`${index}-${rule.minutesBefore}`}>
+                          <input
+                            className="input"
+                            type="number"
+                            min={0}
+                            max={60}
+                            defaultValue={rule.minutesBefore}
+                            aria-label={`Eskalationsstufe ${index + 1}: Minuten vorher`}
+                            onBlur={(e) => replace({ ...rule, minutesBefore: clampInt(Number(e.target.value), 0, 60) })}
+                            onKeyDown={(e) => {
+                              if (e.key === "Enter") e.currentTarget.blur();
+                            }}
+                          />
+                          <span className="hint">Min. vorher</span>
+                          {(Object.keys(ESCALATION_ACTION_LABELS) as EscalationAction[]).map((action) => (
+                            <label className="toggle" key={action}>
+                              <input
+                                type="checkbox"
+                                checked={rule.actions.includes(action)}
+                                disabled={rule.actions.length === 1 && rule.actions.includes(action)}
+                                onChange={(e) => toggleAction(action, e.target.checked)}
+                              />
+                              <span className="toggleLabel">{ESCALATION_ACTION_LABELS[action]}</span>
+                            </label>
+                          ))}
+                          <button
+                            className="btn small"
+                            type="button"
+                            disabled={prefs.reminderEscalation.rules.length === 1}
+                            onClick={() => setRules(prefs.reminderEscalation.rules.filter((_, i) => i !== index))}
+                          >
+                            Entfernen
+                          </button>
+                        </div>
+                      );
+                    })}
+                    <div className="actions">
+                      <button
+                        className="btn"
+                        type="button"
+                        disabled={
+                          prefs.reminderEscalation.rules.length >= MAX_ESCALATION_RULES ||
+                          prefs.reminderEscalation.rules[prefs.reminderEscalation.rules.length - 1]?.minutesBefore === 0
+                        }
+                        onClick={() => {
+                          const rules = prefs.reminderEscalation.rules;
+                          const last = rules[rules.length - 1]?.minutesBefore ?? 2;
+                          updatePrefs({
+                            reminderEscalation: {
+                              ...prefs.reminderEscalation,
+                              rules: [...rules, { minutesBefore: Math.max(0, last - 1), actions: ["sound"] }]
+                            }
+                          });
+                        }}
+                      >
+                        Stufe hinzufügen
+                      </button>
+                    </div>
+                  </div>
+                ) : null}
+
+                {prefs ? (
+                  <div className="settingsBlock" id="settings-push">
+                    <div className="sectionTitle">Aufs Handy</div>
+                    <div className="hint">
+                      Erinnerungen zusätzlich per ntfy oder Pushover aufs Telefon schicken. Ohne Verbindung wird bis zu 10
+                      Minuten lang erneut versucht.
+                    </div>
+
+                    <div className="inline">
+                      <div className="hint">ntfy</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.push.ntfy.enabled}
+                          onChange={(e) => updatePrefs({ push: { ...prefs.push, ntfy: { ...prefs.push.ntfy, enabled: e.target.checked } } })}
+                        />
+                        <span className="toggleLabel">{prefs.push.ntfy.enabled ? "an" : "aus"}</span>
+                      </label>
+                    </div>
+                    {(
+                      [
+                        ["topic", "Topic", "helltime-…", "text"],
+                        ["server", "Server (leer = ntfy.sh)", "https://ntfy.sh", "text"],
+                        ["token", "Zugangstoken (optional)", "tk_…", "password"]
+                      ] as const
+                    ).map(([field, label, placeholder, type]) => (
+                      <div className="field" key={`ntfy-${field}`}>
+                        <label>{label}</label>
+                        <input
+                          type={type}
+                          placeholder={placeholder}
+                          key={`ntfy-${field}-${prefs.push.ntfy[field] ?? ""}`}
+                          defaultValue={prefs.push.ntfy[field] ?? ""}
+                          onBlur={(e) => {
+                            const value = e.target.value.trim();
+                            updatePrefs({
+                              push: { ...prefs.push, ntfy: { ...prefs.push.ntfy, [field]: field === "topic" ? value : value || null } }
+                            });
+                          }}
+                          onKeyDown={(e) => {
+                            if (e.key === "Enter") e.currentTarget.blur();
+                          }}
+                        />
+                      </div>
+                    ))}
+
+                    <div className="inline">
+                      <div className="hint">Pushover</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.push.pushover.enabled}
+                          onChange={(e) =>
+                            updatePrefs({ push: { ...prefs.push, pushover: { ...prefs.push.pushover, enabled: e.target.checked } } })
+                          }
+                        />
+                        <span className="toggleLabel">{prefs.push.pushover.enabled ? "an" : "aus"}</span>
+                      </label>
+                    </div>
+                    {(
+                      [
+                        ["userKey", "User-Key"],
+                        ["appToken", "API-Token der Anwendung"]
+                      ] as const
+                    ).map(([field, label]) => (
+                      <div className="field" key={`pushover-${field}`}>
+                        <label>{label}</label>
+                        <input
+                          type="password"
+                          key={`pushover-${field}-${prefs.push.pushover[field]}`}
+                          defaultValue={prefs.push.pushover[field]}
+                          onBlur={(e) =>
+                            updatePrefs({ push: { ...prefs.push, pushover: { ...prefs.push.pushover, [field]: e.target.value.trim() } } })
+                          }
+                          onKeyDown={(e) => {
+                            if (e.key === "Enter") e.currentTarget.blur();
+                          }}
+                        />
+                      </div>
+                    ))}
+
+                    <div className="actions">
+                      <button
+                        className="btn"
+                        type="button"
+                        disabled={!isTauri() || (!prefs.push.ntfy.enabled && !prefs.push.pushover.enabled)}
+                        onClick={() => {
+                          setPushTestResult(null);
+                          void sendTestPush()
+                            .then(() => setPushTestResult("Testnachricht gesendet."))
+                            .catch((e) => setPushTestResult(`Fehler: ${errorText(e)}`));
+                        }}
+                      >
+                        Testnachricht
+                      </button>
+                    </div>
+                    {pushTestResult ? <div className="hint">{pushTestResult}</div> : null}
+                  </div>
+                ) : null}
+
+                {prefs ? (
+                  <div className="settingsBlock" id="settings-webhook">
+                    <div className="sectionTitle">Webhook</div>
+                    <div className="inline">
+                      <div className="hint">JSON an eigene URL senden (z. B. Home Assistant)</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.webhook.enabled}
+                          onChange={(e) => updatePrefs({ webhook: { ...prefs.webhook, enabled: e.target.checked } })}
+                        />
+                        <span className="toggleLabel">{prefs.webhook.enabled ? "an" : "aus"}</span>
+                      </label>
+                    </div>
+                    <div className="field">
+                      <label>URL</label>
+                      <input
+                        type="text"
+                        placeholder="https://…"
+                        key={`webhook-url-${prefs.webhook.url}`}
+                        defaultValue={prefs.webhook.url}
+                        onBlur={(e) => updatePrefs({ webhook: { ...prefs.webhook, url: e.target.value.trim() } })}
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                    </div>
+                    <div className="toggleRow">
+                      {(Object.keys(WEBHOOK_TRIGGER_LABELS) as WebhookTrigger[]).map((trigger) => (
+                        <label className="toggle" key={trigger}>
+                          <input
+                            type="checkbox"
+                            checked={prefs.webhook.triggers.includes(trigger)}
+                            onChange={(e) => {
+                              const rest = prefs.webhook.triggers.filter((t) => t !== trigger);
+                              updatePrefs({ webhook: { ...prefs.webhook, triggers: e.target.checked ? [...rest, trigger] : rest } });
+                            }}
+                          />
+                          <span className="toggleLabel">{WEBHOOK_TRIGGER_LABELS[trigger]}</span>
+                        </label>
+                      ))}
+                    </div>
+                    <div className="field">
+                      <label>Vorlage (leer = alle Felder)</label>
+                      <textarea
+                        rows={3}
+                        placeholder={'{"text": "{title} beginnt"}'}
+                        key={`webhook-template-${prefs.webhook.template ?? ""}`}
+                        defaultValue={prefs.webhook.template ?? ""}
+                        onBlur={(e) => updatePrefs({ webhook: { ...prefs.webhook, template: e.target.value.trim() || null } })}
+                      />
+                      <div className="hint">
+                        Platzhalter in Anführungszeichen setzen:{" "}
+                        {(Object.keys(WEBHOOK_PLACEHOLDERS) as WebhookTrigger[])
+                          .map((trigger) => `${WEBHOOK_TRIGGER_LABELS[trigger]}: ${WEBHOOK_PLACEHOLDERS[trigger].map((p) => `{${p}}`).join(" ")}`)
+                          .join(" · ")}
+                      </div>
+                    </div>
+                    <div className="field">
+                      <label>Geheimnis für die Signatur (optional)</label>
+                      <input
+                        type="password"
+                        key={`webhook-secret-${prefs.webhook.secret ?? ""}`}
+                        defaultValue={prefs.webhook.secret ?? ""}
+                        onBlur={(e) => updatePrefs({ webhook: { ...prefs.webhook, secret: e.target.value.trim() || null } })}
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                      <div className="hint">Dann trägt jede Anfrage X-Helltime-Signature: sha256=… (HMAC-SHA256 des Bodys).</div>
+                    </div>
+                    <div className="actions">
+                      <button
+                        className="btn"
+                        type="button"
+                        disabled={!isTauri() || !prefs.webhook.enabled}
+                        onClick={() => {
+                          setWebhookTestResult(null);
+                          void testWebhook()
+                            .then(() => setWebhookTestResult("Test gesendet."))
+                            .catch((e) => setWebhookTestResult(`Fehler: ${errorText(e)}`));
+                        }}
+                      >
+                        Test senden
+                      </button>
+                    </div>
+                    {webhookTestResult ? <div className="hint">{webhookTestResult}</div> : null}
+                  </div>
+                ) : null}
+
+                {prefs ? (
+                  <div className="settingsBlock" id="settings-mqtt">
+                    <div className="sectionTitle">MQTT</div>
+                    <div className="inline">
+                      <div className="hint">Countdowns und aktive Events an einen Broker senden</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.mqtt.enabled}
+                          onChange={(e) => updatePrefs({ mqtt: { ...prefs.mqtt, enabled: e.target.checked } })}
+                        />
+                        <span className="toggleLabel">{prefs.mqtt.enabled ? "an" : "aus"}</span>
+                      </label>
+                    </div>
+                    <div className="field">
+                      <label>Broker</label>
+                      <input
+                        type="text"
+                        placeholder="homeassistant.local"
+                        key={`mqtt-host-${prefs.mqtt.host}`}
+                        defaultValue={prefs.mqtt.host}
+                        onBlur={(e) => updatePrefs({ mqtt: { ...prefs.mqtt, host: e.target.value.trim() } })}
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                      <div className="hint">Unverschlüsseltes TCP ohne TLS – nur im eigenen Netz verwenden.</div>
+                    </div>
+                    <div className="field">
+                      <label>Port</label>
+                      <input
+                        type="number"
+                        min={1}
+                        max={65535}
+                        key={prefs.mqtt.port}
+                        defaultValue={prefs.mqtt.port}
+                        onBlur={(e) => updatePrefs({ mqtt: { ...prefs.mqtt, port: clampInt(Number(e.target.value), 1, 65535) } })}
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                    </div>
+                    <div className="field">
+                      <label>Benutzer (optional)</label>
+                      <input
+                        type="text"
+                        key={`mqtt-username-${prefs.mqtt.username ?? ""}`}
+                        defaultValue={prefs.mqtt.username ?? ""}
+                        onBlur={(e) => updatePrefs({ mqtt: { ...prefs.mqtt, username: e.target.value.trim() || null } })}
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                    </div>
+                    <div className="field">
+                      <label>Passwort (optional)</label>
+                      <input
+                        type="password"
+                        key={`mqtt-password-${prefs.mqtt.password ?? ""}`}
+                        defaultValue={prefs.mqtt.password ?? ""}
+                        onBlur={(e) => updatePrefs({ mqtt: { ...prefs.mqtt, password: e.target.value || null } })}
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                    </div>
+                    <div className="field">
+                      <label>Topic-Präfix</label>
+                      <input
+                        type="text"
+                        key={`mqtt-prefix-${prefs.mqtt.topicPrefix}`}
+                        defaultValue={prefs.mqtt.topicPrefix}
+                        onBlur={(e) => updatePrefs({ mqtt: { ...prefs.mqtt, topicPrefix: e.target.value.trim() } })}
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                      <div className="hint">
+                        {`${prefs.mqtt.topicPrefix}/<event>/next_start, …/seconds_until, …/active (ON/OFF), ${prefs.mqtt.topicPrefix}/status`}
+                      </div>
+                    </div>
+                    <div className="inline">
+                      <div className="hint">Home-Assistant-Discovery</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.mqtt.discovery}
+                          onChange={(e) => updatePrefs({ mqtt: { ...prefs.mqtt, discovery: e.target.checked } })}
+                        />
+                        <span className="toggleLabel">{prefs.mqtt.discovery ? "an" : "aus"}</span>
+                      </label>
+                    </div>
+                    {prefs.mqtt.discovery ? (
+                      <div className="field">
+                        <label>Discovery-Präfix</label>
+                        <input
+                          type="text"
+                          key={`mqtt-discovery-${prefs.mqtt.discoveryPrefix}`}
+                          defaultValue={prefs.mqtt.discoveryPrefix}
+                          onBlur={(e) => updatePrefs({ mqtt: { ...prefs.mqtt, discoveryPrefix: e.target.value.trim() } })}
+                          onKeyDown={(e) => {
+                            if (e.key === "Enter") e.currentTarget.blur();
+                          }}
+                        />
+                      </div>
+                    ) : null}
+                  </div>
+                ) : null}
+
+                {prefs ? (
+                  <div className="settingsBlock" id="settings-game-mode">
+                    <div className="sectionTitle">Spielmodus</div>
+                    <div className="inline">
+                      <div className="hint">Weniger Hintergrundarbeit, solange Diablo IV läuft</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.gameMode}
+                          onChange={(e) => updatePrefs({ gameMode: e.target.checked })}
+                        />
+                        <span className="toggleLabel">{prefs.gameMode ? "an" : "aus"}</span>
+                      </label>
+                    </div>
+                    <div className="hint">
+                      Seltener aktualisiert, Animationen pausiert und helltime mit niedrigerer Priorität, damit das Spiel keine
+                      Frames verliert. Erinnerungen kommen weiterhin pünktlich.
+                    </div>
+
+                    <div className="field">
+                      <label>Energiesparen</label>
+                      <select
+                        className="select"
+                        value={prefs.powerSaving}
+                        onChange={(e) => updatePrefs({ powerSaving: e.target.value as PowerSaving })}
+                      >
+                        <option value="auto">Automatisch (Akku oder getaktete Verbindung)</option>
+                        <option value="on">Immer</option>
+                        <option value="off">Nie</option>
+                      </select>
+                      <div className="hint">
+                        Fragt die Zeiten seltener ab und aktualisiert den Overlay-Countdown nur alle 15 Sekunden.
+                        {powerStatus?.saving
+                          ? ` Gerade aktiv${
+                              powerStatus.onBattery ? " (Akkubetrieb)" : powerStatus.metered ? " (getaktete Verbindung)" : ""
+                            }.`
+                          : ""}
+                      </div>
+                    </div>
+                  </div>
+                ) : null}
+
+                {prefs && shortcutList.length ? (
+                  <div className="settingsBlock" id="settings-shortcuts">
+                    <div className="sectionTitle">Tastenkürzel</div>
+                    <div className="hint">
+                      Globale Tastenkürzel (Windows), z. B. „Strg+Alt+N“ oder „Shift+Win+F5“; leer lassen schaltet eines ab.
+                      Alle Aktionen stehen auch im Tray-Menü (Win+B, Pfeiltasten, Kontextmenü-Taste).
+                    </div>
+                    {shortcutList.map((info) => (
+                      <div className="inline" key={info.action}>
+                        <div className="hint">{SHORTCUT_LABELS[info.action]}</div>
+                        <input
+                          className="input"
+                          key={`${info.action}-${info.shortcut ?? ""}`}
+                          defaultValue={info.shortcut ?? ""}
+                          placeholder="aus"
+                          aria-label={`Tastenkürzel: ${SHORTCUT_LABELS[info.action]}`}
+                          onBlur={(e) => {
+                            const value = e.target.value.trim();
+                            if (value !== (info.shortcut ?? "")) setShortcut(info.action, value);
+                          }}
+                        />
+                        {info.shortcut && !info.registered ? <span className="pill small">nicht aktiv</span> : null}
+                        {prefs.shortcuts[info.action] !== undefined ? (
+                          <button className="btn" type="button" onClick={() => setShortcut(info.action, undefined)}>
+                            Standard{info.defaultShortcut ? ` (${info.defaultShortcut})` : ""}
+                          </button>
+                        ) : null}
+                      </div>
+                    ))}
+                  </div>
+                ) : null}
+
+                <div className="settingsBlock" id="settings-chests">
+                  <div className="sectionTitle">Mystery-Truhen</div>
+
+                  <div className="inline">
+                    <div className="hint">Erinnerung vor dem Truhen-Reset</div>
+                    <label className="toggle">
+                      <input
+                        type="checkbox"
+                        checked={settings.chestReminderEnabled}
+                        onChange={(e) => updateSettings((s) => ({ ...s, chestReminderEnabled: e.target.checked }))}
+                      />
+                      <span className="toggleLabel">{settings.chestReminderEnabled ? "an" : "aus"}</span>
+                    </label>
+                  </div>
+                  <div className="hint">Nur während eine Helltide läuft. Die Truhen setzen sich stündlich zurück.</div>
+
+                  <div className="field">
+                    <label>Minuten vorher</label>
+                    <input
+                      type="number"
+                      min={1}
+                      max={30}
+                      disabled={!settings.chestReminderEnabled}
+                      key={settings.chestReminderMinutesBefore}
+                      defaultValue={settings.chestReminderMinutesBefore}
+                      onBlur={(e) =>
+                        updateSettings((s) => ({ ...s, chestReminderMinutesBefore: clampInt(Number(e.target.value), 1, 30) }))
+                      }
+                      onKeyDown={(e) => {
+                        if (e.key === "Enter") e.currentTarget.blur();
+                      }}
+                    />
+                  </div>
+
+                  <div className="inline">
+                    <div className="hint">Erinnerung vor dem Helltide-Ende</div>
+                    <label className="toggle">
+                      <input
+                        type="checkbox"
+                        checked={settings.helltideEndReminderEnabled}
+                        onChange={(e) => updateSettings((s) => ({ ...s, helltideEndReminderEnabled: e.target.checked }))}
+                      />
+                      <span className="toggleLabel">{settings.helltideEndReminderEnabled ? "an" : "aus"}</span>
+                    </label>
+                  </div>
+                  <div className="hint">Damit die Cinders rechtzeitig ausgegeben werden, bevor die Zone schließt.</div>
+
+                  <div className="field">
+                    <label>Minuten vor Ende</label>
+                    <input
+                      type="number"
+                      min={1}
+                      max={30}
+                      disabled={!settings.helltideEndReminderEnabled}
+                      key={settings.helltideEndReminderMinutesBefore}
+                      defaultValue={settings.helltideEndReminderMinutesBefore}
+                      onBlur={(e) =>
+                        updateSettings((s) => ({
+                          ...s,
+                          helltideEndReminderMinutesBefore: clampInt(Number(e.target.value), 1, 30)
+                        }))
+                      }
+                      onKeyDown={(e) => {
+                        if (e.key === "Enter") e.currentTarget.blur();
+                      }}
+                    />
+                  </div>
+                </div>
+
+                <div className="settingsBlock" id="settings-integrations">
+                  <div className="sectionTitle">Integrationen</div>
+
+                  <div className="inline">
+                    <div className="hint">Lokale API</div>
+                    <label className="toggle">
+                      <input
+                        type="checkbox"
+                        checked={settings.localApiEnabled}
+                        onChange={(e) => updateSettings((s) => ({ ...s, localApiEnabled: e.target.checked }))}
+                      />
+                      <span className="toggleLabel">{settings.localApiEnabled ? "an" : "aus"}</span>
+                    </label>
+                  </div>
+                  <div className="hint">
+                    JSON unter http://127.0.0.1:{settings.localApiPort}/next, /schedule und /status für OBS, Stream Deck
+                    oder Skripte. Nur von diesem PC erreichbar.
+                  </div>
+                  <div className="hint">
+                    OBS Browser-Quelle: http://127.0.0.1:{settings.localApiPort}/obs (optional ?kind=helltide,legion
+                    oder &amp;boss=0), transparenter Hintergrund.
+                  </div>
+                  <div className="hint">
+                    Kalender-Abo: webcal://127.0.0.1:{settings.localApiPort}/calendar.ics (Outlook, Thunderbird,
+                    Apple Kalender; Google Kalender erreicht localhost nicht).
+                  </div>
+
+                  <div className="field">
+                    <label>Port</label>
+                    <input
+                      type="number"
+                      min={1024}
+                      max={65535}
+                      disabled={!settings.localApiEnabled}
+                      key={settings.localApiPort}
+                      defaultValue={settings.localApiPort}
+                      onBlur={(e) =>
+                        updateSettings((s) => ({ ...s, localApiPort: clampInt(Number(e.target.value), 1024, 65535) }))
+                      }
+                      onKeyDown={(e) => {
+                        if (e.key === "Enter") e.currentTarget.blur();
+                      }}
+                    />
+                  </div>
+                  {localApiError ? <div className="errorBanner">Lokale API: {localApiError}</div> : null}
+
+                  <div className="inline">
+                    <div className="hint">Discord-Status</div>
+                    <label className="toggle">
+                      <input
+                        type="checkbox"
+                        disabled={!discordAvailable}
+                        checked={settings.discordPresenceEnabled}
+                        onChange={(e) => updateSettings((s) => ({ ...s, discordPresenceEnabled: e.target.checked }))}
+                      />
+                      <span className="toggleLabel">{settings.discordPresenceEnabled ? "an" : "aus"}</span>
+                    </label>
+                  </div>
+                  <div className="hint">
+                    {discordAvailable
+                      ? "Zeigt „Next Helltide in 12m“ als Aktivität in Discord."
+                      : "In diesem Build nicht verfügbar (keine Discord-App-ID)."}
+                  </div>
+                </div>
+
+                {prefs ? (
+                  <div className="settingsBlock" id="settings-telemetry">
+                    <div className="sectionTitle">Nutzungsstatistik</div>
+                    <div className="inline">
+                      <div className="hint">Anonyme Zähler senden</div>
+                      <label className="toggle">
+                        <input
+                          type="checkbox"
+                          checked={prefs.telemetry.enabled}
+                          onChange={(e) => updatePrefs({ telemetry: { ...prefs.telemetry, enabled: e.target.checked } })}
+                        />
+                        <span className="toggleLabel">{prefs.telemetry.enabled ? "an" : "aus"}</span>
+                      </label>
+                    </div>
+                    <div className="hint">
+                      Nur wie oft Funktionen genutzt werden und welche Fehlerarten auftreten, nie Event-Zeiten, Einstellungen
+                      oder Kennungen. Ohne Verbindung wird gesammelt und später gesendet; ausschalten verwirft alles.
+                    </div>
+                    <div className="field">
+                      <label>Ziel-URL</label>
+                      <input
+                        type="text"
+                        placeholder="https://…"
+                        disabled={!prefs.telemetry.enabled}
+                        key={`telemetry-${prefs.telemetry.endpoint ?? ""}`}
+                        defaultValue={prefs.telemetry.endpoint ?? ""}
+                        onBlur={(e) => updatePrefs({ telemetry: { ...prefs.telemetry, endpoint: e.target.value.trim() || null } })}
+                        onKeyDown={(e) => {
+                          if (e.key === "Enter") e.currentTarget.blur();
+                        }}
+                      />
+                    </div>
+                    <div className="actions">
+                      <button
+                        className="btn"
+                        type="button"
+                        onClick={() =>
+                          void previewTelemetryPayload()
+                            .then((payload) => setTelemetryPreview(JSON.stringify(payload, null, 2)))
+                            .catch((e) => setTelemetryPreview(`Fehler: ${errorText(e)}`))
+                        }
+                      >
+                        Was gesendet würde
+                      </button>
+                    </div>
+                    {telemetryPreview ? <pre className="overlayDebugBox">{telemetryPreview}</pre> : null}
+                  </div>
+                ) : null}
+
+                {about ? (
+                  <div className="settingsBlock" id="settings-about">
+                    <div className="sectionTitle">Über helltime</div>
+                    <pre className="overlayDebugBox">{formatAbout(about)}</pre>
+                    <div className="actions">
+                      <button className="btn" type="button" onClick={() => void navigator.clipboard.writeText(formatAbout(about))}>
+                        Kopieren
+                      </button>
+                    </div>
+                  </div>
+                ) : null}
+
+                <div className="modalFooter">
+                  <button className="btn small" type="button" onClick={() => setDebugOpen((v) => !v)} aria-expanded={debugOpen}>
+                    Debug
+                  </button>
+                </div>
+
+	                {debugOpen ? (
+	                  <div className="settingsBlock" id="settings-debug">
+	                    <div className="sectionTitle">Debug</div>
+	                    <div className="actions">
+	                      <button className="btn" type="button" disabled={panicStopEnabled} onClick={() => void refreshOverlayDebug()}>
+	                        Overlay Status
+	                      </button>
+	                      <button
+	                        className="btn"
+	                        type="button"
+	                        disabled={panicStopEnabled}
+	                        onClick={() => {
+	                          clearOverlayDiag();
+	                          setOverlayDebug(null);
+	                        }}
+	                      >
+	                        Logs leeren
+	                      </button>
+	                    </div>
+	                    {overlayDebug ? <pre className="overlayDebugBox">{overlayDebug}</pre> : null}
+
+	                    <div className="actions">
+	                      <button
+	                        className="btn"
+	                        type="button"
+	                        disabled={panicStopEnabled}
+	                        onClick={() =>
+	                          void fetchScheduleRaw()
+	                            .then((raw) => setRawApi(raw ? formatRawResponse(raw) : "Keine Antwort aufgezeichnet."))
+	                            .catch((e) => setRawApi(`Fehler: ${errorText(e)}`))
+	                        }
+	                      >
+	                        API-Rohdaten
+	                      </button>
+	                      <button
+	                        className="btn"
+	                        type="button"
+	                        disabled={panicStopEnabled || !rawApi}
+	                        onClick={() =>
+	                          void saveRawPayload()
+	                            .then((path) => setRawApi((prev) => `Gespeichert: ${path}\n\n${prev ?? ""}`))
+	                            .catch((e) => setError(errorText(e)))
+	                        }
+	                      >
+	                        Für Bugreport speichern
+	                      </button>
+	                    </div>
+	                    {rawApi ? <pre className="overlayDebugBox">{rawApi}</pre> : null}
+
+	                    <div className="actions">
+	                      <button
+	                        className="btn"
+	                        type="button"
+	                        onClick={() =>
+	                          void getMetrics()
+	                            .then((m) => setMetrics(formatMetrics(m)))
+	                            .catch((e) => setMetrics(`Fehler: ${errorText(e)}`))
+	                        }
+	                      >
+	                        Statistik
+	                      </button>
+	                    </div>
+	                    {metrics ? <pre className="overlayDebugBox">{metrics}</pre> : null}
+	                  </div>
+	                ) : null}
+              </div>
+            </div>
+          </div>
+        </div>
+      ) : null}
+
+      <div className="grid">
+        {orderedTypes.map((type) => {
+          const category = settings.categories[type];
+          const next = nextByType ? nextByType[type] : null;
+          const nextStartMs = next ? new Date(next.startTime).getTime() : null;
+          const countdown = nextStartMs ? formatCountdown(nextStartMs - now) : "—";
+          const timeLabel = next ? formatLocalTime(next.startTime) : "—";
+          const zoneLabel = next && prefs?.secondaryZone ? formatZoneTime(next.startTime, prefs.secondaryZone) : null;
+          const name = getEventName(type, next);
+          const titleParts = getEventTitleParts(type, next);
+          const spokenName = getSpokenEventNameWithTemplate(type, next, category.ttsName);
+
+          const isOpen = category.enabled && openCategory === type;
+
+          return (
+            <div
+              className={`card span12 categoryCard ${type} categoryDetails ${isOpen ? "open" : ""} ${category.enabled ? "" : "disabled"}`}
+              key={type}
+              aria-disabled={!category.enabled}
+            >
+              <div className="panelHeaderRow categoryHeader">
+                <button
+                  className="panelHeaderBtn categoryExpandBtn"
+                  type="button"
+                  disabled={!category.enabled}
+                  aria-expanded={isOpen}
+                  onClick={() => {
+                    if (!category.enabled) return;
+                    setOpenCategory((prev) => (prev === type ? null : type));
+                  }}
+                >
+                  <span className="panelHeaderTitle categoryTitle" title={name}>
+                    <span className="categoryTitleMain">{titleParts.title}</span>
+                    <span className={`categoryTitleSub ${titleParts.subtitle ? "" : "placeholder"}`}>{titleParts.subtitle ?? "—"}</span>
+                  </span>
+                  <span className="panelHeaderMeta">{countdown}</span>
+                </button>
+                <div className="panelHeaderRight">
+                  <div className="pill">{timeLabel}</div>
+                  {zoneLabel ? <div className="pill">{zoneLabel}</div> : null}
+                  <label className="toggle">
+                    <input
+                      type="checkbox"
+                      checked={category.enabled}
+                      onChange={(e) => setCategoryEnabled(type, e.target.checked)}
+                    />
+                    <span className="toggleLabel">Erinnern</span>
+                  </label>
+                </div>
+              </div>
+
+              <div className="form">
+                {category.enabled && isOpen ? (
+                  <>
+                    <div className="inline" style={{ justifyContent: "space-between" }}>
+                      <div className="hint">Timer Anzahl</div>
+                      <select
+                        className="select"
+                        value={category.timerCount}
+                        onChange={(e) => setTimerCount(type, clampInt(Number(e.target.value), 1, 3) as 1 | 2 | 3)}
+                      >
+                        <option value={1}>1</option>
+                        <option value={2}>2</option>
+                        <option value={3}>3</option>
+                      </select>
+                    </div>
+
+                    <div className="inline" style={{ justifyContent: "space-between" }}>
+                      <div className="hint">Kompletter Probelauf mit einem Event in 1 Minute</div>
+                      <button
+                        className="btn"
+                        type="button"
+                        disabled={!isTauri()}
+                        onClick={() => {
+                          void testReminder(type).catch((e) => console.warn("test_reminder failed", e));
+                        }}
+                      >
+                        Erinnerung testen
+                      </button>
+                    </div>
+
+                    <div className="field">
+                      <label>Nur dieses Event ({timeLabel})</label>
+                      <div className="inline">
+                        <input
+                          type="number"
+                          min={1}
+                          max={1440}
+                          value={customMinutes}
+                          onChange={(e) => setCustomMinutes(clampInt(Number(e.target.value), 1, 1440))}
+                        />
+                        <span className="hint">Minuten vorher</span>
+                        <button
+                          className="btn"
+                          type="button"
+                          disabled={!isTauri() || !next}
+                          onClick={() => {
+                            if (!next) return;
+                            setCustomReminderError(null);
+                            void addCustomReminder(type, (next as { id: number }).id, customMinutes).catch((e) =>
+                              setCustomReminderError(errorText(e))
+                            );
+                          }}
+                        >
+                          Einmal erinnern
+                        </button>
+                      </div>
+                      {customReminderError ? <div className="hint">{customReminderError}</div> : null}
+                      <div className="inline" style={{ justifyContent: "space-between" }}>
+                        <div className="hint">Bin dabei: keine weiteren Erinnerungen für dieses Event</div>
+                        <button
+                          className="btn"
+                          type="button"
+                          disabled={!isTauri() || !next || attending.has(`${type}:${(next as { id: number }).id}`)}
+                          onClick={() => {
+                            if (!next) return;
+                            void markAttending(type, (next as { id: number }).id).catch((e) => setCustomReminderError(errorText(e)));
+                          }}
+                        >
+                          Bin dabei
+                        </button>
+                      </div>
+                      {customReminders
+                        .filter((r) => r.kind === type)
+                        .map((r) => (
+                          <div className="inline" key={r.id} style={{ justifyContent: "space-between" }}>
+                            <div className="hint">
+                              {`${formatLocalTime(r.eventStart)} · ${r.minutesBefore} min vorher`}
+                            </div>
+                            <button className="btn" type="button" onClick={() => void removeCustomReminder(r.id)}>
+                              Entfernen
+                            </button>
+                          </div>
+                        ))}
                     </div>
 
                     <div className="field">
@@ -1222,7 +3899,7 @@ export default function App() {
             type="checkbox"
             disabled={panicStopEnabled}
             checked={settings.overlayWindowEnabled}
-            onChange={(e) => updateSettings((s) => ({ ...s, overlayWindowEnabled: e.target.checked }))}
+            onChange={(e) => setOverlayEnabled(e.target.checked)}
           />
           <span className="toggleLabel">Overlay</span>
         </label>
@@ -1,10 +1,40 @@
 import React, { useEffect, useMemo, useRef, useState } from "react";
-import { isTauri } from "@tauri-apps/api/core";
+import { invoke, isTauri } from "@tauri-apps/api/core";
 import { fetchSchedule } from "./lib/helltides";
-import { loadSettings } from "./lib/settings";
-import { formatCountdown, formatLocalTime } from "./lib/time";
-import type { ScheduleResponse, ScheduleType, WorldBossScheduleItem } from "./lib/types";
+import {
+  loadSettings,
+  OVERLAY_MAX_SIZE,
+  OVERLAY_MIN_SIZE,
+  OVERLAY_SIZE_PRESETS,
+  type OverlaySizePreset
+} from "./lib/settings";
+import { getPreferences, hiddenEvents, type Preferences } from "./lib/preferences";
+import { formatCountdown, formatLocalTime, serverNow } from "./lib/time";
+import {
+  displayBossName,
+  displayZone,
+  type ScheduleItemBase,
+  type ScheduleResponse,
+  type ScheduleType,
+  type WorldBossScheduleItem
+} from "./lib/types";
 import { findNext } from "./lib/helpers";
+import { activeProgress, getActiveEvents, type ActiveEvent } from "./lib/active";
+import { followGameMode } from "./lib/game_mode";
+import { followReducedMotion } from "./lib/motion";
+import { followPowerSaving, POWER_SAVING_TICK_MS } from "./lib/power_saving";
+import {
+  DEFAULT_OVERLAY_TEXTS,
+  endOverlayPositioning,
+  followOverlayTexts,
+  setOverlayWindowPosition,
+  setOverlayWindowVisible,
+  type OverlayPosition,
+  type OverlayResized,
+  type OverlayStatus,
+  type OverlayTexts
+} from "./lib/overlay_window";
+import { pushOverlayDiag } from "./lib/overlay_diag";
 
 function clampFloat(n: unknown, fallback: number, min: number, max: number): number {
   if (typeof n !== "number" || !Number.isFinite(n)) return fallback;
@@ -42,32 +72,62 @@ function readPositioningUntil(): number {
   }
 }
 
-function typeLabel(type: ScheduleType): string {
-  switch (type) {
-    case "helltide":
-      return "Helltide";
-    case "legion":
-      return "Legion";
-    case "world_boss":
-      return "World Boss";
+function getEventName(
+  type: ScheduleType,
+  item: { startTime: string } | null,
+  texts: OverlayTexts
+): { title: string; subtitle?: string } {
+  const title = texts.labels[type];
+  if (!item) return { title };
+  const zone = displayZone(item as ScheduleItemBase) ?? undefined;
+  if (type === "world_boss") {
+    const boss = displayBossName(item as WorldBossScheduleItem);
+    const subtitle = [boss, zone].filter(Boolean).join(" · ");
+    return subtitle ? { title, subtitle } : { title };
   }
+  return zone ? { title, subtitle: zone } : { title };
 }
 
-function getEventName(type: ScheduleType, item: { startTime: string } | null): { title: string; subtitle?: string } {
-  if (!item) return { title: typeLabel(type) };
-  if (type === "world_boss") {
-    const boss = (item as WorldBossScheduleItem).boss;
-    return boss ? { title: "World Boss", subtitle: boss } : { title: "World Boss" };
+const types: ScheduleType[] = ["helltide", "legion", "world_boss"];
+
+/** Fallbacks behind a chosen overlay font (same stack as the app). */
+const DEFAULT_FONT_STACK =
+  'ui-sans-serif, system-ui, -apple-system, Segoe UI, Roboto, Inter, Arial, "Microsoft YaHei UI", "Yu Gothic UI", "Malgun Gothic", "Microsoft JhengHei UI", "Noto Sans CJK SC", sans-serif';
+
+/** No toast stays on screen longer than this, whatever `durationMs` it asked for. */
+const TOAST_MAX_MS = 30_000;
+/** Fixed row labels of the mini-bar; it always lists the categories in this order. */
+const MINI_BAR_LABELS: Record<ScheduleType, string> = {
+  helltide: "Helltide",
+  legion: "Legion",
+  world_boss: "Boss"
+};
+
+/** How often the toast-mode watchdog checks for a window left visible without a toast. */
+const TOAST_WATCHDOG_MS = 5_000;
+
+/** Arrow-key nudge in positioning mode, in physical pixels (Shift: coarse). */
+const NUDGE_STEP = 1;
+const NUDGE_STEP_COARSE = 10;
+
+async function emitPosition(position: OverlayPosition): Promise<void> {
+  try {
+    const { emit } = await import("@tauri-apps/api/event");
+    await emit("overlay:position-changed", position);
+  } catch (e) {
+    // eslint-disable-next-line no-console
+    console.warn("overlay: emit overlay:position-changed failed", e);
   }
-  return { title: typeLabel(type) };
 }
 
-const types: ScheduleType[] = ["helltide", "legion", "world_boss"];
+/** Edge or corner a resize drag starts from; the top-left corner stays in place. */
+type ResizeEdge = "east" | "south" | "southEast";
 
 type ToastPayload = {
   title: string;
   body: string;
   type?: ScheduleType;
+  kind?: "event" | "chest" | "debug";
   durationMs?: number;
 };
 
@@ -78,22 +138,66 @@ export default function OverlayWindow() {
   const scaleXRef = useRef(1);
   const scaleYRef = useRef(1);
   const positioningRef = useRef(false);
+  const sizePresetRef = useRef<OverlaySizePreset>("medium");
+  const customSizeRef = useRef({ width: 0, height: 0 });
+  const resizeRef = useRef<{ edge: ResizeEdge; x: number; y: number; width: number; height: number } | null>(null);
   const [schedule, setSchedule] = useState<ScheduleResponse | null>(null);
   const [error, setError] = useState<string | null>(null);
-  const [now, setNow] = useState(() => Date.now());
+  const [now, setNow] = useState(() => serverNow());
   const [settings, setSettings] = useState(() => loadSettings());
+  const [prefs, setPrefs] = useState<Preferences | null>(null);
   const [toast, setToast] = useState<{ payload: ToastPayload; shownAt: number } | null>(null);
+  const [active, setActive] = useState<ActiveEvent[]>([]);
 
   useEffect(() => {
     document.body.classList.add("overviewMode");
     return () => document.body.classList.remove("overviewMode");
   }, []);
 
+  useEffect(() => followGameMode(), []);
+  useEffect(() => followReducedMotion(), []);
+
+  const [texts, setTexts] = useState<OverlayTexts>(DEFAULT_OVERLAY_TEXTS);
+  useEffect(() => followOverlayTexts(setTexts), []);
+
+  // Under Wine the window is opaque, so the background alpha and soft shadows only
+  // blend against black; the compat styles drop them.
+  const [wineCompat, setWineCompat] = useState(false);
   useEffect(() => {
-    const id = window.setInterval(() => setNow(Date.now()), 1000);
-    return () => window.clearInterval(id);
+    if (!isTauri()) return;
+    void invoke<OverlayStatus>("overlay_status")
+      .then((status) => setWineCompat(status.compat === "wine"))
+      .catch(() => {});
   }, []);
 
+  // Faded by the backend while the cursor rests on the overlay (hover fade option).
+  const [hoverOpacity, setHoverOpacity] = useState(1);
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+    void (async () => {
+      const { listen } = await import("@tauri-apps/api/event");
+      const fn = await listen<number>("overlay:hover-opacity", (event) => setHoverOpacity(event.payload));
+      if (disposed) fn();
+      else unlisten = fn;
+    })().catch(() => {});
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+
+  // The per-second countdown repaint stops while saving power.
+  const [powerSaving, setPowerSaving] = useState(false);
+  useEffect(() => followPowerSaving(setPowerSaving), []);
+
+  useEffect(() => {
+    setNow(serverNow());
+    const id = window.setInterval(() => setNow(serverNow()), powerSaving ? POWER_SAVING_TICK_MS : 1000);
+    return () => window.clearInterval(id);
+  }, [powerSaving]);
+
   useEffect(() => {
     setSettings(loadSettings());
   }, []);
@@ -123,7 +227,29 @@ export default function OverlayWindow() {
 
     void refresh();
     const id = window.setInterval(() => void refresh(), 60_000);
-    return () => window.clearInterval(id);
+
+    let unlisten: (() => void) | null = null;
+    let unlistenNetwork: (() => void) | null = null;
+    if (isTauri()) {
+      void (async () => {
+        try {
+          const { listen } = await import("@tauri-apps/api/event");
+          unlisten = await listen("system:time-jump", () => {
+            setNow(serverNow());
+            void refresh();
+          });
+          unlistenNetwork = await listen("system:network-changed", () => void refresh());
+        } catch {
+          // ignore
+        }
+      })();
+    }
+
+    return () => {
+      window.clearInterval(id);
+      unlisten?.();
+      unlistenNetwork?.();
+    };
   }, []);
 
   useEffect(() => {
@@ -135,7 +261,7 @@ export default function OverlayWindow() {
         unlisten = await listen<ToastPayload>("helltime:toast", (event) => {
           const payload = event.payload;
           if (!payload?.title) return;
-          setToast({ payload, shownAt: Date.now() });
+          setToast({ payload, shownAt: serverNow() });
         });
       } catch (e) {
         // eslint-disable-next-line no-console
@@ -152,6 +278,47 @@ export default function OverlayWindow() {
     };
   }, []);
 
+  // Reveal hotkey: the backend lifted click-through for a few seconds; show the
+  // positioning handles for exactly that long.
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      const { listen } = await import("@tauri-apps/api/event");
+      unlisten = await listen<number>("overlay:reveal", (event) => {
+        try {
+          localStorage.setItem("helltime:overlayPositioningUntil", String(Date.now() + event.payload));
+        } catch {
+          // ignore
+        }
+        setNow(serverNow());
+      });
+    })();
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  // The event filter is a backend preference; follow changes made in the main window.
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | null = null;
+    void (async () => {
+      setPrefs(await getPreferences());
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+        unlisten = await listen<Preferences>("preferences:changed", (event) => setPrefs(event.payload));
+      } catch (e) {
+        // eslint-disable-next-line no-console
+        console.warn("overlay: listen preferences:changed failed", e);
+      }
+    })();
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
   useEffect(() => {
     if (!isTauri()) return;
     let unlisten: (() => void) | null = null;
@@ -177,6 +344,21 @@ export default function OverlayWindow() {
     };
   }, []);
 
+  // Running events; refreshed often enough to catch a start within a few seconds.
+  useEffect(() => {
+    void getActiveEvents().then(setActive);
+    const id = window.setInterval(() => void getActiveEvents().then(setActive), 10_000);
+    return () => window.clearInterval(id);
+  }, []);
+
+  const activeByType = useMemo(() => {
+    const out: Partial<Record<ScheduleType, ActiveEvent>> = {};
+    for (const event of active) {
+      if (new Date(event.end).getTime() > now) out[event.kind] = event;
+    }
+    return out;
+  }, [active, now]);
+
   const nextByType = useMemo(() => {
     if (!schedule) return null;
     return {
@@ -189,8 +371,9 @@ export default function OverlayWindow() {
   const enabledTypes = useMemo(() => {
     const cats = settings.overlayWindowCategories ?? { helltide: true, legion: true, world_boss: true };
     const enabled = settings.categories;
-    return types.filter((t) => cats[t] !== false && enabled[t]?.enabled !== false);
-  }, [settings.categories, settings.overlayWindowCategories]);
+    const hidden = hiddenEvents(prefs);
+    return types.filter((t) => cats[t] !== false && enabled[t]?.enabled !== false && !hidden.includes(t));
+  }, [settings.categories, settings.overlayWindowCategories, prefs]);
 
   const ordered = useMemo(() => {
     if (!nextByType) return [...enabledTypes];
@@ -207,24 +390,56 @@ export default function OverlayWindow() {
   const scaleX = clampFloat(settings.overlayScaleX, 1, 0.6, 2.0);
   const scaleY = clampFloat(settings.overlayScaleY, 1, 0.6, 2.0);
   const contentScale = scaleY;
-  const positioning = useMemo(() => now < readPositioningUntil(), [now]);
+  const positioning = useMemo(() => Date.now() < readPositioningUntil(), [now]);
   useEffect(() => {
     positioningRef.current = positioning;
   }, [positioning]);
-  const bgAlpha = positioning ? 1 : clampFloat(settings.overlayBgOpacity, 0.2, 0, 1.0);
+  // Opacity applies to the background color only (per-pixel alpha); text and its
+  // shadow stay fully opaque. Never fade the host with CSS `opacity`.
+  const bgAlpha = positioning || wineCompat ? 1 : clampFloat(settings.overlayBgOpacity, 0.2, 0, 1.0);
   const bgHex = positioning ? invertHexColor(settings.overlayBgHex) : settings.overlayBgHex;
-  const bg = hexToRgba(bgHex, bgAlpha);
+  const highContrast = settings.overlayTheme === "highContrast";
+  const bg = highContrast ? "#000000" : hexToRgba(bgHex, bgAlpha);
 
   const toastVisible = useMemo(() => {
     if (!toast) return false;
-    const ms = toast.payload.durationMs ?? 5200;
+    const ms = Math.min(toast.payload.durationMs ?? 5200, TOAST_MAX_MS);
     return now - toast.shownAt < ms;
   }, [toast, now]);
 
   // If we're in overview mode but a toast is visible, show toast instead (prevent overlap)
-  const effectiveMode = toast && toastVisible ? "toast" : settings.overlayWindowMode === "toast" ? "toast" : "overview";
+  const effectiveMode = toast && toastVisible ? "toast" : settings.overlayWindowMode;
   const mode = effectiveMode;
-  const overviewEmpty = mode === "overview" && ordered.length === 0 && !positioning;
+  const overviewEmpty = mode !== "toast" && ordered.length === 0 && !positioning;
+
+  // Watchdog: in toast mode the window should only be visible while a toast is. The
+  // hide is scheduled by whoever showed the toast; if that timer got lost (throttled
+  // or reloaded main window, a racing re-show) hide the window from here.
+  const toastIdleRef = useRef(false);
+  useEffect(() => {
+    toastIdleRef.current = settings.overlayWindowMode === "toast" && !toastVisible && !positioning;
+  }, [settings.overlayWindowMode, toastVisible, positioning]);
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    let idleChecks = 0;
+    const id = window.setInterval(() => {
+      // Two idle checks in a row give a regular hide timer time to fire first.
+      idleChecks = toastIdleRef.current ? idleChecks + 1 : 0;
+      if (idleChecks < 2) return;
+      void (async () => {
+        try {
+          const { getCurrentWebviewWindow } = await import("@tauri-apps/api/webviewWindow");
+          if (!(await getCurrentWebviewWindow().isVisible())) return;
+          pushOverlayDiag("watchdog: overlay visible without a toast, hiding it");
+          await setOverlayWindowVisible(false);
+        } catch {
+          // ignore
+        }
+      })();
+    }, TOAST_WATCHDOG_MS);
+    return () => window.clearInterval(id);
+  }, []);
 
   useEffect(() => {
     scaleXRef.current = scaleX;
@@ -234,22 +449,91 @@ export default function OverlayWindow() {
     scaleYRef.current = scaleY;
   }, [scaleY]);
 
+  useEffect(() => {
+    sizePresetRef.current = settings.overlaySizePreset;
+  }, [settings.overlaySizePreset]);
+
+  useEffect(() => {
+    if (resizeRef.current) return;
+    customSizeRef.current = { width: settings.overlayWidth, height: settings.overlayHeight };
+  }, [settings.overlayWidth, settings.overlayHeight]);
+
   useEffect(() => {
     requestResizeRef.current?.();
-  }, [mode, toastVisible, ordered.length, scaleX, scaleY, bgAlpha]);
+  }, [
+    mode,
+    toastVisible,
+    ordered.length,
+    scaleX,
+    scaleY,
+    bgAlpha,
+    settings.overlaySizePreset,
+    settings.overlayWrapText,
+    settings.overlayWidth,
+    settings.overlayHeight
+  ]);
 
   useEffect(() => {
     if (!isTauri()) return;
-    void (async () => {
-      try {
-        const { getCurrentWebviewWindow } = await import("@tauri-apps/api/webviewWindow");
-        const win = getCurrentWebviewWindow();
-        // Safety: never block clicks unless we're in explicit positioning mode.
-        await win.setIgnoreCursorEvents(!positioningRef.current);
-      } catch {
-        // ignore
-      }
-    })();
+    // Safety: never block clicks unless we're in explicit positioning mode. The
+    // backend owns click-through (window styles included), so ask it.
+    void invoke("overlay_set_click_through", { enabled: !positioningRef.current }).catch(() => {});
+  }, [positioning]);
+
+  // Positioning mode: arrow keys nudge (Shift = 10px), Enter keeps the position and
+  // Escape moves back to where positioning started; both end positioning mode.
+  useEffect(() => {
+    if (!isTauri() || !positioning) return;
+    let start: OverlayPosition | null = null;
+    let disposed = false;
+
+    const win = import("@tauri-apps/api/webviewWindow").then(async ({ getCurrentWebviewWindow }) => {
+      const w = getCurrentWebviewWindow();
+      const pos = await w.outerPosition();
+      start = { x: pos.x, y: pos.y };
+      void emitPosition(start);
+      return w;
+    });
+
+    const onKeyDown = (e: KeyboardEvent) => {
+      const step = e.shiftKey ? NUDGE_STEP_COARSE : NUDGE_STEP;
+      const delta: Partial<Record<string, [number, number]>> = {
+        ArrowLeft: [-step, 0],
+        ArrowRight: [step, 0],
+        ArrowUp: [0, -step],
+        ArrowDown: [0, step]
+      };
+      const move = delta[e.key];
+      if (!move && e.key !== "Enter" && e.key !== "Escape") return;
+      e.preventDefault();
+      void (async () => {
+        try {
+          const w = await win;
+          if (disposed) return;
+          if (move) {
+            const pos = await w.outerPosition();
+            // The backend broadcasts the move (`overlay:position-changed`).
+            await setOverlayWindowPosition(pos.x + move[0], pos.y + move[1]);
+            return;
+          }
+          if (e.key === "Escape" && start) {
+            await setOverlayWindowPosition(start.x, start.y);
+          }
+          const pos = await w.outerPosition();
+          await emitPosition({ x: pos.x, y: pos.y });
+          await endOverlayPositioning();
+        } catch (err) {
+          // eslint-disable-next-line no-console
+          console.warn("overlay: keyboard positioning failed", err);
+        }
+      })();
+    };
+
+    window.addEventListener("keydown", onKeyDown);
+    return () => {
+      disposed = true;
+      window.removeEventListener("keydown", onKeyDown);
+    };
   }, [positioning]);
 
   useEffect(() => {
@@ -271,13 +555,16 @@ export default function OverlayWindow() {
           const currentScaleY = scaleYRef.current;
           const contentH = Math.ceil(contentRef.current?.scrollHeight ?? hostRef.current.scrollHeight);
 
-          // Step 1: width follows X.
-          const baseW = 280;
-          const w = Math.max(170, Math.round(baseW * currentScaleX));
+          const base = OVERLAY_SIZE_PRESETS[sizePresetRef.current];
+          const custom = customSizeRef.current;
+
+          // Step 1: width follows X (or the width dragged in positioning mode).
+          const baseW = base.width;
+          const w = custom.width || Math.max(170, Math.round(baseW * currentScaleX));
 
-          // Step 2: height follows Y (but never clip content).
-          const baseH = 118;
-          const hTarget = Math.max(56, Math.round(baseH * currentScaleY));
+          // Step 2: height follows Y (but never clip content, e.g. wrapped zone names).
+          const baseH = base.height;
+          const hTarget = custom.height || Math.max(56, Math.round(baseH * currentScaleY));
           const h = Math.max(hTarget, Math.max(40, contentH + 8));
           if (Math.abs(w - lastW) <= 1 && Math.abs(h - lastH) <= 1) return;
           lastW = w;
@@ -345,13 +632,63 @@ export default function OverlayWindow() {
     }
   }
 
+  // Resizing is done here rather than with a native resize border: the window size
+  // is otherwise owned by `commit()`, which has to know about the dragged size.
+  function startResizing(edge: ResizeEdge, e: React.PointerEvent<HTMLDivElement>): void {
+    if (!isTauri() || e.button !== 0) return;
+    e.preventDefault();
+    e.stopPropagation();
+    e.currentTarget.setPointerCapture(e.pointerId);
+    resizeRef.current = { edge, x: e.screenX, y: e.screenY, width: window.innerWidth, height: window.innerHeight };
+  }
+
+  function resizeMove(e: React.PointerEvent<HTMLDivElement>): void {
+    const drag = resizeRef.current;
+    if (!drag) return;
+    const width = drag.edge === "south" ? drag.width : drag.width + e.screenX - drag.x;
+    const height = drag.edge === "east" ? drag.height : drag.height + e.screenY - drag.y;
+    customSizeRef.current = {
+      width: Math.round(Math.max(OVERLAY_MIN_SIZE.width, Math.min(OVERLAY_MAX_SIZE.width, width))),
+      height: Math.round(Math.max(OVERLAY_MIN_SIZE.height, Math.min(OVERLAY_MAX_SIZE.height, height)))
+    };
+    requestResizeRef.current?.();
+  }
+
+  async function stopResizing(): Promise<void> {
+    if (!resizeRef.current) return;
+    resizeRef.current = null;
+    try {
+      const { emit } = await import("@tauri-apps/api/event");
+      await emit("overlay:resized", customSizeRef.current satisfies OverlayResized);
+    } catch (e) {
+      // eslint-disable-next-line no-console
+      console.warn("overlay: emit overlay:resized failed", e);
+    }
+  }
+
+  const resizeHandle = (edge: ResizeEdge) => (
+    <div
+      className={`overlayResizeHandle ${edge}`}
+      onPointerDown={(e) => startResizing(edge, e)}
+      onPointerMove={resizeMove}
+      onPointerUp={() => void stopResizing()}
+      onPointerCancel={() => void stopResizing()}
+      aria-hidden="true"
+    />
+  );
+
   return (
     <div
-      className={`overlayHost overlayMode-${mode} ${positioning ? "positioning" : ""}`}
+      className={`overlayHost overlayMode-${mode} overlayShadow-${settings.overlayTextShadow} overlayTheme-${settings.overlayTheme} ${wineCompat ? "overlayCompat-wine" : ""} ${
+        settings.overlayFontWeight ? "customWeight" : ""
+      } ${settings.overlayWrapText ? "wrapText" : ""} ${positioning ? "positioning" : ""}`}
       style={{
+        fontFamily: settings.overlayFontFamily ? `"${settings.overlayFontFamily}", ${DEFAULT_FONT_STACK}` : undefined,
+        ["--overlayFontWeight" as any]: settings.overlayFontWeight ? String(settings.overlayFontWeight) : undefined,
         background:
           (mode === "toast" && !toastVisible && !positioning) || overviewEmpty ? "rgba(0,0,0,0)" : bg,
-        ["--overlayScale" as any]: String(contentScale)
+        ["--overlayScale" as any]: String(contentScale),
+        opacity: hoverOpacity < 1 && !positioning ? hoverOpacity : undefined
       }}
       ref={hostRef}
     >
@@ -362,20 +699,24 @@ export default function OverlayWindow() {
             data-tauri-drag-region
             onPointerDown={(e) => void startDragging(e)}
             role="button"
-            aria-label="Overlay verschieben"
-            title="Ziehen zum Verschieben"
+            aria-label={texts.moveLabel}
+            title={texts.dragHint}
           >
-            <span className="overlayDragHandleText">Ziehen zum Verschieben</span>
+            <span className="overlayDragHandleText">{texts.dragHint}</span>
           </div>
         ) : null}
 
-        {error ? <div className="overlayError">Fehler</div> : null}
+        {error ? <div className="overlayError">{texts.error}</div> : null}
 
         {mode === "toast" ? (
           toast && toastVisible ? (
-            <div className={`overlayToast ${toast.payload.type ?? ""}`} data-tauri-drag-region>
+            <div
+              className={`overlayToast ${toast.payload.type ?? ""} ${toast.payload.kind === "chest" ? "chest" : ""}`}
+              data-tauri-drag-region
+              role="alert"
+            >
               <div className="overlayToastLine">
-                <span className="overlayToastEvent">{toast.payload.title}</span>
+                <span className="overlayToastEvent" dir="auto">{toast.payload.title}</span>
                 <span className="overlayToastTime">{toast.payload.body}</span>
               </div>
             </div>
@@ -383,26 +724,56 @@ export default function OverlayWindow() {
             <div className="overlayToast" data-tauri-drag-region>
               <div className="overlayToastLine">
                 <span className="overlayToastEvent">Overlay</span>
-                <span className="overlayToastTime">ziehen</span>
+                <span className="overlayToastTime">{texts.drag}</span>
               </div>
             </div>
           ) : null
+        ) : mode === "minibar" ? (
+          <div className="overlayMiniBar" data-tauri-drag-region>
+            {enabledTypes.map((type) => {
+              const next = nextByType ? nextByType[type] : null;
+              const running = activeByType[type];
+              const startMs = next ? new Date(next.startTime).getTime() : null;
+              const remaining = running
+                ? `noch ${formatCountdown(new Date(running.end).getTime() - now)}`
+                : startMs
+                  ? formatCountdown(startMs - now)
+                  : "—";
+
+              return (
+                <div className={`overlayMiniRow ${type} ${running ? "active" : ""}`} key={type} data-tauri-drag-region>
+                  <span className="overlayMiniLabel">{MINI_BAR_LABELS[type]}</span>
+                  <span className="overlayMiniTime">{remaining}</span>
+                </div>
+              );
+            })}
+          </div>
         ) : (
           <div className="overlayLines" data-tauri-drag-region>
             {ordered.map((type) => {
               const next = nextByType ? nextByType[type] : null;
+              const running = activeByType[type];
               const startMs = next ? new Date(next.startTime).getTime() : null;
-              const remaining = startMs ? formatCountdown(startMs - now) : "—";
-              const name = getEventName(type, next);
+              const remaining = running
+                ? `noch ${formatCountdown(new Date(running.end).getTime() - now)}`
+                : startMs
+                  ? formatCountdown(startMs - now)
+                  : "—";
+              const name = getEventName(type, next, texts);
               const showSubline = type === "world_boss" && Boolean(name.subtitle);
 
               return (
-                <div className={`overlayLine ${type}`} key={type} data-tauri-drag-region>
+                <div className={`overlayLine ${type} ${running ? "active" : ""}`} key={type} data-tauri-drag-region>
                   <span className="overlayLineEvent">
-                    <span className="overlayLineEventTitle">{name.title}</span>
-                    {showSubline ? <span className="overlayLineEventSub">{name.subtitle}</span> : null}
+                    <span className="overlayLineEventTitle" dir="auto">{name.title}</span>
+                    {showSubline ? <span className="overlayLineEventSub" dir="auto">{name.subtitle}</span> : null}
                   </span>
                   <span className="overlayLineTime">{remaining}</span>
+                  {running ? (
+                    <div className="overlayProgress">
+                      <div className="overlayProgressFill" style={{ width: `${activeProgress(running, now) * 100}%` }} />
+                    </div>
+                  ) : null}
                 </div>
               );
             })}
@@ -410,6 +781,14 @@ export default function OverlayWindow() {
         )}
       </div>
 
+      {positioning ? (
+        <>
+          {resizeHandle("east")}
+          {resizeHandle("south")}
+          {resizeHandle("southEast")}
+        </>
+      ) : null}
+
       {/* keep the window draggable even in empty areas */}
     </div>
   );
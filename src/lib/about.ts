@@ -0,0 +1,47 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+
+export type MonitorInfo = {
+  name: string | null;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  scaleFactor: number;
+  primary: boolean;
+};
+
+/** Version, build and environment facts from `about_info`. */
+export type AboutInfo = {
+  version: string;
+  gitHash: string; // empty outside a git checkout
+  buildDate: string | null; // ISO
+  tauriVersion: string;
+  webviewVersion: string | null;
+  os: string;
+  arch: string;
+  monitors: MonitorInfo[];
+};
+
+export async function getAboutInfo(): Promise<AboutInfo | null> {
+  if (!isTauri()) return null;
+  try {
+    return await invoke<AboutInfo>("about_info");
+  } catch {
+    return null;
+  }
+}
+
+/** Same lines as in the diagnostics report, for copying into bug reports. */
+export function formatAbout(info: AboutInfo): string {
+  const lines = [
+    `helltime ${info.version}${info.gitHash ? ` (${info.gitHash})` : ""}`,
+    `built: ${info.buildDate ?? "?"}`,
+    `tauri: ${info.tauriVersion}, webview: ${info.webviewVersion ?? "missing"}`,
+    `os: ${info.os} (${info.arch})`,
+    ...info.monitors.map(
+      (m) =>
+        `monitor: ${m.name ?? "?"} ${m.width}x${m.height} at ${m.x},${m.y} scale ${m.scaleFactor}${m.primary ? " (primary)" : ""}`
+    )
+  ];
+  return lines.join("\n");
+}
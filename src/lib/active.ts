@@ -0,0 +1,32 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+import type { ScheduleType } from "./types";
+
+/** An event that has started and is still running, as reported by the backend. */
+export type ActiveEvent = {
+  kind: ScheduleType;
+  id: number;
+  start: string; // ISO
+  end: string; // ISO
+  seconds_remaining: number;
+  progress: number; // 0..1 at the time of the call
+  boss: string | null;
+  boss_predicted: boolean;
+  zone: string | null;
+};
+
+export async function getActiveEvents(): Promise<ActiveEvent[]> {
+  if (!isTauri()) return [];
+  try {
+    return await invoke<ActiveEvent[]>("active_events");
+  } catch {
+    return [];
+  }
+}
+
+/** Progress of `event` at `nowMs`, derived from start/end so it moves between refreshes. */
+export function activeProgress(event: ActiveEvent, nowMs: number): number {
+  const start = new Date(event.start).getTime();
+  const end = new Date(event.end).getTime();
+  if (!(end > start)) return 1;
+  return Math.max(0, Math.min(1, (nowMs - start) / (end - start)));
+}
@@ -0,0 +1,16 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+
+/** A mystery chest reset inside a Helltide, computed by the backend from the schedule. */
+export type ChestReset = {
+  helltideId: number;
+  at: string; // ISO
+};
+
+export async function getChestResets(): Promise<ChestReset[]> {
+  if (!isTauri()) return [];
+  try {
+    return await invoke<ChestReset[]>("chest_resets");
+  } catch {
+    return [];
+  }
+}
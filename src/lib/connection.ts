@@ -0,0 +1,18 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+
+/** Backend view of the schedule API: degraded means cached data is still shown. */
+export type ConnectionStatus = {
+  state: "online" | "degraded" | "offline";
+  lastSuccess: string | null; // ISO
+  lastError: string | null;
+  consecutiveFailures: number;
+};
+
+export async function getConnectionStatus(): Promise<ConnectionStatus | null> {
+  if (!isTauri()) return null;
+  try {
+    return await invoke<ConnectionStatus>("connection_status");
+  } catch {
+    return null;
+  }
+}
@@ -0,0 +1,20 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+
+export type CrashInfo = {
+  path: string;
+  summary: string;
+  at: string; // ISO
+};
+
+export async function takePreviousCrash(): Promise<CrashInfo | null> {
+  if (!isTauri()) return null;
+  try {
+    return await invoke<CrashInfo | null>("previous_crash");
+  } catch {
+    return null;
+  }
+}
+
+export async function openCrashReport(path: string): Promise<void> {
+  await invoke("open_crash_report", { path });
+}
@@ -0,0 +1,29 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+import type { ScheduleType } from "./types";
+
+/** A one-off reminder for one particular event, kept by the backend. */
+export type CustomReminder = {
+  id: number;
+  kind: ScheduleType;
+  eventId: number;
+  eventStart: string; // ISO
+  minutesBefore: number;
+  fireAt: string; // ISO
+};
+
+export async function listReminders(): Promise<CustomReminder[]> {
+  if (!isTauri()) return [];
+  try {
+    return await invoke<CustomReminder[]>("list_reminders");
+  } catch {
+    return [];
+  }
+}
+
+export async function addCustomReminder(type: ScheduleType, eventId: number, minutesBefore: number): Promise<CustomReminder> {
+  return invoke<CustomReminder>("add_custom_reminder", { eventType: type, eventId, minutesBefore });
+}
+
+export async function removeCustomReminder(id: number): Promise<boolean> {
+  return invoke<boolean>("remove_custom_reminder", { id });
+}
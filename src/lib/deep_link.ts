@@ -0,0 +1,16 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+import type { ScheduleType } from "./types";
+
+export type DeepLink =
+  | { route: "show" }
+  | { route: "show_next"; kind: ScheduleType }
+  | { route: "settings"; section?: string | null };
+
+export async function takePendingDeepLink(): Promise<DeepLink | null> {
+  if (!isTauri()) return null;
+  try {
+    return await invoke<DeepLink | null>("take_pending_deep_link");
+  } catch {
+    return null;
+  }
+}
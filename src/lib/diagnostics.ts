@@ -0,0 +1,26 @@
+import { invoke } from "@tauri-apps/api/core";
+import { formatAbout, type AboutInfo } from "./about";
+
+export type CheckStatus = "ok" | "warn" | "fail" | "skipped";
+
+export type DiagnosticCheck = {
+  name: string;
+  status: CheckStatus;
+  detail: string;
+};
+
+export type DiagnosticsReport = {
+  generated_at: string;
+  version: string;
+  about: AboutInfo;
+  checks: DiagnosticCheck[];
+};
+
+export async function runDiagnostics(): Promise<DiagnosticsReport> {
+  return await invoke<DiagnosticsReport>("run_diagnostics");
+}
+
+export function formatDiagnostics(report: DiagnosticsReport): string {
+  const lines = report.checks.map((c) => `[${c.status.toUpperCase()}] ${c.name}: ${c.detail}`);
+  return [`helltime ${report.version} diagnostics (${report.generated_at})`, formatAbout(report.about), ...lines].join("\n");
+}
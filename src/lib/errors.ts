@@ -0,0 +1,30 @@
+/** Category of a command error (`AppError` in the backend). */
+export type AppErrorKind =
+  | "network"
+  | "parse"
+  | "rateLimited"
+  | "overlayUnavailable"
+  | "invalidSettings"
+  | "settingsCorrupt"
+  | "storage"
+  | "shuttingDown"
+  | "other";
+
+/** What commands reject with; older commands still reject with a plain string. */
+export type AppError = {
+  kind: AppErrorKind;
+  message: string;
+  /** Only for `rateLimited`. */
+  retryAfterSecs?: number;
+};
+
+export function isAppError(e: unknown, kind?: AppErrorKind): e is AppError {
+  if (typeof e !== "object" || e === null) return false;
+  const err = e as Partial<AppError>;
+  return typeof err.kind === "string" && typeof err.message === "string" && (!kind || err.kind === kind);
+}
+
+/** Message for display, whatever the command rejected with. */
+export function errorText(e: unknown): string {
+  return isAppError(e) ? e.message : String(e);
+}
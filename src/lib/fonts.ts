@@ -0,0 +1,11 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+
+/** Installed font families (sorted), or none outside Tauri / when enumeration fails. */
+export async function listFonts(): Promise<string[]> {
+  if (!isTauri()) return [];
+  try {
+    return await invoke<string[]>("list_fonts");
+  } catch {
+    return [];
+  }
+}
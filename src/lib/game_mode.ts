@@ -0,0 +1,26 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+const CLASS_NAME = "gameRunning";
+
+/**
+ * While Diablo IV runs (`game:running` from the backend) the document gets the
+ * `gameRunning` class, which pauses CSS animations and transitions.
+ */
+export function followGameMode(): () => void {
+  if (!isTauri()) return () => {};
+  const apply = (running: boolean) => document.documentElement.classList.toggle(CLASS_NAME, running);
+  let unlisten: (() => void) | null = null;
+  let disposed = false;
+  void invoke<boolean>("game_running")
+    .then(apply)
+    .catch(() => {});
+  void listen<boolean>("game:running", (event) => apply(event.payload)).then((fn) => {
+    if (disposed) fn();
+    else unlisten = fn;
+  });
+  return () => {
+    disposed = true;
+    unlisten?.();
+  };
+}
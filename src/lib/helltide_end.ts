@@ -0,0 +1,17 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+
+/** "Helltide ends soon", planned by the backend from the schedule and the season's cadence. */
+export type EndReminder = {
+  helltideId: number;
+  end: string; // ISO
+  fireAt: string; // ISO
+};
+
+export async function getHelltideEndReminders(minutesBefore: number): Promise<EndReminder[]> {
+  if (!isTauri()) return [];
+  try {
+    return await invoke<EndReminder[]>("helltide_end_reminders", { minutesBefore });
+  } catch {
+    return [];
+  }
+}
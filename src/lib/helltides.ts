@@ -1,7 +1,45 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { ScheduleResponse } from "./types";
+import type { ScheduleResponse, ScheduleType } from "./types";
+import { setClockOffsetMs } from "./time";
+
+type ClockStatus = {
+  offset_ms: number;
+  samples: number;
+  server_now: string;
+};
+
+/** A schedule plus where it came from (`refresh_schedule`). */
+export type ScheduleRefresh = {
+  schedule: ScheduleResponse;
+  servedFromCache: boolean;
+  fetchedAt: string | null;
+  provider: string;
+};
+
+/** Payload of `schedule:changed`: upcoming events that moved or disappeared. */
+export type ScheduleDiff = {
+  shifted: Array<{ kind: ScheduleType; id: number; from: string; to: string }>;
+  cancelled: Array<{ kind: ScheduleType; id: number; start: string }>;
+};
+
+async function syncClock(): Promise<void> {
+  try {
+    const clock = await invoke<ClockStatus>("clock_status");
+    setClockOffsetMs(clock.offset_ms);
+  } catch {
+    // keep the previous offset
+  }
+}
 
 export async function fetchSchedule(): Promise<ScheduleResponse> {
-  return await invoke<ScheduleResponse>("fetch_schedule");
+  const data = await invoke<ScheduleResponse>("fetch_schedule");
+  await syncClock();
+  return data;
 }
 
+/** `force` skips the backend cache; the rate limit still applies. */
+export async function refreshSchedule(force: boolean): Promise<ScheduleRefresh> {
+  const data = await invoke<ScheduleRefresh>("refresh_schedule", { force });
+  await syncClock();
+  return data;
+}
@@ -0,0 +1,36 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { ScheduleType } from "./types";
+
+export type HistoryEntry = {
+  kind: ScheduleType;
+  id: number;
+  start: string; // ISO
+  boss: string | null;
+  zone: string | null;
+  observed_at: string; // ISO
+  /** Marked with "I'm there" (`markAttending`). */
+  attended: boolean;
+};
+
+export type EventStats = {
+  total: Partial<Record<ScheduleType, number>>;
+  today: Partial<Record<ScheduleType, number>>;
+  average_interval_secs: Partial<Record<ScheduleType, number>>;
+  attended: Partial<Record<ScheduleType, number>>;
+  boss_rotation: Array<{ start: string; boss: string }>;
+};
+
+export async function fetchEventHistory(from?: Date, to?: Date): Promise<HistoryEntry[]> {
+  return await invoke<HistoryEntry[]>("event_history", {
+    range: { from: from?.toISOString() ?? null, to: to?.toISOString() ?? null }
+  });
+}
+
+export async function fetchEventStats(): Promise<EventStats> {
+  return await invoke<EventStats>("event_stats");
+}
+
+/** "I'm at the event": mutes the occurrence's remaining reminders and counts it as attended. */
+export async function markAttending(type: ScheduleType, eventId: number): Promise<HistoryEntry> {
+  return await invoke<HistoryEntry>("mark_attending", { eventId, kind: type });
+}
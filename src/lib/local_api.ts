@@ -0,0 +1,13 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+
+/** Start/stop the localhost integration API; resolves to the bound port or null when off. */
+export async function setLocalApi(enabled: boolean, port: number): Promise<number | null> {
+  if (!isTauri()) return null;
+  return await invoke<number | null>("set_local_api", { enabled, port });
+}
+
+/** Toggle Discord Rich Presence; resolves to false when this build has no Discord app id. */
+export async function setDiscordPresence(enabled: boolean): Promise<boolean> {
+  if (!isTauri()) return false;
+  return await invoke<boolean>("set_discord_presence", { enabled });
+}
@@ -0,0 +1,26 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/** Counters since the app started; nothing is persisted. */
+export type MetricsSnapshot = {
+  fetchAttempts: number;
+  fetchSuccesses: number;
+  fetchFailures: number;
+  averageFetchLatencyMs: number | null;
+  cacheHits: number;
+  remindersFired: number;
+  overlayShows: number;
+};
+
+export async function getMetrics(): Promise<MetricsSnapshot> {
+  return await invoke<MetricsSnapshot>("get_metrics");
+}
+
+export function formatMetrics(m: MetricsSnapshot): string {
+  return [
+    `Abrufe: ${m.fetchAttempts} (ok ${m.fetchSuccesses}, fehlgeschlagen ${m.fetchFailures})`,
+    `Ø Abrufdauer: ${m.averageFetchLatencyMs == null ? "—" : `${m.averageFetchLatencyMs} ms`}`,
+    `Cache-Treffer: ${m.cacheHits}`,
+    `Erinnerungen: ${m.remindersFired}`,
+    `Overlay eingeblendet: ${m.overlayShows}`
+  ].join("\n");
+}
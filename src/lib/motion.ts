@@ -0,0 +1,27 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+const CLASS_NAME = "reducedMotion";
+
+/**
+ * While reduced motion is on (`motion:reduced` from the backend, from the preference
+ * or the system's "Show animations" setting) the document gets the `reducedMotion`
+ * class, which turns off CSS animations and transitions.
+ */
+export function followReducedMotion(): () => void {
+  if (!isTauri()) return () => {};
+  const apply = (reduced: boolean) => document.documentElement.classList.toggle(CLASS_NAME, reduced);
+  let unlisten: (() => void) | null = null;
+  let disposed = false;
+  void invoke<boolean>("reduced_motion")
+    .then(apply)
+    .catch(() => {});
+  void listen<boolean>("motion:reduced", (event) => apply(event.payload)).then((fn) => {
+    if (disposed) fn();
+    else unlisten = fn;
+  });
+  return () => {
+    disposed = true;
+    unlisten?.();
+  };
+}
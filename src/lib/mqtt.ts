@@ -0,0 +1,14 @@
+/** Countdowns and "event active" states published to an MQTT broker; validated by the backend only while enabled. */
+export type MqttSettings = {
+  enabled: boolean;
+  /** Host name or address; plain TCP, no TLS. */
+  host: string;
+  port: number;
+  username: string | null;
+  password: string | null;
+  /** Topics are `<prefix>/<event>/next_start`, `…/seconds_until`, `…/active` and `<prefix>/status`. */
+  topicPrefix: string;
+  /** Publish Home Assistant discovery configs under `discoveryPrefix`. */
+  discovery: boolean;
+  discoveryPrefix: string;
+};
@@ -0,0 +1,19 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+import type { ScheduleType } from "./types";
+
+/** Sent with `notification:activated` when the user clicks a reminder notification. */
+export type NotificationTarget = {
+  kind: ScheduleType;
+  id: number;
+};
+
+/** Desktop notification through the backend, so clicking it can bring the app back. */
+export async function notifyEvent(title: string, body: string, target: NotificationTarget): Promise<void> {
+  if (!isTauri()) return;
+  try {
+    await invoke("notify_event", { title, body, kind: target.kind, id: target.id });
+  } catch (e) {
+    // eslint-disable-next-line no-console
+    console.warn("notify_event failed", e);
+  }
+}
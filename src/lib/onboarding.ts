@@ -0,0 +1,40 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+
+export type OnboardingStep = "notifications" | "autostart" | "overlay_position";
+
+/** First-run state from the backend (`onboarding_status`). */
+export type OnboardingStatus = {
+  completed: boolean;
+  notificationPermission: "granted" | "denied" | "prompt" | "unknown";
+  /** null where autostart is not supported. */
+  autostart: boolean | null;
+  steps: OnboardingStep[];
+};
+
+export async function getOnboardingStatus(): Promise<OnboardingStatus | null> {
+  if (!isTauri()) return null;
+  try {
+    return await invoke<OnboardingStatus>("onboarding_status");
+  } catch {
+    return null;
+  }
+}
+
+export async function completeOnboarding(): Promise<void> {
+  await invoke("complete_onboarding");
+}
+
+/** Positioning step: the backend keeps the overlay clickable with its handles for a minute. */
+export async function enterOverlayConfig(): Promise<void> {
+  await invoke("enter_config");
+}
+
+export async function setAutostart(enabled: boolean): Promise<void> {
+  await invoke("set_autostart", { enabled });
+}
+
+/** Show the OS notification prompt; resolves to whether notifications are allowed now. */
+export async function requestNotificationPermission(): Promise<boolean> {
+  const { requestPermission } = await import("@tauri-apps/plugin-notification");
+  return (await requestPermission()) === "granted";
+}
@@ -1,12 +1,11 @@
-import { isTauri } from "@tauri-apps/api/core";
-import type { Settings } from "./settings";
+import { invoke, isTauri } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type { HudLayout, Settings } from "./settings";
 import type { ScheduleType } from "./types";
 import { pushOverlayDiag } from "./overlay_diag";
+import { errorText } from "./errors";
 
 export const OVERLAY_WINDOW_LABEL = "overlay";
-const OVERLAY_WINDOW_VERSION_KEY = "helltime:overlayWindowVersion";
-const OVERLAY_WINDOW_VERSION = "10";
-
 const ENSURE_INFLIGHT_KEY = "__helltimeEnsureOverlayWindowInFlight";
 
 function getEnsureInFlight(): Promise<void> | null {
@@ -17,123 +16,17 @@ function setEnsureInFlight(promise: Promise<void> | null): void {
   (globalThis as any)[ENSURE_INFLIGHT_KEY] = promise;
 }
 
+/** The backend creates the overlay window (hidden, click-through) with fixed options. */
 export async function ensureOverlayWindow(): Promise<void> {
   if (!isTauri()) return;
   const existingInFlight = getEnsureInFlight();
   if (existingInFlight) return existingInFlight;
   pushOverlayDiag("ensureOverlayWindow()");
-  const ensurePromise = (async () => {
-    const { WebviewWindow } = await import("@tauri-apps/api/webviewWindow");
-    const existing = await WebviewWindow.getByLabel(OVERLAY_WINDOW_LABEL);
-    if (existing) {
-      const version = (() => {
-        try {
-          return localStorage.getItem(OVERLAY_WINDOW_VERSION_KEY);
-        } catch {
-          return null;
-        }
-      })();
-
-      if (version === OVERLAY_WINDOW_VERSION) {
-        // Always enforce workspace behavior even when the window already exists.
-        // This prevents "sticky across desktops" behavior after runtime updates or persisted OS state.
-        try {
-          await existing.setVisibleOnAllWorkspaces(false);
-        } catch {
-          // ignore
-        }
-        pushOverlayDiag("ensureOverlayWindow: already exists");
-        return;
-      }
-
-      // Recreate once when options change (e.g. transparency).
-      try {
-        await existing.destroy();
-      } catch {
-        // ignore
-      }
-    }
-
-    const win = new WebviewWindow(OVERLAY_WINDOW_LABEL, {
-      title: "helltime overlay",
-      url: "/?view=overlay",
-      center: false,
-      // Best-effort: keep the overlay on the current virtual desktop.
-      // (On Windows this might be ignored depending on runtime support.)
-      visibleOnAllWorkspaces: false,
-      x: 40,
-      y: 40,
-      width: 260,
-      height: 130,
-      decorations: false,
-      alwaysOnTop: true,
-      skipTaskbar: true,
-      shadow: false,
-      resizable: false,
-      focus: false,
-      focusable: true,
-      transparent: true,
-      visible: false
-    });
-
-    const created = new Promise<void>((resolve, reject) => {
-      win.once("tauri://created", () => resolve());
-      win.once("tauri://error", (e) => reject(e));
-    });
-
-    try {
-      await created;
-      pushOverlayDiag("overlay window created");
-    } catch (e) {
-      const msg = String((e as any)?.payload ?? e);
-      pushOverlayDiag(`overlay window error: ${msg}`);
-      // If another ensure call created it first, treat as success.
-      if (msg.includes("already exists")) return;
-      const already = await WebviewWindow.getByLabel(OVERLAY_WINDOW_LABEL);
-      if (already) return;
-      throw e;
-    }
-
-    // Make it non-blocking by default: click-through overlay.
-    try {
-      await win.setIgnoreCursorEvents(true);
-    } catch {
-      // ignore
-    }
-
-    try {
-      await win.setBackgroundColor([0, 0, 0, 0]);
-      await win.setAlwaysOnTop(true);
-    } catch {
-      // ignore
-    }
-
-    // Best-effort: keep it confined to the current workspace.
-    try {
-      await win.setVisibleOnAllWorkspaces(false);
-    } catch {
-      // ignore
-    }
-
-    // Best-effort to keep it reachable even if persistence is borked.
-    try {
-      const { LogicalPosition, LogicalSize } = await import("@tauri-apps/api/dpi");
-      await win.setSize(new LogicalSize(260, 130));
-      await win.setPosition(new LogicalPosition(40, 40));
-    } catch {
-      // ignore
-    }
-
-    try {
-      localStorage.setItem(OVERLAY_WINDOW_VERSION_KEY, OVERLAY_WINDOW_VERSION);
-    } catch {
-      // ignore
-    }
-  })()
+  const ensurePromise = invoke<void>("overlay_create")
     .catch((e) => {
       // eslint-disable-next-line no-console
       console.warn("ensureOverlayWindow failed", e);
-      pushOverlayDiag(`ensureOverlayWindow failed: ${String(e)}`);
+      pushOverlayDiag(`ensureOverlayWindow failed: ${errorText(e)}`);
     })
     .finally(() => {
       setEnsureInFlight(null);
@@ -147,27 +40,11 @@ export async function setOverlayWindowVisible(visible: boolean): Promise<void> {
   if (!isTauri()) return;
   pushOverlayDiag(`setOverlayWindowVisible(${visible})`);
   try {
-    const { WebviewWindow } = await import("@tauri-apps/api/webviewWindow");
-    const win = await WebviewWindow.getByLabel(OVERLAY_WINDOW_LABEL);
-    if (!win) {
-      pushOverlayDiag("setOverlayWindowVisible: no window");
-      return;
-    }
-    if (visible) {
-      await win.show();
-      await win.setAlwaysOnTop(true);
-      try {
-        await win.setVisibleOnAllWorkspaces(false);
-      } catch {
-        // ignore
-      }
-    } else {
-      await win.hide();
-    }
+    await invoke(visible ? "overlay_show" : "overlay_hide");
   } catch (e) {
     // eslint-disable-next-line no-console
     console.warn("setOverlayWindowVisible failed", e);
-    pushOverlayDiag(`setOverlayWindowVisible failed: ${String(e)}`);
+    pushOverlayDiag(`setOverlayWindowVisible failed: ${errorText(e)}`);
   }
 }
 
@@ -175,31 +52,20 @@ export async function setOverlayWindowInteractive(interactive: boolean): Promise
   if (!isTauri()) return;
   pushOverlayDiag(`setOverlayWindowInteractive(${interactive})`);
   try {
-    const { WebviewWindow } = await import("@tauri-apps/api/webviewWindow");
-    let win = await WebviewWindow.getByLabel(OVERLAY_WINDOW_LABEL);
-    if (!win && interactive) {
-      await ensureOverlayWindow();
-      win = await WebviewWindow.getByLabel(OVERLAY_WINDOW_LABEL);
-    }
-    if (!win) return;
     // interactive=true => accept clicks; interactive=false => click-through
-    await win.setIgnoreCursorEvents(!interactive);
+    await invoke("overlay_set_click_through", { enabled: !interactive });
     if (interactive) {
-      await win.show();
-      await win.setAlwaysOnTop(true);
-      try {
-        await win.setVisibleOnAllWorkspaces(false);
-      } catch {
-        // ignore
-      }
+      await invoke("overlay_show");
+      const { WebviewWindow } = await import("@tauri-apps/api/webviewWindow");
+      const win = await WebviewWindow.getByLabel(OVERLAY_WINDOW_LABEL);
       try {
-        await win.setFocus();
+        await win?.setFocus();
       } catch {
         // ignore
       }
     }
   } catch (e) {
-    pushOverlayDiag(`setOverlayWindowInteractive failed: ${String(e)}`);
+    pushOverlayDiag(`setOverlayWindowInteractive failed: ${errorText(e)}`);
   }
 }
 
@@ -254,7 +120,7 @@ export async function getOverlayWindowDebugStatus(): Promise<OverlayWindowDebugS
       title: null,
       pos: null,
       size: null,
-      error: String(e)
+      error: errorText(e)
     };
   }
 }
@@ -286,7 +152,7 @@ export async function resetOverlayWindowBounds(): Promise<void> {
     await win.show();
     await win.setAlwaysOnTop(true);
   } catch (e) {
-    pushOverlayDiag(`resetOverlayWindowBounds failed: ${String(e)}`);
+    pushOverlayDiag(`resetOverlayWindowBounds failed: ${errorText(e)}`);
   }
 }
 
@@ -298,18 +164,159 @@ export async function destroyOverlayWindow(): Promise<void> {
     if (!win) return;
     await win.destroy();
   } catch (e) {
-    pushOverlayDiag(`destroyOverlayWindow failed: ${String(e)}`);
+    pushOverlayDiag(`destroyOverlayWindow failed: ${errorText(e)}`);
+  }
+}
+
+/** `overlay:position-changed` payload, in physical pixels. */
+export type OverlayPosition = { x: number; y: number };
+
+/** Backend overlay status, pushed as `overlay:status` whenever it changes. */
+export type OverlayStatus = {
+  requested: "webview" | "native";
+  engine: "webview" | "native";
+  /** False while this build has no native engine; the choice is then hidden. */
+  nativeAvailable: boolean;
+  exists: boolean;
+  visible: boolean;
+  clickThrough: boolean;
+  scheduledVisible: boolean;
+  /** Left out of screen capture per `overlayCaptureHidden`. */
+  captureHidden: boolean;
+  position: OverlayPosition | null;
+  lastError: string | null;
+  /** `wine`: opaque window with simplified styles for Wine/Proton. */
+  compat: "standard" | "wine";
+  wineVersion: string | null;
+  degraded: Array<"transparency" | "click_through_hit_test">;
+};
+
+/** Overlay spots clear of the game's HUD, and the HUD elements the overlay covers now. */
+export type HudPlacement = {
+  suggestions: Array<{ zone: string; x: number; y: number }>;
+  covered: string[];
+};
+
+export const HUD_ZONE_LABELS: Record<string, string> = {
+  top_left: "Oben links",
+  top_center: "Oben Mitte",
+  right_middle: "Rechts unter den Zielen",
+  bottom_center: "Unten Mitte",
+  bottom_right: "Unten rechts"
+};
+
+export const HUD_ELEMENT_LABELS: Record<string, string> = {
+  minimap: "Minikarte",
+  objectives: "Ziele",
+  party: "Gruppe",
+  chat: "Chat",
+  health_globe: "Lebenskugel",
+  skill_bar: "Fertigkeitsleiste",
+  resource_globe: "Ressourcenkugel"
+};
+
+export async function getHudPlacement(layout: HudLayout): Promise<HudPlacement | null> {
+  if (!isTauri()) return null;
+  try {
+    return await invoke<HudPlacement>("overlay_hud_placement", { layout });
+  } catch (e) {
+    pushOverlayDiag(`getHudPlacement failed: ${errorText(e)}`);
+    return null;
   }
 }
 
+/** Move the overlay into a safe zone of `layout` (see `HUD_ZONE_LABELS`). */
+export async function setOverlayAnchor(layout: HudLayout, zone: string): Promise<void> {
+  if (!isTauri()) return;
+  pushOverlayDiag(`setOverlayAnchor(${layout}, ${zone})`);
+  try {
+    await invoke("overlay_set_anchor", { layout, zone });
+  } catch (e) {
+    pushOverlayDiag(`setOverlayAnchor failed: ${errorText(e)}`);
+  }
+}
+
+/** Move the overlay, in physical pixels. */
+export async function setOverlayWindowPosition(x: number, y: number): Promise<void> {
+  if (!isTauri()) return;
+  pushOverlayDiag(`setOverlayWindowPosition(${x}, ${y})`);
+  try {
+    await invoke("overlay_set_position", { x, y });
+  } catch (e) {
+    pushOverlayDiag(`setOverlayWindowPosition failed: ${errorText(e)}`);
+  }
+}
+
+/** Leave positioning mode early; the overlay becomes click-through again. */
+export async function endOverlayPositioning(): Promise<void> {
+  try {
+    localStorage.setItem("helltime:overlayPositioningUntil", "0");
+  } catch {
+    // ignore
+  }
+  await setOverlayWindowInteractive(false);
+}
+
+/** The overlay's own text, from the backend's i18n (`overlay_texts`). */
+export type OverlayTexts = {
+  moveLabel: string;
+  dragHint: string;
+  drag: string;
+  error: string;
+  /** Category titles used when an event has no better title. */
+  labels: Record<ScheduleType, string>;
+};
+
+/** Outside Tauri, where the backend cannot be asked. */
+export const DEFAULT_OVERLAY_TEXTS: OverlayTexts = {
+  moveLabel: "Move overlay",
+  dragHint: "Drag to move",
+  drag: "drag",
+  error: "Error",
+  labels: { helltide: "Helltide", legion: "Legion", world_boss: "World Boss" }
+};
+
+/** Calls `apply` with the overlay text now and again after every `locale:changed`. */
+export function followOverlayTexts(apply: (texts: OverlayTexts) => void): () => void {
+  if (!isTauri()) return () => {};
+  let unlisten: (() => void) | null = null;
+  let disposed = false;
+  const refresh = () =>
+    void invoke<OverlayTexts>("overlay_texts")
+      .then((texts) => {
+        if (!disposed) apply(texts);
+      })
+      .catch(() => {});
+  refresh();
+  void listen("locale:changed", refresh).then((fn) => {
+    if (disposed) fn();
+    else unlisten = fn;
+  });
+  return () => {
+    disposed = true;
+    unlisten?.();
+  };
+}
+
+/** `overlay:resized` payload; the main window persists it into the settings. */
+export type OverlayResized = { width: number; height: number };
+
 export type OverlayWindowSettings = {
   enabled: boolean;
-  mode: "overview" | "toast";
+  mode: Settings["overlayWindowMode"];
   categories: Record<ScheduleType, boolean>;
   bgHex: string;
   bgOpacity: number;
   scaleX: number;
   scaleY: number;
+  textShadow: Settings["overlayTextShadow"];
+  theme: Settings["overlayTheme"];
+  fontFamily: string;
+  fontWeight: number;
+  sizePreset: Settings["overlaySizePreset"];
+  wrapText: boolean;
+  width: number;
+  height: number;
 };
 
 export function toOverlayWindowSettings(settings: Settings): OverlayWindowSettings {
@@ -320,7 +327,15 @@ export function toOverlayWindowSettings(settings: Settings): OverlayWindowSettin
     bgHex: settings.overlayBgHex,
     bgOpacity: settings.overlayBgOpacity,
     scaleX: settings.overlayScaleX,
-    scaleY: settings.overlayScaleY
+    scaleY: settings.overlayScaleY,
+    textShadow: settings.overlayTextShadow,
+    theme: settings.overlayTheme,
+    fontFamily: settings.overlayFontFamily,
+    fontWeight: settings.overlayFontWeight,
+    sizePreset: settings.overlaySizePreset,
+    wrapText: settings.overlayWrapText,
+    width: settings.overlayWidth,
+    height: settings.overlayHeight
   };
 }
 
@@ -328,16 +343,18 @@ export async function broadcastOverlayWindowSettings(settings: Settings): Promis
   if (!isTauri()) return;
   pushOverlayDiag("broadcastOverlayWindowSettings()");
   try {
-    const { emit, emitTo } = await import("@tauri-apps/api/event");
+    const { emit } = await import("@tauri-apps/api/event");
     const payload = toOverlayWindowSettings(settings);
     try {
-      await emitTo(OVERLAY_WINDOW_LABEL, "helltime:overlay-settings", payload);
-    } catch {
+      // Through the overlay facade, so whichever engine draws the overlay gets it.
+      await invoke("overlay_apply_style", { style: payload });
+    } catch (e) {
+      pushOverlayDiag(`overlay_apply_style failed: ${errorText(e)}`);
       await emit("helltime:overlay-settings", payload);
     }
   } catch (e) {
     // eslint-disable-next-line no-console
     console.warn("broadcastOverlayWindowSettings failed", e);
-    pushOverlayDiag(`broadcastOverlayWindowSettings failed: ${String(e)}`);
+    pushOverlayDiag(`broadcastOverlayWindowSettings failed: ${errorText(e)}`);
   }
 }
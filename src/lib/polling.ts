@@ -0,0 +1,21 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+
+/** Schedule polling; adaptive polls every minute right before an event and slower otherwise. */
+export type PollSettings = {
+  mode: "fixed" | "adaptive";
+  intervalMinutes: number; // 1-60
+  /** Courtesy limit for helltides.com, retries included. */
+  maxRequestsPerMinute: number; // 1-60
+  /** How long a fetched schedule is reused without asking the API. */
+  cacheTtlSeconds: number; // 5-600
+};
+
+/** Milliseconds until the next poll as decided by the backend, `null` outside Tauri. */
+export async function nextPollDelay(): Promise<number | null> {
+  if (!isTauri()) return null;
+  try {
+    return await invoke<number>("next_poll_delay");
+  } catch {
+    return null;
+  }
+}
@@ -0,0 +1,38 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+/** Whether power saving is on, and what the system reports. */
+export type PowerStatus = { saving: boolean; onBattery: boolean; metered: boolean };
+
+/** How often the overlay countdown repaints while saving power. */
+export const POWER_SAVING_TICK_MS = 15_000;
+
+export async function getPowerStatus(): Promise<PowerStatus | null> {
+  if (!isTauri()) return null;
+  try {
+    return await invoke<PowerStatus>("power_status");
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Calls `apply` with the current power-saving state and again on every
+ * `power:saving` from the backend (battery, metered connection or the preference).
+ */
+export function followPowerSaving(apply: (saving: boolean) => void): () => void {
+  if (!isTauri()) return () => {};
+  let unlisten: (() => void) | null = null;
+  let disposed = false;
+  void getPowerStatus().then((status) => {
+    if (status && !disposed) apply(status.saving);
+  });
+  void listen<boolean>("power:saving", (event) => apply(event.payload)).then((fn) => {
+    if (disposed) fn();
+    else unlisten = fn;
+  });
+  return () => {
+    disposed = true;
+    unlisten?.();
+  };
+}
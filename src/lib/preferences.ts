@@ -0,0 +1,167 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+import type { MqttSettings } from "./mqtt";
+import type { PollSettings } from "./polling";
+import type { PushSettings } from "./push";
+import type { Escalation, ReminderTimes } from "./reminders";
+import type { ShortcutAction } from "./shortcuts";
+import type { TelemetrySettings } from "./telemetry";
+import type { WebhookSettings } from "./webhook";
+import type { ToastTemplates } from "./toast";
+import type { ScheduleType } from "./types";
+
+/** Preferences persisted by the backend (needed before the UI loads, or by the tray). */
+export type Preferences = {
+  closeBehavior: "hide_to_tray" | "exit";
+  minimizeToTray: boolean;
+  startMinimized: boolean;
+  /** Categories left out everywhere: reminders, tray, overlay and integrations. */
+  hiddenEvents: ScheduleType[];
+  poll: PollSettings;
+  /** Check the repository for a newer season cadence every 12 hours. */
+  cadenceUpdates: boolean;
+  network: NetworkSettings;
+  /** "native" falls back to the webview overlay where it is not available. */
+  overlayEngine: "webview" | "native";
+  /** Overlay on/off; also toggled from the tray. */
+  overlayEnabled: boolean;
+  /** Times of day the overlay may be shown at all; enforced by the backend. */
+  overlayVisibility: VisibilitySchedule;
+  /** Keep the overlay out of screenshots and screen shares (OBS, Discord). */
+  overlayCaptureHidden: boolean;
+  /** Fade the overlay to 20% while the cursor rests on it (after 300 ms). */
+  overlayHoverFade: boolean;
+  /** Master switch for reminders (tray "Reminder"); category toggles apply on top. */
+  remindersEnabled: boolean;
+  /** The settings' lead times, written by the UI; the backend uses them while headless. */
+  reminderTimes: ReminderTimes;
+  /** Overlay toast, then notification and sound, then a repeated sound until acknowledged. */
+  reminderEscalation: Escalation;
+  /** Reminders also sent to phones through ntfy or Pushover, queued while offline. */
+  push: PushSettings;
+  /** JSON posted to a user URL on events starting, schedule changes and fetch errors. */
+  webhook: WebhookSettings;
+  /** Countdowns and event states for smart-home dashboards, with Home Assistant discovery. */
+  mqtt: MqttSettings;
+  /** Throttle background work and lower priority while Diablo IV runs. */
+  gameMode: boolean;
+  /** "auto" saves power on battery or metered connections: fewer polls, slower overlay countdown. */
+  powerSaving: PowerSaving;
+  /** Language of tray and other backend text; null follows the system. */
+  locale: Locale | null;
+  /** Shared zone shown next to local times and used by the .ics export; null = none. */
+  secondaryZone: SecondaryZone | null;
+  /** Folder shared with other PCs that settings are synced through; unset = off. */
+  syncDir?: string;
+  /** Start without the main window (tray and backend reminders only); applies on the next start. */
+  headless: boolean;
+  /** Destroy the hidden main window after this many minutes to free memory; null = only hide. */
+  unloadAfterMinutes: number | null;
+  /** Tray icon variant; "auto" follows the taskbar's light/dark theme. */
+  trayIconStyle: TrayIconStyle;
+  /** Pulse the tray icon 2 minutes before shown events and while a Helltide runs. */
+  trayPulse: boolean;
+  /** Title and text of the overlay's event toasts; rejected on save if invalid. */
+  toastTemplates: ToastTemplates;
+  /** "auto" follows the system's "Show animations"; turns off animations and the tray pulse. */
+  reducedMotion: ReducedMotion;
+  /** Global hotkeys differing from the defaults, e.g. "Ctrl+Alt+N"; "" turns one off. */
+  shortcuts: Partial<Record<ShortcutAction, string>>;
+  /** Anonymous usage counters (features used, error categories); off unless opted in. */
+  telemetry: TelemetrySettings;
+};
+
+/** A fixed UTC offset (no DST), e.g. `{ label: "CET", offsetMinutes: 60 }`. */
+export type SecondaryZone = {
+  label: string; // 1..12 characters
+  offsetMinutes: number; // -720..840
+};
+
+export type TrayIconStyle = "auto" | "light" | "dark" | "monochrome";
+
+export type ReducedMotion = "auto" | "on" | "off";
+
+export type PowerSaving = "auto" | "on" | "off";
+
+export type Locale = "en" | "de";
+
+export type Weekday = "Mon" | "Tue" | "Wed" | "Thu" | "Fri" | "Sat" | "Sun";
+
+export const WEEKDAYS: Weekday[] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/** Local-time window; an end at or before the start runs past midnight. */
+export type VisibilityWindow = {
+  days: Weekday[]; // empty = every day
+  startMinute: number; // 0..1439
+  endMinute: number; // 0..1440
+};
+
+export type VisibilitySchedule = {
+  enabled: boolean;
+  windows: VisibilityWindow[];
+};
+
+/** "HH:MM" for an `<input type="time">`. */
+export function minutesToTime(minutes: number): string {
+  const m = ((minutes % 1440) + 1440) % 1440;
+  return `${String(Math.floor(m / 60)).padStart(2, "0")}:${String(m % 60).padStart(2, "0")}`;
+}
+
+export function timeToMinutes(value: string): number | null {
+  const match = /^(\d{1,2}):(\d{2})$/.exec(value);
+  if (!match) return null;
+  const minutes = Number(match[1]) * 60 + Number(match[2]);
+  return minutes < 1440 ? minutes : null;
+}
+
+/** Proxy, TLS and request header options for the backend's HTTP client. */
+export type NetworkSettings = {
+  proxyMode: "system" | "direct" | "manual";
+  proxyUrl?: string;
+  caBundle?: string; // path to a PEM file
+  insecureTls: boolean;
+  userAgent?: string; // unset = helltime's default
+  apiToken?: string; // sent as "Authorization: Bearer …"
+  extraHeaders: RequestHeader[];
+};
+
+export type RequestHeader = { name: string; value: string };
+
+/** "Name: value" per line; lines without a colon are dropped. */
+export function parseHeaderLines(text: string): RequestHeader[] {
+  return text
+    .split("\n")
+    .map((line) => {
+      const colon = line.indexOf(":");
+      if (colon <= 0) return null;
+      return { name: line.slice(0, colon).trim(), value: line.slice(colon + 1).trim() };
+    })
+    .filter((h): h is RequestHeader => h !== null && h.name.length > 0);
+}
+
+export function formatHeaderLines(headers: RequestHeader[]): string {
+  return headers.map((h) => `${h.name}: ${h.value}`).join("\n");
+}
+
+export async function getPreferences(): Promise<Preferences | null> {
+  if (!isTauri()) return null;
+  try {
+    return await invoke<Preferences>("get_preferences");
+  } catch {
+    return null;
+  }
+}
+
+export async function setPreferences(prefs: Preferences): Promise<void> {
+  await invoke("set_preferences", { prefs });
+}
+
+/** Switch backend text; resolves to the language now in use. */
+export async function setLocale(locale: Locale | null): Promise<Locale | null> {
+  if (!isTauri()) return null;
+  return await invoke<Locale>("set_locale", { locale });
+}
+
+/** Hidden categories, or none outside Tauri / when the backend is unavailable. */
+export function hiddenEvents(prefs: Preferences | null): ScheduleType[] {
+  return prefs?.hiddenEvents ?? [];
+}
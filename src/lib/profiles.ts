@@ -0,0 +1,41 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+import type { Settings } from "./settings";
+import type { ScheduleType } from "./types";
+
+/** A named configuration: event filter and overlay from the preferences, plus the UI's settings. */
+export type Profile = {
+  name: string;
+  hiddenEvents: ScheduleType[];
+  overlayEnabled: boolean;
+  overlayEngine: "webview" | "native";
+  /** UI settings (lead times, overlay style) when the profile was saved. */
+  uiSettings: unknown | null;
+};
+
+export type ProfileList = {
+  active: string | null;
+  profiles: Profile[];
+};
+
+export async function listProfiles(): Promise<ProfileList> {
+  if (!isTauri()) return { active: null, profiles: [] };
+  try {
+    return await invoke<ProfileList>("list_profiles");
+  } catch {
+    return { active: null, profiles: [] };
+  }
+}
+
+/** Save the current configuration as `name`, replacing a profile of the same name. */
+export async function saveProfile(name: string, settings: Settings): Promise<ProfileList> {
+  return invoke<ProfileList>("save_profile", { name, settings });
+}
+
+export async function deleteProfile(name: string): Promise<ProfileList> {
+  return invoke<ProfileList>("delete_profile", { name });
+}
+
+/** Switch to `name`; its UI settings arrive via `profile:activated`. */
+export async function activateProfile(name: string): Promise<void> {
+  await invoke("activate_profile", { name });
+}
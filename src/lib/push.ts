@@ -0,0 +1,26 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/** Reminders also sent to phones; validated by the backend only while enabled. */
+export type PushSettings = {
+  ntfy: {
+    enabled: boolean;
+    /** Own server; null = https://ntfy.sh. */
+    server: string | null;
+    /** Letters, digits, - and _; anyone knowing it can read along. */
+    topic: string;
+    /** Access token for protected topics. */
+    token: string | null;
+  };
+  pushover: {
+    enabled: boolean;
+    /** API token of the user's Pushover application (30 characters). */
+    appToken: string;
+    /** User or group key (30 characters). */
+    userKey: string;
+  };
+};
+
+/** Sends a test message to every enabled service; rejects with the services that failed. */
+export async function sendTestPush(): Promise<void> {
+  await invoke("send_test_push");
+}
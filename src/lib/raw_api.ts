@@ -0,0 +1,30 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/** The schedule API's last response exactly as received, for bug reports. */
+export type RawResponse = {
+  url: string;
+  receivedAt: string; // ISO
+  status: number;
+  headers: Array<[string, string]>;
+  body: string;
+  parseError: string | null;
+};
+
+export async function fetchScheduleRaw(refetch = false): Promise<RawResponse | null> {
+  return await invoke<RawResponse | null>("fetch_schedule_raw", { refetch });
+}
+
+/** Saves the last response to the data dir and returns the file path. */
+export async function saveRawPayload(): Promise<string> {
+  return await invoke<string>("save_raw_payload");
+}
+
+export function formatRawResponse(raw: RawResponse): string {
+  const head = [
+    `GET ${raw.url}`,
+    `HTTP ${raw.status} (${raw.receivedAt})`,
+    ...raw.headers.map(([name, value]) => `${name}: ${value}`),
+    raw.parseError ? `Parse-Fehler: ${raw.parseError}` : "Parse: ok"
+  ];
+  return [...head, "", raw.body].join("\n");
+}
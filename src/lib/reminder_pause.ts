@@ -0,0 +1,19 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+
+/** Reminder pause set from the tray; owned by the backend. */
+export type PauseStatus = {
+  pausedUntil: string | null; // ISO
+};
+
+export async function getReminderPause(): Promise<PauseStatus> {
+  if (!isTauri()) return { pausedUntil: null };
+  try {
+    return await invoke<PauseStatus>("reminder_pause_status");
+  } catch {
+    return { pausedUntil: null };
+  }
+}
+
+export async function resumeReminders(): Promise<void> {
+  await invoke("resume_reminders");
+}
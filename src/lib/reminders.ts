@@ -0,0 +1,69 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+import type { ScheduleType } from "./types";
+
+/**
+ * A reminder fires if the UI notices it no later than this after its time, as the
+ * backend's `reminders::FIRE_WINDOW`; later ones are skipped rather than shown late.
+ */
+export const FIRE_WINDOW_MS = 30_000;
+
+/** A reminder as planned by the backend's reminder engine (`reminder:test` payload). */
+export type PlannedReminder = {
+  event: {
+    kind: ScheduleType;
+    id: number;
+    start: string; // ISO
+    boss: string | null;
+    boss_predicted: boolean;
+    zone: string | null;
+  };
+  timer: number;
+  minutes_before: number;
+  fire_at: string; // ISO
+};
+
+/** Fire a reminder for a synthetic event one minute out; it arrives as `reminder:test`. */
+export async function testReminder(type: ScheduleType): Promise<PlannedReminder | null> {
+  if (!isTauri()) return null;
+  return await invoke<PlannedReminder>("test_reminder", { eventType: type });
+}
+
+/** The settings' timers, mirrored into the preferences for reminders the backend fires while headless. */
+export type ReminderTimes = {
+  helltide: number[];
+  legion: number[];
+  worldBoss: number[];
+  helltideEnd: number | null;
+  chest: number | null;
+};
+
+export type EscalationAction = "overlay_toast" | "notification" | "sound" | "repeat_sound";
+
+/** At `minutesBefore` the start, do `actions`. */
+export type EscalationRule = { minutesBefore: number; actions: EscalationAction[] };
+
+/** Replaces the per-category timers while enabled; rules go from the earliest step to the last. */
+export type Escalation = { enabled: boolean; rules: EscalationRule[] };
+
+export const MAX_ESCALATION_RULES = 5;
+
+/** `reminder:escalation` payload: one step to run now. */
+export type EscalationStep = {
+  reminder: PlannedReminder;
+  actions: EscalationAction[];
+  /** A repetition of a "repeat_sound" step. */
+  repeat: boolean;
+};
+
+/** Stops the event's escalation: its repeated sound and any later steps. */
+export async function acknowledgeReminder(kind: ScheduleType, id: number): Promise<void> {
+  if (!isTauri()) return;
+  await invoke("acknowledge_reminder", { kind, id });
+}
+
+export const ESCALATION_ACTION_LABELS: Record<EscalationAction, string> = {
+  overlay_toast: "Overlay-Hinweis",
+  notification: "Benachrichtigung",
+  sound: "Ton",
+  repeat_sound: "Ton bis bestätigt"
+};
@@ -1,9 +1,35 @@
+import type { ReminderTimes } from "./reminders";
 import type { ScheduleType } from "./types";
 import { readJson, writeJson } from "./storage";
 import { isPanicStopEnabled } from "./safety";
 
 export type BeepPattern = "beep" | "double" | "triple";
 
+/** Overlay text edge: soft glow (default), 8-direction outline, blurred shadow or none. */
+export type OverlayTextShadow = "soft" | "outline8" | "blur" | "none";
+export const OVERLAY_TEXT_SHADOWS: OverlayTextShadow[] = ["soft", "outline8", "blur", "none"];
+
+/** Overlay colors: the chosen background and category tints, or opaque black with
+ * text colors of at least 7:1 contrast (WCAG AAA). */
+export type OverlayTheme = "standard" | "highContrast";
+export const OVERLAY_THEMES: OverlayTheme[] = ["standard", "highContrast"];
+
+/** Diablo IV HUD arrangement the overlay position presets keep clear of. */
+export type HudLayout = "default" | "left_skill_bar";
+export const HUD_LAYOUTS: HudLayout[] = ["default", "left_skill_bar"];
+
+/** Base overlay size before the X/Y scale; the height still grows with the content. */
+export type OverlaySizePreset = "small" | "medium" | "large";
+export const OVERLAY_SIZE_PRESETS: Record<OverlaySizePreset, { width: number; height: number }> = {
+  small: { width: 220, height: 90 },
+  medium: { width: 280, height: 118 },
+  large: { width: 360, height: 150 }
+};
+
+/** Bounds for a size dragged in positioning mode (logical px). */
+export const OVERLAY_MIN_SIZE = { width: 170, height: 40 };
+export const OVERLAY_MAX_SIZE = { width: 1200, height: 900 };
+
 export type TimerSettings = {
   minutesBefore: number; // 1-60
   ttsEnabled: boolean;
@@ -25,12 +51,30 @@ export type Settings = {
   soundEnabled: boolean;
   autoRefreshEnabled: boolean;
   overlayWindowEnabled: boolean;
-  overlayWindowMode: "overview" | "toast";
+  overlayWindowMode: "overview" | "toast" | "minibar";
   overlayWindowCategories: Record<ScheduleType, boolean>;
   overlayBgHex: string; // "#rrggbb"
   overlayScaleX: number; // 0.6-2.0
   overlayScaleY: number; // 0.6-2.0
   overlayBgOpacity: number; // 0-1.0
+  overlayTextShadow: OverlayTextShadow;
+  overlayFontFamily: string; // "" = built-in font stack
+  overlayFontWeight: number; // 0 = built-in weights, else 100-900
+  overlaySizePreset: OverlaySizePreset;
+  overlayWrapText: boolean; // wrap long titles/zones instead of ellipsizing
+  overlayWidth: number; // 0 = preset × scale, else dragged width in logical px
+  overlayHeight: number; // 0 = preset × scale, else dragged minimum height
+  overlayTheme: OverlayTheme;
+  overlayHudLayout: HudLayout;
+  localApiEnabled: boolean;
+  localApiPort: number; // 1024-65535
+  discordPresenceEnabled: boolean;
+  chestReminderEnabled: boolean;
+  chestReminderMinutesBefore: number; // 1-30
+  helltideEndReminderEnabled: boolean;
+  helltideEndReminderMinutesBefore: number; // 1-30
+  desktopNotificationEnabled: boolean;
+  screenReaderAnnouncements: boolean; // announce reminders via live region and system notification
   categories: Record<ScheduleType, CategorySettings>;
 };
 
@@ -70,6 +114,24 @@ const defaults: Settings = {
   overlayScaleX: 1,
   overlayScaleY: 1,
   overlayBgOpacity: 0.2,
+  overlayTextShadow: "soft",
+  overlayFontFamily: "",
+  overlayFontWeight: 0,
+  overlaySizePreset: "medium",
+  overlayWrapText: false,
+  overlayWidth: 0,
+  overlayHeight: 0,
+  overlayTheme: "standard",
+  overlayHudLayout: "default",
+  localApiEnabled: false,
+  localApiPort: 47620,
+  discordPresenceEnabled: false,
+  chestReminderEnabled: false,
+  chestReminderMinutesBefore: 5,
+  helltideEndReminderEnabled: false,
+  helltideEndReminderMinutesBefore: 10,
+  desktopNotificationEnabled: false,
+  screenReaderAnnouncements: false,
   categories: {
     helltide: { ...defaultCategory(true), ttsName: "Höllenhochwasser" },
     legion: { ...defaultCategory(true), ttsName: "Legionellen" },
@@ -89,6 +151,18 @@ function normalizeHexColor(raw: any, fallback: string): string {
   return fallback;
 }
 
+function normalizeFontWeight(raw: unknown, fallback: number): number {
+  if (raw === 0) return 0;
+  const weight = clampInt(raw, fallback, 100, 900);
+  return weight === 0 ? 0 : Math.round(weight / 100) * 100;
+}
+
+/** 0 (or anything invalid) means "not set"; a set size is kept within bounds. */
+function normalizeOverlayDimension(raw: unknown, min: number, max: number): number {
+  if (typeof raw !== "number" || !Number.isFinite(raw) || raw <= 0) return 0;
+  return Math.round(Math.max(min, Math.min(max, raw)));
+}
+
 function clampUnit(n: unknown, fallback: number): number {
   if (typeof n !== "number" || !Number.isFinite(n)) return fallback;
   return Math.max(0, Math.min(1, n));
@@ -178,7 +252,8 @@ export function loadSettings(): Settings {
       autoRefreshEnabled: typeof raw.autoRefreshEnabled === "boolean" ? raw.autoRefreshEnabled : defaults.autoRefreshEnabled,
       overlayWindowEnabled:
         typeof raw.overlayWindowEnabled === "boolean" ? raw.overlayWindowEnabled : defaults.overlayWindowEnabled,
-      overlayWindowMode: raw.overlayWindowMode === "toast" ? "toast" : "overview",
+      overlayWindowMode:
+        raw.overlayWindowMode === "toast" || raw.overlayWindowMode === "minibar" ? raw.overlayWindowMode : "overview",
       overlayWindowCategories: {
         helltide:
           typeof rawOverlayCats.helltide === "boolean" ? rawOverlayCats.helltide : defaults.overlayWindowCategories.helltide,
@@ -193,6 +268,40 @@ export function loadSettings(): Settings {
       overlayScaleX: clampFloat(raw.overlayScaleX, clampFloat(raw.overlayScale, defaults.overlayScaleX, 0.6, 2.0), 0.6, 2.0),
       overlayScaleY: clampFloat(raw.overlayScaleY, clampFloat(raw.overlayScale, defaults.overlayScaleY, 0.6, 2.0), 0.6, 2.0),
       overlayBgOpacity: clampFloat(raw.overlayBgOpacity, defaults.overlayBgOpacity, 0, 1.0),
+      overlayTextShadow: OVERLAY_TEXT_SHADOWS.includes(raw.overlayTextShadow) ? raw.overlayTextShadow : defaults.overlayTextShadow,
+      overlayFontFamily: typeof raw.overlayFontFamily === "string" ? raw.overlayFontFamily.trim().slice(0, 64) : defaults.overlayFontFamily,
+      overlayFontWeight: normalizeFontWeight(raw.overlayFontWeight, defaults.overlayFontWeight),
+      overlaySizePreset: Object.prototype.hasOwnProperty.call(OVERLAY_SIZE_PRESETS, raw.overlaySizePreset) ? raw.overlaySizePreset : defaults.overlaySizePreset,
+      overlayWrapText: typeof raw.overlayWrapText === "boolean" ? raw.overlayWrapText : defaults.overlayWrapText,
+      overlayWidth: normalizeOverlayDimension(raw.overlayWidth, OVERLAY_MIN_SIZE.width, OVERLAY_MAX_SIZE.width),
+      overlayHeight: normalizeOverlayDimension(raw.overlayHeight, OVERLAY_MIN_SIZE.height, OVERLAY_MAX_SIZE.height),
+      overlayTheme: OVERLAY_THEMES.includes(raw.overlayTheme) ? raw.overlayTheme : defaults.overlayTheme,
+      overlayHudLayout: HUD_LAYOUTS.includes(raw.overlayHudLayout) ? raw.overlayHudLayout : defaults.overlayHudLayout,
+      localApiEnabled: typeof raw.localApiEnabled === "boolean" ? raw.localApiEnabled : defaults.localApiEnabled,
+      localApiPort: clampInt(raw.localApiPort, defaults.localApiPort, 1024, 65535),
+      discordPresenceEnabled:
+        typeof raw.discordPresenceEnabled === "boolean" ? raw.discordPresenceEnabled : defaults.discordPresenceEnabled,
+      chestReminderEnabled:
+        typeof raw.chestReminderEnabled === "boolean" ? raw.chestReminderEnabled : defaults.chestReminderEnabled,
+      chestReminderMinutesBefore: clampInt(raw.chestReminderMinutesBefore, defaults.chestReminderMinutesBefore, 1, 30),
+      helltideEndReminderEnabled:
+        typeof raw.helltideEndReminderEnabled === "boolean"
+          ? raw.helltideEndReminderEnabled
+          : defaults.helltideEndReminderEnabled,
+      helltideEndReminderMinutesBefore: clampInt(
+        raw.helltideEndReminderMinutesBefore,
+        defaults.helltideEndReminderMinutesBefore,
+        1,
+        30
+      ),
+      desktopNotificationEnabled:
+        typeof raw.desktopNotificationEnabled === "boolean"
+          ? raw.desktopNotificationEnabled
+          : defaults.desktopNotificationEnabled,
+      screenReaderAnnouncements:
+        typeof raw.screenReaderAnnouncements === "boolean"
+          ? raw.screenReaderAnnouncements
+          : defaults.screenReaderAnnouncements,
       categories: {
         helltide: normalizeCategory(rawCategories.helltide, defaults.categories.helltide),
         legion: normalizeCategory(rawCategories.legion, defaults.categories.legion),
@@ -221,6 +330,24 @@ export function loadSettings(): Settings {
       overlayScaleX: defaults.overlayScaleX,
       overlayScaleY: defaults.overlayScaleY,
       overlayBgOpacity: defaults.overlayBgOpacity,
+      overlayTextShadow: defaults.overlayTextShadow,
+      overlayFontFamily: defaults.overlayFontFamily,
+      overlayFontWeight: defaults.overlayFontWeight,
+      overlaySizePreset: defaults.overlaySizePreset,
+      overlayWrapText: defaults.overlayWrapText,
+      overlayWidth: defaults.overlayWidth,
+      overlayHeight: defaults.overlayHeight,
+      overlayTheme: defaults.overlayTheme,
+      overlayHudLayout: defaults.overlayHudLayout,
+      localApiEnabled: defaults.localApiEnabled,
+      localApiPort: defaults.localApiPort,
+      discordPresenceEnabled: defaults.discordPresenceEnabled,
+      chestReminderEnabled: defaults.chestReminderEnabled,
+      chestReminderMinutesBefore: defaults.chestReminderMinutesBefore,
+      helltideEndReminderEnabled: defaults.helltideEndReminderEnabled,
+      helltideEndReminderMinutesBefore: defaults.helltideEndReminderMinutesBefore,
+      desktopNotificationEnabled: defaults.desktopNotificationEnabled,
+      screenReaderAnnouncements: defaults.screenReaderAnnouncements,
       categories: {
         helltide: normalizeCategory(v4raw.categories?.helltide, defaults.categories.helltide),
         legion: normalizeCategory(v4raw.categories?.legion, defaults.categories.legion),
@@ -248,6 +375,24 @@ export function loadSettings(): Settings {
       overlayScaleX: defaults.overlayScaleX,
       overlayScaleY: defaults.overlayScaleY,
       overlayBgOpacity: defaults.overlayBgOpacity,
+      overlayTextShadow: defaults.overlayTextShadow,
+      overlayFontFamily: defaults.overlayFontFamily,
+      overlayFontWeight: defaults.overlayFontWeight,
+      overlaySizePreset: defaults.overlaySizePreset,
+      overlayWrapText: defaults.overlayWrapText,
+      overlayWidth: defaults.overlayWidth,
+      overlayHeight: defaults.overlayHeight,
+      overlayTheme: defaults.overlayTheme,
+      overlayHudLayout: defaults.overlayHudLayout,
+      localApiEnabled: defaults.localApiEnabled,
+      localApiPort: defaults.localApiPort,
+      discordPresenceEnabled: defaults.discordPresenceEnabled,
+      chestReminderEnabled: defaults.chestReminderEnabled,
+      chestReminderMinutesBefore: defaults.chestReminderMinutesBefore,
+      helltideEndReminderEnabled: defaults.helltideEndReminderEnabled,
+      helltideEndReminderMinutesBefore: defaults.helltideEndReminderMinutesBefore,
+      desktopNotificationEnabled: defaults.desktopNotificationEnabled,
+      screenReaderAnnouncements: defaults.screenReaderAnnouncements,
       categories: {
         helltide: normalizeCategory(v3.categories?.helltide, defaults.categories.helltide),
         legion: normalizeCategory(v3.categories?.legion, defaults.categories.legion),
@@ -279,6 +424,24 @@ export function loadSettings(): Settings {
       overlayScaleX: defaults.overlayScaleX,
       overlayScaleY: defaults.overlayScaleY,
       overlayBgOpacity: defaults.overlayBgOpacity,
+      overlayTextShadow: defaults.overlayTextShadow,
+      overlayFontFamily: defaults.overlayFontFamily,
+      overlayFontWeight: defaults.overlayFontWeight,
+      overlaySizePreset: defaults.overlaySizePreset,
+      overlayWrapText: defaults.overlayWrapText,
+      overlayWidth: defaults.overlayWidth,
+      overlayHeight: defaults.overlayHeight,
+      overlayTheme: defaults.overlayTheme,
+      overlayHudLayout: defaults.overlayHudLayout,
+      localApiEnabled: defaults.localApiEnabled,
+      localApiPort: defaults.localApiPort,
+      discordPresenceEnabled: defaults.discordPresenceEnabled,
+      chestReminderEnabled: defaults.chestReminderEnabled,
+      chestReminderMinutesBefore: defaults.chestReminderMinutesBefore,
+      helltideEndReminderEnabled: defaults.helltideEndReminderEnabled,
+      helltideEndReminderMinutesBefore: defaults.helltideEndReminderMinutesBefore,
+      desktopNotificationEnabled: defaults.desktopNotificationEnabled,
+      screenReaderAnnouncements: defaults.screenReaderAnnouncements,
       categories: {
         helltide: {
           enabled: typeof enabled.helltide === "boolean" ? enabled.helltide : true,
@@ -326,6 +489,24 @@ export function loadSettings(): Settings {
       overlayScaleX: defaults.overlayScaleX,
       overlayScaleY: defaults.overlayScaleY,
       overlayBgOpacity: defaults.overlayBgOpacity,
+      overlayTextShadow: defaults.overlayTextShadow,
+      overlayFontFamily: defaults.overlayFontFamily,
+      overlayFontWeight: defaults.overlayFontWeight,
+      overlaySizePreset: defaults.overlaySizePreset,
+      overlayWrapText: defaults.overlayWrapText,
+      overlayWidth: defaults.overlayWidth,
+      overlayHeight: defaults.overlayHeight,
+      overlayTheme: defaults.overlayTheme,
+      overlayHudLayout: defaults.overlayHudLayout,
+      localApiEnabled: defaults.localApiEnabled,
+      localApiPort: defaults.localApiPort,
+      discordPresenceEnabled: defaults.discordPresenceEnabled,
+      chestReminderEnabled: defaults.chestReminderEnabled,
+      chestReminderMinutesBefore: defaults.chestReminderMinutesBefore,
+      helltideEndReminderEnabled: defaults.helltideEndReminderEnabled,
+      helltideEndReminderMinutesBefore: defaults.helltideEndReminderMinutesBefore,
+      desktopNotificationEnabled: defaults.desktopNotificationEnabled,
+      screenReaderAnnouncements: defaults.screenReaderAnnouncements,
       categories: {
         helltide: { enabled, ttsName: defaults.categories.helltide.ttsName, timerCount: 1, timers },
         legion: { ...defaultCategory(false) },
@@ -347,3 +528,18 @@ export function loadSettings(): Settings {
 export function saveSettings(settings: Settings): void {
   writeJson(STORAGE_KEY, settings);
 }
+
+/** What the backend needs of the settings to fire the same reminders while headless. */
+export function reminderTimes(settings: Settings): ReminderTimes {
+  const timers = (type: ScheduleType) => {
+    const category = settings.categories[type];
+    return category.enabled ? category.timers.slice(0, category.timerCount).map((t) => t.minutesBefore) : [];
+  };
+  return {
+    helltide: timers("helltide"),
+    legion: timers("legion"),
+    worldBoss: timers("world_boss"),
+    helltideEnd: settings.helltideEndReminderEnabled ? settings.helltideEndReminderMinutesBefore : null,
+    chest: settings.chestReminderEnabled ? settings.chestReminderMinutesBefore : null
+  };
+}
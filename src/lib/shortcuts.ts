@@ -0,0 +1,41 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+
+/** Tray actions that have a global hotkey (`ShortcutAction` in the backend). */
+export type ShortcutAction =
+  | "toggle_window"
+  | "peek_next_event"
+  | "reveal_overlay"
+  | "toggle_overlay"
+  | "toggle_reminders"
+  | "pause_reminders"
+  | "resume_reminders";
+
+export const SHORTCUT_LABELS: Record<ShortcutAction, string> = {
+  toggle_window: "Fenster zeigen/verstecken",
+  peek_next_event: "Nächstes Event im Overlay",
+  reveal_overlay: "Overlay anklickbar machen",
+  toggle_overlay: "Overlay an/aus",
+  toggle_reminders: "Erinnerungen an/aus",
+  pause_reminders: "Erinnerungen 1 Stunde pausieren",
+  resume_reminders: "Erinnerungen fortsetzen"
+};
+
+export type ShortcutInfo = {
+  action: ShortcutAction;
+  /** Binding in effect, e.g. "Ctrl+Alt+N"; null = none. */
+  shortcut: string | null;
+  defaultShortcut: string | null;
+  /** Id of the tray menu item doing the same. */
+  trayItem: string;
+  /** False when unbound or taken by another program (hotkeys only work on Windows). */
+  registered: boolean;
+};
+
+export async function listShortcuts(): Promise<ShortcutInfo[]> {
+  if (!isTauri()) return [];
+  try {
+    return await invoke<ShortcutInfo[]>("list_shortcuts");
+  } catch {
+    return [];
+  }
+}
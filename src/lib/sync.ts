@@ -0,0 +1,21 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+import type { Settings } from "./settings";
+
+/** Payload of `sync:changed`: another PC wrote the sync folder's settings file. */
+export type SyncChanged = {
+  device: string;
+  updatedAt: string; // RFC 3339
+  /** That PC's UI settings; null if it never pushed any. */
+  uiSettings: unknown | null;
+};
+
+/** Hand the UI settings to the backend for the sync folder; a no-op while sync is off. */
+export async function pushSyncedSettings(settings: Settings): Promise<void> {
+  if (!isTauri()) return;
+  await invoke("sync_push_settings", { settings });
+}
+
+/** Sync through `dir` from now on (null turns it off); adopts an existing sync file there. */
+export async function setSyncDir(dir: string | null, settings: Settings): Promise<void> {
+  await invoke("set_sync_dir", { dir, settings });
+}
@@ -0,0 +1,19 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/** Opt-in anonymous usage counters; nothing is counted while disabled. */
+export type TelemetrySettings = {
+  enabled: boolean;
+  /** Where the counters are posted; without one they only queue up locally. */
+  endpoint: string | null;
+};
+
+/** Exactly what the next send would post. */
+export type TelemetryPayload = {
+  appVersion: string;
+  os: string;
+  batches: Array<{ features: Record<string, number>; errors: Record<string, number> }>;
+};
+
+export async function previewTelemetryPayload(): Promise<TelemetryPayload> {
+  return await invoke<TelemetryPayload>("preview_telemetry_payload");
+}
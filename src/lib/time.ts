@@ -1,3 +1,14 @@
+// Milliseconds to add to the local clock to get API server time (see `clock_status`).
+let clockOffsetMs = 0;
+
+export function setClockOffsetMs(offsetMs: number): void {
+  clockOffsetMs = Number.isFinite(offsetMs) ? offsetMs : 0;
+}
+
+export function serverNow(): number {
+  return Date.now() + clockOffsetMs;
+}
+
 export function formatLocalTime(iso: string): string {
   const date = new Date(iso);
   return new Intl.DateTimeFormat(undefined, {
@@ -6,6 +17,21 @@ export function formatLocalTime(iso: string): string {
   }).format(date);
 }
 
+/** `HH:MM label` at a fixed UTC offset, like `SecondaryZone::format` in the backend. */
+export function formatZoneTime(iso: string, zone: { label: string; offsetMinutes: number }): string {
+  const shifted = new Date(new Date(iso).getTime() + zone.offsetMinutes * 60_000);
+  const hh = shifted.getUTCHours().toString().padStart(2, "0");
+  const mm = shifted.getUTCMinutes().toString().padStart(2, "0");
+  return `${hh}:${mm} ${zone.label.trim()}`;
+}
+
+/** `UTC+05:30` for an offset in minutes. */
+export function formatUtcOffset(offsetMinutes: number): string {
+  const sign = offsetMinutes < 0 ? "−" : "+";
+  const abs = Math.abs(offsetMinutes);
+  return `UTC${sign}${Math.floor(abs / 60).toString().padStart(2, "0")}:${(abs % 60).toString().padStart(2, "0")}`;
+}
+
 export function formatCountdown(msRemaining: number): string {
   const totalSeconds = Math.max(0, Math.floor(msRemaining / 1000));
   const hours = Math.floor(totalSeconds / 3600);
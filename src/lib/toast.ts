@@ -0,0 +1,41 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+import type { ScheduleType } from "./types";
+
+/**
+ * Overlay toast text with placeholders: {type}, {title}, {boss}, {zone}, {minutes},
+ * {countdown}, {time}. `{{` and `}}` are literal braces. Rendered by the backend.
+ */
+export type ToastTemplates = {
+  title: string;
+  body: string;
+};
+
+export const TOAST_PLACEHOLDERS = ["type", "title", "boss", "zone", "minutes", "countdown", "time"];
+
+export type ToastEvent = {
+  kind: ScheduleType;
+  start: string; // ISO
+  boss?: string | null;
+  bossPredicted?: boolean;
+  zone?: string | null;
+};
+
+export type RenderedToast = {
+  title: string;
+  body: string;
+};
+
+/** The toast for `event` from the saved templates; null outside Tauri or on failure. */
+export async function renderToast(event: ToastEvent): Promise<RenderedToast | null> {
+  if (!isTauri()) return null;
+  try {
+    return await invoke<RenderedToast>("render_toast", { event });
+  } catch {
+    return null;
+  }
+}
+
+/** Unsaved `templates` rendered for a sample event; rejects with an `invalidSettings` AppError. */
+export async function previewToast(templates: ToastTemplates, eventType: ScheduleType): Promise<RenderedToast> {
+  return await invoke<RenderedToast>("preview_toast", { templates, eventType });
+}
@@ -1,21 +1,26 @@
 export type ScheduleType = "helltide" | "legion" | "world_boss";
 
+export type Zone = {
+  id: string;
+  name: string;
+  isWhisper: boolean;
+  boss?: string;
+};
+
 export type ScheduleItemBase = {
   id: number;
   timestamp: number; // seconds
   startTime: string; // ISO
   type: ScheduleType;
+  zone?: Zone[] | string;
+  location?: string; // some mirrors send this instead of `zone`
+  predicted?: boolean; // extrapolated by the backend from the season cadence, not from the API
 };
 
 export type WorldBossScheduleItem = ScheduleItemBase & {
   type: "world_boss";
   boss: string;
-  zone?: Array<{
-    id: string;
-    name: string;
-    isWhisper: boolean;
-    boss?: string;
-  }>;
+  predictedBoss?: string; // filled in by the backend rotation predictor when `boss` is missing
 };
 
 export type LegionScheduleItem = ScheduleItemBase & {
@@ -32,3 +37,23 @@ export type ScheduleResponse = {
   helltide: HelltideScheduleItem[];
 };
 
+
+export function displayBossName(item: WorldBossScheduleItem): string | null {
+  if (item.boss) return item.boss;
+  if (item.predictedBoss) return `${item.predictedBoss}?`;
+  return null;
+}
+
+/** Spawn zone(s) as shown in titles, matching the backend's `parse_zone`. */
+export function displayZone(item: Pick<ScheduleItemBase, "zone" | "location">): string | null {
+  const names =
+    typeof item.zone === "string"
+      ? [item.zone]
+      : Array.isArray(item.zone)
+        ? item.zone.map((z) => z?.name ?? "")
+        : item.location
+          ? [item.location]
+          : [];
+  const cleaned = names.map((n) => n.trim()).filter((n) => n.length > 0);
+  return cleaned.length > 0 ? cleaned.join(" / ") : null;
+}
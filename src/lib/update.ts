@@ -0,0 +1,21 @@
+import { invoke, isTauri } from "@tauri-apps/api/core";
+
+export type UpdateInfo = {
+  current: string;
+  latest: string;
+  url: string;
+  notes?: string | null;
+};
+
+export async function checkForUpdate(): Promise<UpdateInfo | null> {
+  if (!isTauri()) return null;
+  try {
+    return await invoke<UpdateInfo | null>("check_for_update");
+  } catch {
+    return null;
+  }
+}
+
+export async function openUpdatePage(): Promise<void> {
+  await invoke("open_update_page");
+}
@@ -0,0 +1,32 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type WebhookTrigger = "event_starting" | "schedule_changed" | "app_error";
+
+/** JSON posted to a user URL; validated by the backend only while enabled. */
+export type WebhookSettings = {
+  enabled: boolean;
+  url: string;
+  triggers: WebhookTrigger[];
+  /** JSON with {placeholders} inside string literals; null posts all fields as one object. */
+  template: string | null;
+  /** Signs the body as `X-Helltime-Signature: sha256=<hex>` (HMAC-SHA256). */
+  secret: string | null;
+};
+
+export const WEBHOOK_TRIGGER_LABELS: Record<WebhookTrigger, string> = {
+  event_starting: "Event beginnt",
+  schedule_changed: "Zeitplan geändert",
+  app_error: "Fehler beim Abruf"
+};
+
+/** Mirrors `webhook::placeholders` in the backend. */
+export const WEBHOOK_PLACEHOLDERS: Record<WebhookTrigger, string[]> = {
+  event_starting: ["trigger", "at", "event", "id", "title", "start", "zone", "predicted"],
+  schedule_changed: ["trigger", "at", "shifted", "cancelled"],
+  app_error: ["trigger", "at", "kind", "message"]
+};
+
+/** Posts a sample "event starting" once, without retries. */
+export async function testWebhook(): Promise<void> {
+  await invoke("test_webhook");
+}
@@ -16,11 +16,10 @@ function readBounds(storageKey: string): WindowBounds | null {
     if (![obj.x, obj.y, obj.w, obj.h].every(Number.isFinite)) return null;
 
     // Safety guard: don't let a persisted overlay turn into a full-screen blocker.
-    const isOverlay = storageKey === "helltime:overlayWindowBounds";
-    const maxW = isOverlay ? 520 : 1400;
-    const maxH = isOverlay ? 320 : 1000;
-    const minW = isOverlay ? 120 : 240;
-    const minH = isOverlay ? 60 : 180;
+    const maxW = 520;
+    const maxH = 320;
+    const minW = 120;
+    const minH = 60;
 
     // Clamp position to reasonable screen bounds to prevent off-screen windows
     // Be MORE aggressive: position must be on-screen or just barely off-screen
@@ -48,6 +47,10 @@ function writeBounds(storageKey: string, bounds: WindowBounds): void {
   }
 }
 
+/**
+ * Overlay window only: the main window's placement is saved by the backend
+ * (`preferences.window`, see `window_geometry.rs`).
+ */
 export async function initWindowPersistence(storageKey: string): Promise<void> {
   if (!isTauri()) return;
   try {
@@ -105,7 +108,3 @@ export async function initWindowPersistence(storageKey: string): Promise<void> {
     // ignore
   }
 }
-
-export async function initMainWindowPersistence(): Promise<void> {
-  return initWindowPersistence("helltime:mainWindowBounds");
-}
@@ -5,7 +5,7 @@ import App from "./App";
 import OverlayWindow from "./OverlayWindow";
 import { ErrorBoundary } from "./ErrorBoundary";
 import { enablePanicStop, startUiWatchdog } from "./lib/safety";
-import { initMainWindowPersistence, initWindowPersistence } from "./lib/window_state";
+import { initWindowPersistence } from "./lib/window_state";
 import "./styles.css";
 
 const view = new URLSearchParams(window.location.search).get("view");
@@ -27,7 +27,12 @@ if (view === "overlay") {
   void initWindowPersistence("helltime:overlayWindowBounds");
 } else {
   startUiWatchdog();
-  void initMainWindowPersistence();
+  // The backend restores the main window; drop what the old frontend persistence saved.
+  try {
+    localStorage.removeItem("helltime:mainWindowBounds");
+  } catch {
+    // ignore
+  }
 }
 
 ReactDOM.createRoot(document.getElementById("root")!).render(